@@ -374,6 +374,9 @@ pub fn build_async_decoder<R: AsyncRead + Send + Unpin + 'static>(
             Ok(Box::new(AsyncLzmaDecoder::new(input)))
         }
         CodecMethod::Deflate => Ok(Box::new(AsyncDeflateDecoder::new(input))),
+        CodecMethod::Deflate64 => Err(Error::UnsupportedFeature {
+            feature: "async Deflate64 decompression",
+        }),
         CodecMethod::BZip2 => Ok(Box::new(AsyncBzip2Decoder::new(input))),
         CodecMethod::PPMd => Err(Error::UnsupportedFeature {
             feature: "async PPMd decompression",
@@ -416,6 +419,9 @@ pub fn build_async_encoder<W: AsyncWrite + Send + Unpin + 'static>(
             Ok(Box::new(AsyncLzmaEncoder::new(output)))
         }
         CodecMethod::Deflate => Ok(Box::new(AsyncDeflateEncoder::new(output, level))),
+        CodecMethod::Deflate64 => Err(Error::UnsupportedFeature {
+            feature: "async Deflate64 compression",
+        }),
         CodecMethod::BZip2 => Ok(Box::new(AsyncBzip2Encoder::new(output, level))),
         CodecMethod::PPMd => Err(Error::UnsupportedFeature {
             feature: "async PPMd compression",
@@ -10,7 +10,9 @@ use std::sync::Arc;
 use tokio_util::sync::CancellationToken;
 
 use crate::format::streams::ResourceLimits;
-use crate::read::{LinkPolicy, OverwritePolicy, PathSafety, PreserveMetadata, Threads};
+use crate::read::{
+    ExtractResult, LinkPolicy, OverwritePolicy, PathSafety, PreserveMetadata, Threads,
+};
 
 #[cfg(feature = "aes")]
 use crate::async_password::AsyncPasswordProvider;
@@ -284,6 +286,41 @@ pub enum ProgressEvent {
         /// Whether extraction was successful.
         success: bool,
     },
+    /// Extraction of the archive has finished.
+    ///
+    /// Only sent by [`AsyncArchive::extract_with_events`], as the last event.
+    ///
+    /// [`AsyncArchive::extract_with_events`]: crate::AsyncArchive::extract_with_events
+    Done {
+        /// Number of entries extracted successfully.
+        entries_extracted: usize,
+        /// Number of entries that failed to extract.
+        entries_failed: usize,
+        /// Total bytes extracted.
+        bytes_extracted: u64,
+        /// The error that aborted extraction, if any.
+        error: Option<String>,
+    },
+}
+
+impl ProgressEvent {
+    /// Creates the final event for an extraction result.
+    pub(crate) fn done(result: &crate::Result<ExtractResult>) -> Self {
+        match result {
+            Ok(result) => Self::Done {
+                entries_extracted: result.entries_extracted,
+                entries_failed: result.entries_failed,
+                bytes_extracted: result.bytes_extracted,
+                error: None,
+            },
+            Err(e) => Self::Done {
+                entries_extracted: 0,
+                entries_failed: 0,
+                bytes_extracted: 0,
+                error: Some(e.to_string()),
+            },
+        }
+    }
 }
 
 impl ChannelProgressReporter {
@@ -295,6 +332,11 @@ impl ChannelProgressReporter {
         let (tx, rx) = tokio::sync::mpsc::channel(buffer_size);
         (Self { sender: tx }, rx)
     }
+
+    /// Returns a sender for the same channel.
+    pub(crate) fn sender(&self) -> tokio::sync::mpsc::Sender<ProgressEvent> {
+        self.sender.clone()
+    }
 }
 
 impl AsyncProgressCallback for ChannelProgressReporter {
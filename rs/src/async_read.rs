@@ -25,20 +25,25 @@
 //! ```
 
 use std::path::Path;
+use std::sync::Arc;
 
+use futures::stream::{self, Stream, StreamExt};
 use tokio::fs::File;
 use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeek, BufReader};
 use tokio_util::sync::CancellationToken;
 
 use std::io::{Cursor, Read, Write};
 
-use crate::async_options::{AsyncExtractOptions, AsyncTestOptions};
+use crate::async_options::{
+    AsyncExtractOptions, AsyncTestOptions, ChannelProgressReporter, ProgressEvent,
+};
 use crate::format::SIGNATURE_HEADER_SIZE;
 use crate::format::header::StartHeader;
 use crate::format::parser::{ArchiveHeader, read_archive_header};
 use crate::format::streams::ResourceLimits;
 use crate::read::{
-    ArchiveInfo, Entry, EntrySelector, ExtractResult, OverwritePolicy, PathSafety, TestResult,
+    ArchiveInfo, Entry, EntryOutcome, EntrySelector, ExtractResult, OverwritePolicy, PathSafety,
+    TestResult,
 };
 use crate::streaming::Crc32Sink;
 use crate::{Error, READ_BUFFER_SIZE, Result, codec};
@@ -46,6 +51,9 @@ use crate::{Error, READ_BUFFER_SIZE, Result, codec};
 #[cfg(feature = "aes")]
 use crate::Password;
 
+/// Capacity of the event channel used by [`AsyncArchive::extract_with_events`].
+const EVENT_CHANNEL_CAPACITY: usize = 64;
+
 /// Result of async archive opening (internal helper to avoid CFG duplication).
 struct AsyncOpenResult<R> {
     reader: R,
@@ -256,12 +264,7 @@ impl<R: AsyncRead + AsyncSeek + Unpin + Send> AsyncArchive<R> {
             return Err(Error::Cancelled);
         }
 
-        let mut result = TestResult {
-            entries_tested: 0,
-            entries_passed: 0,
-            entries_failed: 0,
-            failures: Vec::new(),
-        };
+        let mut result = TestResult::default();
 
         // Collect entries to test
         let entries_to_test: Vec<_> = self
@@ -427,6 +430,11 @@ impl<R: AsyncRead + AsyncSeek + Unpin + Send> AsyncArchive<R> {
             .filter(|(_, e)| selector.select(e))
             .map(|(idx, e)| (idx, e.clone()))
             .collect();
+        let total_bytes: u64 = entries_to_extract
+            .iter()
+            .filter(|(_, e)| !e.is_directory)
+            .map(|(_, e)| e.size)
+            .sum();
 
         for (idx, entry) in entries_to_extract {
             // Check cancellation before each entry
@@ -446,16 +454,13 @@ impl<R: AsyncRead + AsyncSeek + Unpin + Send> AsyncArchive<R> {
                 let dir_path = dest.join(entry.path.as_str());
                 match tokio::fs::create_dir_all(&dir_path).await {
                     Ok(_) => {
-                        result.entries_extracted += 1;
+                        result.record(EntryOutcome::new(entry.path.as_str()));
                         if let Some(progress) = &options.progress {
                             progress.on_entry_complete(entry.path.as_str(), true).await;
                         }
                     }
                     Err(e) => {
-                        result.entries_failed += 1;
-                        result
-                            .failures
-                            .push((entry.path.as_str().to_string(), e.to_string()));
+                        result.record(EntryOutcome::new(entry.path.as_str()).with_error(e));
                         if let Some(progress) = &options.progress {
                             progress.on_entry_complete(entry.path.as_str(), false).await;
                         }
@@ -465,19 +470,24 @@ impl<R: AsyncRead + AsyncSeek + Unpin + Send> AsyncArchive<R> {
                 // Extract file
                 let entry_path = entry.path.as_str().to_string();
                 match self.extract_entry_async(idx, &dest, options).await {
-                    Ok(bytes) => {
-                        result.entries_extracted += 1;
-                        result.bytes_extracted += bytes;
+                    Ok(outcome) => {
+                        result.record(outcome);
                         if let Some(progress) = &options.progress {
+                            progress
+                                .on_progress(result.bytes_extracted, total_bytes)
+                                .await;
                             progress.on_entry_complete(&entry_path, true).await;
                         }
                     }
                     Err(e) => {
+                        let outcome = EntryOutcome::new(entry_path.as_str()).with_error(e);
                         if matches!(options.overwrite, OverwritePolicy::Skip) {
-                            result.entries_skipped += 1;
+                            result.record(EntryOutcome {
+                                skipped: true,
+                                ..outcome
+                            });
                         } else {
-                            result.entries_failed += 1;
-                            result.failures.push((entry_path.clone(), e.to_string()));
+                            result.record(outcome);
                         }
                         if let Some(progress) = &options.progress {
                             progress.on_entry_complete(&entry_path, false).await;
@@ -490,6 +500,56 @@ impl<R: AsyncRead + AsyncSeek + Unpin + Send> AsyncArchive<R> {
         Ok(result)
     }
 
+    /// Extracts all entries, streaming progress events as they happen.
+    ///
+    /// The returned stream drives the extraction: nothing is extracted until
+    /// it is polled. It yields the [`ProgressEvent`]s of a
+    /// [`ChannelProgressReporter`] (entry start, bytes extracted, entry
+    /// complete) and ends with a [`ProgressEvent::Done`] summarizing the
+    /// result. Any progress callback in `options` is replaced.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use futures::StreamExt;
+    /// use zesven::{AsyncArchive, AsyncExtractOptions, ProgressEvent};
+    ///
+    /// let mut archive = AsyncArchive::open_path("archive.7z").await?;
+    /// let mut events = std::pin::pin!(
+    ///     archive.extract_with_events("output_dir", AsyncExtractOptions::default())
+    /// );
+    /// while let Some(event) = events.next().await {
+    ///     if let ProgressEvent::Progress { bytes_extracted, total_bytes } = event {
+    ///         println!("{bytes_extracted}/{total_bytes}");
+    ///     }
+    /// }
+    /// ```
+    pub fn extract_with_events(
+        &mut self,
+        dest: impl AsRef<Path>,
+        mut options: AsyncExtractOptions,
+    ) -> impl Stream<Item = ProgressEvent> + '_ {
+        let dest = dest.as_ref().to_path_buf();
+        let (reporter, receiver) = ChannelProgressReporter::new(EVENT_CHANNEL_CAPACITY);
+        let sender = reporter.sender();
+        options.progress = Some(Arc::new(reporter));
+
+        // The receiver ends once the extraction has dropped its reporter and
+        // sent the final event.
+        let extraction = async move {
+            let result = self.extract(&dest, (), &options).await;
+            drop(options);
+            let _ = sender.send(ProgressEvent::done(&result)).await;
+        };
+        let events = stream::unfold(receiver, |mut receiver| async move {
+            receiver.recv().await.map(|event| (event, receiver))
+        });
+        stream::select(
+            events,
+            stream::once(extraction).filter_map(|()| async { None }),
+        )
+    }
+
     /// Extracts entries with a cancellation token.
     ///
     /// This is a convenience method that wraps extraction with explicit
@@ -514,8 +574,9 @@ impl<R: AsyncRead + AsyncSeek + Unpin + Send> AsyncArchive<R> {
         entry_idx: usize,
         dest: &Path,
         options: &AsyncExtractOptions,
-    ) -> Result<u64> {
+    ) -> Result<EntryOutcome> {
         let entry_path_str = self.entries[entry_idx].path.as_str().to_string();
+        let mut outcome = EntryOutcome::new(entry_path_str.as_str());
         let entry_size = self.entries[entry_idx].size;
         let entry_crc = self.entries[entry_idx].crc32;
         let folder_index = self.entries[entry_idx].folder_index;
@@ -540,9 +601,10 @@ impl<R: AsyncRead + AsyncSeek + Unpin + Send> AsyncArchive<R> {
                     )));
                 }
                 OverwritePolicy::Skip => {
-                    return Ok(0);
+                    outcome.skipped = true;
+                    return Ok(outcome);
                 }
-                OverwritePolicy::Overwrite => {}
+                OverwritePolicy::Overwrite => outcome.overwritten = true,
             }
         }
 
@@ -554,7 +616,7 @@ impl<R: AsyncRead + AsyncSeek + Unpin + Send> AsyncArchive<R> {
                 tokio::fs::File::create(&safe_path)
                     .await
                     .map_err(Error::Io)?;
-                return Ok(0);
+                return Ok(outcome);
             }
         };
 
@@ -600,9 +662,11 @@ impl<R: AsyncRead + AsyncSeek + Unpin + Send> AsyncArchive<R> {
                     actual: actual_crc,
                 });
             }
+            outcome.crc_verified = true;
         }
 
-        Ok(bytes_written)
+        outcome.bytes_written = bytes_written;
+        Ok(outcome)
     }
 
     /// Synchronous extraction helper for use in spawn_blocking.
@@ -83,6 +83,9 @@ pub struct AsyncWriter<W> {
     stream_info: StreamInfo,
     /// Total compressed bytes written.
     compressed_bytes: u64,
+    /// Content hashes of the entries added so far.
+    #[cfg(feature = "merkle")]
+    content_digest: crate::write::content_digest::ContentDigest,
 }
 
 impl AsyncWriter<BufWriter<File>> {
@@ -131,6 +134,8 @@ impl<W: AsyncWrite + AsyncSeek + Unpin + Send> AsyncWriter<W> {
             entries: Vec::new(),
             stream_info: StreamInfo::default(),
             compressed_bytes: 0,
+            #[cfg(feature = "merkle")]
+            content_digest: Default::default(),
         })
     }
 
@@ -273,6 +278,9 @@ impl<W: AsyncWrite + AsyncSeek + Unpin + Send> AsyncWriter<W> {
         self.stream_info.unpack_sizes.push(uncompressed_size);
         self.stream_info.crcs.push(crc);
 
+        #[cfg(feature = "merkle")]
+        self.content_digest.add_bytes(archive_path.as_str(), data);
+
         // Add entry
         let entry = PendingEntry {
             path: archive_path,
@@ -379,6 +387,8 @@ impl<W: AsyncWrite + AsyncSeek + Unpin + Send> AsyncWriter<W> {
             compressed_size: self.compressed_bytes,
             volume_count: 1,
             volume_sizes: vec![],
+            #[cfg(feature = "merkle")]
+            content_digest: self.content_digest.digest(),
         };
 
         Ok((result, self.sink))
@@ -529,7 +539,10 @@ fn compress_deflate_sync(data: &[u8], level: u32) -> Result<Vec<u8>> {
 fn compress_bzip2_sync(data: &[u8], level: u32) -> Result<Vec<u8>> {
     use crate::codec::bzip2::{Bzip2Encoder, Bzip2EncoderOptions};
 
-    let opts = Bzip2EncoderOptions { level };
+    let opts = Bzip2EncoderOptions {
+        level,
+        block_size: None,
+    };
     let mut output = Vec::new();
     {
         let mut encoder = Bzip2Encoder::new(&mut output, &opts);
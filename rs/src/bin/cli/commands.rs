@@ -24,6 +24,7 @@ pub struct ExtractConfig<'a> {
     pub overwrite: OverwriteMode,
     pub password: Option<String>,
     pub preserve_metadata: bool,
+    pub to_stdout: bool,
     pub format: OutputFormat,
     pub quiet: bool,
     pub thread_count: usize,
@@ -74,6 +75,11 @@ pub fn extract(config: &ExtractConfig<'_>) -> ExitCode {
         }
     };
 
+    if config.to_stdout {
+        let mut archive = archive;
+        return extract_to_stdout(&mut archive, &selector);
+    }
+
     // Build options
     let overwrite_policy = match config.overwrite {
         OverwriteMode::Always => OverwritePolicy::Overwrite,
@@ -425,6 +431,36 @@ pub fn info(
     ExitCode::Success
 }
 
+/// Writes the data of every selected file to standard output, in archive order.
+fn extract_to_stdout<R: std::io::Read + std::io::Seek>(
+    archive: &mut Archive<R>,
+    selector: &FileSelector,
+) -> ExitCode {
+    use std::io::Write;
+    use zesven::read::EntrySelector;
+
+    let paths: Vec<String> = archive
+        .entries()
+        .iter()
+        .filter(|e| !e.is_directory && selector.select(e))
+        .map(|e| e.path.as_str().to_string())
+        .collect();
+
+    let mut stdout = std::io::stdout().lock();
+    for path in &paths {
+        if let Err(e) = archive.extract_entry_to(path, &mut stdout) {
+            eprintln!("Error extracting '{}': {}", path, e);
+            return error_to_exit_code(&e);
+        }
+    }
+    if let Err(e) = stdout.flush() {
+        eprintln!("Error writing to standard output: {}", e);
+        return ExitCode::IoError;
+    }
+
+    ExitCode::Success
+}
+
 /// Helper to open an archive with optional password
 fn open_archive(
     path: &Path,
@@ -56,6 +56,7 @@ pub fn error_to_exit_code(error: &Error) -> ExitCode {
         Error::InvalidFormat(_) | Error::CorruptHeader { .. } => ExitCode::BadArchive,
         Error::WrongPassword { .. } => ExitCode::WrongPassword,
         Error::CrcMismatch { .. } => ExitCode::BadArchive,
+        Error::FolderCrcMismatch { .. } => ExitCode::BadArchive,
         Error::UnsupportedMethod { .. } => ExitCode::BadArchive,
         Error::UnsupportedFeature { .. } => ExitCode::BadArchive,
         Error::PathTraversal { .. } => ExitCode::FatalError,
@@ -66,6 +67,9 @@ pub fn error_to_exit_code(error: &Error) -> ExitCode {
         Error::VolumeCorrupted { .. } => ExitCode::BadArchive,
         Error::IncompleteArchive { .. } => ExitCode::BadArchive,
         Error::Cancelled => ExitCode::UserInterrupt,
+        // Strict mode escalates a warning; report it with the warning code
+        Error::Strict(_) => ExitCode::Warning,
+        Error::InvalidGlob { .. } => ExitCode::BadArgs,
         #[cfg(feature = "regex")]
         Error::InvalidRegex { .. } => ExitCode::BadArgs,
         // Future error variants - required by #[non_exhaustive]
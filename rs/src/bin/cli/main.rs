@@ -66,6 +66,10 @@ enum Commands {
         /// Preserve file permissions and timestamps
         #[arg(long)]
         preserve_metadata: bool,
+
+        /// Write the selected files to standard output instead of a directory
+        #[arg(long, conflicts_with_all = ["output", "overwrite", "preserve_metadata"])]
+        stdout: bool,
     },
 
     /// Create archive (alias: a)
@@ -212,6 +216,7 @@ fn main() {
             overwrite,
             password,
             preserve_metadata,
+            stdout,
         } => commands::extract(&commands::ExtractConfig {
             archive_path: &archive,
             output_dir: &output,
@@ -220,6 +225,7 @@ fn main() {
             overwrite,
             password,
             preserve_metadata,
+            to_stdout: stdout,
             format: cli.format,
             quiet: cli.quiet,
             thread_count: cli.threads,
@@ -0,0 +1,80 @@
+//! BSD file flags support.
+//!
+//! macOS and the BSDs keep per-file flags in `st_flags` (for example
+//! `UF_IMMUTABLE`, shown as `uchg`, and `UF_HIDDEN`), separate from the Unix
+//! mode. When requested they are read while archiving, stored in the archive
+//! header, and restored with `chflags(2)` on extraction. Other platforms do
+//! not read or restore them.
+
+use std::io;
+use std::path::Path;
+
+/// Returns the flags of the file at `path`, or `None` if it has none.
+#[cfg(any(
+    target_os = "macos",
+    target_os = "freebsd",
+    target_os = "openbsd",
+    target_os = "netbsd"
+))]
+pub(crate) fn read_flags(path: &Path) -> io::Result<Option<u32>> {
+    #[cfg(target_os = "freebsd")]
+    use std::os::freebsd::fs::MetadataExt;
+    #[cfg(target_os = "macos")]
+    use std::os::macos::fs::MetadataExt;
+    #[cfg(target_os = "netbsd")]
+    use std::os::netbsd::fs::MetadataExt;
+    #[cfg(target_os = "openbsd")]
+    use std::os::openbsd::fs::MetadataExt;
+
+    let flags = std::fs::metadata(path)?.st_flags();
+    Ok((flags != 0).then_some(flags))
+}
+
+#[cfg(not(any(
+    target_os = "macos",
+    target_os = "freebsd",
+    target_os = "openbsd",
+    target_os = "netbsd"
+)))]
+pub(crate) fn read_flags(_path: &Path) -> io::Result<Option<u32>> {
+    Ok(None)
+}
+
+/// Sets the flags of the file at `path`.
+#[cfg(any(
+    target_os = "macos",
+    target_os = "freebsd",
+    target_os = "openbsd",
+    target_os = "netbsd"
+))]
+pub(crate) fn apply_flags(path: &Path, flags: u32) -> io::Result<()> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    #[cfg(any(target_os = "macos", target_os = "openbsd"))]
+    type Flags = u32;
+    #[cfg(any(target_os = "freebsd", target_os = "netbsd"))]
+    type Flags = std::ffi::c_ulong;
+
+    unsafe extern "C" {
+        fn chflags(path: *const std::ffi::c_char, flags: Flags) -> std::ffi::c_int;
+    }
+
+    let path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    // SAFETY: `path` is a valid NUL-terminated string for the whole call
+    if unsafe { chflags(path.as_ptr(), Flags::from(flags)) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(not(any(
+    target_os = "macos",
+    target_os = "freebsd",
+    target_os = "openbsd",
+    target_os = "netbsd"
+)))]
+pub(crate) fn apply_flags(_path: &Path, _flags: u32) -> io::Result<()> {
+    Ok(())
+}
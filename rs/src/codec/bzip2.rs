@@ -49,11 +49,18 @@ impl<R: Read + Send> Decoder for Bzip2Decoder<R> {
 pub struct Bzip2EncoderOptions {
     /// Compression level (1-9, default 9).
     pub level: u32,
+    /// Block size in units of 100 KB (1-9).
+    ///
+    /// BZip2 derives its block size from the level, so `None` uses `level`.
+    pub block_size: Option<u8>,
 }
 
 impl Default for Bzip2EncoderOptions {
     fn default() -> Self {
-        Self { level: 9 }
+        Self {
+            level: 9,
+            block_size: None,
+        }
     }
 }
 
@@ -62,8 +69,25 @@ impl Bzip2EncoderOptions {
     pub fn with_level(level: u32) -> Self {
         Self {
             level: level.clamp(1, 9),
+            block_size: None,
         }
     }
+
+    /// Sets the block size in units of 100 KB (clamped to 1-9).
+    ///
+    /// Larger blocks give the Burrows-Wheeler transform more context and
+    /// usually compress better, at the cost of more memory on both the
+    /// encoding and decoding side. The block size is recorded in the stream
+    /// header, so the decoder needs no configuration.
+    pub fn block_size(mut self, size: u8) -> Self {
+        self.block_size = Some(size.clamp(1, 9));
+        self
+    }
+
+    /// Returns the effective block size in units of 100 KB.
+    fn effective_block_size(&self) -> u32 {
+        self.block_size.map_or(self.level, u32::from)
+    }
 }
 
 /// BZip2 encoder.
@@ -86,7 +110,7 @@ impl<W: Write + Send> Bzip2Encoder<W> {
     /// * `options` - Encoder options
     pub fn new(output: W, options: &Bzip2EncoderOptions) -> Self {
         Self {
-            inner: BzEncoder::new(output, Compression::new(options.level)),
+            inner: BzEncoder::new(output, Compression::new(options.effective_block_size())),
         }
     }
 
@@ -157,6 +181,13 @@ mod tests {
 
         let opts = Bzip2EncoderOptions::with_level(100);
         assert_eq!(opts.level, 9); // Clamped
+
+        let opts = Bzip2EncoderOptions::with_level(5).block_size(2);
+        assert_eq!(opts.block_size, Some(2));
+        assert_eq!(opts.effective_block_size(), 2);
+
+        let opts = Bzip2EncoderOptions::default().block_size(0);
+        assert_eq!(opts.block_size, Some(1)); // Clamped
     }
 
     #[test]
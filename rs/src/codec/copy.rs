@@ -1,8 +1,8 @@
 //! Copy codec (no compression).
 
-use std::io::{self, Read};
+use std::io::{self, Read, Write};
 
-use super::{Decoder, method};
+use super::{Decoder, Encoder, method};
 
 /// A decoder that passes data through unchanged (no compression).
 pub struct CopyDecoder<R> {
@@ -44,6 +44,38 @@ impl<R: Read + Send> Decoder for CopyDecoder<R> {
     }
 }
 
+/// An encoder that passes data through unchanged (no compression).
+pub struct CopyEncoder<W> {
+    inner: W,
+}
+
+impl<W: Write + Send> CopyEncoder<W> {
+    /// Creates a new copy encoder writing to `inner`.
+    pub fn new(inner: W) -> Self {
+        Self { inner }
+    }
+}
+
+impl<W: Write + Send> Write for CopyEncoder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<W: Write + Send> Encoder for CopyEncoder<W> {
+    fn method_id(&self) -> &'static [u8] {
+        method::COPY
+    }
+
+    fn finish(mut self: Box<Self>) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -87,4 +119,14 @@ mod tests {
         let decoder = CopyDecoder::new(cursor, 0);
         assert_eq!(decoder.method_id(), method::COPY);
     }
+
+    #[test]
+    fn test_copy_encoder() {
+        let mut output = Vec::new();
+        let mut encoder = Box::new(CopyEncoder::new(&mut output));
+        encoder.write_all(b"Hello").unwrap();
+        assert_eq!(encoder.method_id(), method::COPY);
+        encoder.finish().unwrap();
+        assert_eq!(output, b"Hello");
+    }
 }
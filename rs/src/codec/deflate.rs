@@ -1,4 +1,4 @@
-//! Deflate codec implementation.
+//! Deflate and Deflate64 codec implementations.
 
 use std::io::{self, Read, Write};
 
@@ -44,6 +44,382 @@ impl<R: io::BufRead + Send> Decoder for DeflateDecoder<R> {
     }
 }
 
+/// Window size of Deflate64 (64 KiB instead of Deflate's 32 KiB).
+const DEFLATE64_WINDOW_SIZE: usize = 1 << 16;
+
+/// Base lengths for length codes 257..=285.
+///
+/// Unlike Deflate, code 285 is not a fixed length of 258 but 3 plus 16 extra bits.
+const DEFLATE64_LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 3,
+];
+
+/// Extra bits for length codes 257..=285.
+const DEFLATE64_LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 16,
+];
+
+/// Base distances for distance codes 0..=31 (30 and 31 are Deflate64 only).
+const DEFLATE64_DIST_BASE: [u32; 32] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577, 32769, 49153,
+];
+
+/// Extra bits for distance codes 0..=31.
+const DEFLATE64_DIST_EXTRA: [u8; 32] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13, 14, 14,
+];
+
+/// Order in which code length code lengths are stored.
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+fn invalid_deflate64(msg: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("invalid Deflate64 data: {}", msg),
+    )
+}
+
+/// LSB-first bit reader over a buffered input.
+struct BitReader<R> {
+    input: R,
+    bits: u32,
+    count: u32,
+}
+
+impl<R: io::BufRead> BitReader<R> {
+    fn bits(&mut self, n: u32) -> io::Result<u32> {
+        while self.count < n {
+            let byte = match self.input.fill_buf()?.first() {
+                Some(&byte) => byte,
+                None => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "Deflate64 stream ended unexpectedly",
+                    ));
+                }
+            };
+            self.input.consume(1);
+            self.bits |= u32::from(byte) << self.count;
+            self.count += 8;
+        }
+        let value = self.bits & ((1u64 << n) - 1) as u32;
+        self.bits = self.bits.checked_shr(n).unwrap_or(0);
+        self.count -= n;
+        Ok(value)
+    }
+
+    /// Discards the bits up to the next byte boundary.
+    fn align(&mut self) {
+        let skip = self.count % 8;
+        self.bits >>= skip;
+        self.count -= skip;
+    }
+}
+
+/// Canonical Huffman code, decoded one bit at a time.
+struct Huffman {
+    /// Number of codes of each length (index 0 is unused).
+    counts: [u16; 16],
+    /// Symbols ordered by code.
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> io::Result<Self> {
+        let mut counts = [0u16; 16];
+        for &len in lengths {
+            counts[usize::from(len)] += 1;
+        }
+        counts[0] = 0;
+
+        // Reject over-subscribed codes; incomplete codes are allowed
+        let mut left = 1i32;
+        for &count in &counts[1..] {
+            left = (left << 1) - i32::from(count);
+            if left < 0 {
+                return Err(invalid_deflate64("over-subscribed Huffman code"));
+            }
+        }
+
+        let mut offsets = [0u16; 16];
+        for len in 1..15 {
+            offsets[len + 1] = offsets[len] + counts[len];
+        }
+        let mut symbols = vec![0u16; usize::from(offsets[15] + counts[15])];
+        for (symbol, &len) in lengths.iter().enumerate() {
+            if len != 0 {
+                let slot = &mut offsets[usize::from(len)];
+                symbols[usize::from(*slot)] = symbol as u16;
+                *slot += 1;
+            }
+        }
+
+        Ok(Self { counts, symbols })
+    }
+
+    fn decode<R: io::BufRead>(&self, bits: &mut BitReader<R>) -> io::Result<u16> {
+        let mut code = 0i32;
+        let mut first = 0i32;
+        let mut index = 0i32;
+        for &count in &self.counts[1..] {
+            code |= bits.bits(1)? as i32;
+            let count = i32::from(count);
+            if code - first < count {
+                return Ok(self.symbols[(index + code - first) as usize]);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        Err(invalid_deflate64("invalid Huffman code"))
+    }
+}
+
+/// Decoding position within the stream.
+enum Deflate64Block {
+    /// Expecting a block header.
+    Header,
+    /// Inside a stored block with the given number of bytes left.
+    Stored(u16),
+    /// Inside a Huffman-coded block.
+    Huffman,
+    /// The final block has ended.
+    Done,
+}
+
+/// Deflate64 decoder.
+///
+/// Deflate64 ("enhanced deflate", method ID `04 01 09`) extends Deflate with
+/// a 64 KiB window, distance codes 30 and 31, and a length code 285 that
+/// carries 16 extra bits. It is produced by WinZip-based tools and by 7-Zip
+/// on request.
+pub struct Deflate64Decoder<R> {
+    input: BitReader<R>,
+    block: Deflate64Block,
+    last_block: bool,
+    literals: Huffman,
+    distances: Huffman,
+    window: Vec<u8>,
+    window_pos: usize,
+    window_filled: usize,
+    /// Remaining length and distance of the match being copied.
+    pending_match: (usize, usize),
+}
+
+impl<R> std::fmt::Debug for Deflate64Decoder<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Deflate64Decoder").finish_non_exhaustive()
+    }
+}
+
+impl<R: io::BufRead + Send> Deflate64Decoder<R> {
+    /// Creates a new Deflate64 decoder.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The compressed data source (must implement BufRead)
+    pub fn new(input: R) -> Self {
+        Self {
+            input: BitReader {
+                input,
+                bits: 0,
+                count: 0,
+            },
+            block: Deflate64Block::Header,
+            last_block: false,
+            literals: Huffman {
+                counts: [0; 16],
+                symbols: Vec::new(),
+            },
+            distances: Huffman {
+                counts: [0; 16],
+                symbols: Vec::new(),
+            },
+            window: vec![0; DEFLATE64_WINDOW_SIZE],
+            window_pos: 0,
+            window_filled: 0,
+            pending_match: (0, 0),
+        }
+    }
+
+    fn push_byte(&mut self, byte: u8) {
+        self.window[self.window_pos] = byte;
+        self.window_pos = (self.window_pos + 1) % DEFLATE64_WINDOW_SIZE;
+        self.window_filled = (self.window_filled + 1).min(DEFLATE64_WINDOW_SIZE);
+    }
+
+    fn read_block_header(&mut self) -> io::Result<()> {
+        self.last_block = self.input.bits(1)? == 1;
+        match self.input.bits(2)? {
+            0 => {
+                self.input.align();
+                let len = self.input.bits(16)? as u16;
+                let nlen = self.input.bits(16)? as u16;
+                if len != !nlen {
+                    return Err(invalid_deflate64("stored block length mismatch"));
+                }
+                self.block = Deflate64Block::Stored(len);
+            }
+            1 => {
+                let mut lengths = [0u8; 288];
+                lengths[..144].fill(8);
+                lengths[144..256].fill(9);
+                lengths[256..280].fill(7);
+                lengths[280..].fill(8);
+                self.literals = Huffman::new(&lengths)?;
+                self.distances = Huffman::new(&[5; 32])?;
+                self.block = Deflate64Block::Huffman;
+            }
+            2 => {
+                self.read_dynamic_tables()?;
+                self.block = Deflate64Block::Huffman;
+            }
+            _ => return Err(invalid_deflate64("reserved block type")),
+        }
+        Ok(())
+    }
+
+    fn read_dynamic_tables(&mut self) -> io::Result<()> {
+        let num_literals = self.input.bits(5)? as usize + 257;
+        let num_distances = self.input.bits(5)? as usize + 1;
+        let num_code_lengths = self.input.bits(4)? as usize + 4;
+        if num_literals > 286 {
+            return Err(invalid_deflate64("too many literal/length codes"));
+        }
+
+        let mut code_lengths = [0u8; 19];
+        for &index in &CODE_LENGTH_ORDER[..num_code_lengths] {
+            code_lengths[index] = self.input.bits(3)? as u8;
+        }
+        let code_length_code = Huffman::new(&code_lengths)?;
+
+        let total = num_literals + num_distances;
+        let mut lengths = vec![0u8; total];
+        let mut i = 0;
+        while i < total {
+            let symbol = code_length_code.decode(&mut self.input)?;
+            let (value, repeat) = match symbol {
+                0..=15 => (symbol as u8, 1),
+                16 => {
+                    let previous = i
+                        .checked_sub(1)
+                        .map(|p| lengths[p])
+                        .ok_or_else(|| invalid_deflate64("repeat without a previous length"))?;
+                    (previous, 3 + self.input.bits(2)? as usize)
+                }
+                17 => (0, 3 + self.input.bits(3)? as usize),
+                _ => (0, 11 + self.input.bits(7)? as usize),
+            };
+            if i + repeat > total {
+                return Err(invalid_deflate64("code lengths overflow"));
+            }
+            lengths[i..i + repeat].fill(value);
+            i += repeat;
+        }
+        if lengths[256] == 0 {
+            return Err(invalid_deflate64("missing end-of-block code"));
+        }
+
+        self.literals = Huffman::new(&lengths[..num_literals])?;
+        self.distances = Huffman::new(&lengths[num_literals..])?;
+        Ok(())
+    }
+
+    /// Decodes the next symbol of a Huffman block, starting a match if needed.
+    ///
+    /// Returns the literal byte, if the symbol was one.
+    fn decode_symbol(&mut self) -> io::Result<Option<u8>> {
+        let symbol = self.literals.decode(&mut self.input)?;
+        match symbol {
+            0..=255 => Ok(Some(symbol as u8)),
+            256 => {
+                self.block = Deflate64Block::Header;
+                Ok(None)
+            }
+            _ => {
+                let index = usize::from(symbol - 257);
+                if index >= DEFLATE64_LENGTH_BASE.len() {
+                    return Err(invalid_deflate64("invalid length code"));
+                }
+                let length = usize::from(DEFLATE64_LENGTH_BASE[index])
+                    + self.input.bits(u32::from(DEFLATE64_LENGTH_EXTRA[index]))? as usize;
+
+                let index = usize::from(self.distances.decode(&mut self.input)?);
+                if index >= DEFLATE64_DIST_BASE.len() {
+                    return Err(invalid_deflate64("invalid distance code"));
+                }
+                let distance = DEFLATE64_DIST_BASE[index] as usize
+                    + self.input.bits(u32::from(DEFLATE64_DIST_EXTRA[index]))? as usize;
+                if distance > self.window_filled {
+                    return Err(invalid_deflate64("distance too far back"));
+                }
+                self.pending_match = (length, distance);
+                Ok(None)
+            }
+        }
+    }
+}
+
+impl<R: io::BufRead + Send> Read for Deflate64Decoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut written = 0;
+        while written < buf.len() {
+            let (length, distance) = self.pending_match;
+            if length > 0 {
+                let n = length.min(buf.len() - written);
+                for _ in 0..n {
+                    let from = (self.window_pos + DEFLATE64_WINDOW_SIZE - distance)
+                        % DEFLATE64_WINDOW_SIZE;
+                    let byte = self.window[from];
+                    self.push_byte(byte);
+                    buf[written] = byte;
+                    written += 1;
+                }
+                self.pending_match.0 -= n;
+                continue;
+            }
+
+            let byte = match self.block {
+                Deflate64Block::Done => break,
+                Deflate64Block::Header if self.last_block => {
+                    self.block = Deflate64Block::Done;
+                    None
+                }
+                Deflate64Block::Header => {
+                    self.read_block_header()?;
+                    None
+                }
+                Deflate64Block::Stored(0) => {
+                    self.block = Deflate64Block::Header;
+                    None
+                }
+                Deflate64Block::Stored(remaining) => {
+                    self.block = Deflate64Block::Stored(remaining - 1);
+                    Some(self.input.bits(8)? as u8)
+                }
+                Deflate64Block::Huffman => self.decode_symbol()?,
+            };
+            if let Some(byte) = byte {
+                self.push_byte(byte);
+                buf[written] = byte;
+                written += 1;
+            }
+        }
+        Ok(written)
+    }
+}
+
+impl<R: io::BufRead + Send> Decoder for Deflate64Decoder<R> {
+    fn method_id(&self) -> &'static [u8] {
+        method::DEFLATE64
+    }
+}
+
 /// Deflate encoder options.
 #[derive(Debug, Clone)]
 pub struct DeflateEncoderOptions {
@@ -117,6 +493,270 @@ impl<W: Write + Send> Encoder for DeflateEncoder<W> {
     }
 }
 
+/// Longest match Deflate64 can express (length code 285 with 16 extra bits).
+const DEFLATE64_MAX_MATCH: usize = 3 + 0xFFFF;
+
+/// Input buffered before a Deflate64 block is emitted.
+const DEFLATE64_BLOCK_SIZE: usize = 1 << 20;
+
+/// Number of hash chain heads of the Deflate64 encoder.
+const DEFLATE64_HASH_SIZE: usize = 1 << 15;
+
+/// Deflate64 encoder.
+///
+/// Finds matches with hash chains over a 64 KiB window and emits fixed
+/// Huffman blocks, or stored blocks at level 0. Higher levels search longer
+/// chains.
+pub struct Deflate64Encoder<W: Write> {
+    output: W,
+    /// History followed by input that has not been encoded yet.
+    data: Vec<u8>,
+    /// Stream offset of `data[0]`.
+    base: u64,
+    /// Index in `data` of the first byte not yet encoded.
+    pending: usize,
+    /// Most recent stream offset for each hash, or `u64::MAX`.
+    head: Vec<u64>,
+    /// Previous stream offset with the same hash, indexed by offset modulo the window.
+    prev: Vec<u64>,
+    max_chain: usize,
+    bits: u64,
+    bit_count: u32,
+    out: Vec<u8>,
+}
+
+impl<W: Write> std::fmt::Debug for Deflate64Encoder<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Deflate64Encoder").finish_non_exhaustive()
+    }
+}
+
+impl<W: Write + Send> Deflate64Encoder<W> {
+    /// Creates a new Deflate64 encoder.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The destination for compressed data
+    /// * `options` - Encoder options
+    pub fn new(output: W, options: &DeflateEncoderOptions) -> Self {
+        let level = options.level.min(9);
+        Self {
+            output,
+            data: Vec::new(),
+            base: 0,
+            pending: 0,
+            head: vec![u64::MAX; DEFLATE64_HASH_SIZE],
+            prev: vec![u64::MAX; DEFLATE64_WINDOW_SIZE],
+            max_chain: if level == 0 { 0 } else { 1 << (level + 1) },
+            bits: 0,
+            bit_count: 0,
+            out: Vec::new(),
+        }
+    }
+
+    /// Finishes encoding and flushes all data.
+    pub fn try_finish(mut self) -> io::Result<W> {
+        self.encode_block(true)?;
+        self.output.flush()?;
+        Ok(self.output)
+    }
+
+    fn put_bits(&mut self, value: u32, n: u32) {
+        self.bits |= u64::from(value) << self.bit_count;
+        self.bit_count += n;
+        while self.bit_count >= 8 {
+            self.out.push(self.bits as u8);
+            self.bits >>= 8;
+            self.bit_count -= 8;
+        }
+    }
+
+    /// Writes a Huffman code, most significant bit first.
+    fn put_code(&mut self, code: u32, len: u32) {
+        self.put_bits(code.reverse_bits() >> (32 - len), len);
+    }
+
+    fn put_literal(&mut self, symbol: u32) {
+        match symbol {
+            0..=143 => self.put_code(0x30 + symbol, 8),
+            144..=255 => self.put_code(0x190 + symbol - 144, 9),
+            256..=279 => self.put_code(symbol - 256, 7),
+            _ => self.put_code(0xC0 + symbol - 280, 8),
+        }
+    }
+
+    fn put_match(&mut self, length: usize, distance: usize) {
+        if length > 258 {
+            self.put_literal(285);
+            self.put_bits((length - 3) as u32, 16);
+        } else {
+            let index = DEFLATE64_LENGTH_BASE[..28]
+                .iter()
+                .rposition(|&base| usize::from(base) <= length)
+                .unwrap();
+            self.put_literal(257 + index as u32);
+            self.put_bits(
+                (length - usize::from(DEFLATE64_LENGTH_BASE[index])) as u32,
+                u32::from(DEFLATE64_LENGTH_EXTRA[index]),
+            );
+        }
+        let index = DEFLATE64_DIST_BASE
+            .iter()
+            .rposition(|&base| base as usize <= distance)
+            .unwrap();
+        self.put_code(index as u32, 5);
+        self.put_bits(
+            (distance - DEFLATE64_DIST_BASE[index] as usize) as u32,
+            u32::from(DEFLATE64_DIST_EXTRA[index]),
+        );
+    }
+
+    fn hash(&self, i: usize) -> usize {
+        let d = &self.data[i..i + 3];
+        ((usize::from(d[0]) << 10) ^ (usize::from(d[1]) << 5) ^ usize::from(d[2]))
+            % DEFLATE64_HASH_SIZE
+    }
+
+    fn insert(&mut self, i: usize) {
+        if i + 3 > self.data.len() {
+            return;
+        }
+        let h = self.hash(i);
+        let offset = self.base + i as u64;
+        self.prev[offset as usize % DEFLATE64_WINDOW_SIZE] = self.head[h];
+        self.head[h] = offset;
+    }
+
+    /// Returns the longest match (length, distance) for the bytes at `i`.
+    fn find_match(&self, i: usize) -> (usize, usize) {
+        let max_len = DEFLATE64_MAX_MATCH.min(self.data.len() - i);
+        if max_len < 3 {
+            return (0, 0);
+        }
+        let offset = self.base + i as u64;
+        let mut candidate = self.head[self.hash(i)];
+        let mut best = (0, 0);
+        for _ in 0..self.max_chain {
+            if candidate == u64::MAX || offset - candidate > DEFLATE64_WINDOW_SIZE as u64 {
+                break;
+            }
+            let j = (candidate - self.base) as usize;
+            let len = self.data[j..]
+                .iter()
+                .zip(&self.data[i..i + max_len])
+                .take_while(|(a, b)| a == b)
+                .count();
+            if len > best.0 {
+                best = (len, i - j);
+                if len == max_len {
+                    break;
+                }
+            }
+            let next = self.prev[candidate as usize % DEFLATE64_WINDOW_SIZE];
+            if next >= candidate {
+                break;
+            }
+            candidate = next;
+        }
+        if best.0 >= 3 { best } else { (0, 0) }
+    }
+
+    /// Encodes the pending input as one block.
+    ///
+    /// Unless `last` is set, the final [`DEFLATE64_MAX_MATCH`] bytes are kept
+    /// back so matches can extend into input that has not arrived yet.
+    fn encode_block(&mut self, last: bool) -> io::Result<()> {
+        let end = if last {
+            self.data.len()
+        } else {
+            self.data.len().saturating_sub(DEFLATE64_MAX_MATCH)
+        };
+        if self.max_chain == 0 {
+            self.encode_stored(end, last);
+        } else {
+            self.put_bits(u32::from(last), 1);
+            self.put_bits(1, 2);
+            let mut i = self.pending;
+            while i < end {
+                let (length, distance) = self.find_match(i);
+                if length == 0 {
+                    self.put_literal(u32::from(self.data[i]));
+                    self.insert(i);
+                    i += 1;
+                } else {
+                    self.put_match(length, distance);
+                    for k in i..i + length {
+                        self.insert(k);
+                    }
+                    i += length;
+                }
+            }
+            self.put_literal(256);
+            self.pending = i;
+        }
+        if last && self.bit_count > 0 {
+            self.put_bits(0, 8 - self.bit_count);
+        }
+        self.output.write_all(&self.out)?;
+        self.out.clear();
+
+        // Keep one window of history before the pending input
+        let keep_from = self.pending.saturating_sub(DEFLATE64_WINDOW_SIZE);
+        if keep_from > 0 {
+            self.data.drain(..keep_from);
+            self.base += keep_from as u64;
+            self.pending -= keep_from;
+        }
+        Ok(())
+    }
+
+    fn encode_stored(&mut self, end: usize, last: bool) {
+        let mut i = self.pending;
+        loop {
+            let len = (end - i).min(0xFFFF);
+            let final_block = last && i + len == end;
+            self.put_bits(u32::from(final_block), 1);
+            self.put_bits(0, 2);
+            if self.bit_count > 0 {
+                self.put_bits(0, 8 - self.bit_count);
+            }
+            self.put_bits(len as u32, 16);
+            self.put_bits(!(len as u32) & 0xFFFF, 16);
+            self.out.extend_from_slice(&self.data[i..i + len]);
+            i += len;
+            if i == end {
+                break;
+            }
+        }
+        self.pending = end;
+    }
+}
+
+impl<W: Write + Send> Write for Deflate64Encoder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.data.extend_from_slice(buf);
+        if self.data.len() - self.pending >= DEFLATE64_BLOCK_SIZE + DEFLATE64_MAX_MATCH {
+            self.encode_block(false)?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.output.flush()
+    }
+}
+
+impl<W: Write + Send> Encoder for Deflate64Encoder<W> {
+    fn method_id(&self) -> &'static [u8] {
+        method::DEFLATE64
+    }
+
+    fn finish(self: Box<Self>) -> io::Result<()> {
+        self.try_finish()?;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -144,6 +784,207 @@ mod tests {
         assert_eq!(decompressed, data);
     }
 
+    /// LSB-first bit writer for hand-built Deflate64 streams.
+    #[derive(Default)]
+    struct BitWriter {
+        bytes: Vec<u8>,
+        bits: u64,
+        count: u32,
+    }
+
+    impl BitWriter {
+        fn bits(&mut self, value: u32, n: u32) {
+            self.bits |= u64::from(value) << self.count;
+            self.count += n;
+            while self.count >= 8 {
+                self.bytes.push(self.bits as u8);
+                self.bits >>= 8;
+                self.count -= 8;
+            }
+        }
+
+        /// Writes a Huffman code, most significant bit first.
+        fn code(&mut self, code: u32, len: u32) {
+            for i in (0..len).rev() {
+                self.bits((code >> i) & 1, 1);
+            }
+        }
+
+        /// Writes a symbol of the fixed literal/length code.
+        fn fixed_literal(&mut self, symbol: u32) {
+            match symbol {
+                0..=143 => self.code(0x30 + symbol, 8),
+                144..=255 => self.code(0x190 + symbol - 144, 9),
+                256..=279 => self.code(symbol - 256, 7),
+                _ => self.code(0xC0 + symbol - 280, 8),
+            }
+        }
+
+        fn finish(mut self) -> Vec<u8> {
+            if self.count > 0 {
+                self.bytes.push(self.bits as u8);
+            }
+            self.bytes
+        }
+    }
+
+    fn inflate64(compressed: &[u8]) -> io::Result<Vec<u8>> {
+        let mut decoder = Deflate64Decoder::new(Cursor::new(compressed));
+        let mut decompressed = Vec::new();
+        decoder.read_to_end(&mut decompressed)?;
+        Ok(decompressed)
+    }
+
+    #[test]
+    fn test_deflate64_long_match_far_back() {
+        // 40000 literals followed by one match that only Deflate64 can express:
+        // length 5000 (code 285 with 16 extra bits) at distance 40000 (code 30)
+        let literals: Vec<u8> = (0..40_000u32).map(|i| (i * 7 + i / 251) as u8).collect();
+        let mut writer = BitWriter::default();
+        writer.bits(1, 1); // final block
+        writer.bits(1, 2); // fixed Huffman
+        for &byte in &literals {
+            writer.fixed_literal(u32::from(byte));
+        }
+        writer.fixed_literal(285);
+        writer.bits(5000 - 3, 16);
+        writer.code(30, 5);
+        writer.bits(40_000 - 32_769, 14);
+        writer.fixed_literal(256);
+        let compressed = writer.finish();
+
+        let mut expected = literals.clone();
+        expected.extend_from_slice(&literals[..5000]);
+        assert_eq!(inflate64(&compressed).unwrap(), expected);
+
+        // Size-limited reads see a prefix of the same output
+        let decoder = Deflate64Decoder::new(Cursor::new(&compressed));
+        let mut prefix = Vec::new();
+        decoder.take(41_000).read_to_end(&mut prefix).unwrap();
+        assert_eq!(prefix, expected[..41_000]);
+
+        // Small reads split matches across calls
+        let mut decoder = Deflate64Decoder::new(Cursor::new(&compressed));
+        let mut chunked = Vec::new();
+        let mut buf = [0u8; 7];
+        loop {
+            let n = decoder.read(&mut buf).unwrap();
+            if n == 0 {
+                break;
+            }
+            chunked.extend_from_slice(&buf[..n]);
+        }
+        assert_eq!(chunked, expected);
+    }
+
+    #[test]
+    fn test_deflate64_stored_and_dynamic_blocks() {
+        // Deflate streams are valid Deflate64 streams as long as they do not
+        // use length code 285, which only appears for matches of 258 bytes
+        let words = ["alpha", "beta", "gamma", "delta", "epsilon", "zeta", "eta"];
+        let mut seed = 12345u32;
+        let mut text = String::new();
+        for _ in 0..2000 {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            text.push_str(words[(seed >> 16) as usize % words.len()]);
+            text.push(' ');
+        }
+        let mut compressed = Vec::new();
+        {
+            let opts = DeflateEncoderOptions::with_level(9);
+            let mut encoder = DeflateEncoder::new(Cursor::new(&mut compressed), &opts);
+            encoder.write_all(text.as_bytes()).unwrap();
+            Box::new(encoder).finish().unwrap();
+        }
+        assert_eq!(inflate64(&compressed).unwrap(), text.as_bytes());
+
+        let opts = DeflateEncoderOptions::with_level(0);
+        let mut stored = Vec::new();
+        {
+            let mut encoder = DeflateEncoder::new(Cursor::new(&mut stored), &opts);
+            encoder.write_all(text.as_bytes()).unwrap();
+            Box::new(encoder).finish().unwrap();
+        }
+        assert_eq!(inflate64(&stored).unwrap(), text.as_bytes());
+    }
+
+    #[test]
+    fn test_deflate64_rejects_invalid_streams() {
+        // Reserved block type
+        let err = inflate64(&[0x07]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        // Distance before the start of the output
+        let mut writer = BitWriter::default();
+        writer.bits(1, 1);
+        writer.bits(1, 2);
+        writer.fixed_literal(u32::from(b'a'));
+        writer.fixed_literal(257);
+        writer.code(1, 5); // distance 2
+        writer.fixed_literal(256);
+        let err = inflate64(&writer.finish()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        // Truncated stream
+        let err = inflate64(&[0x02]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    fn deflate64(data: &[u8], level: u32) -> Vec<u8> {
+        let mut encoder =
+            Deflate64Encoder::new(Vec::new(), &DeflateEncoderOptions::with_level(level));
+        encoder.write_all(data).unwrap();
+        encoder.try_finish().unwrap()
+    }
+
+    #[test]
+    fn test_deflate64_encoder_roundtrip() {
+        let data = b"Hello, World! This is a test of Deflate64 compression.".repeat(50);
+        for level in [0, 1, 6, 9] {
+            assert_eq!(inflate64(&deflate64(&data, level)).unwrap(), data);
+        }
+        assert_eq!(inflate64(&deflate64(b"", 6)).unwrap(), b"");
+        assert_eq!(inflate64(&deflate64(b"ab", 6)).unwrap(), b"ab");
+    }
+
+    #[test]
+    fn test_deflate64_encoder_uses_large_window() {
+        // A 48 KiB pseudo-random chunk repeated: the repeat lies beyond
+        // Deflate's 32 KiB window but within Deflate64's 64 KiB one
+        let mut state = 0x2545_f491_u32;
+        let chunk: Vec<u8> = (0..48 * 1024)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                state as u8
+            })
+            .collect();
+        let data = chunk.repeat(4);
+
+        let compressed = deflate64(&data, 6);
+        assert_eq!(inflate64(&compressed).unwrap(), data);
+        assert!(compressed.len() < chunk.len() + chunk.len() / 4);
+    }
+
+    #[test]
+    fn test_deflate64_encoder_spans_blocks() {
+        // Input larger than one block is written in several calls
+        let data: Vec<u8> = (0..3 * DEFLATE64_BLOCK_SIZE as u32)
+            .map(|i| (i % 251) as u8 ^ (i / 4096) as u8)
+            .collect();
+        let mut encoder = Deflate64Encoder::new(Vec::new(), &DeflateEncoderOptions::with_level(1));
+        for chunk in data.chunks(10_000) {
+            encoder.write_all(chunk).unwrap();
+        }
+        let compressed = encoder.try_finish().unwrap();
+        assert_eq!(inflate64(&compressed).unwrap(), data);
+        assert_eq!(
+            Deflate64Encoder::new(Vec::new(), &DeflateEncoderOptions::default()).method_id(),
+            method::DEFLATE64
+        );
+    }
+
     #[test]
     fn test_deflate_encoder_options() {
         let opts = DeflateEncoderOptions::default();
@@ -25,6 +25,10 @@ impl<R: Read + Send> LzmaDecoder<R> {
     /// * `properties` - LZMA properties (5 bytes: 1 byte props + 4 byte dict size)
     /// * `uncompressed_size` - Expected uncompressed size
     ///
+    /// Any dictionary size up to 4GB - 1 is accepted. The decoder window is
+    /// never larger than `uncompressed_size`, so a large declared dictionary
+    /// only costs memory when the data is that large too.
+    ///
     /// # Errors
     ///
     /// Returns an error if properties are invalid.
@@ -36,7 +40,10 @@ impl<R: Read + Send> LzmaDecoder<R> {
         }
 
         let props_byte = properties[0];
-        let dict_size = u32::from_le_bytes(properties[1..5].try_into().unwrap());
+        // Sizes within 16 bytes of 4GB exceed what the decoder can round up
+        // to its buffer alignment; such windows behave the same when clamped.
+        let dict_size =
+            u32::from_le_bytes(properties[1..5].try_into().unwrap()).min(lzma_rust2::DICT_SIZE_MAX);
 
         let reader = lzma_rust2::LzmaReader::new_with_props(
             input,
@@ -221,6 +228,55 @@ fn decode_lzma2_dict_size(prop: u8) -> Result<u32> {
     Ok(dict_size)
 }
 
+/// Finds the chunks of an LZMA2 stream that reset the dictionary.
+///
+/// Decoding can start afresh at any such chunk. Returns the packed offset of
+/// each one together with the number of unpacked bytes that precede it, by
+/// walking the chunk headers without decoding. The first entry is always at
+/// offset 0 for a well-formed stream.
+pub(crate) fn lzma2_dict_reset_points(data: &[u8]) -> Result<Vec<(usize, u64)>> {
+    let truncated = || Error::InvalidFormat("truncated LZMA2 chunk header".into());
+    let be16 = |pos: usize| -> Result<usize> {
+        let bytes = data.get(pos..pos + 2).ok_or_else(truncated)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]) as usize)
+    };
+
+    let mut points = Vec::new();
+    let mut pos = 0usize;
+    let mut unpacked = 0u64;
+    loop {
+        let control = *data.get(pos).ok_or_else(truncated)?;
+        let (header_len, packed_len, unpacked_len) = match control {
+            0x00 => break,
+            0x01 | 0x02 => {
+                let size = be16(pos + 1)? + 1;
+                (3, size, size)
+            }
+            0x80..=0xFF => {
+                let unpacked_len = (((control & 0x1F) as usize) << 16) + be16(pos + 1)? + 1;
+                let packed_len = be16(pos + 3)? + 1;
+                let props_len = usize::from(control >= 0xC0);
+                (5 + props_len, packed_len, unpacked_len)
+            }
+            _ => {
+                return Err(Error::InvalidFormat(format!(
+                    "invalid LZMA2 control byte {:#04x}",
+                    control
+                )));
+            }
+        };
+
+        // 0x01 and 0xE0-0xFF reset the dictionary
+        if control == 0x01 || control >= 0xE0 {
+            points.push((pos, unpacked));
+        }
+        pos += header_len + packed_len;
+        unpacked += unpacked_len as u64;
+    }
+
+    Ok(points)
+}
+
 /// Encodes a dictionary size into the LZMA2 property byte.
 ///
 /// Returns the property byte (0-40) for the given dictionary size.
@@ -245,6 +301,27 @@ pub fn encode_lzma2_dict_size(dict_size: u32) -> u8 {
     40
 }
 
+/// Match finder used by the LZMA encoder.
+///
+/// The hash chain is faster; the binary tree searches more thoroughly and
+/// usually produces smaller output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchFinder {
+    /// Hash chain over 4-byte prefixes (used by presets 0-3).
+    HashChain4,
+    /// Binary tree over 4-byte prefixes (used by presets 4-9).
+    BinaryTree4,
+}
+
+impl MatchFinder {
+    fn to_mf_type(self) -> lzma_rust2::MfType {
+        match self {
+            Self::HashChain4 => lzma_rust2::MfType::Hc4,
+            Self::BinaryTree4 => lzma_rust2::MfType::Bt4,
+        }
+    }
+}
+
 /// LZMA encoder options.
 #[derive(Debug, Clone)]
 pub struct LzmaEncoderOptions {
@@ -252,6 +329,8 @@ pub struct LzmaEncoderOptions {
     pub preset: u32,
     /// Dictionary size in bytes (optional, uses preset default if None).
     pub dict_size: Option<u32>,
+    /// Match finder (optional, uses preset default if None).
+    pub match_finder: Option<MatchFinder>,
 }
 
 impl Default for LzmaEncoderOptions {
@@ -259,6 +338,7 @@ impl Default for LzmaEncoderOptions {
         Self {
             preset: 6,
             dict_size: None,
+            match_finder: None,
         }
     }
 }
@@ -269,6 +349,7 @@ impl LzmaEncoderOptions {
         Self {
             preset: preset.min(9),
             dict_size: None,
+            match_finder: None,
         }
     }
 
@@ -278,12 +359,21 @@ impl LzmaEncoderOptions {
         self
     }
 
+    /// Sets the match finder, overriding the preset's choice.
+    pub fn with_match_finder(mut self, match_finder: MatchFinder) -> Self {
+        self.match_finder = Some(match_finder);
+        self
+    }
+
     /// Converts to lzma_rust2 options.
     fn to_lzma_options(&self) -> lzma_rust2::LzmaOptions {
         let mut opts = lzma_rust2::LzmaOptions::with_preset(self.preset);
         if let Some(dict_size) = self.dict_size {
             opts.dict_size = dict_size;
         }
+        if let Some(match_finder) = self.match_finder {
+            opts.mf = match_finder.to_mf_type();
+        }
         opts
     }
 
@@ -372,6 +462,8 @@ pub struct Lzma2EncoderOptions {
     pub preset: u32,
     /// Dictionary size in bytes (optional, uses preset default if None).
     pub dict_size: Option<u32>,
+    /// Match finder (optional, uses preset default if None).
+    pub match_finder: Option<MatchFinder>,
 }
 
 impl Default for Lzma2EncoderOptions {
@@ -379,6 +471,7 @@ impl Default for Lzma2EncoderOptions {
         Self {
             preset: 6,
             dict_size: None,
+            match_finder: None,
         }
     }
 }
@@ -389,6 +482,7 @@ impl Lzma2EncoderOptions {
         Self {
             preset: preset.min(9),
             dict_size: None,
+            match_finder: None,
         }
     }
 
@@ -398,12 +492,21 @@ impl Lzma2EncoderOptions {
         self
     }
 
+    /// Sets the match finder, overriding the preset's choice.
+    pub fn with_match_finder(mut self, match_finder: MatchFinder) -> Self {
+        self.match_finder = Some(match_finder);
+        self
+    }
+
     /// Converts to lzma_rust2 options.
     fn to_lzma2_options(&self) -> lzma_rust2::Lzma2Options {
         let mut opts = lzma_rust2::Lzma2Options::with_preset(self.preset);
         if let Some(dict_size) = self.dict_size {
             opts.lzma_options.dict_size = dict_size;
         }
+        if let Some(match_finder) = self.match_finder {
+            opts.lzma_options.mf = match_finder.to_mf_type();
+        }
         opts
     }
 
@@ -480,6 +583,22 @@ impl<W: Write + Send> Encoder for Lzma2Encoder<W> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_lzma2_dict_reset_points() {
+        // Uncompressed chunks: reset "abc", continue "de", reset "f"
+        let stream = [
+            0x01, 0x00, 0x02, b'a', b'b', b'c', 0x02, 0x00, 0x01, b'd', b'e', 0x01, 0x00, 0x00,
+            b'f', 0x00,
+        ];
+        assert_eq!(
+            lzma2_dict_reset_points(&stream).unwrap(),
+            vec![(0, 0), (11, 5)]
+        );
+
+        assert!(lzma2_dict_reset_points(&stream[..2]).is_err());
+        assert!(lzma2_dict_reset_points(&[0x05, 0x00]).is_err());
+    }
+
     #[test]
     fn test_decode_lzma2_dict_size() {
         // Prop 0: 2^12 = 4KB
@@ -511,6 +630,32 @@ mod tests {
         assert!(matches!(err, Error::InvalidFormat(_)));
     }
 
+    #[test]
+    fn test_lzma_decoder_large_dictionary() {
+        use std::io::Cursor;
+
+        let data = b"Hello, World! This is a test of LZMA compression.";
+        let opts = LzmaEncoderOptions::with_preset(0);
+        let mut compressed = Vec::new();
+        {
+            let mut encoder = LzmaEncoder::new(Cursor::new(&mut compressed), &opts).unwrap();
+            encoder.write_all(data).unwrap();
+            Box::new(encoder).finish().unwrap();
+        }
+
+        // A stream may declare a larger dictionary than its encoder used
+        for dict_size in [2u32 << 30, u32::MAX] {
+            let mut props = opts.properties();
+            props[1..5].copy_from_slice(&dict_size.to_le_bytes());
+
+            let reader = Cursor::new(&compressed);
+            let mut decoder = LzmaDecoder::new(reader, &props, data.len() as u64).unwrap();
+            let mut decompressed = Vec::new();
+            decoder.read_to_end(&mut decompressed).unwrap();
+            assert_eq!(decompressed, data);
+        }
+    }
+
     #[test]
     fn test_lzma2_decoder_properties_missing() {
         use std::io::Cursor;
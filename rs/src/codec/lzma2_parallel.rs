@@ -28,7 +28,7 @@ use std::io::{self, Write};
 use rayon::prelude::*;
 
 use super::Encoder;
-use super::lzma::{Lzma2Encoder, Lzma2EncoderOptions, encode_lzma2_dict_size};
+use super::lzma::{Lzma2Encoder, Lzma2EncoderOptions, MatchFinder, encode_lzma2_dict_size};
 use crate::{Error, Result};
 
 /// Default block size for parallel compression (4 MB).
@@ -48,6 +48,8 @@ pub struct ParallelLzma2Options {
     pub threads: Option<usize>,
     /// Block size for parallel compression.
     pub block_size: usize,
+    /// Match finder (None = derived from level).
+    pub match_finder: Option<MatchFinder>,
 }
 
 impl Default for ParallelLzma2Options {
@@ -57,6 +59,7 @@ impl Default for ParallelLzma2Options {
             dict_size: None,
             threads: None,
             block_size: DEFAULT_BLOCK_SIZE,
+            match_finder: None,
         }
     }
 }
@@ -91,6 +94,12 @@ impl ParallelLzma2Options {
         self
     }
 
+    /// Sets the match finder used for every block.
+    pub fn match_finder(mut self, match_finder: MatchFinder) -> Self {
+        self.match_finder = Some(match_finder);
+        self
+    }
+
     /// Returns the effective dictionary size for this configuration.
     pub fn effective_dict_size(&self) -> u32 {
         self.dict_size.unwrap_or(
@@ -130,6 +139,9 @@ impl ParallelLzma2Options {
         if let Some(dict_size) = self.dict_size {
             opts = opts.with_dict_size(dict_size);
         }
+        if let Some(match_finder) = self.match_finder {
+            opts = opts.with_match_finder(match_finder);
+        }
         opts
     }
 }
@@ -384,6 +396,17 @@ impl<W: Write + Send> Write for StreamingParallelLzma2Encoder<W> {
     }
 }
 
+#[cfg(feature = "parallel")]
+impl<W: Write + Send> Encoder for StreamingParallelLzma2Encoder<W> {
+    fn method_id(&self) -> &'static [u8] {
+        super::method::LZMA2
+    }
+
+    fn finish(self: Box<Self>) -> io::Result<()> {
+        StreamingParallelLzma2Encoder::finish(*self).map(drop)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
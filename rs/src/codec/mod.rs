@@ -86,12 +86,13 @@ pub trait Encoder: Write + Send {
     fn finish(self: Box<Self>) -> io::Result<()>;
 }
 
-/// Copy decoder (no compression).
-pub use copy::CopyDecoder;
+/// Copy decoder and encoder (no compression).
+pub use copy::{CopyDecoder, CopyEncoder};
 
 #[cfg(feature = "lzma")]
 pub use lzma::{
     Lzma2Decoder, Lzma2Encoder, Lzma2EncoderOptions, LzmaDecoder, LzmaEncoder, LzmaEncoderOptions,
+    MatchFinder,
 };
 
 #[cfg(all(feature = "lzma", feature = "parallel"))]
@@ -104,7 +105,9 @@ pub use lzma2_parallel::{
 };
 
 #[cfg(feature = "deflate")]
-pub use deflate::{DeflateDecoder, DeflateEncoder, DeflateEncoderOptions};
+pub use deflate::{
+    Deflate64Decoder, Deflate64Encoder, DeflateDecoder, DeflateEncoder, DeflateEncoderOptions,
+};
 
 #[cfg(feature = "bzip2")]
 pub use bzip2::{Bzip2Decoder, Bzip2Encoder, Bzip2EncoderOptions};
@@ -118,7 +121,7 @@ pub use lz4::{Lz4Decoder, Lz4Encoder, Lz4EncoderOptions};
 #[cfg(feature = "zstd")]
 pub use self::zstd::{
     ZstdDecoderWithDict, ZstdDictionary, ZstdEncoderOptions, ZstdEncoderOptionsWithDict,
-    ZstdEncoderWithDict, ZstdStreamDecoder, ZstdStreamEncoder,
+    ZstdEncoderWithDict, ZstdStreamDecoder, ZstdStreamEncoder, train_zstd_dictionary,
 };
 
 #[cfg(feature = "brotli")]
@@ -142,6 +145,33 @@ pub use filters::{
 
 pub use bcj2::{Bcj2Decoder, Bcj2DecoderWrapper, Bcj2EncodedStreams, RangeDecoder, bcj2_encode};
 
+/// Maps zesven compression level (0-9) to Zstd level (1-22).
+///
+/// | Input | Zstd | Characteristic |
+/// |-------|------|----------------|
+/// | 0-1   | 1    | Fastest        |
+/// | 2     | 2    | Fast           |
+/// | 3     | 3    | Fast           |
+/// | 4     | 5    | Balanced       |
+/// | 5     | 7    | Balanced       |
+/// | 6     | 9    | Balanced       |
+/// | 7     | 12   | High           |
+/// | 8     | 15   | High           |
+/// | 9     | 19   | Maximum        |
+#[cfg(feature = "zstd")]
+pub(crate) const ZSTD_LEVEL_MAP: [i32; 10] = [1, 1, 2, 3, 5, 7, 9, 12, 15, 19];
+
+/// Maps zesven compression level (0-9) to Brotli quality (0-11).
+///
+/// | Input | Brotli | Characteristic |
+/// |-------|--------|----------------|
+/// | 0-6   | 0-6    | Direct mapping |
+/// | 7     | 8      | High           |
+/// | 8     | 10     | High           |
+/// | 9     | 11     | Maximum        |
+#[cfg(feature = "brotli")]
+pub(crate) const BROTLI_QUALITY_MAP: [u32; 10] = [0, 1, 2, 3, 4, 5, 6, 8, 10, 11];
+
 /// Method IDs for compression algorithms.
 pub mod method {
     /// Copy (no compression).
@@ -152,6 +182,8 @@ pub mod method {
     pub const LZMA2: &[u8] = &[0x21];
     /// Deflate compression.
     pub const DEFLATE: &[u8] = &[0x04, 0x01, 0x08];
+    /// Deflate64 compression.
+    pub const DEFLATE64: &[u8] = &[0x04, 0x01, 0x09];
     /// BZip2 compression.
     pub const BZIP2: &[u8] = &[0x04, 0x02, 0x02];
     /// PPMd compression.
@@ -215,6 +247,7 @@ pub mod method {
             LZMA => "LZMA",
             LZMA2 => "LZMA2",
             DEFLATE => "Deflate",
+            DEFLATE64 => "Deflate64",
             BZIP2 => "BZip2",
             PPMD => "PPMd",
             LZ4 => "LZ4",
@@ -280,6 +313,13 @@ pub(crate) fn build_decoder<R: Read + Send + 'static>(
             Ok(Box::new(decoder))
         }
 
+        #[cfg(feature = "deflate")]
+        method::DEFLATE64 => {
+            let buf_reader = std::io::BufReader::new(input);
+            let decoder = deflate::Deflate64Decoder::new(buf_reader);
+            Ok(Box::new(decoder))
+        }
+
         #[cfg(feature = "bzip2")]
         method::BZIP2 => {
             let decoder = bzip2::Bzip2Decoder::new(input);
@@ -303,6 +343,12 @@ pub(crate) fn build_decoder<R: Read + Send + 'static>(
 
         #[cfg(feature = "zstd")]
         method::ZSTD => {
+            if let Some(id) = zstd::properties_dictionary_id(properties) {
+                return Err(Error::InvalidFormat(format!(
+                    "ZSTD stream needs dictionary {}; supply it with ExtractOptions::zstd_dictionary",
+                    id
+                )));
+            }
             let decoder = zstd::ZstdStreamDecoder::new(input)
                 .map_err(|e| Error::InvalidFormat(format!("ZSTD init error: {}", e)))?;
             Ok(Box::new(decoder))
@@ -358,7 +404,9 @@ pub(crate) fn build_decoder<R: Read + Send + 'static>(
 
         // AES requires password - use build_decoder_encrypted instead
         #[cfg(feature = "aes")]
-        method::AES => Err(Error::PasswordRequired),
+        method::AES => Err(Error::PasswordRequired {
+            encrypted_header: false,
+        }),
 
         _ => {
             let method_id_u64 = coder.method_id_u64();
@@ -375,6 +423,7 @@ pub(crate) fn build_decoder<R: Read + Send + 'static>(
 /// - Single coder (simple decompression)
 /// - Two coders with filter (filter + codec, e.g., BCJ + LZMA2)
 /// - Two coders without filter (sequential codec chain)
+/// - Linear chains of three or more coders (e.g., Delta + BCJ + LZMA2)
 ///
 /// For encrypted folders, use [`build_encrypted_folder_decoder`] instead.
 ///
@@ -395,6 +444,53 @@ pub(crate) fn build_decoder_chain<R: Read + Send + 'static>(
     input: R,
     folder: &Folder,
     uncompressed_size: u64,
+) -> Result<Box<dyn Read + Send>> {
+    build_decoder_chain_with(
+        Box::new(input),
+        folder,
+        uncompressed_size,
+        &|input, coder, size| build_decoder(input, coder, size),
+    )
+}
+
+/// Builds a decoder chain for a folder whose ZSTD coder may need `dict`.
+///
+/// ZSTD coders whose properties record the dictionary's ID decode with it;
+/// all other coders are built as in [`build_decoder_chain`].
+#[cfg(feature = "zstd")]
+pub(crate) fn build_decoder_chain_with_zstd_dictionary<R: Read + Send + 'static>(
+    input: R,
+    folder: &Folder,
+    uncompressed_size: u64,
+    dict: &ZstdDictionary,
+) -> Result<Box<dyn Read + Send>> {
+    build_decoder_chain_with(
+        Box::new(input),
+        folder,
+        uncompressed_size,
+        &|input, coder, size| {
+            let properties = coder.properties.as_deref().unwrap_or(&[]);
+            let dict_id = zstd::properties_dictionary_id(properties);
+            if coder.method_id.as_slice() == method::ZSTD && dict_id == Some(dict.id()) {
+                let decoder = ZstdStreamDecoder::with_dictionary(input, dict)
+                    .map_err(|e| Error::InvalidFormat(format!("ZSTD init error: {}", e)))?;
+                return Ok(Box::new(decoder));
+            }
+            build_decoder(input, coder, size)
+        },
+    )
+}
+
+/// Builds a decoder for one coder of a folder.
+type CoderDecoderFn<'a> =
+    dyn Fn(Box<dyn Read + Send>, &Coder, u64) -> Result<Box<dyn Decoder>> + 'a;
+
+/// Chains the folder's coders, building each one with `build`.
+fn build_decoder_chain_with(
+    input: Box<dyn Read + Send>,
+    folder: &Folder,
+    uncompressed_size: u64,
+    build: &CoderDecoderFn<'_>,
 ) -> Result<Box<dyn Read + Send>> {
     match folder.coders.len() {
         0 => Err(Error::InvalidFormat("folder has no coders".into())),
@@ -402,7 +498,7 @@ pub(crate) fn build_decoder_chain<R: Read + Send + 'static>(
         1 => {
             // Single coder - simple case
             let coder = &folder.coders[0];
-            let decoder = build_decoder(input, coder, uncompressed_size)?;
+            let decoder = build(input, coder, uncompressed_size)?;
             Ok(Box::new(decoder))
         }
 
@@ -425,10 +521,11 @@ pub(crate) fn build_decoder_chain<R: Read + Send + 'static>(
                     .get(1)
                     .copied()
                     .unwrap_or(uncompressed_size);
-                let codec_decoder = build_decoder(input, codec_coder, codec_output_size)?;
+                let codec_decoder = build(input, codec_coder, codec_output_size)?;
 
                 // Then apply the filter
-                let filter_decoder = build_decoder(codec_decoder, filter_coder, uncompressed_size)?;
+                let filter_decoder =
+                    build(Box::new(codec_decoder), filter_coder, uncompressed_size)?;
 
                 Ok(Box::new(filter_decoder))
             } else {
@@ -439,23 +536,97 @@ pub(crate) fn build_decoder_chain<R: Read + Send + 'static>(
                     .first()
                     .copied()
                     .unwrap_or(uncompressed_size);
-                let first_decoder = build_decoder(input, filter_coder, first_output_size)?;
+                let first_decoder = build(input, filter_coder, first_output_size)?;
 
                 // Second coder processes first decoder's output
-                let second_decoder = build_decoder(first_decoder, codec_coder, uncompressed_size)?;
+                let second_decoder =
+                    build(Box::new(first_decoder), codec_coder, uncompressed_size)?;
 
                 Ok(Box::new(second_decoder))
             }
         }
 
-        _ => {
-            // Complex chains with 3+ coders need special handling
-            // For now, fall back to first coder only (BCJ2 handled separately)
-            let coder = &folder.coders[0];
-            let decoder = build_decoder(input, coder, uncompressed_size)?;
-            Ok(Box::new(decoder))
+        // Longer chains are resolved through the bind pairs (BCJ2 handled separately)
+        _ => build_linear_decoder_chain(input, folder, uncompressed_size, build),
+    }
+}
+
+/// Builds a decoder for a linear chain of single-stream coders.
+///
+/// Starting from the coder that produces the folder output, each coder's
+/// input is followed through `bind_pairs` to the coder feeding it, until
+/// the input bound to the packed stream is reached. The decoders are then
+/// stacked in data-flow order. Folders where a coder has several streams,
+/// or whose coders do not form a single chain, are rejected.
+fn build_linear_decoder_chain(
+    input: Box<dyn Read + Send>,
+    folder: &Folder,
+    uncompressed_size: u64,
+    build: &CoderDecoderFn<'_>,
+) -> Result<Box<dyn Read + Send>> {
+    if folder.packed_streams.len() != 1
+        || folder
+            .coders
+            .iter()
+            .any(|c| c.num_in_streams != 1 || c.num_out_streams != 1)
+    {
+        return Err(Error::UnsupportedFeature {
+            feature: "coder graphs with multiple streams per coder",
+        });
+    }
+
+    let main = folder
+        .main_coder_index()
+        .ok_or_else(|| Error::InvalidFormat("folder has no coders".into()))?;
+    let offsets = folder.coder_stream_offsets();
+
+    // Walk upstream from the folder output to the packed stream
+    let mut chain = vec![main];
+    let mut coder_idx = main;
+    loop {
+        let in_stream_idx = offsets[coder_idx].0 as u64;
+        if folder.find_packed_stream_index(in_stream_idx).is_some() {
+            break;
+        }
+        let bind_pair = folder
+            .find_bind_pair_for_in_stream(in_stream_idx)
+            .ok_or_else(|| {
+                Error::InvalidFormat(format!(
+                    "input stream {} of coder {} is not bound",
+                    in_stream_idx, coder_idx
+                ))
+            })?;
+        // Every coder has exactly one output, so output streams map 1:1 to coders
+        coder_idx = bind_pair.out_index as usize;
+        if coder_idx >= folder.coders.len() || chain.contains(&coder_idx) {
+            return Err(Error::InvalidFormat(
+                "bind pairs do not form a linear coder chain".into(),
+            ));
         }
+        chain.push(coder_idx);
     }
+    if chain.len() != folder.coders.len() {
+        return Err(Error::InvalidFormat(format!(
+            "{} of {} coders are not connected to the folder output",
+            folder.coders.len() - chain.len(),
+            folder.coders.len()
+        )));
+    }
+
+    let mut decoder = input;
+    for &coder_idx in chain.iter().rev() {
+        let output_size = if coder_idx == main {
+            uncompressed_size
+        } else {
+            folder
+                .unpack_sizes
+                .get(coder_idx)
+                .copied()
+                .unwrap_or(uncompressed_size)
+        };
+        decoder = Box::new(build(decoder, &folder.coders[coder_idx], output_size)?);
+    }
+    Ok(decoder)
 }
 
 /// Builds a decoder for an encrypted coder specification.
@@ -518,7 +689,10 @@ impl<R: Read + Send> Decoder for AesDecoderWrapper<R> {
 /// Builds a decoder chain for an encrypted folder.
 ///
 /// This function handles folders where AES encryption is combined with compression.
-/// It decrypts the data first, then applies the compression decoder.
+/// It decrypts the data first, then applies the compression decoder. Folders
+/// of three or more coders in another order than `[AES, filter, compression]`,
+/// such as `[filter, compression, AES]` as written by 7-Zip, are resolved
+/// through their bind pairs like unencrypted linear chains.
 ///
 /// # Arguments
 ///
@@ -535,14 +709,16 @@ impl<R: Read + Send> Decoder for AesDecoderWrapper<R> {
 ///
 /// This function validates the password early by checking if the first
 /// decrypted block looks like valid compression data. This avoids wasting
-/// time decompressing garbage data when the password is wrong.
+/// time decompressing garbage data when the password is wrong. Folders
+/// resolved through their bind pairs are not validated early; a wrong
+/// password is then detected by the CRC check after decompression.
 #[cfg(feature = "aes")]
 pub(crate) fn build_encrypted_folder_decoder<R: Read + Send + 'static>(
     input: R,
     folder: &Folder,
     uncompressed_size: u64,
     password: &crate::crypto::Password,
-) -> Result<Box<dyn Decoder>> {
+) -> Result<Box<dyn Read + Send>> {
     if folder.coders.is_empty() {
         return Err(Error::InvalidFormat("folder has no coders".into()));
     }
@@ -553,7 +729,7 @@ pub(crate) fn build_encrypted_folder_decoder<R: Read + Send + 'static>(
         .iter()
         .position(|c| c.method_id.as_slice() == method::AES);
 
-    match (folder.coders.len(), aes_coder_idx) {
+    let decoder = match (folder.coders.len(), aes_coder_idx) {
         // Single AES coder - just decrypt (data is encrypted but not compressed)
         (1, Some(0)) => {
             let coder = &folder.coders[0];
@@ -647,6 +823,16 @@ pub(crate) fn build_encrypted_folder_decoder<R: Read + Send + 'static>(
             build_decoder(decompressed, filter_coder, filter_size)
         }
 
+        // Other linear chains, e.g. [filter, compression, AES]
+        (3.., Some(_)) => {
+            return build_linear_decoder_chain(
+                Box::new(input),
+                folder,
+                uncompressed_size,
+                &|input, coder, size| build_decoder_encrypted(input, coder, size, password),
+            );
+        }
+
         // No encryption - delegate to non-encrypted decoder
         (_, None) => {
             // This folder is not encrypted - use regular decoder chain
@@ -659,7 +845,8 @@ pub(crate) fn build_encrypted_folder_decoder<R: Read + Send + 'static>(
         _ => Err(Error::UnsupportedFeature {
             feature: "encrypted folder with unsupported coder arrangement",
         }),
-    }
+    }?;
+    Ok(Box::new(decoder))
 }
 
 /// Validates a password against an encrypted folder without full decompression.
@@ -713,6 +900,138 @@ pub fn validate_encrypted_folder_password(
     Ok(aes_decoder.validate_first_block(compression_method)?)
 }
 
+/// Decodes a whole folder from its raw packed streams.
+///
+/// `packed` holds the folder's packed streams in the order of
+/// [`Folder::packed_streams`]: one stream for most folders, four for BCJ2.
+/// Encrypted folders are decrypted with `password`. The output is read up
+/// to `uncompressed_size` bytes, which is normally the folder's
+/// [`final_unpack_size`](Folder::final_unpack_size).
+///
+/// The folder CRC is not checked; compare [`Folder::unpack_crc`] against
+/// the output if it is set.
+///
+/// # Errors
+///
+/// Returns [`Error::PasswordRequired`] for an encrypted folder without a
+/// password, [`Error::InvalidFormat`] if the number of packed streams does
+/// not match the folder or the output is shorter than `uncompressed_size`,
+/// and any error raised by the decoders.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use zesven::codec::decode_folder;
+///
+/// let folder = &header.folders()[0];
+/// let size = folder.final_unpack_size().unwrap_or(0);
+/// let data = decode_folder(folder, &[packed], size, None)?;
+/// ```
+#[cfg(feature = "aes")]
+pub fn decode_folder(
+    folder: &Folder,
+    packed: &[Vec<u8>],
+    uncompressed_size: u64,
+    password: Option<&crate::crypto::Password>,
+) -> Result<Vec<u8>> {
+    if folder
+        .coders
+        .iter()
+        .any(|c| c.method_id.as_slice() == method::AES)
+    {
+        let password = password.ok_or(Error::PasswordRequired {
+            encrypted_header: false,
+        })?;
+        let input = single_packed_stream(folder, packed)?;
+        let decoder = build_encrypted_folder_decoder(
+            Cursor::new(input),
+            folder,
+            uncompressed_size,
+            password,
+        )?;
+        return read_folder_output(decoder, uncompressed_size);
+    }
+
+    let decoder = build_plain_folder_decoder(folder, packed, uncompressed_size)?;
+    read_folder_output(decoder, uncompressed_size)
+}
+
+/// Decodes a whole folder from its raw packed streams.
+///
+/// `packed` holds the folder's packed streams in the order of
+/// [`Folder::packed_streams`]: one stream for most folders, four for BCJ2.
+/// The output is read up to `uncompressed_size` bytes, which is normally
+/// the folder's [`final_unpack_size`](Folder::final_unpack_size).
+///
+/// The folder CRC is not checked; compare [`Folder::unpack_crc`] against
+/// the output if it is set.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidFormat`] if the number of packed streams does
+/// not match the folder or the output is shorter than `uncompressed_size`,
+/// and any error raised by the decoders.
+#[cfg(not(feature = "aes"))]
+pub fn decode_folder(
+    folder: &Folder,
+    packed: &[Vec<u8>],
+    uncompressed_size: u64,
+) -> Result<Vec<u8>> {
+    let decoder = build_plain_folder_decoder(folder, packed, uncompressed_size)?;
+    read_folder_output(decoder, uncompressed_size)
+}
+
+/// Builds the decoder for an unencrypted folder, including BCJ2 folders.
+fn build_plain_folder_decoder(
+    folder: &Folder,
+    packed: &[Vec<u8>],
+    uncompressed_size: u64,
+) -> Result<Box<dyn Read + Send>> {
+    #[cfg(feature = "lzma")]
+    if folder.uses_bcj2() {
+        if packed.len() != folder.packed_streams.len() {
+            return Err(packed_stream_count_error(folder, packed));
+        }
+        return Ok(Box::new(build_bcj2_folder_decoder(folder, packed)?));
+    }
+
+    let input = single_packed_stream(folder, packed)?;
+    build_decoder_chain(Cursor::new(input), folder, uncompressed_size)
+}
+
+/// Returns the only packed stream of a single-input folder.
+fn single_packed_stream(folder: &Folder, packed: &[Vec<u8>]) -> Result<Vec<u8>> {
+    match packed {
+        [stream] if folder.packed_streams.len() == 1 => Ok(stream.clone()),
+        _ => Err(packed_stream_count_error(folder, packed)),
+    }
+}
+
+fn packed_stream_count_error(folder: &Folder, packed: &[Vec<u8>]) -> Error {
+    Error::InvalidFormat(format!(
+        "folder has {} packed streams, got {}",
+        folder.packed_streams.len(),
+        packed.len()
+    ))
+}
+
+/// Reads exactly `uncompressed_size` bytes of folder output.
+fn read_folder_output(decoder: impl Read, uncompressed_size: u64) -> Result<Vec<u8>> {
+    let mut output = Vec::new();
+    decoder
+        .take(uncompressed_size)
+        .read_to_end(&mut output)
+        .map_err(Error::Io)?;
+    if (output.len() as u64) < uncompressed_size {
+        return Err(Error::InvalidFormat(format!(
+            "folder decoded to {} bytes, expected {}",
+            output.len(),
+            uncompressed_size
+        )));
+    }
+    Ok(output)
+}
+
 /// Builds a multi-threaded decoder for LZMA2 streams.
 ///
 /// Falls back to single-threaded decoder for non-LZMA2 methods.
@@ -965,6 +1284,8 @@ pub enum CodecMethod {
     Lzma2,
     /// Deflate compression.
     Deflate,
+    /// Deflate64 compression (Deflate with a 64 KiB window).
+    Deflate64,
     /// BZip2 compression.
     BZip2,
     /// PPMd compression.
@@ -985,6 +1306,7 @@ impl CodecMethod {
             method::LZMA => Ok(Self::Lzma),
             method::LZMA2 => Ok(Self::Lzma2),
             method::DEFLATE => Ok(Self::Deflate),
+            method::DEFLATE64 => Ok(Self::Deflate64),
             method::BZIP2 => Ok(Self::BZip2),
             method::PPMD => Ok(Self::PPMd),
             method::LZ4 => Ok(Self::Lz4),
@@ -1008,6 +1330,7 @@ impl CodecMethod {
             Self::Lzma => 0x030101,
             Self::Lzma2 => 0x21,
             Self::Deflate => 0x040108,
+            Self::Deflate64 => 0x040109,
             Self::BZip2 => 0x040202,
             Self::PPMd => 0x030401,
             Self::Lz4 => 0x04F71104,
@@ -1016,6 +1339,122 @@ impl CodecMethod {
         }
     }
 
+    /// Builds an encoder for this method that writes compressed data to `output`.
+    ///
+    /// `level` (0-9) is mapped the same way [`Writer`](crate::Writer) maps
+    /// [`WriteOptions::level`](crate::WriteOptions::level): LZMA and LZMA2 use
+    /// a dictionary of `64 KiB << min(level, 7)`, Zstd and Brotli go through
+    /// their level tables, and PPMd picks its order and memory size from the
+    /// level. Call [`Encoder::finish`] once all data has been written.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidCompressionLevel`] if `level` is greater than 9
+    /// and [`Error::UnsupportedMethod`] if the codec's feature is not enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Write;
+    /// use zesven::codec::CodecMethod;
+    ///
+    /// let mut compressed = Vec::new();
+    /// let mut encoder = CodecMethod::Copy.build_encoder(&mut compressed, 5)?;
+    /// encoder.write_all(b"data")?;
+    /// encoder.finish()?;
+    /// assert_eq!(compressed, b"data");
+    /// # Ok::<(), zesven::Error>(())
+    /// ```
+    pub fn build_encoder<'a, W: Write + Send + 'a>(
+        &self,
+        output: W,
+        level: u8,
+    ) -> Result<Box<dyn Encoder + 'a>> {
+        if level > 9 {
+            return Err(Error::InvalidCompressionLevel {
+                level: level as u32,
+            });
+        }
+
+        match self {
+            Self::Copy => Ok(Box::new(CopyEncoder::new(output))),
+            #[cfg(feature = "lzma")]
+            Self::Lzma => {
+                let opts = LzmaEncoderOptions {
+                    dict_size: Some(1 << (16 + level.min(7))),
+                    ..Default::default()
+                };
+                Ok(Box::new(LzmaEncoder::new(output, &opts)?))
+            }
+            #[cfg(feature = "lzma")]
+            Self::Lzma2 => {
+                let opts = Lzma2EncoderOptions {
+                    dict_size: Some(1 << (16 + level.min(7))),
+                    ..Default::default()
+                };
+                Ok(Box::new(Lzma2Encoder::new(output, &opts)))
+            }
+            #[cfg(feature = "deflate")]
+            Self::Deflate => {
+                let opts = DeflateEncoderOptions {
+                    level: level as u32,
+                };
+                Ok(Box::new(DeflateEncoder::new(output, &opts)))
+            }
+            #[cfg(feature = "deflate")]
+            Self::Deflate64 => {
+                let opts = DeflateEncoderOptions {
+                    level: level as u32,
+                };
+                Ok(Box::new(Deflate64Encoder::new(output, &opts)))
+            }
+            #[cfg(feature = "bzip2")]
+            Self::BZip2 => {
+                let opts = Bzip2EncoderOptions {
+                    level: level as u32,
+                    block_size: None,
+                };
+                Ok(Box::new(Bzip2Encoder::new(output, &opts)))
+            }
+            #[cfg(feature = "ppmd")]
+            Self::PPMd => {
+                let (order, mem_size) = match level {
+                    0..=2 => (4, 4 * 1024 * 1024),
+                    3..=4 => (6, 8 * 1024 * 1024),
+                    5..=6 => (6, 16 * 1024 * 1024),
+                    7..=8 => (8, 32 * 1024 * 1024),
+                    _ => (8, 64 * 1024 * 1024),
+                };
+                let opts = PpmdEncoderOptions::new(order, mem_size);
+                Ok(Box::new(PpmdEncoder::new(output, &opts)?))
+            }
+            #[cfg(feature = "lz4")]
+            Self::Lz4 => Ok(Box::new(Lz4Encoder::new(
+                output,
+                &Lz4EncoderOptions::default(),
+            ))),
+            #[cfg(feature = "zstd")]
+            Self::Zstd => {
+                let opts = ZstdEncoderOptions {
+                    level: ZSTD_LEVEL_MAP[level as usize],
+                };
+                Ok(Box::new(ZstdStreamEncoder::new(output, &opts)?))
+            }
+            #[cfg(feature = "brotli")]
+            Self::Brotli => {
+                let opts = BrotliEncoderOptions {
+                    quality: BROTLI_QUALITY_MAP[level as usize],
+                    lg_window_size: 22,
+                };
+                Ok(Box::new(BrotliEncoder::new(output, &opts)))
+            }
+            #[allow(unreachable_patterns)]
+            _ => Err(Error::UnsupportedMethod {
+                method_id: self.method_id(),
+            }),
+        }
+    }
+
     /// Returns whether this codec is available in the current build.
     ///
     /// Some codecs require optional features to be enabled at compile time.
@@ -1038,7 +1477,7 @@ impl CodecMethod {
         match self {
             Self::Copy => true,
             Self::Lzma | Self::Lzma2 => cfg!(feature = "lzma"),
-            Self::Deflate => cfg!(feature = "deflate"),
+            Self::Deflate | Self::Deflate64 => cfg!(feature = "deflate"),
             Self::BZip2 => cfg!(feature = "bzip2"),
             Self::PPMd => cfg!(feature = "ppmd"),
             Self::Lz4 => cfg!(feature = "lz4"),
@@ -1064,7 +1503,7 @@ impl CodecMethod {
         match self {
             Self::Copy => None,
             Self::Lzma | Self::Lzma2 => Some("lzma"),
-            Self::Deflate => Some("deflate"),
+            Self::Deflate | Self::Deflate64 => Some("deflate"),
             Self::BZip2 => Some("bzip2"),
             Self::PPMd => Some("ppmd"),
             Self::Lz4 => Some("lz4"),
@@ -1094,6 +1533,8 @@ pub enum FilterMethod {
     BcjIa64,
     /// BCJ (RISC-V) filter.
     BcjRiscv,
+    /// BCJ2 (x86) 4-stream filter.
+    Bcj2,
     /// Delta filter.
     Delta,
 }
@@ -1110,6 +1551,7 @@ impl FilterMethod {
             method::BCJ_SPARC => Ok(Self::BcjSparc),
             method::BCJ_IA64 => Ok(Self::BcjIa64),
             method::BCJ_RISCV => Ok(Self::BcjRiscv),
+            method::BCJ2 => Ok(Self::Bcj2),
             method::DELTA => Ok(Self::Delta),
             _ => Err(Error::UnsupportedMethod {
                 method_id: coder.method_id_u64(),
@@ -1231,6 +1673,10 @@ mod tests {
             FilterMethod::from_coder(&make_coder(method::DELTA)).unwrap(),
             FilterMethod::Delta
         );
+        assert_eq!(
+            FilterMethod::from_coder(&make_coder(method::BCJ2)).unwrap(),
+            FilterMethod::Bcj2
+        );
     }
 
     #[test]
@@ -1299,6 +1745,7 @@ mod tests {
         assert_eq!(CodecMethod::Lzma.required_feature(), Some("lzma"));
         assert_eq!(CodecMethod::Lzma2.required_feature(), Some("lzma"));
         assert_eq!(CodecMethod::Deflate.required_feature(), Some("deflate"));
+        assert_eq!(CodecMethod::Deflate64.required_feature(), Some("deflate"));
         assert_eq!(CodecMethod::BZip2.required_feature(), Some("bzip2"));
         assert_eq!(CodecMethod::PPMd.required_feature(), Some("ppmd"));
         assert_eq!(CodecMethod::Lz4.required_feature(), Some("lz4"));
@@ -1315,6 +1762,7 @@ mod tests {
             CodecMethod::Lzma,
             CodecMethod::Lzma2,
             CodecMethod::Deflate,
+            CodecMethod::Deflate64,
             CodecMethod::BZip2,
             CodecMethod::PPMd,
             CodecMethod::Lz4,
@@ -1354,6 +1802,48 @@ mod tests {
         assert_eq!(decoder.method_id(), method::COPY);
     }
 
+    /// Tests that build_encoder() output decodes with build_decoder().
+    #[test]
+    fn test_build_encoder_roundtrip() {
+        let data = b"build_encoder roundtrip data, build_encoder roundtrip data".repeat(20);
+        let methods = [
+            (CodecMethod::Copy, None),
+            // 2 MiB dictionary, as used at level 5
+            (CodecMethod::Lzma2, Some(vec![18])),
+            (CodecMethod::Deflate, None),
+            (CodecMethod::Deflate64, None),
+            (CodecMethod::BZip2, None),
+            (CodecMethod::Zstd, None),
+            (CodecMethod::Brotli, None),
+        ];
+        for (codec, properties) in methods.into_iter().filter(|(m, _)| m.is_available()) {
+            let mut compressed = Vec::new();
+            let mut encoder = codec.build_encoder(&mut compressed, 5).unwrap();
+            let coder = Coder {
+                properties,
+                ..make_coder(encoder.method_id())
+            };
+            encoder.write_all(&data).unwrap();
+            encoder.finish().unwrap();
+
+            let mut decoder =
+                build_decoder(Cursor::new(compressed), &coder, data.len() as u64).unwrap();
+            let mut output = Vec::new();
+            decoder.read_to_end(&mut output).unwrap();
+            assert_eq!(output, data, "{:?}", codec);
+        }
+    }
+
+    /// Tests that build_encoder() rejects levels above 9.
+    #[test]
+    fn test_build_encoder_invalid_level() {
+        let result = CodecMethod::Copy.build_encoder(Vec::new(), 10);
+        assert!(matches!(
+            result,
+            Err(Error::InvalidCompressionLevel { level: 10 })
+        ));
+    }
+
     /// Tests that build_decoder() returns UnsupportedMethod for unknown method IDs.
     #[test]
     fn test_build_decoder_unsupported_method() {
@@ -1388,7 +1878,9 @@ mod tests {
         let result = build_decoder(cursor, &coder, 100);
 
         match result {
-            Err(Error::PasswordRequired) => {
+            Err(Error::PasswordRequired {
+                encrypted_header: false,
+            }) => {
                 // Expected - password required for AES decoding
             }
             Err(other) => panic!("Expected PasswordRequired, got: {:?}", other),
@@ -1464,6 +1956,20 @@ mod tests {
         }
     }
 
+    /// Tests that build_decoder() creates a Deflate64 decoder.
+    #[cfg(feature = "deflate")]
+    #[test]
+    fn test_build_decoder_deflate64() {
+        let coder = make_coder(method::DEFLATE64);
+        let cursor = Cursor::new(vec![0u8; 100]);
+
+        let result = build_decoder(cursor, &coder, 0);
+        match result {
+            Ok(decoder) => assert_eq!(decoder.method_id(), method::DEFLATE64),
+            Err(e) => panic!("Should create Deflate64 decoder: {}", e),
+        }
+    }
+
     /// Tests that build_decoder() creates a working BZip2 decoder.
     #[cfg(feature = "bzip2")]
     #[test]
@@ -1645,4 +2151,169 @@ mod tests {
         assert_eq!(output.len(), limit as usize);
         assert_eq!(&output[..], &data[..limit as usize]);
     }
+
+    /// Encodes `data` with Delta(4), then BCJ x86, then LZMA2.
+    #[cfg(feature = "lzma")]
+    fn delta_bcj_lzma2(data: &[u8]) -> (Vec<u8>, Vec<u8>) {
+        let options = lzma::Lzma2EncoderOptions::default();
+        let mut packed = Vec::new();
+        let lzma2 = lzma::Lzma2Encoder::new(&mut packed, &options);
+        let bcj = bcj_encoders::BcjX86Encoder::new(lzma2);
+        let mut delta = bcj_encoders::DeltaEncoder::new(bcj, 4);
+        delta.write_all(data).unwrap();
+        delta
+            .try_finish()
+            .unwrap()
+            .try_finish()
+            .unwrap()
+            .try_finish()
+            .unwrap();
+        (packed, lzma::Lzma2Encoder::<Vec<u8>>::properties(&options))
+    }
+
+    /// Builds a folder from `(coder, in_index <- out_index)` bindings.
+    #[cfg(feature = "lzma")]
+    fn chain_folder(coders: Vec<Coder>, bind_pairs: &[(u64, u64)], packed: u64) -> Folder {
+        let unpack_sizes = vec![0; coders.len()];
+        Folder {
+            coders,
+            bind_pairs: bind_pairs
+                .iter()
+                .map(|&(in_index, out_index)| crate::format::streams::BindPair {
+                    in_index,
+                    out_index,
+                })
+                .collect(),
+            packed_streams: vec![packed],
+            unpack_sizes,
+            unpack_crc: None,
+        }
+    }
+
+    #[cfg(feature = "lzma")]
+    #[test]
+    fn test_build_decoder_chain_three_coders() {
+        let data: Vec<u8> = (0..20_000u32)
+            .flat_map(|i| [0xE8, (i % 7) as u8, 0, 0, (i % 251) as u8])
+            .collect();
+        let (packed, lzma2_props) = delta_bcj_lzma2(&data);
+
+        let delta = Coder {
+            properties: Some(vec![3]),
+            ..make_coder(method::DELTA)
+        };
+        let bcj = make_coder(method::BCJ_X86);
+        let lzma2 = Coder {
+            properties: Some(lzma2_props),
+            ..make_coder(method::LZMA2)
+        };
+
+        // Standard layout: [Delta, BCJ, LZMA2] with LZMA2 reading the packed stream
+        let mut folder = chain_folder(
+            vec![delta.clone(), bcj.clone(), lzma2.clone()],
+            &[(0, 1), (1, 2)],
+            2,
+        );
+        folder.unpack_sizes = vec![data.len() as u64; 3];
+        let mut output = Vec::new();
+        build_decoder_chain(Cursor::new(packed.clone()), &folder, data.len() as u64)
+            .unwrap()
+            .read_to_end(&mut output)
+            .unwrap();
+        assert_eq!(output, data);
+
+        // The chain is resolved through bind pairs, not coder order
+        let mut folder = chain_folder(vec![lzma2, delta, bcj], &[(1, 2), (2, 0)], 0);
+        folder.unpack_sizes = vec![data.len() as u64; 3];
+        let mut output = Vec::new();
+        build_decoder_chain(Cursor::new(packed), &folder, data.len() as u64)
+            .unwrap()
+            .read_to_end(&mut output)
+            .unwrap();
+        assert_eq!(output, data);
+    }
+
+    #[cfg(all(feature = "aes", feature = "lzma2"))]
+    #[test]
+    fn test_build_encrypted_folder_decoder_any_coder_order() {
+        use crate::read::Archive;
+        use crate::{ArchivePath, Password, WriteOptions, Writer};
+
+        let data: Vec<u8> = (0..20_000u32)
+            .flat_map(|i| [0xE8, (i % 7) as u8, 0, 0, (i % 251) as u8])
+            .collect();
+        let options = WriteOptions::new()
+            .method(CodecMethod::Lzma2)
+            .bcj_x86()
+            .password("secret")
+            .encrypt_data(true);
+        let mut writer = Writer::create(Cursor::new(Vec::new()))
+            .unwrap()
+            .options(options);
+        writer
+            .add_bytes(ArchivePath::new("code.bin").unwrap(), &data)
+            .unwrap();
+        let (_, cursor) = writer.finish_into_inner().unwrap();
+        let bytes = cursor.into_inner();
+
+        let archive = Archive::open_with_password(Cursor::new(bytes.clone()), "secret").unwrap();
+        let folder = archive.header.unpack_info.as_ref().unwrap().folders[0].clone();
+        let pack_info = archive.header.pack_info.as_ref().unwrap();
+        let pack_start = (32 + pack_info.pack_pos) as usize;
+        let packed = bytes[pack_start..pack_start + pack_info.pack_sizes[0] as usize].to_vec();
+        let password = Password::new("secret");
+
+        // Written as [AES, BCJ, LZMA2]
+        assert_eq!(folder.coders[0].method_id.as_slice(), method::AES);
+        let decode = |folder: &Folder| {
+            let mut output = Vec::new();
+            build_encrypted_folder_decoder(
+                Cursor::new(packed.clone()),
+                folder,
+                data.len() as u64,
+                &password,
+            )
+            .unwrap()
+            .take(data.len() as u64)
+            .read_to_end(&mut output)
+            .unwrap();
+            output
+        };
+        assert_eq!(decode(&folder), data);
+
+        // 7-Zip order: [BCJ, LZMA2, AES] with AES reading the packed stream
+        let mut reordered = chain_folder(
+            vec![
+                folder.coders[1].clone(),
+                folder.coders[2].clone(),
+                folder.coders[0].clone(),
+            ],
+            &[(0, 1), (1, 2)],
+            2,
+        );
+        reordered.unpack_sizes = vec![
+            folder.unpack_sizes[1],
+            folder.unpack_sizes[2],
+            folder.unpack_sizes[0],
+        ];
+        assert_eq!(decode(&reordered), data);
+    }
+
+    #[cfg(feature = "lzma")]
+    #[test]
+    fn test_build_decoder_chain_rejects_unsupported_topologies() {
+        let copy = || make_coder(method::COPY);
+
+        // A coder with two inputs is not a linear chain
+        let mut folder = chain_folder(vec![copy(), copy(), copy()], &[(0, 1), (1, 2)], 3);
+        folder.coders[0].num_in_streams = 2;
+        folder.packed_streams = vec![2, 3];
+        let err = build_decoder_chain(Cursor::new(Vec::new()), &folder, 0).err();
+        assert!(matches!(err, Some(Error::UnsupportedFeature { .. })));
+
+        // Bind pairs forming a cycle leave part of the folder disconnected
+        let folder = chain_folder(vec![copy(), copy(), copy()], &[(1, 2), (2, 1)], 0);
+        let err = build_decoder_chain(Cursor::new(Vec::new()), &folder, 0).err();
+        assert!(matches!(err, Some(Error::InvalidFormat(_))));
+    }
 }
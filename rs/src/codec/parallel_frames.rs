@@ -29,7 +29,8 @@
 //!
 //! // Compress data into frames
 //! let encoder = ParallelFrameEncoder::new(FrameCodec::Lzma2, 5)
-//!     .frame_size(1024 * 1024);  // 1 MB frames
+//!     .frame_size(1024 * 1024)  // 1 MB frames
+//!     .with_threads(4);         // 4 worker threads
 //!
 //! let result = encoder.compress(&large_data)?;
 //! println!("Compressed {} frames", result.frame_count);
@@ -236,6 +237,7 @@ pub struct ParallelFrameEncoder {
     codec: FrameCodec,
     level: i32,
     frame_size: usize,
+    threads: Option<usize>,
 }
 
 impl ParallelFrameEncoder {
@@ -245,6 +247,7 @@ impl ParallelFrameEncoder {
             codec,
             level: level.clamp(1, 22),
             frame_size: 4 * 1024 * 1024, // 4 MB default
+            threads: None,
         }
     }
 
@@ -254,6 +257,17 @@ impl ParallelFrameEncoder {
         self
     }
 
+    /// Sets the number of worker threads used to compress frames.
+    ///
+    /// By default the global rayon pool is used. A count of 1 compresses
+    /// frames sequentially on the calling thread; 0 is treated as 1. The
+    /// output is identical for every thread count. Without the `parallel`
+    /// feature, frames are always compressed sequentially.
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = Some(threads.max(1));
+        self
+    }
+
     /// Compresses data into parallel frames.
     #[cfg(feature = "parallel")]
     pub fn compress(&self, data: &[u8]) -> io::Result<FrameCompressionResult> {
@@ -263,19 +277,18 @@ impl ParallelFrameEncoder {
 
         // Split data into chunks
         let chunks: Vec<&[u8]> = data.chunks(self.frame_size).collect();
-        let frame_count = chunks.len();
-
-        // Compress chunks in parallel
-        let compressed_frames: Vec<io::Result<Vec<u8>>> = chunks
-            .par_iter()
-            .map(|chunk| self.codec.compress_frame(chunk, self.level))
-            .collect();
 
-        // Collect results, propagating any errors
-        let mut frames: Vec<Vec<u8>> = Vec::with_capacity(frame_count);
-        for result in compressed_frames {
-            frames.push(result?);
-        }
+        let frames = match self.threads {
+            Some(1) => self.compress_frames_sequential(&chunks)?,
+            Some(threads) => {
+                let pool = rayon::ThreadPoolBuilder::new()
+                    .num_threads(threads)
+                    .build()
+                    .map_err(io::Error::other)?;
+                pool.install(|| self.compress_frames_parallel(&chunks))?
+            }
+            None => self.compress_frames_parallel(&chunks)?,
+        };
 
         self.build_result(data, &chunks, frames)
     }
@@ -289,17 +302,29 @@ impl ParallelFrameEncoder {
 
         // Split data into chunks
         let chunks: Vec<&[u8]> = data.chunks(self.frame_size).collect();
-        let frame_count = chunks.len();
-
-        // Compress chunks sequentially
-        let mut frames: Vec<Vec<u8>> = Vec::with_capacity(frame_count);
-        for chunk in &chunks {
-            frames.push(self.codec.compress_frame(chunk, self.level)?);
-        }
+        let frames = self.compress_frames_sequential(&chunks)?;
 
         self.build_result(data, &chunks, frames)
     }
 
+    /// Compresses chunks in parallel on the current rayon pool.
+    #[cfg(feature = "parallel")]
+    fn compress_frames_parallel(&self, chunks: &[&[u8]]) -> io::Result<Vec<Vec<u8>>> {
+        // Collecting into a Result propagates the first error in frame order
+        chunks
+            .par_iter()
+            .map(|chunk| self.codec.compress_frame(chunk, self.level))
+            .collect()
+    }
+
+    /// Compresses chunks one after another on the calling thread.
+    fn compress_frames_sequential(&self, chunks: &[&[u8]]) -> io::Result<Vec<Vec<u8>>> {
+        chunks
+            .iter()
+            .map(|chunk| self.codec.compress_frame(chunk, self.level))
+            .collect()
+    }
+
     fn create_empty_result(&self) -> FrameCompressionResult {
         let mut data = Vec::new();
         data.extend_from_slice(FRAME_MAGIC);
@@ -767,6 +792,42 @@ mod tests {
         assert_eq!(decompressed, data);
     }
 
+    #[test]
+    fn test_thread_count_does_not_change_output() {
+        let data: Vec<u8> = (0..20_000u32).map(|i| (i * 7 % 251) as u8).collect();
+
+        let single = ParallelFrameEncoder::new(FrameCodec::Copy, 1)
+            .frame_size(4096)
+            .with_threads(1)
+            .compress(&data)
+            .unwrap();
+        let multi = ParallelFrameEncoder::new(FrameCodec::Copy, 1)
+            .frame_size(4096)
+            .with_threads(4)
+            .compress(&data)
+            .unwrap();
+
+        assert_eq!(single.data, multi.data);
+        for result in [&single, &multi] {
+            assert_eq!(result.frame_count, 5);
+            let sizes: Vec<u64> = result
+                .index
+                .frames
+                .iter()
+                .map(|f| f.uncompressed_size)
+                .collect();
+            assert_eq!(sizes, [4096, 4096, 4096, 4096, 3616]);
+            let offsets: Vec<u64> = result.index.frames.iter().map(|f| f.offset).collect();
+            assert_eq!(offsets, [0, 4096, 8192, 12288, 16384]);
+            assert_eq!(result.index.total_uncompressed_size(), data.len() as u64);
+
+            let decompressed = ParallelFrameDecoder::new()
+                .decompress(&result.data)
+                .unwrap();
+            assert_eq!(decompressed, data);
+        }
+    }
+
     #[test]
     fn test_invalid_magic() {
         let data = b"XXXX\x00\x01\x00";
@@ -290,7 +290,7 @@ impl RadixMatchFinder {
         }
 
         // Sort by length descending
-        matches.sort_by(|a, b| b.length.cmp(&a.length));
+        matches.sort_by_key(|b| std::cmp::Reverse(b.length));
         matches
     }
 
@@ -63,6 +63,15 @@ impl<R: Read + Send> ZstdStreamDecoder<R> {
         let decoder = ZstdDecoder::new(input)?;
         Ok(Self { inner: decoder })
     }
+
+    /// Creates a new ZSTD decoder for streams compressed with `dict`.
+    ///
+    /// Unlike [`ZstdDecoderWithDict`], the dictionary is copied into the
+    /// decoder, so the decoder does not borrow it.
+    pub fn with_dictionary(input: R, dict: &ZstdDictionary) -> io::Result<Self> {
+        let decoder = ZstdDecoder::with_dictionary(BufReader::new(input), dict.as_bytes())?;
+        Ok(Self { inner: decoder })
+    }
 }
 
 impl<R: Read + Send> Read for ZstdStreamDecoder<R> {
@@ -102,6 +111,19 @@ impl<'a, W: Write + Send> ZstdStreamEncoder<'a, W> {
         Ok(Self { inner: encoder })
     }
 
+    /// Creates a new ZSTD encoder that compresses with `dict`.
+    ///
+    /// The dictionary is copied into the encoder, so the encoder does not
+    /// borrow it.
+    pub fn with_dictionary(
+        output: W,
+        options: &ZstdEncoderOptions,
+        dict: &ZstdDictionary,
+    ) -> io::Result<Self> {
+        let encoder = ZstdEncoderInner::with_dictionary(output, options.level, dict.as_bytes())?;
+        Ok(Self { inner: encoder })
+    }
+
     /// Finishes encoding and returns the underlying writer.
     pub fn try_finish(self) -> io::Result<W> {
         self.inner.finish()
@@ -192,10 +214,12 @@ impl ZstdDictionary {
     /// - Larger dictionaries can provide better compression but use more memory
     pub fn train(samples: &[Vec<u8>], dict_size: usize) -> io::Result<Self> {
         let sample_refs: Vec<&[u8]> = samples.iter().map(|s| s.as_slice()).collect();
-        let dict_data =
-            zstd::dict::from_samples(&sample_refs, dict_size).map_err(io::Error::other)?;
+        Self::from_bytes(train_zstd_dictionary(&sample_refs, dict_size)?)
+    }
 
-        Self::from_bytes(dict_data)
+    /// Returns the coder properties recording this dictionary's ID.
+    pub(crate) fn coder_properties(&self) -> Vec<u8> {
+        self.id.to_le_bytes().to_vec()
     }
 
     /// Creates a dictionary from raw dictionary data.
@@ -259,6 +283,30 @@ impl ZstdDictionary {
     }
 }
 
+/// Trains a dictionary from sample data and returns its raw bytes.
+///
+/// The result can be passed to
+/// [`WriteOptions::zstd_dictionary`](crate::WriteOptions::zstd_dictionary)
+/// or loaded with [`ZstdDictionary::from_bytes`].
+///
+/// # Arguments
+///
+/// * `samples` - Sample data to train on
+/// * `dict_size` - Target dictionary size in bytes (typically 4KB-128KB)
+pub fn train_zstd_dictionary(samples: &[&[u8]], dict_size: usize) -> io::Result<Vec<u8>> {
+    zstd::dict::from_samples(samples, dict_size).map_err(io::Error::other)
+}
+
+/// Returns the dictionary ID recorded in ZSTD coder properties.
+///
+/// Streams compressed with a dictionary carry its 4-byte ID as coder
+/// properties. Properties of other lengths, such as the 3- or 5-byte
+/// version records written by other tools, carry no dictionary.
+pub(crate) fn properties_dictionary_id(properties: &[u8]) -> Option<u32> {
+    let id: [u8; 4] = properties.try_into().ok()?;
+    Some(u32::from_le_bytes(id))
+}
+
 /// ZSTD decoder that uses a pre-loaded dictionary.
 ///
 /// Dictionary decompression is faster than loading the dictionary for each
@@ -495,6 +543,44 @@ mod tests {
         assert_eq!(decompressed, original);
     }
 
+    #[test]
+    fn test_zstd_dictionary_coder_properties() {
+        let mut dict_data = vec![0u8; 128];
+        dict_data[..8].copy_from_slice(&[0x37, 0xA4, 0x30, 0xEC, 0x78, 0x56, 0x34, 0x12]);
+        let dict = ZstdDictionary::from_bytes(dict_data).unwrap();
+
+        assert_eq!(
+            properties_dictionary_id(&dict.coder_properties()),
+            Some(0x12345678)
+        );
+        // Version records written by other tools carry no dictionary
+        assert_eq!(properties_dictionary_id(&[1, 5, 3]), None);
+        assert_eq!(properties_dictionary_id(&[1, 5, 3, 0, 0]), None);
+        assert_eq!(properties_dictionary_id(&[]), None);
+    }
+
+    #[test]
+    fn test_zstd_stream_codec_with_dictionary() {
+        let samples: Vec<Vec<u8>> = (0..50)
+            .map(|i| format!("prefix_data_{}_suffix", i).into_bytes())
+            .collect();
+        let sample_refs: Vec<&[u8]> = samples.iter().map(|s| s.as_slice()).collect();
+        let dict_data = train_zstd_dictionary(&sample_refs, 4096).unwrap();
+        let dict = ZstdDictionary::from_bytes(dict_data).unwrap();
+
+        let original = b"prefix_data_999_suffix";
+        let options = ZstdEncoderOptions::default();
+        let mut encoder = ZstdStreamEncoder::with_dictionary(Vec::new(), &options, &dict).unwrap();
+        encoder.write_all(original).unwrap();
+        let compressed = encoder.try_finish().unwrap();
+
+        let mut decoder =
+            ZstdStreamDecoder::with_dictionary(Cursor::new(compressed), &dict).unwrap();
+        let mut decompressed = Vec::new();
+        decoder.read_to_end(&mut decompressed).unwrap();
+        assert_eq!(decompressed, original);
+    }
+
     #[test]
     fn test_zstd_dictionary_compression_improvement() {
         // Train a dictionary on specific data
@@ -17,7 +17,7 @@ mod properties;
 use aes::Aes256;
 use cbc::cipher::{BlockDecryptMut, BlockEncryptMut, KeyIvInit};
 use sha2::{Digest, Sha256};
-use std::io::{self, Read, Write};
+use std::io::{self, Read, Seek, SeekFrom, Write};
 use std::num::NonZeroUsize;
 use std::sync::Mutex;
 
@@ -45,6 +45,9 @@ fn lock_or_recover<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
 /// AES block size in bytes.
 const BLOCK_SIZE: usize = 16;
 
+/// Size of the decoder's reusable ciphertext buffer (a multiple of [`BLOCK_SIZE`]).
+const DECRYPT_BUFFER_SIZE: usize = 4096;
+
 /// Maximum allowed value for `num_cycles_power` in key derivation.
 ///
 /// This limits key derivation to 2^30 = ~1 billion iterations, which takes
@@ -343,16 +346,8 @@ fn validate_lzma_header(data: &[u8]) -> bool {
         return false;
     }
 
-    // Additional heuristic: dictionary size should be reasonable
-    // (if we have enough data)
-    if data.len() >= 5 {
-        let dict_size = u32::from_le_bytes([data[1], data[2], data[3], data[4]]);
-        // Dict size should be power of 2 or close to it, and <= 1GB
-        if dict_size > 1 << 30 {
-            return false;
-        }
-    }
-
+    // The dictionary size in bytes 1..5 is not checked: LZMA accepts any
+    // 32-bit value, and 7-Zip writes dictionaries of up to 4GB - 1.
     true
 }
 
@@ -430,10 +425,14 @@ fn validate_ppmd_header(_data: &[u8]) -> bool {
 /// AES-256 decoder for reading encrypted streams.
 pub struct Aes256Decoder<R> {
     inner: R,
+    /// Reusable buffer, decrypted in place; `buffer[pos..len]` is unread plaintext.
     buffer: Vec<u8>,
+    len: usize,
     pos: usize,
     key: [u8; 32],
     iv: [u8; 16],
+    /// IV of the first block, restored when seeking back to the start.
+    initial_iv: [u8; 16],
     finished: bool,
 }
 
@@ -464,24 +463,19 @@ impl<R: Read + Send> Aes256Decoder<R> {
         let iv_len = props.iv.len().min(16);
         iv[..iv_len].copy_from_slice(&props.iv[..iv_len]);
 
-        Ok(Self {
-            inner: input,
-            buffer: Vec::new(),
-            pos: 0,
-            key,
-            iv,
-            finished: false,
-        })
+        Ok(Self::with_key_iv(input, key, iv))
     }
 
     /// Creates a decoder with explicit key and IV.
     pub fn with_key_iv(input: R, key: [u8; 32], iv: [u8; 16]) -> Self {
         Self {
             inner: input,
-            buffer: Vec::new(),
+            buffer: vec![0u8; DECRYPT_BUFFER_SIZE],
+            len: 0,
             pos: 0,
             key,
             iv,
+            initial_iv: iv,
             finished: false,
         }
     }
@@ -509,30 +503,32 @@ impl<R: Read + Send> Aes256Decoder<R> {
     /// create a new decoder if validation fails.
     pub fn validate_first_block(&mut self, compression_method: &[u8]) -> io::Result<bool> {
         // Ensure we have data in the buffer
-        if self.buffer.is_empty() && !self.finished {
+        if self.len == 0 && !self.finished {
             self.decrypt_buffer()?;
         }
 
-        if self.buffer.is_empty() {
+        if self.len == 0 {
             // No data to validate - this is unusual but not necessarily wrong
             return Ok(true);
         }
 
         // Validate the decrypted data against expected compression header
-        Ok(validate_decrypted_header(&self.buffer, compression_method))
+        Ok(validate_decrypted_header(
+            &self.buffer[..self.len],
+            compression_method,
+        ))
     }
 
     /// Returns a reference to the currently buffered decrypted data.
     ///
     /// This can be used after `validate_first_block()` to inspect the decrypted data.
     pub fn buffered_data(&self) -> &[u8] {
-        &self.buffer[self.pos..]
+        &self.buffer[self.pos..self.len]
     }
 
     fn decrypt_buffer(&mut self) -> io::Result<()> {
-        // Read up to 4KB at a time (must be multiple of 16)
-        let mut encrypted = vec![0u8; 4096];
-        let n = self.inner.read(&mut encrypted)?;
+        // Read up to DECRYPT_BUFFER_SIZE bytes into the reusable buffer
+        let n = self.inner.read(&mut self.buffer)?;
 
         if n == 0 {
             self.finished = true;
@@ -548,44 +544,72 @@ impl<R: Read + Send> Aes256Decoder<R> {
             ));
         }
 
-        encrypted.truncate(aligned_len);
+        let encrypted = &mut self.buffer[..aligned_len];
 
         // Save the last block for IV update before decrypting
-        let next_iv: [u8; 16] = if encrypted.len() >= BLOCK_SIZE {
-            encrypted[encrypted.len() - BLOCK_SIZE..]
-                .try_into()
-                .expect("slice is exactly BLOCK_SIZE bytes after length check")
-        } else {
-            self.iv
-        };
+        let next_iv: [u8; 16] = encrypted[aligned_len - BLOCK_SIZE..]
+            .try_into()
+            .expect("slice is exactly BLOCK_SIZE bytes");
 
         // Decrypt in place
         let decryptor = Aes256CbcDec::new(&self.key.into(), &self.iv.into());
-        let decrypted = decryptor
-            .decrypt_padded_mut::<cbc::cipher::block_padding::NoPadding>(&mut encrypted)
+        decryptor
+            .decrypt_padded_mut::<cbc::cipher::block_padding::NoPadding>(encrypted)
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
 
         // Update IV for next block (CBC mode uses last ciphertext block as next IV)
         self.iv = next_iv;
 
-        self.buffer = decrypted.to_vec();
+        self.len = aligned_len;
         self.pos = 0;
 
         Ok(())
     }
 }
 
+impl<R: Read + Seek + Send> Aes256Decoder<R> {
+    /// Positions the decoder at the start of the given 16-byte block.
+    ///
+    /// In CBC mode the IV of a block is the preceding ciphertext block, so
+    /// only those 16 bytes are read; nothing before them is decrypted. Block
+    /// offsets are relative to the start of the inner reader, which must be
+    /// the first ciphertext block. Any buffered plaintext is discarded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if seeking or reading the preceding block fails.
+    pub fn seek_to_block(&mut self, block_index: u64) -> io::Result<()> {
+        let block_size = BLOCK_SIZE as u64;
+        let offset = block_index
+            .checked_mul(block_size)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "block index overflow"))?;
+
+        if block_index == 0 {
+            self.inner.seek(SeekFrom::Start(0))?;
+            self.iv = self.initial_iv;
+        } else {
+            self.inner.seek(SeekFrom::Start(offset - block_size))?;
+            self.inner.read_exact(&mut self.iv)?;
+        }
+
+        self.len = 0;
+        self.pos = 0;
+        self.finished = false;
+        Ok(())
+    }
+}
+
 impl<R: Read + Send> Read for Aes256Decoder<R> {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        if self.pos >= self.buffer.len() && !self.finished {
+        if self.pos >= self.len && !self.finished {
             self.decrypt_buffer()?;
         }
 
-        if self.pos >= self.buffer.len() {
+        if self.pos >= self.len {
             return Ok(0);
         }
 
-        let available = &self.buffer[self.pos..];
+        let available = &self.buffer[self.pos..self.len];
         let to_copy = available.len().min(buf.len());
         buf[..to_copy].copy_from_slice(&available[..to_copy]);
         self.pos += to_copy;
@@ -727,6 +751,78 @@ impl<W: Write + Send> Write for Aes256Encoder<W> {
     }
 }
 
+/// Re-encrypts AES-256-CBC ciphertext under a different key and IV.
+///
+/// Ciphertext is processed in block-aligned chunks: each chunk is decrypted
+/// with the old key and encrypted again with the new one, in place. The
+/// output is exactly as long as the input and the plaintext, padding
+/// included, is unchanged, so the stream can replace the original without
+/// touching any sizes.
+pub(crate) struct Aes256Rekey {
+    old_key: [u8; 32],
+    old_iv: [u8; 16],
+    new_key: [u8; 32],
+    new_iv: [u8; 16],
+}
+
+impl Aes256Rekey {
+    /// Creates a re-keyer from the old and new keys and IVs.
+    pub(crate) fn new(
+        old_key: [u8; 32],
+        old_iv: [u8; 16],
+        new_key: [u8; 32],
+        new_iv: [u8; 16],
+    ) -> Self {
+        Self {
+            old_key,
+            old_iv,
+            new_key,
+            new_iv,
+        }
+    }
+
+    /// Decrypts the next chunk of ciphertext in place.
+    ///
+    /// Must be followed by [`encrypt`](Self::encrypt) on the same chunk.
+    pub(crate) fn decrypt(&mut self, data: &mut [u8]) -> io::Result<()> {
+        let Some(next_iv) = last_block(data)? else {
+            return Ok(());
+        };
+        Aes256CbcDec::new(&self.old_key.into(), &self.old_iv.into())
+            .decrypt_padded_mut::<cbc::cipher::block_padding::NoPadding>(data)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+        self.old_iv = next_iv;
+        Ok(())
+    }
+
+    /// Encrypts a chunk decrypted by [`decrypt`](Self::decrypt) in place.
+    pub(crate) fn encrypt(&mut self, data: &mut [u8]) -> io::Result<()> {
+        let len = data.len();
+        Aes256CbcEnc::new(&self.new_key.into(), &self.new_iv.into())
+            .encrypt_padded_mut::<cbc::cipher::block_padding::NoPadding>(data, len)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+        if let Some(next_iv) = last_block(data)? {
+            self.new_iv = next_iv;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the last block of block-aligned `data`, or `None` if it is empty.
+fn last_block(data: &[u8]) -> io::Result<Option<[u8; 16]>> {
+    if data.len() % BLOCK_SIZE != 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "encrypted data not block-aligned",
+        ));
+    }
+    Ok(data.len().checked_sub(BLOCK_SIZE).map(|start| {
+        data[start..]
+            .try_into()
+            .expect("slice is exactly BLOCK_SIZE bytes")
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -797,6 +893,68 @@ mod tests {
         assert_eq!(&decrypted[..], &data[..]);
     }
 
+    #[test]
+    fn test_aes_decoder_multi_megabyte_stream() {
+        let data: Vec<u8> = (0..3 * 1024 * 1024 + 5)
+            .map(|i: u32| (i.wrapping_mul(2_654_435_761) >> 24) as u8)
+            .collect();
+        let key = [7u8; 32];
+        let iv = [9u8; 16];
+
+        let mut encrypted = Vec::new();
+        {
+            let mut encoder = Aes256Encoder::with_key_iv(Cursor::new(&mut encrypted), key, iv);
+            encoder.write_all(&data).unwrap();
+            encoder.finish().unwrap();
+        }
+
+        let mut decoder = Aes256Decoder::with_key_iv(Cursor::new(&encrypted), key, iv);
+        let buffer_ptr = decoder.buffer.as_ptr();
+        let mut decrypted = Vec::with_capacity(encrypted.len());
+        let mut chunk = [0u8; 1000];
+        loop {
+            let n = decoder.read(&mut chunk).unwrap();
+            if n == 0 {
+                break;
+            }
+            decrypted.extend_from_slice(&chunk[..n]);
+            // The ciphertext buffer is reused, never reallocated
+            assert_eq!(decoder.buffer.as_ptr(), buffer_ptr);
+        }
+
+        assert_eq!(decrypted.len(), encrypted.len());
+        assert_eq!(&decrypted[..data.len()], &data[..]);
+    }
+
+    #[test]
+    fn test_aes_decoder_seek_to_block() {
+        let data: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        let key = [3u8; 32];
+        let iv = [5u8; 16];
+
+        let mut encrypted = Vec::new();
+        {
+            let mut encoder = Aes256Encoder::with_key_iv(Cursor::new(&mut encrypted), key, iv);
+            encoder.write_all(&data).unwrap();
+            encoder.finish().unwrap();
+        }
+
+        let mut decoder = Aes256Decoder::with_key_iv(Cursor::new(&encrypted), key, iv);
+        for block in [400, 1, 0, 624] {
+            decoder.seek_to_block(block).unwrap();
+            let mut chunk = [0u8; 16];
+            decoder.read_exact(&mut chunk).unwrap();
+            let start = block as usize * BLOCK_SIZE;
+            assert_eq!(chunk, data[start..start + BLOCK_SIZE], "block {}", block);
+        }
+
+        // Reading past the sought block continues the CBC chain
+        decoder.seek_to_block(100).unwrap();
+        let mut rest = Vec::new();
+        decoder.read_to_end(&mut rest).unwrap();
+        assert_eq!(&rest[..data.len() - 1600], &data[1600..]);
+    }
+
     #[test]
     fn test_password_utf16le() {
         let password = Password::new("test");
@@ -904,6 +1062,10 @@ mod tests {
         // Valid LZMA properties byte (0x00 = lc=0, lp=0, pb=0)
         assert!(validate_lzma_header(&[0x00, 0x00, 0x00, 0x01, 0x00]));
 
+        // Dictionaries above 1GB are valid (2GB and 4GB - 1)
+        assert!(validate_lzma_header(&[0x5D, 0x00, 0x00, 0x00, 0x80]));
+        assert!(validate_lzma_header(&[0x5D, 0xFF, 0xFF, 0xFF, 0xFF]));
+
         // Invalid: pb >= 5 would require props_byte >= 225
         // 225 = 0 + 0*9 + 5*45, which is invalid since pb must be < 5
         assert!(!validate_lzma_header(&[0xE1])); // 225
@@ -1108,4 +1270,32 @@ mod tests {
         let result = derive_key(&password, &salt, 63);
         assert!(result.is_err(), "cycles_power=63 should be rejected");
     }
+
+    #[test]
+    fn test_rekey_matches_encrypting_with_new_key() {
+        let plaintext: Vec<u8> = (0..1000u32).map(|i| (i * 7) as u8).collect();
+        let (old_key, old_iv) = ([1u8; 32], [2u8; 16]);
+        let (new_key, new_iv) = ([3u8; 32], [4u8; 16]);
+
+        let mut encoder = Aes256Encoder::with_key_iv(Vec::new(), old_key, old_iv);
+        encoder.write_all(&plaintext).unwrap();
+        let mut ciphertext = encoder.finish().unwrap();
+        let original_len = ciphertext.len();
+
+        // Re-key in uneven, block-aligned chunks
+        let mut rekey = Aes256Rekey::new(old_key, old_iv, new_key, new_iv);
+        for chunk in ciphertext.chunks_mut(48) {
+            rekey.decrypt(chunk).unwrap();
+            rekey.encrypt(chunk).unwrap();
+        }
+        assert_eq!(ciphertext.len(), original_len);
+
+        let mut decoder = Aes256Decoder::with_key_iv(Cursor::new(ciphertext), new_key, new_iv);
+        let mut decrypted = Vec::new();
+        decoder.read_to_end(&mut decrypted).unwrap();
+        assert_eq!(&decrypted[..plaintext.len()], plaintext.as_slice());
+
+        let mut unaligned = [0u8; 20];
+        assert!(rekey.decrypt(&mut unaligned).is_err());
+    }
 }
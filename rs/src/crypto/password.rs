@@ -6,6 +6,11 @@ use zeroize::Zeroizing;
 ///
 /// This type stores the password securely and provides conversion to UTF-16LE
 /// as required by 7z's key derivation function.
+///
+/// An empty password is a valid key, distinct from having no password. APIs
+/// that may run without a password take `Option<Password>`, where `None`
+/// means "no password" and `Some(Password::new(""))` means "the empty
+/// password".
 #[derive(Clone)]
 pub struct Password {
     inner: Zeroizing<String>,
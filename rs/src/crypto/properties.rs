@@ -212,6 +212,22 @@ impl NoncePolicy {
         }
     }
 
+    /// Returns the policy with its num_cycles_power replaced.
+    pub fn with_num_cycles_power(mut self, power: u8) -> Self {
+        match &mut self {
+            Self::Random {
+                num_cycles_power, ..
+            }
+            | Self::Deterministic {
+                num_cycles_power, ..
+            }
+            | Self::Explicit {
+                num_cycles_power, ..
+            } => *num_cycles_power = power,
+        }
+        self
+    }
+
     /// Generates salt and IV according to the policy.
     ///
     /// # Returns
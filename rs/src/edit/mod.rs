@@ -5,6 +5,8 @@
 //! - Deleting entries
 //! - Updating entry contents
 //! - Adding new entries
+//! - Changing the password of an encrypted archive
+//!   ([`Archive::reencrypt`](crate::read::Archive::reencrypt))
 //!
 //! # Example
 //!
@@ -44,10 +46,14 @@
 //! 5. Skipping deleted entries
 //! 6. Adding updated and new entries
 //!
-//! Future optimizations may include raw stream copying for unchanged solid blocks.
+//! Future optimizations may include raw stream copying for unchanged solid blocks,
+//! as [`ArchiveAppender`](crate::write::ArchiveAppender) already does when it
+//! only adds entries.
 
 mod editor;
 mod operation;
+#[cfg(feature = "aes")]
+mod reencrypt;
 
 pub use editor::{ArchiveEditor, EditResult, EditableArchive};
 pub use operation::{Operation, OperationBuilder};
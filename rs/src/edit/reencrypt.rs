@@ -0,0 +1,345 @@
+//! Password rotation for encrypted archives.
+
+use std::io::{Read, Seek, SeekFrom, Write};
+
+use crate::codec::method;
+use crate::crypto::{
+    Aes256Decoder, Aes256Rekey, AesProperties, KeyCache, NoncePolicy, Password, derive_key,
+};
+use crate::format::streams::Folder;
+use crate::read::{Archive, Entry};
+use crate::write::{EntryMeta, RawFolder, WriteOptions, WriteResult, Writer, plan_raw_folders};
+use crate::{Error, READ_BUFFER_SIZE, Result};
+
+impl<R: Read + Seek> Archive<R> {
+    /// Writes a copy of this archive encrypted with a new password.
+    ///
+    /// Encrypted folders are re-keyed without being decompressed: the packed
+    /// stream read by each AES coder is decrypted with `old_password` and
+    /// encrypted again under `new_password`, with a freshly derived key, salt
+    /// and IV per folder, as it is copied to `output`. All other coders,
+    /// packed streams and the solid block layout are kept as they are, so
+    /// the copy has the same codecs, filters and compression settings.
+    /// Nothing is extracted to disk and no entry is held in memory.
+    ///
+    /// The header is encrypted if it was before, and the copy keeps the
+    /// entry metadata and the comment. Folders without encryption are copied
+    /// unchanged when only the header was encrypted. A few folders cannot be
+    /// re-keyed in place and are decompressed and compressed again under the
+    /// new password instead: folders without encryption in an archive whose
+    /// contents are to be encrypted (including archives without any
+    /// encryption), folders that decrypt after decompressing, and folders
+    /// whose streams lack CRCs.
+    ///
+    /// `old_password` is checked against the first block of every encrypted
+    /// folder, and fully against the smallest encrypted entry, before
+    /// anything is written.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::WrongPassword`] or
+    /// [`Error::CrcMismatch`] if
+    /// `old_password` does not decrypt the entries, or any error from writing
+    /// the new archive.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use std::fs::File;
+    /// use zesven::Archive;
+    ///
+    /// let archive = Archive::open_path_with_password("secret.7z", "old password")?;
+    /// let output = File::create("rotated.7z")?;
+    /// archive.reencrypt("old password", "new password", output)?;
+    /// ```
+    pub fn reencrypt<W: Write + Seek>(
+        mut self,
+        old_password: impl Into<Password>,
+        new_password: impl Into<Password>,
+        output: W,
+    ) -> Result<WriteResult> {
+        let old_password = old_password.into();
+        let new_password = new_password.into();
+        self.password = Some(old_password.clone());
+
+        let info = self.info();
+        let encrypt_header = info.has_encrypted_header;
+        let encrypt_data = info.has_encrypted_entries || !encrypt_header;
+        let mut options = WriteOptions::new()
+            .password(new_password.clone())
+            .encrypt_header(encrypt_header)
+            .encrypt_data(encrypt_data);
+        if let Some(&method) = info.compression_methods.first() {
+            options = options.method(method);
+        }
+        if let Some(comment) = info.comment() {
+            options = options.comment(comment);
+        }
+        let nonce_policy = options.nonce_policy.clone();
+
+        // Folders left as `None` are decompressed and compressed again
+        let keys = KeyCache::new(4);
+        let mut plans = Vec::new();
+        for plan in plan_raw_folders(&self)? {
+            let rekeyed = match plan {
+                Some((offset, raw)) => rekey_folder(
+                    &mut self.reader,
+                    offset,
+                    raw,
+                    &keys,
+                    &old_password,
+                    &new_password,
+                    &nonce_policy,
+                )?,
+                None => None,
+            };
+            plans.push(rekeyed.filter(|folder| folder.is_encrypted() || !encrypt_data));
+        }
+
+        self.verify_old_password(&plans)?;
+
+        let mut writer = Writer::create(output)?.options(options);
+        let entries: Vec<Entry> = self.entries.clone();
+        for (entry_idx, entry) in entries.into_iter().enumerate() {
+            let meta = EntryMeta {
+                is_directory: entry.is_directory,
+                size: entry.size,
+                modification_time: entry.modification_time,
+                creation_time: entry.creation_time,
+                access_time: entry.access_time,
+                attributes: entry.attributes,
+                is_anti: entry.is_anti,
+                sparse_holes: entry.sparse_holes,
+                bsd_flags: entry.bsd_flags,
+            };
+            let path = entry.path;
+
+            let Some(folder_idx) = entry.folder_index else {
+                writer.add_copied_entry(path, meta)?;
+                continue;
+            };
+
+            match plans.get_mut(folder_idx) {
+                Some(Some(folder)) => {
+                    if let Some(raw) = folder.raw.take() {
+                        self.reader
+                            .seek(SeekFrom::Start(folder.offset))
+                            .map_err(Error::Io)?;
+                        let mut source = RekeyedPackStreams {
+                            inner: &mut self.reader,
+                            streams: raw
+                                .pack_sizes
+                                .iter()
+                                .copied()
+                                .zip(std::mem::take(&mut folder.streams))
+                                .collect(),
+                            current: 0,
+                            buffer: Vec::new(),
+                            pos: 0,
+                        };
+                        writer.copy_raw_folder(raw, &mut source)?;
+                    }
+                    writer.add_copied_entry(path, meta)?;
+                }
+                _ => {
+                    let data = self.extract_entry_to_vec_by_index(entry_idx)?;
+                    writer.add_stream(path, &mut data.as_slice(), meta)?;
+                }
+            }
+        }
+
+        let (result, _) = writer.finish_into_inner()?;
+        Ok(result)
+    }
+
+    /// Checks `old_password` by decoding the smallest entry stored first in
+    /// a re-keyed encrypted folder.
+    ///
+    /// Re-keying never decompresses data, so without this check a wrong
+    /// password that slips past the first-block validation would produce
+    /// an archive that no password opens.
+    fn verify_old_password(&mut self, plans: &[Option<RekeyedFolder>]) -> Result<()> {
+        let encrypted = |folder_idx: usize| {
+            plans
+                .get(folder_idx)
+                .and_then(Option::as_ref)
+                .is_some_and(RekeyedFolder::is_encrypted)
+        };
+        let smallest = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.stream_index == Some(0) && e.folder_index.is_some_and(encrypted))
+            .min_by_key(|(_, e)| e.size)
+            .map(|(idx, _)| idx);
+
+        if let Some(entry_idx) = smallest {
+            self.stream_entry_by_index(entry_idx, &mut std::io::sink(), "sink")?;
+        }
+        Ok(())
+    }
+}
+
+/// A folder to copy with its AES streams re-keyed.
+struct RekeyedFolder {
+    /// Offset of the folder's first packed stream in the source.
+    offset: u64,
+    /// The folder with updated AES properties, taken when it is copied.
+    raw: Option<RawFolder>,
+    /// Re-keyer for each packed stream, or `None` for streams copied as is.
+    streams: Vec<Option<Aes256Rekey>>,
+}
+
+impl RekeyedFolder {
+    /// Returns true if any packed stream of the folder is re-keyed.
+    fn is_encrypted(&self) -> bool {
+        self.streams.iter().any(Option::is_some)
+    }
+}
+
+/// Prepares a folder for copying with new AES keys.
+///
+/// Every AES coder that reads a packed stream gets a new salt and IV under
+/// `new_password`, and `old_password` is validated against the first block
+/// of its stream. Returns `None` if an AES coder reads the output of another
+/// coder, since re-keying that stream would need decompression.
+fn rekey_folder<R: Read + Seek>(
+    reader: &mut R,
+    offset: u64,
+    mut raw: RawFolder,
+    keys: &KeyCache,
+    old_password: &Password,
+    new_password: &Password,
+    nonce_policy: &NoncePolicy,
+) -> Result<Option<RekeyedFolder>> {
+    let mut streams: Vec<Option<Aes256Rekey>> = raw.pack_sizes.iter().map(|_| None).collect();
+
+    let mut in_index = 0u64;
+    for coder_idx in 0..raw.folder.coders.len() {
+        let coder = &raw.folder.coders[coder_idx];
+        let coder_in_index = in_index;
+        in_index += coder.num_in_streams;
+        if coder.method_id.as_slice() != method::AES {
+            continue;
+        }
+
+        let Some(pack_idx) = raw
+            .folder
+            .packed_streams
+            .iter()
+            .position(|&idx| idx == coder_in_index)
+        else {
+            return Ok(None);
+        };
+        let pack_size = raw.pack_sizes[pack_idx];
+        if pack_size % 16 != 0 {
+            return Err(Error::InvalidFormat(format!(
+                "encrypted stream size {} is not a multiple of the AES block size",
+                pack_size
+            )));
+        }
+
+        let props = AesProperties::parse(coder.properties.as_deref().unwrap_or(&[]))?;
+        let old_key = keys.derive_key(old_password, &props.salt, props.num_cycles_power)?;
+        let mut old_iv = [0u8; 16];
+        old_iv.copy_from_slice(&props.iv[..16]);
+
+        // Check the old password like the reader does before decoding
+        if let Some(next_method) = decoded_by(&raw.folder, coder_idx) {
+            let stream_offset = offset + raw.pack_sizes[..pack_idx].iter().sum::<u64>();
+            reader
+                .seek(SeekFrom::Start(stream_offset))
+                .map_err(Error::Io)?;
+            let mut probe = vec![0u8; pack_size.min(READ_BUFFER_SIZE as u64) as usize];
+            reader.read_exact(&mut probe).map_err(Error::Io)?;
+            let mut decoder =
+                Aes256Decoder::with_key_iv(std::io::Cursor::new(probe), old_key, old_iv);
+            if !decoder.validate_first_block(next_method)? {
+                return Err(Error::WrongPassword {
+                    entry_index: None,
+                    entry_name: None,
+                    detection_method: crate::error::PasswordDetectionMethod::EarlyHeaderValidation,
+                });
+            }
+        }
+
+        let (salt, new_iv) = nonce_policy.generate()?;
+        let new_key = derive_key(new_password, &salt, nonce_policy.num_cycles_power())?;
+        raw.folder.coders[coder_idx].properties = Some(AesProperties::encode(
+            nonce_policy.num_cycles_power(),
+            &salt,
+            &new_iv,
+        ));
+        streams[pack_idx] = Some(Aes256Rekey::new(old_key, old_iv, new_key, new_iv));
+    }
+
+    Ok(Some(RekeyedFolder {
+        offset,
+        raw: Some(raw),
+        streams,
+    }))
+}
+
+/// Returns the method of the coder that reads the output of `coder_idx`.
+fn decoded_by(folder: &Folder, coder_idx: usize) -> Option<&[u8]> {
+    let out_index: u64 = folder.coders[..coder_idx]
+        .iter()
+        .map(|c| c.num_out_streams)
+        .sum();
+    let bind_pair = folder
+        .bind_pairs
+        .iter()
+        .find(|bp| bp.out_index == out_index)?;
+
+    let mut in_index = 0u64;
+    folder.coders.iter().find_map(|coder| {
+        let range = in_index..in_index + coder.num_in_streams;
+        in_index = range.end;
+        range
+            .contains(&bind_pair.in_index)
+            .then_some(coder.method_id.as_slice())
+    })
+}
+
+/// Reads a folder's packed streams, re-keying the encrypted ones.
+struct RekeyedPackStreams<'a, R> {
+    inner: &'a mut R,
+    /// Size and re-keyer of each packed stream, in order.
+    streams: Vec<(u64, Option<Aes256Rekey>)>,
+    /// Index of the stream being read.
+    current: usize,
+    /// Processed bytes of the current stream; `buffer[pos..]` is unread.
+    buffer: Vec<u8>,
+    pos: usize,
+}
+
+impl<R: Read> Read for RekeyedPackStreams<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        while self.pos == self.buffer.len() {
+            let Some((remaining, rekey)) = self.streams.get_mut(self.current) else {
+                return Ok(0);
+            };
+            if *remaining == 0 {
+                self.current += 1;
+                continue;
+            }
+
+            // READ_BUFFER_SIZE is a multiple of the AES block size, and
+            // encrypted streams are block-aligned, so chunks stay aligned
+            let n = (*remaining).min(READ_BUFFER_SIZE as u64) as usize;
+            self.buffer.resize(n, 0);
+            self.inner.read_exact(&mut self.buffer)?;
+            if let Some(rekey) = rekey {
+                rekey.decrypt(&mut self.buffer)?;
+                rekey.encrypt(&mut self.buffer)?;
+            }
+            *remaining -= n as u64;
+            self.pos = 0;
+        }
+
+        let n = buf.len().min(self.buffer.len() - self.pos);
+        buf[..n].copy_from_slice(&self.buffer[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
@@ -349,6 +349,19 @@ pub enum Error {
     #[error("Operation cancelled")]
     Cancelled,
 
+    /// Decompressing an entry took longer than allowed.
+    ///
+    /// Returned when [`ExtractOptions::per_entry_timeout`] is set and an
+    /// entry does not finish decoding in time. Such entries are usually
+    /// crafted to be slow to decompress.
+    ///
+    /// [`ExtractOptions::per_entry_timeout`]: crate::read::ExtractOptions::per_entry_timeout
+    #[error("Decompression timed out after {timeout:?}")]
+    Timeout {
+        /// The timeout that was exceeded.
+        timeout: std::time::Duration,
+    },
+
     /// A cryptographic operation failed.
     ///
     /// This indicates an internal error in the encryption/decryption process,
@@ -378,6 +391,22 @@ pub enum Error {
         actual: u32,
     },
 
+    /// The CRC of a fully decoded folder did not match the folder CRC.
+    ///
+    /// Some archives store a CRC for the concatenated output of a solid
+    /// block in addition to the per-entry CRCs. This error is returned when
+    /// the whole-folder check fails even though it may not be attributable
+    /// to a single entry.
+    #[error("CRC mismatch for folder {folder_index}: expected {expected:#x}, got {actual:#x}")]
+    FolderCrcMismatch {
+        /// The index of the folder (compression block).
+        folder_index: usize,
+        /// The expected folder CRC value from the archive.
+        expected: u32,
+        /// The actual CRC value of the decoded folder data.
+        actual: u32,
+    },
+
     /// Path traversal attack detected in an archive entry.
     ///
     /// This is a **security error** indicating the archive contains paths
@@ -419,7 +448,9 @@ pub enum Error {
     ///
     /// # Security
     ///
-    /// The default [`LinkPolicy::Forbid`] rejects all symlinks. If you need to
+    /// The default [`LinkPolicy::Forbid`] rejects all symlinks. Extraction
+    /// skips rejected entries and records this error as the reason on their
+    /// [`EntryOutcome`](crate::read::EntryOutcome). If you need to
     /// extract symlinks, use [`LinkPolicy::ValidateTargets`] to validate that
     /// symlink targets stay within the extraction directory.
     ///
@@ -597,6 +628,20 @@ pub enum Error {
         reason: String,
     },
 
+    /// An invalid glob pattern was provided.
+    ///
+    /// This error occurs when creating a [`SelectByGlob`] selector with
+    /// a malformed pattern, such as an unclosed character class.
+    ///
+    /// [`SelectByGlob`]: crate::read::SelectByGlob
+    #[error("Invalid glob pattern '{pattern}': {reason}")]
+    InvalidGlob {
+        /// The invalid glob pattern.
+        pattern: String,
+        /// Description of why the pattern is invalid.
+        reason: String,
+    },
+
     /// An invalid compression level was provided.
     ///
     /// Compression levels must be in the range 0-9:
@@ -627,7 +672,9 @@ pub enum Error {
     /// A password is required but none was provided.
     ///
     /// This error is returned when attempting to read or extract an encrypted
-    /// archive without providing a password.
+    /// archive without providing a password. With an encrypted header it is
+    /// returned by the `open` functions themselves, before any entry is known;
+    /// otherwise opening succeeds and extracting an encrypted entry fails.
     ///
     /// # Recovery
     ///
@@ -638,7 +685,7 @@ pub enum Error {
     ///
     /// fn open_encrypted(path: &str) -> zesven::Result<Archive<_>> {
     ///     match Archive::open_path(path) {
-    ///         Err(Error::PasswordRequired) => {
+    ///         Err(Error::PasswordRequired { encrypted_header: true }) => {
     ///             let password = prompt_user_for_password();
     ///             Archive::open_path_with_password(path, Password::new(&password))
     ///         }
@@ -646,8 +693,27 @@ pub enum Error {
     ///     }
     /// }
     /// ```
-    #[error("password required for encrypted archive")]
-    PasswordRequired,
+    #[error(
+        "password required for encrypted archive{}",
+        if *encrypted_header { " header" } else { "" }
+    )]
+    PasswordRequired {
+        /// Whether the archive header is encrypted, as opposed to only the
+        /// entry data.
+        encrypted_header: bool,
+    },
+
+    /// A warning was raised while strict mode was enabled.
+    ///
+    /// In strict mode, conditions that would normally be reported as
+    /// warnings (such as an entry skipped during extraction) abort the
+    /// operation instead. See [`ExtractOptions::strict`] and
+    /// [`RecoveryOptions::strict`].
+    ///
+    /// [`ExtractOptions::strict`]: crate::read::ExtractOptions::strict
+    /// [`RecoveryOptions::strict`]: crate::recovery::RecoveryOptions::strict
+    #[error("strict mode: {0}")]
+    Strict(String),
 }
 
 impl Error {
@@ -706,7 +772,7 @@ impl Error {
     pub fn is_recoverable(&self) -> bool {
         match self {
             Error::WrongPassword { .. } => true,
-            Error::PasswordRequired => true,
+            Error::PasswordRequired { .. } => true,
             Error::Cancelled => true,
             // VolumeMissing is recoverable: user can provide the missing volume
             Error::VolumeMissing { .. } => true,
@@ -728,7 +794,9 @@ impl Error {
     pub fn is_corruption(&self) -> bool {
         matches!(
             self,
-            Error::CrcMismatch { .. } | Error::CorruptHeader { .. }
+            Error::CrcMismatch { .. }
+                | Error::FolderCrcMismatch { .. }
+                | Error::CorruptHeader { .. }
         )
     }
 
@@ -738,7 +806,7 @@ impl Error {
     pub fn is_encryption_error(&self) -> bool {
         matches!(
             self,
-            Error::WrongPassword { .. } | Error::CryptoError(_) | Error::PasswordRequired
+            Error::WrongPassword { .. } | Error::CryptoError(_) | Error::PasswordRequired { .. }
         )
     }
 
@@ -1433,7 +1501,9 @@ mod tests {
 
     #[test]
     fn test_password_required_error() {
-        let err = Error::PasswordRequired;
+        let err = Error::PasswordRequired {
+            encrypted_header: false,
+        };
 
         // Check error classification methods
         assert!(err.is_encryption_error());
@@ -1444,5 +1514,23 @@ mod tests {
         let msg = err.to_string();
         assert!(msg.contains("password required"));
         assert!(msg.contains("encrypted"));
+        assert!(!msg.contains("header"));
+
+        let err = Error::PasswordRequired {
+            encrypted_header: true,
+        };
+        assert!(err.is_recoverable());
+        assert_eq!(
+            err.to_string(),
+            "password required for encrypted archive header"
+        );
+    }
+
+    #[test]
+    fn test_strict_error() {
+        let err = Error::Strict("entry 'a.txt' skipped".into());
+        assert_eq!(err.to_string(), "strict mode: entry 'a.txt' skipped");
+        assert!(!err.is_recoverable());
+        assert!(!err.is_security_error());
     }
 }
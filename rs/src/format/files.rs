@@ -35,6 +35,10 @@ pub struct ArchiveEntry {
     pub mtime: Option<u64>,
     /// Windows file attributes.
     pub attributes: Option<u32>,
+    /// Holes of a sparse file as `(offset, length)` pairs.
+    pub sparse_holes: Vec<(u64, u64)>,
+    /// BSD file flags (`st_flags`).
+    pub bsd_flags: Option<u32>,
 }
 
 impl ArchiveEntry {
@@ -108,7 +112,9 @@ impl FilesInfo {
                         });
                     }
 
-                    // Read names as UTF-16LE null-terminated strings
+                    // Read names as UTF-16LE null-terminated strings. Names are
+                    // kept verbatim: separator normalization (e.g. backslashes)
+                    // is deferred to extraction, see `BackslashPolicy`.
                     for entry in &mut entries {
                         entry.name = read_utf16le_string(r)?;
                     }
@@ -144,6 +150,14 @@ impl FilesInfo {
                     parse_attributes(r, &mut entries)?;
                 }
 
+                property_id::SPARSE_HOLES => {
+                    parse_sparse_holes(r, &mut entries)?;
+                }
+
+                property_id::BSD_FLAGS => {
+                    parse_bsd_flags(r, &mut entries)?;
+                }
+
                 property_id::COMMENT => {
                     // Read comment as UTF-16LE string
                     // The format is: external flag (1 byte) + UTF-16LE null-terminated string
@@ -156,6 +170,12 @@ impl FilesInfo {
                     comment = Some(read_utf16le_string(r)?);
                 }
 
+                property_id::START_POS => {
+                    // Deprecated, with no defined meaning; archives locate
+                    // their streams from the signature header instead
+                    let _ = read_bytes(r, prop_size as usize)?;
+                }
+
                 _ => {
                     // Skip unknown property
                     let _ = read_bytes(r, prop_size as usize)?;
@@ -320,6 +340,40 @@ fn parse_attributes<R: Read>(r: &mut R, entries: &mut [ArchiveEntry]) -> Result<
     Ok(())
 }
 
+/// Parses sparse file hole maps for entries.
+///
+/// Each defined entry stores its hole count followed by `(offset, length)`
+/// pairs, all as variable-length numbers.
+fn parse_sparse_holes<R: Read>(r: &mut R, entries: &mut [ArchiveEntry]) -> Result<()> {
+    let defined = read_all_or_bits(r, entries.len())?;
+
+    for (entry, &has_holes) in entries.iter_mut().zip(defined.iter()) {
+        if has_holes {
+            let num_holes = read_variable_u64(r)?;
+            for _ in 0..num_holes {
+                let offset = read_variable_u64(r)?;
+                let len = read_variable_u64(r)?;
+                entry.sparse_holes.push((offset, len));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Parses BSD file flags for entries, stored as little-endian u32 values.
+fn parse_bsd_flags<R: Read>(r: &mut R, entries: &mut [ArchiveEntry]) -> Result<()> {
+    let defined = read_all_or_bits(r, entries.len())?;
+
+    for (entry, &has_flags) in entries.iter_mut().zip(defined.iter()) {
+        if has_flags {
+            entry.bsd_flags = Some(read_u32_le(r)?);
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -625,4 +679,50 @@ mod tests {
 
         assert_eq!(files_info.comment(), Some("日本語コメント 🎉"));
     }
+
+    #[test]
+    fn test_files_info_ignores_start_pos() {
+        let mut data = Vec::new();
+
+        // num_files = 2
+        write_variable_u64(&mut data, 2);
+
+        // K_NAME property
+        data.push(property_id::NAME);
+        let mut names_data = Vec::new();
+        names_data.push(0x00);
+        write_utf16le_string(&mut names_data, "a.txt");
+        write_utf16le_string(&mut names_data, "b.txt");
+        write_variable_u64(&mut data, names_data.len() as u64);
+        data.extend_from_slice(&names_data);
+
+        // K_START_POS property (all defined, not external)
+        data.push(property_id::START_POS);
+        let mut start_pos_data = vec![0x01, 0x00];
+        start_pos_data.extend_from_slice(&4096u64.to_le_bytes());
+        start_pos_data.extend_from_slice(&4096u64.to_le_bytes());
+        write_variable_u64(&mut data, start_pos_data.len() as u64);
+        data.extend_from_slice(&start_pos_data);
+
+        // K_WIN_ATTRIBUTES property after it is still parsed
+        data.push(property_id::WIN_ATTRIBUTES);
+        let mut attributes_data = vec![0x01, 0x00];
+        attributes_data.extend_from_slice(&0x20u32.to_le_bytes());
+        attributes_data.extend_from_slice(&0x21u32.to_le_bytes());
+        write_variable_u64(&mut data, attributes_data.len() as u64);
+        data.extend_from_slice(&attributes_data);
+
+        // K_END
+        data.push(property_id::END);
+
+        let mut cursor = Cursor::new(&data);
+        let limits = ResourceLimits::default();
+        let sizes = vec![1, 2];
+        let crcs = vec![None, None];
+        let files_info = FilesInfo::parse(&mut cursor, &sizes, &crcs, &limits).unwrap();
+
+        assert_eq!(files_info.entries[1].name, "b.txt");
+        assert_eq!(files_info.entries[0].attributes, Some(0x20));
+        assert_eq!(files_info.entries[1].attributes, Some(0x21));
+    }
 }
@@ -4,7 +4,7 @@ use crate::{Error, Result};
 use std::io::{Read, Seek, SeekFrom};
 
 use super::reader::{read_u8, read_u32_le};
-use super::{SIGNATURE, VERSION_MAJOR, VERSION_MINOR};
+use super::{SIGNATURE, SIGNATURE_HEADER_SIZE, VERSION_MAJOR, VERSION_MINOR};
 
 /// Maximum search range for 7z signature in self-extracting archives.
 pub const SFX_SEARCH_LIMIT: usize = 1024 * 1024; // 1 MiB
@@ -38,7 +38,7 @@ impl StartHeader {
     ///
     /// Returns an error if:
     /// - The signature is invalid
-    /// - The version is unsupported
+    /// - The major version is unsupported
     /// - The CRC doesn't match
     /// - An I/O error occurs
     pub fn parse<R: Read>(r: &mut R) -> Result<Self> {
@@ -53,15 +53,23 @@ impl StartHeader {
         let version_major = read_u8(r)?;
         let version_minor = read_u8(r)?;
 
-        // Check version compatibility (we support 0.x where x <= 4)
-        // Archives with higher versions may have features we don't understand
-        if version_major > VERSION_MAJOR
-            || (version_major == VERSION_MAJOR && version_minor > VERSION_MINOR)
-        {
+        // Only a major version bump signals an incompatible layout. Newer
+        // minor versions are parsed anyway; anything we don't understand in
+        // them surfaces as an error while reading the header.
+        if version_major != VERSION_MAJOR {
             return Err(Error::UnsupportedFeature {
                 feature: "unsupported archive version",
             });
         }
+        if version_minor > VERSION_MINOR {
+            log::warn!(
+                "archive format version {}.{} is newer than {}.{}; attempting to read it anyway",
+                version_major,
+                version_minor,
+                VERSION_MAJOR,
+                VERSION_MINOR
+            );
+        }
 
         // Read start header CRC
         let start_header_crc = read_u32_le(r)?;
@@ -132,7 +140,9 @@ pub struct DetectedSfxOffset {
 /// # Returns
 ///
 /// Returns `Ok(Some(offset))` if the signature is found, where offset is
-/// the byte position of the signature. Returns `Ok(None)` if the signature
+/// the byte position of the signature. The first signature whose start
+/// header CRC validates is preferred; if none validates, the first match
+/// with plausible version bytes is returned. Returns `Ok(None)` if the signature
 /// is not found within the search limit.
 ///
 /// # Example
@@ -164,6 +174,10 @@ pub fn find_signature<R: Read + Seek>(
     // Search for signature with valid version bytes
     // The signature is 6 bytes, followed by 2 version bytes (major, minor)
     // We need at least 8 bytes to validate
+    //
+    // SFX stubs may themselves contain signature bytes, so a match whose
+    // start header CRC validates wins over earlier raw matches.
+    let mut first_match = None;
     let mut search_start = 0;
     while search_start + 8 <= buffer.len() {
         if let Some(rel_pos) = buffer[search_start..]
@@ -179,7 +193,10 @@ pub fn find_signature<R: Read + Seek>(
                 // Major version should be 0, minor should be reasonable (< 10)
                 if version_major == VERSION_MAJOR && version_minor <= 10 {
                     let absolute_offset = start_pos + pos as u64;
-                    return Ok(Some(absolute_offset));
+                    if start_header_crc_matches(&buffer[pos..]) {
+                        return Ok(Some(absolute_offset));
+                    }
+                    first_match.get_or_insert(absolute_offset);
                 }
             }
             // Move past this false positive and continue searching
@@ -189,7 +206,17 @@ pub fn find_signature<R: Read + Seek>(
         }
     }
 
-    Ok(None)
+    Ok(first_match)
+}
+
+/// Returns true if `data` starts with a signature header whose start header
+/// CRC matches the 20 bytes it covers.
+fn start_header_crc_matches(data: &[u8]) -> bool {
+    if data.len() < SIGNATURE_HEADER_SIZE as usize {
+        return false;
+    }
+    let stored_crc = u32::from_le_bytes([data[8], data[9], data[10], data[11]]);
+    stored_crc == crc32fast::hash(&data[12..32])
 }
 
 /// Detects if a file is a self-extracting archive and returns information about it.
@@ -293,6 +320,20 @@ mod tests {
         assert_eq!(header.next_header_crc, 0xDEADBEEF);
     }
 
+    #[test]
+    fn test_version_compatibility() {
+        // Newer minor versions are read with a warning
+        let mut data = create_valid_header(100, 50, 0);
+        data[7] = 5;
+        let header = StartHeader::parse(&mut Cursor::new(&data)).unwrap();
+        assert_eq!((header.version_major, header.version_minor), (0, 5));
+
+        // A different major version is rejected
+        data[6] = 1;
+        let err = StartHeader::parse(&mut Cursor::new(&data)).unwrap_err();
+        assert!(matches!(err, Error::UnsupportedFeature { .. }));
+    }
+
     #[test]
     fn test_invalid_signature() {
         let mut data = create_valid_header(100, 50, 0);
@@ -83,9 +83,22 @@ pub mod property_id {
     /// Encoded header.
     pub const ENCODED_HEADER: u8 = 0x17;
     /// Start position.
+    ///
+    /// Deprecated and without defined semantics: zesven never writes it
+    /// and skips it when reading.
     pub const START_POS: u8 = 0x18;
     /// Dummy marker.
     pub const DUMMY: u8 = 0x19;
+    /// Hole maps of sparse files.
+    ///
+    /// This is a zesven extension; other readers skip it as an unknown
+    /// property.
+    pub const SPARSE_HOLES: u8 = 0x7F;
+    /// BSD file flags (`st_flags`).
+    ///
+    /// This is a zesven extension; other readers skip it as an unknown
+    /// property.
+    pub const BSD_FLAGS: u8 = 0x7E;
 }
 
 /// Windows file attribute constants.
@@ -26,6 +26,8 @@ pub struct ArchiveHeader {
     pub files_info: Option<FilesInfo>,
     /// Whether the header itself was encrypted (required password to read metadata).
     pub header_encrypted: bool,
+    /// AES coder properties used to encrypt the header, if it was encrypted.
+    pub header_aes_properties: Option<Vec<u8>>,
 }
 
 impl ArchiveHeader {
@@ -137,6 +139,14 @@ impl HeaderParser {
 
                 property_id::MAIN_STREAMS_INFO => {
                     self.parse_streams_info(r, &mut header)?;
+
+                    // Without SubStreamsInfo, each folder holds a single stream
+                    if let (None, Some(unpack_info)) =
+                        (&header.substreams_info, &header.unpack_info)
+                    {
+                        header.substreams_info =
+                            Some(SubStreamsInfo::one_per_folder(&unpack_info.folders));
+                    }
                 }
 
                 property_id::FILES_INFO => {
@@ -221,6 +231,7 @@ impl HeaderParser {
 
         // Check if header is encrypted (uses AES codec)
         let header_encrypted = Self::folder_uses_encryption(&streams_header);
+        let header_aes_properties = Self::folder_aes_properties(&streams_header);
 
         // For encrypted headers, the data is embedded right after streams_info.
         // Use current position as the base for pack_pos instead of archive_data_start.
@@ -244,6 +255,7 @@ impl HeaderParser {
                 let mut cursor = Cursor::new(&decompressed[1..]);
                 let mut header = self.parse_main_header(&mut cursor)?;
                 header.header_encrypted = header_encrypted;
+                header.header_aes_properties = header_aes_properties;
                 Ok(header)
             }
             property_id::ENCODED_HEADER => {
@@ -269,6 +281,8 @@ impl HeaderParser {
                     let mut header = self.parse_main_header(&mut inner_cursor)?;
                     // Header is encrypted if either level used encryption
                     header.header_encrypted = header_encrypted || nested_encrypted;
+                    header.header_aes_properties = header_aes_properties
+                        .or_else(|| Self::folder_aes_properties(&nested_streams));
                     Ok(header)
                 } else {
                     Err(Error::InvalidFormat(format!(
@@ -298,6 +312,16 @@ impl HeaderParser {
         false
     }
 
+    /// Returns the properties of the first AES coder in the header's folders.
+    fn folder_aes_properties(header: &ArchiveHeader) -> Option<Vec<u8>> {
+        header
+            .folders()
+            .iter()
+            .flat_map(|folder| &folder.coders)
+            .find(|coder| coder.method_id.as_slice() == codec::method::AES)
+            .and_then(|coder| coder.properties.clone())
+    }
+
     /// Decompresses encoded header data.
     ///
     /// Uses the pack_info and unpack_info from the streams header to locate
@@ -359,6 +383,17 @@ impl HeaderParser {
         let mut decoder = self.build_header_decoder(cursor, folder, unpack_size)?;
 
         let mut decompressed = Vec::with_capacity(unpack_size as usize);
+
+        // With a wrong password the decrypted header is garbage; check that it
+        // starts like a header before decoding the rest
+        if Self::folder_uses_encryption(header) && unpack_size >= 2 {
+            let mut prefix = [0u8; 2];
+            if decoder.read_exact(&mut prefix).is_err() || !is_header_start(prefix) {
+                return Err(wrong_header_password());
+            }
+            decompressed.extend_from_slice(&prefix);
+        }
+
         decoder.read_to_end(&mut decompressed)?;
 
         // Verify CRC if available
@@ -407,14 +442,17 @@ impl HeaderParser {
             #[cfg(feature = "aes")]
             if inner_coder.method_id.as_slice() == codec::method::AES {
                 // Encrypted header: AES (inner) -> LZMA2 (outer)
-                let password = self.password.as_ref().ok_or(Error::PasswordRequired)?;
-
-                // Get intermediate size (AES output = compressed size)
-                let aes_unpack_size = folder.unpack_sizes.get(1).copied().unwrap_or(unpack_size);
-
-                // First decrypt with AES
-                let decrypted =
-                    codec::build_decoder_encrypted(input, inner_coder, aes_unpack_size, password)?;
+                let password = self.password.as_ref().ok_or(Error::PasswordRequired {
+                    encrypted_header: true,
+                })?;
+
+                // First decrypt with AES, rejecting a first block that does not
+                // look like the start of a compressed stream
+                let properties = inner_coder.properties.as_deref().unwrap_or(&[]);
+                let mut decrypted = crate::crypto::Aes256Decoder::new(input, properties, password)?;
+                if !decrypted.validate_first_block(&outer_coder.method_id)? {
+                    return Err(wrong_header_password());
+                }
 
                 // Then decompress with LZMA2
                 return Ok(Box::new(codec::build_decoder(
@@ -447,20 +485,13 @@ impl HeaderParser {
     }
 
     /// Gets file sizes and CRCs from parsed structures.
+    ///
+    /// SubStreamsInfo is always present once folders have been parsed, since
+    /// an omitted one is filled in with one stream per folder.
     fn get_file_sizes_and_crcs(&self, header: &ArchiveHeader) -> (Vec<u64>, Vec<Option<u32>>) {
-        if let Some(ref substreams) = header.substreams_info {
-            (substreams.unpack_sizes.clone(), substreams.digests.clone())
-        } else if let Some(ref unpack_info) = header.unpack_info {
-            // Single file per folder
-            let sizes: Vec<u64> = unpack_info
-                .folders
-                .iter()
-                .filter_map(|f| f.final_unpack_size())
-                .collect();
-            let crcs: Vec<Option<u32>> = unpack_info.folders.iter().map(|f| f.unpack_crc).collect();
-            (sizes, crcs)
-        } else {
-            (Vec::new(), Vec::new())
+        match header.substreams_info {
+            Some(ref substreams) => (substreams.unpack_sizes.clone(), substreams.digests.clone()),
+            None => (Vec::new(), Vec::new()),
         }
     }
 
@@ -476,6 +507,40 @@ impl HeaderParser {
     }
 }
 
+/// Returns whether `prefix` looks like the start of a decoded header.
+///
+/// A header starts with the `kHeader` or `kEncodedHeader` marker, followed by
+/// one of the property IDs that may open that structure.
+fn is_header_start(prefix: [u8; 2]) -> bool {
+    match prefix[0] {
+        property_id::HEADER => matches!(
+            prefix[1],
+            property_id::END
+                | property_id::ARCHIVE_PROPERTIES
+                | property_id::ADDITIONAL_STREAMS_INFO
+                | property_id::MAIN_STREAMS_INFO
+                | property_id::FILES_INFO
+        ),
+        property_id::ENCODED_HEADER => matches!(
+            prefix[1],
+            property_id::END
+                | property_id::PACK_INFO
+                | property_id::UNPACK_INFO
+                | property_id::SUBSTREAMS_INFO
+        ),
+        _ => false,
+    }
+}
+
+/// Error for an encrypted header that does not decrypt to a valid header.
+fn wrong_header_password() -> Error {
+    Error::WrongPassword {
+        entry_index: None,
+        entry_name: None,
+        detection_method: crate::error::PasswordDetectionMethod::EarlyHeaderValidation,
+    }
+}
+
 impl Default for HeaderParser {
     fn default() -> Self {
         Self::new()
@@ -536,6 +601,29 @@ pub fn read_archive_header_with_offset_and_password<R: Read + Seek>(
     read_archive_header_internal(r, limits, sfx_offset, password)
 }
 
+/// Checks the next header read from `header_pos` against the CRC stored in
+/// the start header.
+///
+/// A mismatch is reported as [`Error::CorruptHeader`] at `header_pos`, with
+/// both CRC values, before any header byte is interpreted.
+fn verify_next_header_crc(
+    start_header: &StartHeader,
+    header_pos: u64,
+    header_data: &[u8],
+) -> Result<()> {
+    let actual_crc = crc32fast::hash(header_data);
+    if actual_crc != start_header.next_header_crc {
+        return Err(Error::CorruptHeader {
+            offset: header_pos,
+            reason: format!(
+                "next header CRC mismatch: expected {:#x}, got {:#x}",
+                start_header.next_header_crc, actual_crc
+            ),
+        });
+    }
+    Ok(())
+}
+
 /// Internal implementation for reading archive headers.
 #[cfg(feature = "aes")]
 fn read_archive_header_internal<R: Read + Seek>(
@@ -561,17 +649,7 @@ fn read_archive_header_internal<R: Read + Seek>(
     let mut header_data = vec![0u8; start_header.next_header_size as usize];
     r.read_exact(&mut header_data)?;
 
-    // Verify CRC of header data
-    let actual_crc = crc32fast::hash(&header_data);
-    if actual_crc != start_header.next_header_crc {
-        return Err(Error::CorruptHeader {
-            offset: header_pos,
-            reason: format!(
-                "next header CRC mismatch: expected {:#x}, got {:#x}",
-                start_header.next_header_crc, actual_crc
-            ),
-        });
-    }
+    verify_next_header_crc(&start_header, header_pos, &header_data)?;
 
     // Parse the header from the buffer, but pass the original reader
     // for seeking if we encounter an encoded header
@@ -636,17 +714,7 @@ fn read_archive_header_internal<R: Read + Seek>(
     let mut header_data = vec![0u8; start_header.next_header_size as usize];
     r.read_exact(&mut header_data)?;
 
-    // Verify CRC of header data
-    let actual_crc = crc32fast::hash(&header_data);
-    if actual_crc != start_header.next_header_crc {
-        return Err(Error::CorruptHeader {
-            offset: header_pos,
-            reason: format!(
-                "next header CRC mismatch: expected {:#x}, got {:#x}",
-                start_header.next_header_crc, actual_crc
-            ),
-        });
-    }
+    verify_next_header_crc(&start_header, header_pos, &header_data)?;
 
     // Parse the header from the buffer, but pass the original reader
     // for seeking if we encounter an encoded header
@@ -779,4 +847,23 @@ mod tests {
         assert_eq!(parser.limits.max_entries, 10);
         assert_eq!(parser.limits.max_header_bytes, 100);
     }
+
+    #[test]
+    fn test_is_header_start() {
+        assert!(is_header_start([
+            property_id::HEADER,
+            property_id::MAIN_STREAMS_INFO
+        ]));
+        assert!(is_header_start([property_id::HEADER, property_id::END]));
+        assert!(is_header_start([
+            property_id::ENCODED_HEADER,
+            property_id::PACK_INFO
+        ]));
+        assert!(!is_header_start([
+            property_id::HEADER,
+            property_id::PACK_INFO
+        ]));
+        assert!(!is_header_start([property_id::NAME, property_id::END]));
+        assert!(!is_header_start([0xA7, 0x3C]));
+    }
 }
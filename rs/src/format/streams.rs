@@ -95,7 +95,11 @@ impl RatioLimit {
                     ratio, self.max_ratio
                 ))),
                 LimitMode::Warn => {
-                    // In a real implementation, this would log a warning
+                    log::warn!(
+                        "Compression ratio {} exceeds limit {}",
+                        ratio,
+                        self.max_ratio
+                    );
                     Ok(())
                 }
                 LimitMode::Ignore => Ok(()),
@@ -119,6 +123,8 @@ pub struct ResourceLimits {
     pub max_entry_unpacked: u64,
     /// Compression ratio limit (for bomb protection).
     pub ratio_limit: Option<RatioLimit>,
+    /// Handling of pack streams that extend past the end of the archive.
+    pub pack_bounds: LimitMode,
 }
 
 impl Default for ResourceLimits {
@@ -131,6 +137,7 @@ impl Default for ResourceLimits {
     /// | `max_total_unpacked` | 1 TiB | Maximum total extracted size |
     /// | `max_entry_unpacked` | 64 GiB | Maximum single entry size |
     /// | `ratio_limit` | 1000:1 (HardError) | Compression bomb protection |
+    /// | `pack_bounds` | HardError | Pack streams must fit in the archive |
     ///
     /// These defaults are designed to protect against malicious archives
     /// while allowing most legitimate archives to be processed. Use
@@ -142,6 +149,7 @@ impl Default for ResourceLimits {
             max_total_unpacked: 1 << 40,  // 1 TiB
             max_entry_unpacked: 64 << 30, // 64 GiB
             ratio_limit: Some(RatioLimit::default()),
+            pack_bounds: LimitMode::HardError,
         }
     }
 }
@@ -160,6 +168,7 @@ impl ResourceLimits {
             max_total_unpacked: u64::MAX,
             max_entry_unpacked: u64::MAX,
             ratio_limit: None,
+            pack_bounds: LimitMode::Ignore,
         }
     }
 
@@ -192,6 +201,22 @@ impl ResourceLimits {
         self.ratio_limit = limit;
         self
     }
+
+    /// Sets how opening handles pack streams that extend past the archive end.
+    ///
+    /// When opening, the end of the packed data (pack start plus the sum of
+    /// all declared pack sizes) is compared with the stream length. With
+    /// [`LimitMode::HardError`] an archive whose header declares more packed
+    /// data than the stream holds fails with [`Error::CorruptHeader`] instead
+    /// of failing late during extraction. [`LimitMode::Warn`] logs the
+    /// problem and opens the archive anyway, which allows inspecting truncated
+    /// downloads with [`Archive::check_truncation`].
+    ///
+    /// [`Archive::check_truncation`]: crate::read::Archive::check_truncation
+    pub fn pack_bounds(mut self, mode: LimitMode) -> Self {
+        self.pack_bounds = mode;
+        self
+    }
 }
 
 /// Information about packed (compressed) streams.
@@ -573,6 +598,113 @@ impl Folder {
         Ok(())
     }
 
+    /// Validates that bind_pairs and packed_streams connect the coders into a
+    /// single graph ending in the folder output.
+    ///
+    /// Every input stream must be fed exactly once (by a bind_pair or a packed
+    /// stream), every output stream may be consumed at most once, exactly one
+    /// output stream must remain unbound, and every coder must be reachable from
+    /// that output without cycles.
+    ///
+    /// Index bounds are checked by [`validate_bind_pairs`](Self::validate_bind_pairs)
+    /// and [`validate_packed_streams`](Self::validate_packed_streams); this check
+    /// ignores out-of-range indices.
+    pub fn validate_stream_graph(&self) -> crate::Result<()> {
+        let mut in_feeds = vec![0usize; self.total_in_streams() as usize];
+        let mut out_uses = vec![0usize; self.total_out_streams() as usize];
+        for bp in &self.bind_pairs {
+            if let Some(count) = in_feeds.get_mut(bp.in_index as usize) {
+                *count += 1;
+            }
+            if let Some(count) = out_uses.get_mut(bp.out_index as usize) {
+                *count += 1;
+            }
+        }
+        for &in_stream_idx in &self.packed_streams {
+            if let Some(count) = in_feeds.get_mut(in_stream_idx as usize) {
+                *count += 1;
+            }
+        }
+
+        if let Some((i, &count)) = in_feeds.iter().enumerate().find(|&(_, &n)| n != 1) {
+            return Err(crate::Error::InvalidFormat(format!(
+                "input stream {} is fed {} times, expected exactly once",
+                i, count
+            )));
+        }
+        if let Some((i, &count)) = out_uses.iter().enumerate().find(|&(_, &n)| n > 1) {
+            return Err(crate::Error::InvalidFormat(format!(
+                "output stream {} is consumed {} times",
+                i, count
+            )));
+        }
+        let unbound: Vec<usize> = (0..out_uses.len()).filter(|&i| out_uses[i] == 0).collect();
+        if unbound.len() != 1 {
+            return Err(crate::Error::InvalidFormat(format!(
+                "folder has {} unbound output streams, expected 1",
+                unbound.len()
+            )));
+        }
+
+        // Walk from the coder producing the folder output back through bind_pairs
+        let offsets = self.coder_stream_offsets();
+        let mut state = vec![VisitState::Unvisited; self.coders.len()];
+        if let Some(main) = self.coder_for_out_stream(&offsets, unbound[0]) {
+            self.visit_coder(main, &offsets, &mut state)?;
+        }
+        if let Some(coder_idx) = state.iter().position(|&s| s == VisitState::Unvisited) {
+            return Err(crate::Error::InvalidFormat(format!(
+                "coder {} is not connected to the folder output",
+                coder_idx
+            )));
+        }
+        Ok(())
+    }
+
+    /// Depth-first visit of the coders feeding `coder_idx`, rejecting cycles.
+    fn visit_coder(
+        &self,
+        coder_idx: usize,
+        offsets: &[(usize, usize)],
+        state: &mut [VisitState],
+    ) -> crate::Result<()> {
+        match state[coder_idx] {
+            VisitState::Done => return Ok(()),
+            VisitState::InProgress => {
+                return Err(crate::Error::InvalidFormat(format!(
+                    "bind pairs form a cycle through coder {}",
+                    coder_idx
+                )));
+            }
+            VisitState::Unvisited => {}
+        }
+        state[coder_idx] = VisitState::InProgress;
+
+        let first_in = offsets[coder_idx].0;
+        for in_stream_idx in first_in..first_in + self.coders[coder_idx].num_in_streams as usize {
+            if let Some(bp) = self.find_bind_pair_for_in_stream(in_stream_idx as u64) {
+                if let Some(source) = self.coder_for_out_stream(offsets, bp.out_index as usize) {
+                    self.visit_coder(source, offsets, state)?;
+                }
+            }
+        }
+
+        state[coder_idx] = VisitState::Done;
+        Ok(())
+    }
+
+    /// Returns the index of the coder producing the given output stream.
+    fn coder_for_out_stream(
+        &self,
+        offsets: &[(usize, usize)],
+        out_stream_idx: usize,
+    ) -> Option<usize> {
+        self.coders.iter().enumerate().position(|(i, coder)| {
+            let first_out = offsets[i].1;
+            (first_out..first_out + coder.num_out_streams as usize).contains(&out_stream_idx)
+        })
+    }
+
     /// Finds the main coder index (the one whose output is the final output).
     ///
     /// The main coder is the one whose output is NOT consumed by any bind_pair.
@@ -612,6 +744,36 @@ impl Folder {
         Some(0)
     }
 
+    /// Returns the coder indices in decoding order, from the folder output
+    /// towards the packed streams.
+    ///
+    /// Starts at the [main coder](Self::main_coder_index) and visits the
+    /// coders feeding each input depth first, in input order. Coders not
+    /// connected to the folder output come last, in index order.
+    pub fn coder_chain(&self) -> Vec<usize> {
+        let offsets = self.coder_stream_offsets();
+        let mut chain = Vec::with_capacity(self.coders.len());
+        let mut pending: Vec<usize> = self.main_coder_index().into_iter().collect();
+        while let Some(coder_idx) = pending.pop() {
+            if chain.contains(&coder_idx) {
+                continue;
+            }
+            chain.push(coder_idx);
+            let first_in = offsets[coder_idx].0;
+            let num_in = self.coders[coder_idx].num_in_streams as usize;
+            let sources: Vec<usize> = (first_in..first_in + num_in)
+                .filter_map(|in_idx| self.find_bind_pair_for_in_stream(in_idx as u64))
+                .filter_map(|bp| self.coder_for_out_stream(&offsets, bp.out_index as usize))
+                .collect();
+            pending.extend(sources.into_iter().rev());
+        }
+        let unconnected: Vec<usize> = (0..self.coders.len())
+            .filter(|idx| !chain.contains(idx))
+            .collect();
+        chain.extend(unconnected);
+        chain
+    }
+
     /// Returns the stream offsets for each coder.
     ///
     /// Each tuple contains (first_in_stream_idx, first_out_stream_idx).
@@ -654,6 +816,14 @@ impl Folder {
     }
 }
 
+/// Traversal state of a coder in [`Folder::validate_stream_graph`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VisitState {
+    Unvisited,
+    InProgress,
+    Done,
+}
+
 /// Unpack info containing folder definitions.
 #[derive(Debug, Clone, Default)]
 pub struct UnpackInfo {
@@ -819,7 +989,9 @@ impl SubStreamsInfo {
                         )));
                     }
 
-                    // Count streams that need CRCs (those without folder CRC)
+                    // Count streams that need CRCs. Only single-stream folders
+                    // reuse the folder CRC; multi-stream folders always list
+                    // per-substream CRCs, even when a folder CRC is also present.
                     let mut streams_needing_crc = 0usize;
                     for (folder_idx, &num_streams) in
                         num_unpack_streams_in_folders.iter().enumerate()
@@ -896,6 +1068,21 @@ impl SubStreamsInfo {
         })
     }
 
+    /// Creates the info implied when SubStreamsInfo is absent.
+    ///
+    /// Each folder then holds exactly one stream, with the folder's unpack
+    /// size and CRC.
+    pub fn one_per_folder(folders: &[Folder]) -> Self {
+        Self {
+            num_unpack_streams_in_folders: vec![1; folders.len()],
+            unpack_sizes: folders
+                .iter()
+                .map(|f| f.final_unpack_size().unwrap_or(0))
+                .collect(),
+            digests: folders.iter().map(|f| f.unpack_crc).collect(),
+        }
+    }
+
     /// Returns the total number of substreams.
     pub fn total_streams(&self) -> u64 {
         self.num_unpack_streams_in_folders.iter().sum()
@@ -1139,6 +1326,19 @@ mod tests {
         assert_eq!(substreams.total_streams(), 2);
     }
 
+    #[test]
+    fn test_substreams_info_one_per_folder() {
+        let folders = vec![
+            create_test_folder(1000, true),
+            create_test_folder(2000, false),
+        ];
+
+        let substreams = SubStreamsInfo::one_per_folder(&folders);
+        assert_eq!(substreams.num_unpack_streams_in_folders, vec![1, 1]);
+        assert_eq!(substreams.unpack_sizes, vec![1000, 2000]);
+        assert_eq!(substreams.digests, vec![Some(0x12345678), None]);
+    }
+
     #[test]
     fn test_substreams_info_multiple_files() {
         // 2 files in first folder (solid), 1 in second
@@ -1173,6 +1373,40 @@ mod tests {
         assert_eq!(substreams.total_streams(), 3);
     }
 
+    #[test]
+    fn test_substreams_info_multi_stream_folder_with_folder_crc() {
+        // Solid folder with a folder CRC followed by a single-stream folder
+        // whose CRC is inherited from the folder
+        let folders = vec![
+            create_test_folder(1500, true),
+            create_test_folder(500, true),
+        ];
+
+        let mut data = Vec::new();
+        data.push(property_id::NUM_UNPACK_STREAM);
+        write_variable_u64(&mut data, 2);
+        write_variable_u64(&mut data, 1);
+        data.push(property_id::SIZE);
+        write_variable_u64(&mut data, 1000);
+
+        // Only the two substreams of folder 0 carry explicit CRCs
+        data.push(property_id::CRC);
+        data.push(1); // all defined
+        data.extend_from_slice(&0xAAAAAAAAu32.to_le_bytes());
+        data.extend_from_slice(&0xBBBBBBBBu32.to_le_bytes());
+        data.push(property_id::END);
+
+        let mut cursor = Cursor::new(&data);
+        let limits = ResourceLimits::default();
+        let substreams = SubStreamsInfo::parse(&mut cursor, &folders, &limits).unwrap();
+
+        assert_eq!(
+            substreams.digests,
+            vec![Some(0xAAAAAAAA), Some(0xBBBBBBBB), Some(0x12345678)]
+        );
+        assert_eq!(cursor.position() as usize, data.len());
+    }
+
     #[test]
     fn test_substreams_info_empty() {
         let folders: Vec<Folder> = vec![];
@@ -1276,6 +1510,12 @@ mod tests {
         assert_eq!(folder.main_coder_index(), Some(0));
     }
 
+    #[test]
+    fn test_folder_coder_chain_bcj2() {
+        let folder = create_bcj2_folder();
+        assert_eq!(folder.coder_chain(), vec![0, 1, 2, 3, 4]);
+    }
+
     #[test]
     fn test_folder_main_coder_index_simple() {
         let folder = create_test_folder(1000, true);
@@ -1569,6 +1809,65 @@ mod tests {
         assert!(folder.validate_bind_pairs().is_ok());
     }
 
+    fn copy_coder() -> Coder {
+        Coder {
+            method_id: vec![0x00],
+            num_in_streams: 1,
+            num_out_streams: 1,
+            properties: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_stream_graph_valid() {
+        assert!(create_bcj2_folder().validate_stream_graph().is_ok());
+
+        let folder = Folder {
+            coders: vec![copy_coder(), copy_coder()],
+            bind_pairs: vec![BindPair {
+                in_index: 0,
+                out_index: 1,
+            }],
+            packed_streams: vec![1],
+            unpack_sizes: vec![100, 100],
+            unpack_crc: None,
+        };
+        assert!(folder.validate_stream_graph().is_ok());
+    }
+
+    #[test]
+    fn test_validate_stream_graph_detached_coder() {
+        // Coder 1 feeds itself, so nothing reaches coder 0's packed input chain
+        let folder = Folder {
+            coders: vec![copy_coder(), copy_coder()],
+            bind_pairs: vec![BindPair {
+                in_index: 1,
+                out_index: 1,
+            }],
+            packed_streams: vec![0],
+            unpack_sizes: vec![100, 100],
+            unpack_crc: None,
+        };
+        let err = folder.validate_stream_graph().unwrap_err().to_string();
+        assert!(err.contains("coder 1"), "unexpected error: {}", err);
+    }
+
+    #[test]
+    fn test_validate_stream_graph_input_fed_twice() {
+        let folder = Folder {
+            coders: vec![copy_coder(), copy_coder()],
+            bind_pairs: vec![BindPair {
+                in_index: 0,
+                out_index: 1,
+            }],
+            packed_streams: vec![0],
+            unpack_sizes: vec![100, 100],
+            unpack_crc: None,
+        };
+        let err = folder.validate_stream_graph().unwrap_err().to_string();
+        assert!(err.contains("input stream 0"), "unexpected error: {}", err);
+    }
+
     // =========================================================================
     // ResourceLimits Builder Tests
     // =========================================================================
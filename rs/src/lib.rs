@@ -111,6 +111,7 @@
 //! | `brotli` | No | Brotli compression support |
 //! | `fast-lzma2` | No | Fast LZMA2 encoder with radix match-finder |
 //! | `regex` | No | Regex-based file filtering |
+//! | `chrono` | No | `Timestamp` conversions to and from chrono's `DateTime<Utc>` |
 //! | `sysinfo` | No | System info for adaptive memory limits |
 //! | `async` | No | Async/await API with Tokio integration |
 //! | `wasm` | No | WebAssembly/browser support |
@@ -301,7 +302,7 @@ pub use volume::VolumeConfig;
 
 // Re-export safety utilities
 pub use format::streams::{LimitMode, RatioLimit, ResourceLimits};
-pub use safety::{LimitedReader, validate_extract_path};
+pub use safety::{DeadlineReader, LimitedReader, validate_extract_path};
 
 // Re-export streaming API
 pub use streaming::{
@@ -314,22 +315,24 @@ pub use stats::{ReadStats, StatsConfig, StatsReader, WithStats};
 
 // Re-export progress API
 pub use progress::{
-    AtomicProgress, NoProgress, ProgressReporter, ProgressState, StatisticsProgress,
-    ThrottledProgress, progress_fn,
+    AtomicProgress, ByteIntervalThrottle, NoProgress, ProgressReporter, ProgressState,
+    ProgressThrottle, StatisticsProgress, ThrottledProgress, TimeThrottle, progress_fn,
 };
 
 // Re-export edit API
 pub use edit::{ArchiveEditor, EditResult, EditableArchive, Operation, OperationBuilder};
 
+mod bsd_flags;
 mod s3fifo;
+mod sparse;
 
 // Re-export SFX API
 pub use sfx::{SfxBuilder, SfxConfig, SfxFormat, SfxInfo, SfxResult, SfxStub, create_sfx};
 
 // Re-export recovery API
 pub use recovery::{
-    FailedEntry, RecoveredEntry, RecoveryOptions, RecoveryResult, RecoveryStatus, SignatureScanner,
-    find_all_signatures, is_valid_archive, recover_archive,
+    FailedEntry, RecordRepair, RecoveredEntry, RecoveryOptions, RecoveryResult, RecoveryStatus,
+    SignatureScanner, find_all_signatures, is_valid_archive, recover_archive, repair_with_record,
 };
 
 // Re-export ownership API
@@ -103,12 +103,14 @@ impl UnixOwnership {
     /// Applies ownership to a file path.
     ///
     /// This only works on Unix systems and requires appropriate permissions.
+    /// Whichever of the UID and GID are present are applied; names are not
+    /// resolved.
     #[cfg(unix)]
     pub fn apply_to_path(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
         use std::os::unix::fs::chown;
 
-        if let (Some(uid), Some(gid)) = (self.uid, self.gid) {
-            chown(path, Some(uid), Some(gid))?;
+        if self.uid.is_some() || self.gid.is_some() {
+            chown(path, self.uid, self.gid)?;
         }
         Ok(())
     }
@@ -264,14 +264,99 @@ impl ProgressReporter for StatisticsProgress {
     }
 }
 
+/// Strategy deciding which progress updates a [`ThrottledProgress`] forwards.
+///
+/// Implementations see every update and return `true` for the ones the
+/// wrapped reporter should receive. The final update, where the processed
+/// bytes reach the total, is forwarded regardless.
+pub trait ProgressThrottle: Send {
+    /// Returns whether the update should be forwarded.
+    fn should_report(&mut self, bytes_processed: u64, total_bytes: u64) -> bool;
+}
+
+/// Forwards at most one update per time interval.
+#[derive(Debug, Clone)]
+pub struct TimeThrottle {
+    min_interval: Duration,
+    last_report: Instant,
+}
+
+impl TimeThrottle {
+    /// Creates a strategy forwarding updates at least `min_interval` apart.
+    pub fn new(min_interval: Duration) -> Self {
+        Self {
+            min_interval,
+            last_report: Instant::now(),
+        }
+    }
+}
+
+impl ProgressThrottle for TimeThrottle {
+    fn should_report(&mut self, _bytes_processed: u64, _total_bytes: u64) -> bool {
+        let now = Instant::now();
+        if now.duration_since(self.last_report) >= self.min_interval {
+            self.last_report = now;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Forwards an update each time the processed bytes cross a multiple of
+/// a byte interval.
+///
+/// An update that crosses several multiples at once is forwarded once.
+///
+/// # Example
+///
+/// ```rust
+/// use zesven::progress::{BYTES_MIB, ByteIntervalThrottle, NoProgress, ThrottledProgress};
+///
+/// // Report every 16 MiB
+/// let progress = ThrottledProgress::with_strategy(
+///     NoProgress,
+///     ByteIntervalThrottle::new(16 * BYTES_MIB),
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct ByteIntervalThrottle {
+    interval: u64,
+    next_report: u64,
+}
+
+impl ByteIntervalThrottle {
+    /// Creates a strategy forwarding updates every `interval` bytes.
+    ///
+    /// An interval of 0 forwards every update.
+    pub fn new(interval: u64) -> Self {
+        Self {
+            interval,
+            next_report: interval,
+        }
+    }
+}
+
+impl ProgressThrottle for ByteIntervalThrottle {
+    fn should_report(&mut self, bytes_processed: u64, _total_bytes: u64) -> bool {
+        if bytes_processed < self.next_report {
+            return false;
+        }
+        if let Some(intervals) = bytes_processed.checked_div(self.interval) {
+            self.next_report = (intervals + 1).saturating_mul(self.interval);
+        }
+        true
+    }
+}
+
 /// A progress reporter that rate-limits callbacks.
 ///
 /// Useful for reducing overhead when progress is reported very frequently.
+/// Which updates get through is decided by a [`ProgressThrottle`]
+/// strategy; [`new`](Self::new) uses a [`TimeThrottle`].
 pub struct ThrottledProgress<P> {
     inner: P,
-    min_interval: Duration,
-    last_callback: Instant,
-    last_bytes: u64,
+    strategy: Box<dyn ProgressThrottle>,
 }
 
 impl<P: ProgressReporter> ThrottledProgress<P> {
@@ -279,12 +364,7 @@ impl<P: ProgressReporter> ThrottledProgress<P> {
     ///
     /// `min_interval` is the minimum time between progress callbacks.
     pub fn new(inner: P, min_interval: Duration) -> Self {
-        Self {
-            inner,
-            min_interval,
-            last_callback: Instant::now(),
-            last_bytes: 0,
-        }
+        Self::with_strategy(inner, TimeThrottle::new(min_interval))
     }
 
     /// Creates with default 100ms interval.
@@ -292,6 +372,14 @@ impl<P: ProgressReporter> ThrottledProgress<P> {
         Self::new(inner, Duration::from_millis(100))
     }
 
+    /// Creates a throttled progress reporter with a custom strategy.
+    pub fn with_strategy(inner: P, strategy: impl ProgressThrottle + 'static) -> Self {
+        Self {
+            inner,
+            strategy: Box::new(strategy),
+        }
+    }
+
     /// Returns the inner reporter.
     pub fn into_inner(self) -> P {
         self.inner
@@ -304,13 +392,10 @@ impl<P: ProgressReporter> ProgressReporter for ThrottledProgress<P> {
     }
 
     fn on_progress(&mut self, bytes_processed: u64, total_bytes: u64) -> bool {
-        let now = Instant::now();
-        let elapsed = now.duration_since(self.last_callback);
+        let report = self.strategy.should_report(bytes_processed, total_bytes);
 
         // Always call on completion
-        if bytes_processed >= total_bytes || elapsed >= self.min_interval {
-            self.last_callback = now;
-            self.last_bytes = bytes_processed;
+        if report || bytes_processed >= total_bytes {
             self.inner.on_progress(bytes_processed, total_bytes)
         } else {
             true
@@ -623,6 +708,35 @@ mod tests {
         assert!(throttled.on_progress(30, 100));
     }
 
+    #[test]
+    fn test_byte_interval_throttle_fires_at_each_mib() {
+        #[derive(Default)]
+        struct Recorder(Vec<u64>);
+
+        impl ProgressReporter for Recorder {
+            fn on_progress(&mut self, bytes_processed: u64, _total_bytes: u64) -> bool {
+                self.0.push(bytes_processed);
+                true
+            }
+        }
+
+        let total = 4 * BYTES_MIB;
+        let mut throttled = ThrottledProgress::with_strategy(
+            Recorder::default(),
+            ByteIntervalThrottle::new(BYTES_MIB),
+        );
+
+        throttled.on_total(total);
+        let mut processed = 0;
+        while processed < total {
+            processed += 256 * BYTES_KIB;
+            assert!(throttled.on_progress(processed, total));
+        }
+
+        let reported = throttled.into_inner().0;
+        assert_eq!(reported, [1, 2, 3, 4].map(|mib| mib * BYTES_MIB));
+    }
+
     #[test]
     fn test_atomic_progress() {
         let progress = AtomicProgress::shared();
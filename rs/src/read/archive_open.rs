@@ -4,19 +4,21 @@
 //! including files, readers, and encrypted archives.
 
 use std::fs::File;
-use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::io::{BufReader, Cursor, Read, Seek, SeekFrom};
 use std::path::Path;
 use std::sync::Arc;
 use std::sync::atomic::AtomicU64;
+use std::time::Duration;
 
+use crate::format::SIGNATURE_HEADER_SIZE;
 use crate::format::header::detect_sfx;
 use crate::format::parser::ArchiveHeader;
 #[cfg(not(feature = "aes"))]
 use crate::format::parser::read_archive_header_with_offset;
 #[cfg(feature = "aes")]
 use crate::format::parser::read_archive_header_with_offset_and_password;
-use crate::format::streams::ResourceLimits;
-use crate::safety::LimitedReader;
+use crate::format::streams::{LimitMode, ResourceLimits};
+use crate::safety::{DeadlineReader, LimitedReader};
 use crate::{Error, Result};
 
 #[cfg(feature = "aes")]
@@ -25,6 +27,56 @@ use crate::Password;
 use super::multivolume::{detect_multivolume_base, open_multivolume_as_single};
 use super::{Archive, ArchiveInfo, Entry, entries};
 
+/// Checks that the pack streams declared by `header` fit in the stream.
+///
+/// A crafted or truncated header may declare more packed data than the
+/// stream holds; catching this at open time avoids over-reads deep in
+/// extraction. `mode` decides whether this is an error, a warning, or ignored.
+fn check_pack_bounds<R: Seek>(
+    reader: &mut R,
+    header: &ArchiveHeader,
+    sfx_offset: u64,
+    mode: LimitMode,
+) -> Result<()> {
+    let Some(pack_info) = header.pack_info.as_ref() else {
+        return Ok(());
+    };
+    if mode == LimitMode::Ignore {
+        return Ok(());
+    }
+
+    let stream_len = reader.seek(SeekFrom::End(0)).map_err(Error::Io)?;
+    let pack_end = (sfx_offset + SIGNATURE_HEADER_SIZE)
+        .checked_add(pack_info.pack_pos)
+        .and_then(|start| {
+            pack_info
+                .pack_sizes
+                .iter()
+                .try_fold(start, |end, &size| end.checked_add(size))
+        });
+    if pack_end.is_some_and(|end| end <= stream_len) {
+        return Ok(());
+    }
+
+    let reason = match pack_end {
+        Some(end) => format!(
+            "pack streams end at offset {} but the archive is only {} bytes",
+            end, stream_len
+        ),
+        None => "pack stream sizes overflow".to_string(),
+    };
+    match mode {
+        LimitMode::HardError => Err(Error::CorruptHeader {
+            offset: stream_len,
+            reason,
+        }),
+        LimitMode::Warn | LimitMode::Ignore => {
+            log::warn!("{}", reason);
+            Ok(())
+        }
+    }
+}
+
 /// Result of opening an archive (internal helper to avoid CFG duplication).
 pub(crate) struct OpenResult<R> {
     pub reader: R,
@@ -45,8 +97,12 @@ pub(crate) struct ExtractionLimits {
     pub max_total_bytes: u64,
     /// Maximum compression ratio allowed.
     pub max_ratio: Option<u32>,
+    /// How to handle entries that exceed `max_ratio`.
+    pub ratio_mode: LimitMode,
     /// Shared counter for total bytes extracted (across entries).
     pub total_tracker: Arc<AtomicU64>,
+    /// Maximum time to spend decoding a single entry.
+    pub entry_timeout: Option<Duration>,
 }
 
 impl ExtractionLimits {
@@ -56,7 +112,20 @@ impl ExtractionLimits {
             max_entry_bytes: limits.max_entry_unpacked,
             max_total_bytes: limits.max_total_unpacked,
             max_ratio: limits.ratio_limit.as_ref().map(|r| r.max_ratio),
+            ratio_mode: limits
+                .ratio_limit
+                .as_ref()
+                .map_or(LimitMode::Ignore, |r| r.mode),
             total_tracker: Arc::new(AtomicU64::new(0)),
+            entry_timeout: None,
+        }
+    }
+
+    /// Moves a decoder onto a worker thread if an entry timeout is set.
+    pub fn wrap_timeout<D: Read + Send + 'static>(&self, decoder: D) -> Box<dyn Read + Send> {
+        match self.entry_timeout {
+            Some(timeout) => Box::new(DeadlineReader::new(decoder, timeout)),
+            None => Box::new(decoder),
         }
     }
 
@@ -68,7 +137,7 @@ impl ExtractionLimits {
             .total_tracker(self.total_tracker.clone(), self.max_total_bytes);
 
         if let Some(ratio) = self.max_ratio {
-            limited = limited.max_ratio(ratio);
+            limited = limited.max_ratio(ratio).ratio_mode(self.ratio_mode);
         }
 
         limited
@@ -80,7 +149,9 @@ impl ExtractionLimits {
             max_entry_bytes: u64::MAX,
             max_total_bytes: u64::MAX,
             max_ratio: None,
+            ratio_mode: LimitMode::Ignore,
             total_tracker: Arc::new(AtomicU64::new(0)),
+            entry_timeout: None,
         }
     }
 }
@@ -92,8 +163,12 @@ impl ExtractionLimits {
 pub(crate) fn map_io_error(e: std::io::Error) -> Error {
     // Try to extract a boxed Error from the io::Error
     if let Some(inner) = e.get_ref() {
-        if let Some(Error::ResourceLimitExceeded(msg)) = inner.downcast_ref::<Error>() {
-            return Error::ResourceLimitExceeded(msg.clone());
+        match inner.downcast_ref::<Error>() {
+            Some(Error::ResourceLimitExceeded(msg)) => {
+                return Error::ResourceLimitExceeded(msg.clone());
+            }
+            Some(Error::Timeout { timeout }) => return Error::Timeout { timeout: *timeout },
+            _ => {}
         }
     }
     // Check if it's an io::Error::other() containing our error
@@ -138,8 +213,9 @@ impl Archive<BufReader<File>> {
 
         // Single-file archive
         let file = File::open(path).map_err(Error::Io)?;
+        let source_file = file.try_clone().ok();
         let reader = BufReader::new(file);
-        Self::open(reader)
+        Self::open(reader).map(|archive| archive.with_source_file(source_file))
     }
 
     /// Opens an archive from a file path with custom resource limits.
@@ -155,8 +231,9 @@ impl Archive<BufReader<File>> {
     /// or the specified resource limits are violated.
     pub fn open_path_with_limits(path: impl AsRef<Path>, limits: ResourceLimits) -> Result<Self> {
         let file = File::open(path.as_ref()).map_err(Error::Io)?;
+        let source_file = file.try_clone().ok();
         let reader = BufReader::new(file);
-        Self::open_with_limits(reader, limits)
+        Self::open_with_limits(reader, limits).map(|archive| archive.with_source_file(source_file))
     }
 
     /// Opens an encrypted archive from a file path.
@@ -176,8 +253,62 @@ impl Archive<BufReader<File>> {
         password: impl Into<Password>,
     ) -> Result<Self> {
         let file = File::open(path.as_ref()).map_err(Error::Io)?;
+        let source_file = file.try_clone().ok();
         let reader = BufReader::new(file);
         Self::open_with_password(reader, password)
+            .map(|archive| archive.with_source_file(source_file))
+    }
+
+    /// Opens an archive from a file path with an optional password.
+    ///
+    /// See [`Archive::open_with_optional_password`] for how `None` differs
+    /// from an empty password.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened, the archive is invalid,
+    /// or the password is incorrect.
+    #[cfg(feature = "aes")]
+    pub fn open_path_with_optional_password(
+        path: impl AsRef<Path>,
+        password: Option<Password>,
+    ) -> Result<Self> {
+        let file = File::open(path.as_ref()).map_err(Error::Io)?;
+        let source_file = file.try_clone().ok();
+        let reader = BufReader::new(file);
+        Self::open_with_optional_password(reader, password)
+            .map(|archive| archive.with_source_file(source_file))
+    }
+
+    /// Keeps a duplicate handle of the archive file for the stored-entry fast path.
+    #[cfg(target_os = "linux")]
+    pub(crate) fn with_source_file(mut self, source_file: Option<File>) -> Self {
+        self.source_file = source_file;
+        self
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub(crate) fn with_source_file(self, _source_file: Option<File>) -> Self {
+        self
+    }
+}
+
+impl<'a> Archive<Cursor<&'a [u8]>> {
+    /// Opens an archive from a borrowed byte slice.
+    ///
+    /// The archive reads directly from `data` without copying it, which
+    /// suits memory-mapped files and buffers owned elsewhere. The returned
+    /// archive borrows `data` for `'a`, so the slice must outlive it.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The complete archive bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the archive is invalid.
+    pub fn open_slice(data: &'a [u8]) -> Result<Self> {
+        Self::open(Cursor::new(data))
     }
 }
 
@@ -215,6 +346,10 @@ impl<R: Read + Seek> Archive<R> {
 
     /// Opens an encrypted archive from a reader.
     ///
+    /// The password is always used as the key, even when empty: passing `""`
+    /// opens archives encrypted with the empty password. To open an archive
+    /// without any password, use [`Archive::open`].
+    ///
     /// # Arguments
     ///
     /// * `reader` - A reader providing the archive data
@@ -229,6 +364,67 @@ impl<R: Read + Seek> Archive<R> {
         Self::open_internal(reader, Some(password.into()), None)
     }
 
+    /// Opens an archive from a reader with an optional password.
+    ///
+    /// `None` means no password is known: unencrypted archives open and
+    /// extract normally, while an encrypted header or encrypted entries fail
+    /// with [`Error::PasswordRequired`]. `Some(password)` uses `password` as
+    /// the key, including `Some(Password::new(""))` for archives encrypted
+    /// with the empty password. This is equivalent to [`Archive::open`] or
+    /// [`Archive::open_with_password`], for callers that hold an
+    /// `Option<Password>`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the archive is invalid, cannot be read,
+    /// or the password is missing or incorrect for an encrypted header.
+    #[cfg(feature = "aes")]
+    pub fn open_with_optional_password(reader: R, password: Option<Password>) -> Result<Self> {
+        Self::open_internal(reader, password, None)
+    }
+
+    /// Opens an archive with an encrypted header once its password is known.
+    ///
+    /// This is the second step of opening an archive whose password is only
+    /// asked for when needed: [`Archive::open`] or [`Archive::open_path`]
+    /// fail with [`Error::PasswordRequired`] and `encrypted_header: true`
+    /// when the header is encrypted, and the caller then obtains a password
+    /// and resumes here. The reader is rewound to its start first, so the
+    /// same reader can serve both attempts when passed as `&mut reader`.
+    ///
+    /// Archives whose header is not encrypted open as with
+    /// [`Archive::open_with_password`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> zesven::Result<()> {
+    /// use std::fs::File;
+    /// use zesven::{Archive, Error};
+    ///
+    /// let mut file = File::open("secret.7z")?;
+    /// let archive = match Archive::open(&mut file) {
+    ///     Err(Error::PasswordRequired { encrypted_header: true }) => {
+    ///         let password = "asked-for-now";
+    ///         Archive::open_encrypted_header(&mut file, password)?
+    ///     }
+    ///     other => other?,
+    /// };
+    /// println!("{} entries", archive.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the archive is invalid, cannot be read,
+    /// or the password is incorrect.
+    #[cfg(feature = "aes")]
+    pub fn open_encrypted_header(mut reader: R, password: impl Into<Password>) -> Result<Self> {
+        reader.seek(SeekFrom::Start(0)).map_err(Error::Io)?;
+        Self::open_internal(reader, Some(password.into()), None)
+    }
+
     /// Opens an encrypted archive from a reader with custom resource limits.
     ///
     /// This combines password-based decryption with custom resource limit
@@ -275,12 +471,14 @@ impl<R: Read + Seek> Archive<R> {
         // Read main header (also parses start header internally)
         // Use provided limits or fall back to defaults
         let limits = limits.unwrap_or_default();
+        let pack_bounds = limits.pack_bounds;
         let (_start_header, header) = read_archive_header_with_offset_and_password(
             &mut reader,
             Some(limits),
             sfx_offset,
             password,
         )?;
+        check_pack_bounds(&mut reader, &header, sfx_offset, pack_bounds)?;
 
         // Build entries from files info
         let entries = entries::build_entries(&header);
@@ -319,8 +517,10 @@ impl<R: Read + Seek> Archive<R> {
         // Read main header (also parses start header internally)
         // Use provided limits or fall back to defaults
         let limits = limits.unwrap_or_default();
+        let pack_bounds = limits.pack_bounds;
         let (_start_header, header) =
             read_archive_header_with_offset(&mut reader, Some(limits), sfx_offset)?;
+        check_pack_bounds(&mut reader, &header, sfx_offset, pack_bounds)?;
 
         // Build entries from files info
         let entries = entries::build_entries(&header);
@@ -352,6 +552,11 @@ impl<R: Read + Seek> Archive<R> {
             password,
             volume_info: None,
             sfx_offset: result.sfx_offset,
+            read_ahead: None,
+            #[cfg(feature = "zstd")]
+            zstd_dictionary: None,
+            #[cfg(target_os = "linux")]
+            source_file: None,
         })
     }
 
@@ -369,6 +574,11 @@ impl<R: Read + Seek> Archive<R> {
             info: result.info,
             volume_info: None,
             sfx_offset: result.sfx_offset,
+            read_ahead: None,
+            #[cfg(feature = "zstd")]
+            zstd_dictionary: None,
+            #[cfg(target_os = "linux")]
+            source_file: None,
         })
     }
 }
@@ -5,8 +5,13 @@
 
 use std::io::{Read, Seek};
 use std::path::PathBuf;
+use std::time::Duration;
 
-use super::{Archive, ArchiveInfo, Entry};
+use super::{Archive, ArchiveInfo, CapabilitySet, CodecStat, EncryptionInfo, Entry, FolderSummary};
+use crate::codec::{CodecMethod, FilterMethod, method};
+use crate::format::SIGNATURE_HEADER_SIZE;
+use crate::ntfs::parse_ads_path;
+use crate::{Error, Result};
 
 impl<R: Read + Seek> Archive<R> {
     /// Returns information about the archive.
@@ -14,6 +19,66 @@ impl<R: Read + Seek> Archive<R> {
         &self.info
     }
 
+    /// Estimates how long extracting the whole archive takes.
+    ///
+    /// Divides the total unpacked size by `measured_throughput_bps`, the
+    /// decompression throughput in bytes per second, for example from
+    /// [`benchmark_throughput`](Self::benchmark_throughput). The estimate is
+    /// rough: it ignores per-file overhead and disk speed. A throughput that
+    /// is not positive yields [`Duration::MAX`].
+    pub fn estimate_extract_time(&self, measured_throughput_bps: f64) -> Duration {
+        if self.info.total_size == 0 {
+            return Duration::ZERO;
+        }
+        if measured_throughput_bps.is_nan() || measured_throughput_bps <= 0.0 {
+            return Duration::MAX;
+        }
+        Duration::try_from_secs_f64(self.info.total_size as f64 / measured_throughput_bps)
+            .unwrap_or(Duration::MAX)
+    }
+
+    /// Returns the AES encryption parameters of the archive, if it is encrypted.
+    ///
+    /// Reports the key derivation cycles power, salt and IV sizes, and whether
+    /// the header is encrypted. No decryption is performed, so this works for
+    /// archives with encrypted entries opened without a password. Archives
+    /// with an encrypted header can only be opened with a password.
+    ///
+    /// Always returns `None` when the `aes` feature is disabled.
+    pub fn encryption_info(&self) -> Option<EncryptionInfo> {
+        self.info.encryption_info.clone()
+    }
+
+    /// Returns the methods needed to extract this archive.
+    ///
+    /// Collects the distinct codecs and filters of all folders' coders, and
+    /// whether AES is needed for the contents or the header. Only the header
+    /// is inspected, so this works for encrypted entries without a password.
+    /// Useful for routing an archive to a build that supports it before
+    /// attempting extraction.
+    pub fn required_capabilities(&self) -> CapabilitySet {
+        let mut caps = CapabilitySet {
+            aes: self.info.has_encrypted_header,
+            ..Default::default()
+        };
+        for coder in self.header.folders().iter().flat_map(|f| &f.coders) {
+            if coder.method_id.as_slice() == method::AES {
+                caps.aes = true;
+            } else if let Ok(codec) = CodecMethod::from_coder(coder) {
+                if !caps.codecs.contains(&codec) {
+                    caps.codecs.push(codec);
+                }
+            } else if let Ok(filter) = FilterMethod::from_coder(coder) {
+                if !caps.filters.contains(&filter) {
+                    caps.filters.push(filter);
+                }
+            } else if !caps.other_methods.contains(&coder.method_id) {
+                caps.other_methods.push(coder.method_id.clone());
+            }
+        }
+        caps
+    }
+
     /// Returns all entries in the archive.
     pub fn entries(&self) -> &[Entry] {
         &self.entries
@@ -45,6 +110,211 @@ impl<R: Read + Seek> Archive<R> {
         self.entries.iter().find(|e| e.path.as_str() == path)
     }
 
+    /// Returns the NTFS alternate data stream entries attached to `base`.
+    ///
+    /// Alternate streams are stored as separate entries named
+    /// `base:stream_name` (see [`parse_ads_path`]). Entries are returned in
+    /// archive order.
+    pub fn alt_streams_for(&self, base: &str) -> Vec<&Entry> {
+        self.entries
+            .iter()
+            .filter(|e| parse_ads_path(e.path.as_str()).is_some_and(|(b, _)| b == base))
+            .collect()
+    }
+
+    /// Returns all entries that are not NTFS alternate data streams.
+    ///
+    /// Together with [`alt_streams_for`](Self::alt_streams_for), this lets a
+    /// UI list files once and show their alternate streams as children.
+    pub fn base_entries(&self) -> Vec<&Entry> {
+        self.entries
+            .iter()
+            .filter(|e| parse_ads_path(e.path.as_str()).is_none())
+            .collect()
+    }
+
+    /// Returns the entries of each folder (solid block).
+    ///
+    /// The outer vector has one element per folder, in folder order. Each
+    /// element lists the indices into [`entries`](Self::entries) of the files
+    /// stored in that folder, in substream order. Directories and empty files
+    /// have no data stream and appear in no folder.
+    pub fn folder_members(&self) -> Vec<Vec<usize>> {
+        let num_folders = self
+            .header
+            .unpack_info
+            .as_ref()
+            .map_or(0, |ui| ui.folders.len());
+        let mut members = vec![Vec::new(); num_folders];
+        for (idx, entry) in self.entries.iter().enumerate() {
+            if let Some(folder) = entry.folder_index.and_then(|f| members.get_mut(f)) {
+                folder.push((entry.stream_index, idx));
+            }
+        }
+        members
+            .into_iter()
+            .map(|mut folder| {
+                folder.sort_unstable();
+                folder.into_iter().map(|(_, idx)| idx).collect()
+            })
+            .collect()
+    }
+
+    /// Returns the number of folders (solid blocks) in the archive.
+    pub fn folder_count(&self) -> usize {
+        self.header.folders().len()
+    }
+
+    /// Reads the packed bytes of a folder without decoding them.
+    ///
+    /// Returns the folder's pack streams concatenated in order, exactly as
+    /// stored: still compressed, and encrypted for encrypted folders. This
+    /// is meant for tools that inspect archive internals.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ResourceLimitExceeded`] without reading anything if
+    /// the folder's packed size exceeds `max_size`, and an error if
+    /// `folder_index` is out of range or the data cannot be read.
+    pub fn packed_folder_bytes(&mut self, folder_index: usize, max_size: u64) -> Result<Vec<u8>> {
+        let folders = self.header.folders();
+        let folder = folders.get(folder_index).ok_or_else(|| {
+            Error::InvalidFormat(format!("folder index {} out of range", folder_index))
+        })?;
+        let pack_sizes = self
+            .header
+            .pack_info
+            .as_ref()
+            .map_or(&[][..], |pi| pi.pack_sizes.as_slice());
+
+        let pack_base: usize = folders[..folder_index]
+            .iter()
+            .map(|f| f.packed_streams.len())
+            .sum();
+        let folder_sizes = pack_sizes
+            .get(pack_base..pack_base + folder.packed_streams.len())
+            .ok_or_else(|| {
+                Error::InvalidFormat(format!("missing pack sizes for folder {}", folder_index))
+            })?;
+
+        let overflow =
+            || Error::InvalidFormat(format!("pack sizes of folder {} overflow", folder_index));
+        let size = folder_sizes
+            .iter()
+            .try_fold(0u64, |sum, &s| sum.checked_add(s))
+            .ok_or_else(overflow)?;
+        if size > max_size {
+            return Err(Error::ResourceLimitExceeded(format!(
+                "folder {} packed size {} exceeds limit {}",
+                folder_index, size, max_size
+            )));
+        }
+
+        let pack_pos = self.header.pack_info.as_ref().map_or(0, |pi| pi.pack_pos);
+        let offset = (self.sfx_offset + SIGNATURE_HEADER_SIZE)
+            .checked_add(pack_pos)
+            .and_then(|start| {
+                pack_sizes[..pack_base]
+                    .iter()
+                    .try_fold(start, |end, &s| end.checked_add(s))
+            })
+            .ok_or_else(overflow)?;
+        self.read_pack_data(offset, size)
+    }
+
+    /// Returns a compact summary of each folder (solid block), in folder order.
+    ///
+    /// Lists each folder's coders in decoding order, from the folder output
+    /// towards the packed data, with its packed and unpacked sizes and the
+    /// number of entries it holds. Only the header is inspected, so this
+    /// works for encrypted entries without a password.
+    pub fn folder_summaries(&self) -> Vec<FolderSummary> {
+        let pack_sizes = self
+            .header
+            .pack_info
+            .as_ref()
+            .map_or(&[][..], |pi| pi.pack_sizes.as_slice());
+        let members = self.folder_members();
+        let mut pack_base = 0;
+
+        self.header
+            .folders()
+            .iter()
+            .enumerate()
+            .map(|(folder_idx, folder)| {
+                let num_packed = folder.packed_streams.len();
+                let packed_size = pack_sizes.iter().skip(pack_base).take(num_packed).sum();
+                pack_base += num_packed;
+
+                let unpacked_size = folder
+                    .main_coder_index()
+                    .and_then(|main| {
+                        let out_idx = folder.coder_stream_offsets()[main].1;
+                        folder.unpack_sizes.get(out_idx).copied()
+                    })
+                    .or_else(|| folder.final_unpack_size())
+                    .unwrap_or(0);
+
+                let chain = folder.coder_chain();
+                let methods = chain
+                    .iter()
+                    .map(|&idx| method::name(&folder.coders[idx].method_id))
+                    .collect::<Vec<_>>()
+                    .join(" → ");
+
+                FolderSummary {
+                    methods,
+                    packed_size,
+                    unpacked_size,
+                    entry_count: members.get(folder_idx).map_or(0, Vec::len),
+                    encrypted: folder
+                        .coders
+                        .iter()
+                        .any(|c| c.method_id.as_slice() == method::AES),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns packed and unpacked totals per compression method.
+    ///
+    /// Each folder is attributed to the first coder in its decoding chain
+    /// that is neither a filter nor encryption, so a `BCJ → LZMA2 → AES-256`
+    /// folder counts towards LZMA2. Methods are listed in order of first
+    /// appearance. Like [`folder_summaries`](Self::folder_summaries), only
+    /// the header is inspected.
+    pub fn codec_breakdown(&self) -> Vec<CodecStat> {
+        let mut stats: Vec<CodecStat> = Vec::new();
+        for (folder, summary) in self.header.folders().iter().zip(self.folder_summaries()) {
+            let chain = folder.coder_chain();
+            let codec = chain
+                .iter()
+                .map(|&idx| folder.coders[idx].method_id.as_slice())
+                .find(|id| !method::is_filter(id) && *id != method::AES)
+                .or_else(|| {
+                    chain
+                        .last()
+                        .map(|&idx| folder.coders[idx].method_id.as_slice())
+                })
+                .map_or("Unknown", method::name);
+
+            let stat = match stats.iter_mut().position(|s| s.method == codec) {
+                Some(pos) => &mut stats[pos],
+                None => {
+                    stats.push(CodecStat {
+                        method: codec.to_string(),
+                        ..CodecStat::default()
+                    });
+                    stats.last_mut().unwrap()
+                }
+            };
+            stat.packed_size += summary.packed_size;
+            stat.unpacked_size += summary.unpacked_size;
+            stat.folder_count += 1;
+        }
+        stats
+    }
+
     /// Returns whether this is a multi-volume archive.
     pub fn is_multivolume(&self) -> bool {
         self.volume_info.is_some()
@@ -3,12 +3,17 @@
 //! This module provides methods for testing archive integrity by verifying
 //! CRC checksums without extraction.
 
-use std::io::{Read, Seek, SeekFrom};
+use std::io::{Cursor, Read, Seek, SeekFrom};
+use std::time::{Duration, Instant};
 
+use crate::format::{SIGNATURE, SIGNATURE_HEADER_SIZE};
 use crate::streaming::Crc32Sink;
-use crate::{Error, Result};
+use crate::{Error, READ_BUFFER_SIZE, Result};
 
-use super::{Archive, EntrySelector, ExtractionLimits, TestOptions, TestResult};
+use super::{
+    Archive, EntrySelector, EntryTestStatus, ExtractionLimits, StructureReport, TestOptions,
+    TestResult, TruncationInfo, map_io_error,
+};
 
 impl<R: Read + Seek> Archive<R> {
     /// Tests the archive for integrity.
@@ -42,42 +47,126 @@ impl<R: Read + Seek> Archive<R> {
 
         for idx in entries_to_test {
             let entry = &self.entries[idx];
-            let entry_path = entry.path.as_str().to_string();
+            let mut status = EntryTestStatus {
+                index: idx,
+                path: entry.path.as_str().to_string(),
+                expected_crc: entry.crc32,
+                actual_crc: None,
+                passed: true,
+            };
 
             result.entries_tested += 1;
 
             // Directories always pass
-            if entry.is_directory {
-                result.entries_passed += 1;
-                continue;
+            if !entry.is_directory {
+                let outcome = self.entry_crc_by_index(idx).and_then(|actual| {
+                    status.actual_crc = Some(actual);
+                    self.check_entry_crc(idx, actual)
+                });
+                if let Err(e) = outcome {
+                    status.passed = false;
+                    result.failures.push((status.path.clone(), e.to_string()));
+                }
             }
 
-            // Test this entry
-            match self.test_entry_by_index(idx) {
-                Ok(()) => {
-                    result.entries_passed += 1;
-                }
-                Err(e) => {
-                    result.entries_failed += 1;
-                    result.failures.push((entry_path, e.to_string()));
-                }
+            if status.passed {
+                result.entries_passed += 1;
+            } else {
+                result.entries_failed += 1;
             }
+            result.entries.push(status);
         }
 
         Ok(result)
     }
 
-    /// Tests a single entry by decompressing and verifying CRC.
-    pub(crate) fn test_entry_by_index(&mut self, entry_idx: usize) -> Result<()> {
+    /// Measures the decompression throughput of this archive in bytes per second.
+    ///
+    /// Decodes folders in order, discarding the output, until `sample_size`
+    /// bytes have been produced, and divides that by the time taken to read
+    /// and decode them. The sample goes through the archive's own codecs and
+    /// filters, so the result suits
+    /// [`estimate_extract_time`](Self::estimate_extract_time). Folders with
+    /// several packed streams (BCJ2) are skipped. Returns `f64::INFINITY`
+    /// when there is nothing to decode.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a sampled folder cannot be read or decoded.
+    pub fn benchmark_throughput(&mut self, sample_size: u64) -> Result<f64> {
+        let folders = self.header.folders().to_vec();
+        let pack_sizes = self
+            .header
+            .pack_info
+            .as_ref()
+            .map(|pi| pi.pack_sizes.clone())
+            .unwrap_or_default();
+
+        let mut decoded = 0u64;
+        let mut elapsed = Duration::ZERO;
+        for (folder_idx, folder) in folders.iter().enumerate() {
+            if decoded >= sample_size {
+                break;
+            }
+            if folder.packed_streams.len() != 1 {
+                continue;
+            }
+            let pack_size = pack_sizes
+                .get(folder_idx)
+                .copied()
+                .ok_or_else(|| Error::InvalidFormat("missing pack size".into()))?;
+
+            let start = Instant::now();
+            let pack_pos = self.calculate_pack_position(folder_idx)?;
+            self.reader
+                .seek(SeekFrom::Start(pack_pos))
+                .map_err(Error::Io)?;
+            let mut packed_data = vec![0u8; pack_size as usize];
+            self.reader
+                .read_exact(&mut packed_data)
+                .map_err(Error::Io)?;
+
+            let uncompressed_size = folder.final_unpack_size().unwrap_or(0);
+            let decoder =
+                self.build_decoder_chain(Cursor::new(packed_data), folder, uncompressed_size)?;
+            decoded += std::io::copy(
+                &mut decoder.take(sample_size - decoded),
+                &mut std::io::sink(),
+            )
+            .map_err(map_io_error)?;
+            elapsed += start.elapsed();
+        }
+
+        if decoded == 0 {
+            return Ok(f64::INFINITY);
+        }
+        Ok(decoded as f64 / elapsed.as_secs_f64())
+    }
+
+    /// Compares the CRC of an entry's decoded data with the stored one.
+    fn check_entry_crc(&self, entry_idx: usize, actual_crc: u32) -> Result<()> {
+        let entry = &self.entries[entry_idx];
+        match entry.crc32 {
+            Some(expected_crc) if expected_crc != actual_crc => Err(Error::CrcMismatch {
+                entry_index: entry_idx,
+                entry_name: Some(entry.path.as_str().to_string()),
+                expected: expected_crc,
+                actual: actual_crc,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Decompresses a single entry and returns the CRC of its data.
+    fn entry_crc_by_index(&mut self, entry_idx: usize) -> Result<u32> {
         let entry_size = self.entries[entry_idx].size;
-        let entry_crc = self.entries[entry_idx].crc32;
         let folder_index = self.entries[entry_idx].folder_index;
         let stream_index = self.entries[entry_idx].stream_index;
 
-        // Empty files always pass
+        // Empty files have no folder
         let folder_idx = match folder_index {
             Some(idx) => idx,
-            None => return Ok(()),
+            None => return Ok(crc32fast::hash(&[])),
         };
 
         // Get folder and pack info - clone folder to release borrow before mutable operations
@@ -157,19 +246,245 @@ impl<R: Read + Seek> Archive<R> {
             &limits,
         )?;
 
-        // Verify CRC if available
-        if let Some(expected_crc) = entry_crc {
-            let actual_crc = sink.finalize();
-            if actual_crc != expected_crc {
-                return Err(Error::CrcMismatch {
-                    entry_index: entry_idx,
-                    entry_name: Some(self.entries[entry_idx].path.as_str().to_string()),
-                    expected: expected_crc,
-                    actual: actual_crc,
-                });
+        Ok(sink.finalize())
+    }
+
+    /// Validates the archive structure without decompressing any entry data.
+    ///
+    /// This is a quick acceptance check that covers:
+    /// - the start header CRC and the next header CRC,
+    /// - the coder graph of every folder (bind pairs, packed stream indices,
+    ///   BCJ2 stream counts, and graph connectivity),
+    /// - that the folders consume exactly the pack streams described by the
+    ///   pack info and that every pack stream lies within the archive,
+    /// - the CRCs of pack streams that store one.
+    ///
+    /// Structural problems are collected in the returned [`StructureReport`];
+    /// an `Err` is only returned when the archive cannot be read.
+    pub fn validate_structure(&mut self) -> Result<StructureReport> {
+        let mut report = StructureReport::default();
+        let archive_len = self.reader.seek(SeekFrom::End(0)).map_err(Error::Io)?;
+
+        self.validate_start_header(archive_len, &mut report)?;
+        self.validate_folders(&mut report);
+        self.validate_pack_streams(archive_len, &mut report)?;
+
+        Ok(report)
+    }
+
+    /// Checks whether the archive stream is shorter than its headers declare.
+    ///
+    /// The expected length is the larger of the end of the last pack stream
+    /// (pack start plus the sum of all pack sizes) and the end of the header
+    /// referenced by the start header. This catches downloads that were cut
+    /// short, which may still open (e.g. when the header precedes the packed
+    /// data) but fail late during extraction.
+    ///
+    /// Returns `None` when the stream is long enough.
+    pub fn check_truncation(&mut self) -> Result<Option<TruncationInfo>> {
+        let actual_size = self.reader.seek(SeekFrom::End(0)).map_err(Error::Io)?;
+        let data_start = self.sfx_offset + SIGNATURE_HEADER_SIZE;
+
+        let mut start_header = [0u8; SIGNATURE_HEADER_SIZE as usize];
+        self.reader
+            .seek(SeekFrom::Start(self.sfx_offset))
+            .map_err(Error::Io)?;
+        self.reader
+            .read_exact(&mut start_header)
+            .map_err(Error::Io)?;
+        let next_header_offset = u64::from_le_bytes(start_header[12..20].try_into().unwrap());
+        let next_header_size = u64::from_le_bytes(start_header[20..28].try_into().unwrap());
+        let header_end = data_start
+            .saturating_add(next_header_offset)
+            .saturating_add(next_header_size);
+
+        let pack_end = self.header.pack_info.as_ref().map_or(0, |pack_info| {
+            pack_info.pack_sizes.iter().fold(
+                data_start.saturating_add(pack_info.pack_pos),
+                |end, &size| end.saturating_add(size),
+            )
+        });
+
+        let expected_size = header_end.max(pack_end);
+        if actual_size >= expected_size {
+            return Ok(None);
+        }
+
+        Ok(Some(TruncationInfo {
+            expected_size,
+            actual_size,
+            missing_bytes: expected_size - actual_size,
+        }))
+    }
+
+    /// Checks the start header CRC and the CRC of the next header it points to.
+    fn validate_start_header(
+        &mut self,
+        archive_len: u64,
+        report: &mut StructureReport,
+    ) -> Result<()> {
+        if archive_len < self.sfx_offset + SIGNATURE_HEADER_SIZE {
+            report.push(None, "archive is shorter than the signature header");
+            return Ok(());
+        }
+
+        let mut start_header = [0u8; SIGNATURE_HEADER_SIZE as usize];
+        self.reader
+            .seek(SeekFrom::Start(self.sfx_offset))
+            .map_err(Error::Io)?;
+        self.reader
+            .read_exact(&mut start_header)
+            .map_err(Error::Io)?;
+
+        if start_header[..6] != *SIGNATURE {
+            report.push(None, "invalid 7z signature");
+        }
+        let stored_crc = u32::from_le_bytes(start_header[8..12].try_into().unwrap());
+        let actual_crc = crc32fast::hash(&start_header[12..32]);
+        if stored_crc != actual_crc {
+            report.push(
+                None,
+                format!(
+                    "start header CRC mismatch: expected {:#x}, got {:#x}",
+                    stored_crc, actual_crc
+                ),
+            );
+            return Ok(());
+        }
+
+        let next_header_offset = u64::from_le_bytes(start_header[12..20].try_into().unwrap());
+        let next_header_size = u64::from_le_bytes(start_header[20..28].try_into().unwrap());
+        let next_header_crc = u32::from_le_bytes(start_header[28..32].try_into().unwrap());
+        let next_header_pos = self.sfx_offset + SIGNATURE_HEADER_SIZE + next_header_offset;
+
+        if next_header_pos
+            .checked_add(next_header_size)
+            .is_none_or(|end| end > archive_len)
+        {
+            report.push(None, "next header extends past the end of the archive");
+            return Ok(());
+        }
+        let actual_crc = self.crc_of_range(next_header_pos, next_header_size)?;
+        if actual_crc != next_header_crc {
+            report.push(
+                None,
+                format!(
+                    "next header CRC mismatch: expected {:#x}, got {:#x}",
+                    next_header_crc, actual_crc
+                ),
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Checks the coder graph of every folder and the pack stream assignment.
+    fn validate_folders(&self, report: &mut StructureReport) {
+        let num_pack_streams = self
+            .header
+            .pack_info
+            .as_ref()
+            .map_or(0, |pack_info| pack_info.num_streams());
+        let folders = self.header.folders();
+        let mut consumed_pack_streams = 0;
+
+        for (folder_idx, folder) in folders.iter().enumerate() {
+            let checks = [
+                folder.validate_bind_pairs(),
+                folder.validate_packed_streams(folder.packed_streams.len()),
+                folder.validate_bcj2_streams(),
+            ];
+            let mut graph_valid = true;
+            for err in checks.into_iter().filter_map(|check| check.err()) {
+                report.push(Some(folder_idx), err.to_string());
+                graph_valid = false;
             }
+            // The graph walk assumes in-range indices
+            if graph_valid {
+                if let Err(err) = folder.validate_stream_graph() {
+                    report.push(Some(folder_idx), err.to_string());
+                }
+            }
+
+            if folder.unpack_sizes.len() as u64 != folder.total_out_streams() {
+                report.push(
+                    Some(folder_idx),
+                    format!(
+                        "folder has {} unpack sizes for {} output streams",
+                        folder.unpack_sizes.len(),
+                        folder.total_out_streams()
+                    ),
+                );
+            }
+            consumed_pack_streams += folder.packed_streams.len();
+        }
+
+        if consumed_pack_streams != num_pack_streams {
+            report.push(
+                None,
+                format!(
+                    "folders consume {} pack streams, but the archive has {}",
+                    consumed_pack_streams, num_pack_streams
+                ),
+            );
+        }
+        report.folders_checked = folders.len();
+    }
+
+    /// Checks that pack streams lie within the archive and verifies their CRCs.
+    fn validate_pack_streams(
+        &mut self,
+        archive_len: u64,
+        report: &mut StructureReport,
+    ) -> Result<()> {
+        let Some(pack_info) = self.header.pack_info.clone() else {
+            return Ok(());
+        };
+
+        let mut pos = self.sfx_offset + SIGNATURE_HEADER_SIZE + pack_info.pack_pos;
+        for (i, &size) in pack_info.pack_sizes.iter().enumerate() {
+            report.pack_streams_checked += 1;
+            let Some(end) = pos.checked_add(size).filter(|&end| end <= archive_len) else {
+                report.push(
+                    None,
+                    format!("pack stream {} extends past the end of the archive", i),
+                );
+                return Ok(());
+            };
+
+            if let Some(Some(expected)) = pack_info.pack_crcs.get(i) {
+                let actual = self.crc_of_range(pos, size)?;
+                report.pack_crcs_checked += 1;
+                if actual != *expected {
+                    report.push(
+                        None,
+                        format!(
+                            "pack stream {} CRC mismatch: expected {:#x}, got {:#x}",
+                            i, expected, actual
+                        ),
+                    );
+                }
+            }
+            pos = end;
         }
 
         Ok(())
     }
+
+    /// Computes the CRC32 of `len` bytes starting at `pos`.
+    fn crc_of_range(&mut self, pos: u64, len: u64) -> Result<u32> {
+        self.reader.seek(SeekFrom::Start(pos)).map_err(Error::Io)?;
+        let mut hasher = crc32fast::Hasher::new();
+        let mut buf = [0u8; READ_BUFFER_SIZE];
+        let mut remaining = len;
+        while remaining > 0 {
+            let to_read = buf.len().min(remaining as usize);
+            self.reader
+                .read_exact(&mut buf[..to_read])
+                .map_err(Error::Io)?;
+            hasher.update(&buf[..to_read]);
+            remaining -= to_read as u64;
+        }
+        Ok(hasher.finalize())
+    }
 }
@@ -63,6 +63,7 @@ impl<R: Read + std::io::Seek> Archive<R> {
 
         // Build decoder chain - handles both single coders and filter+codec chains
         let decoder = self.build_decoder_chain(cursor, folder, uncompressed_size)?;
+        let decoder = limits.wrap_timeout(decoder);
 
         // Wrap decoder with LimitedReader for resource limit enforcement
         let mut limited_decoder = limits.wrap_reader(decoder, compressed_size);
@@ -85,11 +86,12 @@ impl<R: Read + std::io::Seek> Archive<R> {
     /// Builds a decoder chain for a folder, handling filter+codec combinations
     /// and encrypted folders.
     ///
-    /// For single-coder folders, returns a simple decoder.
-    /// For two-coder folders (filter + codec), chains them in correct order:
-    /// packed_data -> codec -> filter -> output
+    /// Unencrypted folders are handled by [`codec::build_decoder_chain`],
+    /// which chains the coders in data-flow order:
+    /// packed_data -> codec -> filter(s) -> output
     ///
-    /// For encrypted folders, uses the stored password to decrypt.
+    /// For encrypted folders, uses the stored password to decrypt. ZSTD
+    /// coders compressed with a dictionary use the archive's dictionary.
     pub(crate) fn build_decoder_chain<T: Read + Send + 'static>(
         &self,
         input: T,
@@ -99,81 +101,28 @@ impl<R: Read + std::io::Seek> Archive<R> {
         // Check if the folder uses AES encryption
         #[cfg(feature = "aes")]
         if entries::folder_uses_encryption(folder) {
-            let password = self.password.as_ref().ok_or(Error::PasswordRequired)?;
-            let decoder =
-                codec::build_encrypted_folder_decoder(input, folder, uncompressed_size, password)?;
-            return Ok(Box::new(decoder));
+            let password = self.password.as_ref().ok_or(Error::PasswordRequired {
+                encrypted_header: false,
+            })?;
+            return codec::build_encrypted_folder_decoder(
+                input,
+                folder,
+                uncompressed_size,
+                password,
+            );
         }
 
-        match folder.coders.len() {
-            0 => Err(Error::InvalidFormat("folder has no coders".into())),
-
-            1 => {
-                // Single coder - simple case
-                let coder = &folder.coders[0];
-                let decoder = codec::build_decoder(input, coder, uncompressed_size)?;
-                Ok(Box::new(decoder))
-            }
-
-            2 => {
-                // Two coders - typically filter + codec
-                // In 7z, the coder order in the list is: [filter, codec]
-                // But data flows: packed -> codec -> filter -> output
-                // The bind_pair connects them: filter's input comes from codec's output
-
-                let filter_coder = &folder.coders[0];
-                let codec_coder = &folder.coders[1];
-
-                // Check if first coder is a filter (BCJ, Delta)
-                let is_filter = self.is_filter_method(&filter_coder.method_id);
-
-                if is_filter {
-                    // First decompress with the codec
-                    let codec_output_size = folder
-                        .unpack_sizes
-                        .get(1)
-                        .copied()
-                        .unwrap_or(uncompressed_size);
-                    let codec_decoder =
-                        codec::build_decoder(input, codec_coder, codec_output_size)?;
-
-                    // Then apply the filter
-                    let filter_decoder =
-                        codec::build_decoder(codec_decoder, filter_coder, uncompressed_size)?;
-
-                    Ok(Box::new(filter_decoder))
-                } else {
-                    // Not a standard filter chain - try sequential decoding
-                    // First coder processes packed data
-                    let first_output_size = folder
-                        .unpack_sizes
-                        .first()
-                        .copied()
-                        .unwrap_or(uncompressed_size);
-                    let first_decoder =
-                        codec::build_decoder(input, filter_coder, first_output_size)?;
-
-                    // Second coder processes first decoder's output
-                    let second_decoder =
-                        codec::build_decoder(first_decoder, codec_coder, uncompressed_size)?;
-
-                    Ok(Box::new(second_decoder))
-                }
-            }
-
-            _ => {
-                // Complex chains with 3+ coders need special handling
-                // For now, fall back to first coder only (BCJ2 handled separately)
-                let coder = &folder.coders[0];
-                let decoder = codec::build_decoder(input, coder, uncompressed_size)?;
-                Ok(Box::new(decoder))
-            }
+        #[cfg(feature = "zstd")]
+        if let Some(dict) = &self.zstd_dictionary {
+            return codec::build_decoder_chain_with_zstd_dictionary(
+                input,
+                folder,
+                uncompressed_size,
+                dict,
+            );
         }
-    }
 
-    /// Checks if a method ID represents a filter (not a compression codec).
-    pub(crate) fn is_filter_method(&self, method_id: &[u8]) -> bool {
-        codec::method::is_filter(method_id)
+        codec::build_decoder_chain(input, folder, uncompressed_size)
     }
 
     /// Decompresses solid block entry to a sink.
@@ -207,7 +156,11 @@ impl<R: Read + std::io::Seek> Archive<R> {
 
         let cursor = Cursor::new(packed_data);
         // Build decoder chain to handle filter+codec combinations (e.g., BCJ + LZMA2)
-        let mut decoder = codec::build_decoder_chain(cursor, folder, uncompressed_size)?;
+        let decoder = self.build_decoder_chain(cursor, folder, uncompressed_size)?;
+        let mut decoder = limits.wrap_timeout(decoder);
+
+        // Hash the whole folder when its CRC is checkable (last stream only)
+        let mut folder_hasher = Self::folder_crc_hasher(folder, stream_index, entry_sizes.len());
 
         // Skip entries before the target (no limit enforcement on skipped data)
         for &skip_size in entry_sizes.iter().take(stream_index) {
@@ -215,10 +168,13 @@ impl<R: Read + std::io::Seek> Archive<R> {
             let mut buf = [0u8; READ_BUFFER_SIZE];
             while remaining > 0 {
                 let to_read = buf.len().min(remaining as usize);
-                let n = decoder.read(&mut buf[..to_read]).map_err(Error::Io)?;
+                let n = decoder.read(&mut buf[..to_read]).map_err(map_io_error)?;
                 if n == 0 {
                     break;
                 }
+                if let Some(hasher) = folder_hasher.as_mut() {
+                    hasher.update(&buf[..n]);
+                }
                 remaining -= n as u64;
             }
         }
@@ -240,10 +196,17 @@ impl<R: Read + std::io::Seek> Archive<R> {
                 break;
             }
             sink.write_all(&buf[..n]).map_err(Error::Io)?;
+            if let Some(hasher) = folder_hasher.as_mut() {
+                hasher.update(&buf[..n]);
+            }
             total += n as u64;
             remaining -= n as u64;
         }
 
+        if let Some(hasher) = folder_hasher {
+            Self::verify_folder_crc(folder, folder_idx, hasher)?;
+        }
+
         Ok(total)
     }
 }
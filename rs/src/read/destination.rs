@@ -9,6 +9,9 @@
 //! - [`MemoryDestination`] - Extracts to in-memory buffers
 //! - [`NullDestination`] - Discards extracted data (for testing/benchmarking)
 //!
+//! [`FilesystemDestination`] also implements [`VfsDestination`], the
+//! operation-level trait used by `Archive::extract_to_vfs`.
+//!
 //! # Custom Destinations
 //!
 //! You can implement [`ExtractDestination`] for custom extraction targets:
@@ -41,7 +44,9 @@ use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
-use crate::read::Entry;
+use super::metadata::apply_metadata;
+use super::path_safety::create_symlink;
+use crate::read::{Entry, ExtractOptions, PreserveMetadata, VfsDestination};
 use crate::{Error, Result};
 
 /// Trait for extraction destinations.
@@ -170,8 +175,10 @@ impl FilesystemDestination {
     }
 
     fn resolve_path(&self, entry: &Entry) -> Result<PathBuf> {
-        let entry_path = Path::new(entry.path.as_str());
+        self.resolve_relative(Path::new(entry.path.as_str()))
+    }
 
+    fn resolve_relative(&self, entry_path: &Path) -> Result<PathBuf> {
         // Check for path traversal
         let resolved = self.output_dir.join(entry_path);
         let canonical_output = self
@@ -194,7 +201,7 @@ impl FilesystemDestination {
             if !canonical_resolved.starts_with(&canonical_output) {
                 return Err(Error::PathTraversal {
                     entry_index: self.current_entry_index,
-                    path: entry.path.as_str().to_string(),
+                    path: entry_path.to_string_lossy().into_owned(),
                 });
             }
         }
@@ -270,6 +277,66 @@ impl ExtractDestination for FilesystemDestination {
     }
 }
 
+impl VfsDestination for FilesystemDestination {
+    fn create_dir(&mut self, path: &Path) -> Result<()> {
+        let path = self.resolve_relative(path)?;
+        fs::create_dir_all(&path).map_err(Error::Io)
+    }
+
+    fn create_file(&mut self, path: &Path) -> Result<()> {
+        let path = self.resolve_relative(path)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(Error::Io)?;
+        }
+        File::create(&path).map_err(Error::Io)?;
+        Ok(())
+    }
+
+    fn write(&mut self, path: &Path, data: &[u8]) -> Result<()> {
+        let path = self.resolve_relative(path)?;
+        let mut file = fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .map_err(Error::Io)?;
+        file.write_all(data).map_err(Error::Io)
+    }
+
+    fn set_metadata(&mut self, path: &Path, entry: &Entry) -> Result<()> {
+        // Metadata calls would follow the link to its target
+        if entry.is_symlink {
+            return Ok(());
+        }
+
+        self.current_entry_index = entry.index;
+        let path = self.resolve_relative(path)?;
+
+        let preserve = PreserveMetadata {
+            modification_time: true,
+            creation_time: true,
+            attributes: self.preserve_permissions,
+            ownership: false,
+        };
+        apply_metadata(
+            &path,
+            &preserve,
+            entry.modification_time,
+            entry.creation_time,
+            entry.attributes,
+            entry.bsd_flags,
+        );
+        Ok(())
+    }
+
+    fn symlink(&mut self, path: &Path, target: &str) -> Result<()> {
+        let path = self.resolve_relative(path)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(Error::Io)?;
+        }
+        create_symlink(&path, target)?;
+        Ok(())
+    }
+}
+
 /// In-memory extraction destination.
 ///
 /// Extracts entries to in-memory buffers. Useful for processing archive
@@ -289,9 +356,16 @@ impl ExtractDestination for FilesystemDestination {
 ///     println!("{}: {} bytes", path, data.len());
 /// }
 /// ```
+///
+/// With [`ExtractOptions::output_alignment`], contents are stored in
+/// [`AlignedBuffer`]s instead; see [`aligned_files`](Self::aligned_files).
 pub struct MemoryDestination {
     /// Extracted file contents by path
     files: HashMap<String, Vec<u8>>,
+    /// Extracted file contents by path, when an alignment is set
+    aligned_files: HashMap<String, AlignedBuffer>,
+    /// Alignment of output buffers (1 for plain vectors)
+    alignment: usize,
     /// Current entry path being written
     current_path: Option<String>,
     /// Current buffer being written to (shared with writer)
@@ -303,38 +377,128 @@ impl MemoryDestination {
     pub fn new() -> Self {
         Self {
             files: HashMap::new(),
+            aligned_files: HashMap::new(),
+            alignment: 1,
             current_path: None,
             current_buffer: None,
         }
     }
 
+    /// Creates a memory destination configured by extraction options.
+    ///
+    /// Honors [`ExtractOptions::output_alignment`]: with an alignment above 1,
+    /// each file is stored in an [`AlignedBuffer`] whose data starts at a
+    /// multiple of that many bytes.
+    pub fn from_options(options: &ExtractOptions) -> Self {
+        Self {
+            alignment: options.output_alignment.max(1).next_power_of_two(),
+            ..Self::new()
+        }
+    }
+
     /// Returns the extracted files.
     ///
-    /// Returns a map from entry paths to their extracted contents.
+    /// Returns a map from entry paths to their extracted contents. Files
+    /// extracted with an output alignment are in
+    /// [`aligned_files`](Self::aligned_files) instead.
     pub fn files(&self) -> &HashMap<String, Vec<u8>> {
         &self.files
     }
 
+    /// Returns the files extracted into aligned buffers.
+    pub fn aligned_files(&self) -> &HashMap<String, AlignedBuffer> {
+        &self.aligned_files
+    }
+
     /// Takes ownership of the extracted files.
     ///
     /// Consumes the destination and returns the extracted file contents.
+    /// Aligned buffers are converted to plain vectors.
     pub fn into_files(self) -> HashMap<String, Vec<u8>> {
-        self.files
+        let mut files = self.files;
+        files.extend(
+            self.aligned_files
+                .into_iter()
+                .map(|(path, buffer)| (path, buffer.into_vec())),
+        );
+        files
     }
 
     /// Gets the extracted content for a specific path.
     pub fn get(&self, path: &str) -> Option<&[u8]> {
-        self.files.get(path).map(|v| v.as_slice())
+        self.files
+            .get(path)
+            .map(|v| v.as_slice())
+            .or_else(|| self.aligned_files.get(path).map(|b| b.as_slice()))
     }
 
     /// Returns the number of extracted files.
     pub fn len(&self) -> usize {
-        self.files.len()
+        self.files.len() + self.aligned_files.len()
     }
 
     /// Returns true if no files have been extracted.
     pub fn is_empty(&self) -> bool {
-        self.files.is_empty()
+        self.files.is_empty() && self.aligned_files.is_empty()
+    }
+}
+
+/// Byte buffer whose contents start at an aligned address.
+///
+/// Produced by [`MemoryDestination`] when an output alignment is set. The
+/// data lives in an over-allocated vector, starting at its first suitably
+/// aligned byte, and dereferences to `&[u8]`.
+#[derive(Clone)]
+pub struct AlignedBuffer {
+    storage: Vec<u8>,
+    offset: usize,
+}
+
+impl AlignedBuffer {
+    /// Copies `data` into a buffer aligned to `alignment` bytes.
+    ///
+    /// `alignment` is rounded up to a power of two.
+    pub fn new(data: &[u8], alignment: usize) -> Self {
+        let alignment = alignment.max(1).next_power_of_two();
+        let mut storage: Vec<u8> = Vec::with_capacity(data.len() + alignment - 1);
+        let offset = storage.as_ptr().align_offset(alignment);
+        assert!(offset < alignment, "buffer cannot be aligned");
+        storage.resize(offset, 0);
+        storage.extend_from_slice(data);
+        Self { storage, offset }
+    }
+
+    /// Returns the data as a slice.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.storage[self.offset..]
+    }
+
+    /// Converts the buffer into a plain vector, dropping the alignment.
+    pub fn into_vec(mut self) -> Vec<u8> {
+        self.storage.drain(..self.offset);
+        self.storage
+    }
+}
+
+impl std::ops::Deref for AlignedBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl AsRef<[u8]> for AlignedBuffer {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl std::fmt::Debug for AlignedBuffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AlignedBuffer")
+            .field("len", &self.len())
+            .finish_non_exhaustive()
     }
 }
 
@@ -397,7 +561,12 @@ impl ExtractDestination for MemoryDestination {
                         // Buffer is still referenced elsewhere, clone the data
                         arc.lock().map(|guard| guard.clone()).unwrap_or_default()
                     });
-                self.files.insert(path, data);
+                if self.alignment > 1 {
+                    self.aligned_files
+                        .insert(path, AlignedBuffer::new(&data, self.alignment));
+                } else {
+                    self.files.insert(path, data);
+                }
             }
         }
 
@@ -515,10 +684,26 @@ mod tests {
             is_symlink: false,
             is_anti: false,
             ownership: None,
+            sparse_holes: Vec::new(),
+            bsd_flags: None,
             index: 0,
             folder_index: None,
             stream_index: None,
+            solid_member: false,
+        }
+    }
+
+    #[test]
+    fn test_aligned_buffer() {
+        let data: Vec<u8> = (0..=255).collect();
+        for alignment in [1, 2, 16, 48, 64, 4096] {
+            let buffer = AlignedBuffer::new(&data, alignment);
+            let expected = alignment.next_power_of_two();
+            assert_eq!(buffer.as_ptr() as usize % expected, 0);
+            assert_eq!(&buffer[..], &data[..]);
+            assert_eq!(buffer.into_vec(), data);
         }
+        assert!(AlignedBuffer::new(&[], 64).is_empty());
     }
 
     #[test]
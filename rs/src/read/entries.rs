@@ -76,6 +76,13 @@ pub(crate) fn build_entries(header: &ArchiveHeader) -> Vec<Entry> {
         let (folder_index, stream_index) = if !archive_entry.has_stream {
             (None, None)
         } else {
+            // Folders without unpack streams (e.g. alignment padding) hold no entries
+            if let Some(ss) = substreams {
+                while ss.num_unpack_streams_in_folders.get(folder_idx) == Some(&0) {
+                    folder_idx += 1;
+                }
+            }
+
             // Map to folder and stream
             let fi = folder_idx;
             let si = stream_idx;
@@ -115,15 +122,28 @@ pub(crate) fn build_entries(header: &ArchiveHeader) -> Vec<Entry> {
             is_symlink,
             is_anti: archive_entry.is_anti,
             ownership: None,
+            sparse_holes: archive_entry.sparse_holes.clone(),
+            bsd_flags: archive_entry.bsd_flags,
             index: idx,
             folder_index,
             stream_index,
+            solid_member: is_solid_member(header, folder_index),
         });
     }
 
     entries
 }
 
+/// Checks if the folder of an entry holds more than one unpack stream.
+pub(crate) fn is_solid_member(header: &ArchiveHeader, folder_index: Option<usize>) -> bool {
+    let (Some(ss), Some(idx)) = (header.substreams_info.as_ref(), folder_index) else {
+        return false;
+    };
+    ss.num_unpack_streams_in_folders
+        .get(idx)
+        .is_some_and(|&count| count > 1)
+}
+
 /// Checks if an entry is encrypted based on its folder's coders.
 pub(crate) fn is_entry_encrypted(
     unpack_info: Option<&UnpackInfo>,
@@ -177,23 +197,21 @@ pub(crate) fn build_info(header: &ArchiveHeader, entries: &[Entry]) -> ArchiveIn
         })
         .unwrap_or(false);
 
-    let compression_methods = header
-        .unpack_info
-        .as_ref()
-        .map(|ui| {
-            let mut methods = Vec::new();
-            for folder in &ui.folders {
-                for coder in &folder.coders {
-                    if let Ok(method) = codec::CodecMethod::from_coder(coder) {
-                        if !methods.contains(&method) {
-                            methods.push(method);
-                        }
-                    }
-                }
+    // Every coder of every folder counts, so a BCJ2 folder contributes its
+    // filter and the codecs of each of its streams
+    let mut compression_methods = Vec::new();
+    let mut filter_methods = Vec::new();
+    for coder in header.folders().iter().flat_map(|f| &f.coders) {
+        if let Ok(method) = codec::CodecMethod::from_coder(coder) {
+            if !compression_methods.contains(&method) {
+                compression_methods.push(method);
             }
-            methods
-        })
-        .unwrap_or_default();
+        } else if let Ok(filter) = codec::FilterMethod::from_coder(coder) {
+            if !filter_methods.contains(&filter) {
+                filter_methods.push(filter);
+            }
+        }
+    }
 
     let comment = header.files_info.as_ref().and_then(|fi| fi.comment.clone());
 
@@ -211,6 +229,7 @@ pub(crate) fn build_info(header: &ArchiveHeader, entries: &[Entry]) -> ArchiveIn
         has_encrypted_entries: entries.iter().any(|e| e.is_encrypted),
         has_encrypted_header: header.header_encrypted,
         compression_methods,
+        filter_methods,
         folder_count,
         comment,
         encryption_info,
@@ -218,31 +237,24 @@ pub(crate) fn build_info(header: &ArchiveHeader, entries: &[Entry]) -> ArchiveIn
 }
 
 /// Extracts encryption parameters from the archive header.
+///
+/// Parameters come from the first AES coder of the data folders, or from the
+/// header's own AES coder when only the header is encrypted.
 #[cfg(feature = "aes")]
 pub(crate) fn extract_encryption_info(header: &ArchiveHeader) -> Option<EncryptionInfo> {
-    let unpack_info = header.unpack_info.as_ref()?;
-
-    for folder in &unpack_info.folders {
-        for coder in &folder.coders {
-            if coder.method_id.as_slice() == codec::method::AES {
-                if let Some(ref props) = coder.properties {
-                    if let Ok(aes_props) = AesProperties::parse(props) {
-                        return Some(EncryptionInfo::new(
-                            aes_props.num_cycles_power,
-                            aes_props.salt.len(),
-                            aes_props
-                                .iv
-                                .iter()
-                                .rev()
-                                .position(|&b| b != 0)
-                                .map(|p| 16 - p)
-                                .unwrap_or(0),
-                        ));
-                    }
-                }
-            }
-        }
-    }
-
-    None
+    let folder_props = header
+        .folders()
+        .iter()
+        .flat_map(|folder| &folder.coders)
+        .filter(|coder| coder.method_id.as_slice() == codec::method::AES)
+        .find_map(|coder| coder.properties.as_deref());
+    let props = folder_props.or(header.header_aes_properties.as_deref())?;
+
+    let aes_props = AesProperties::parse(props).ok()?;
+    // The parsed IV is zero-padded to 16 bytes; the stored length is what follows the salt
+    let iv_size = props.len().saturating_sub(2 + aes_props.salt.len()).min(16);
+
+    let mut info = EncryptionInfo::new(aes_props.num_cycles_power, aes_props.salt.len(), iv_size);
+    info.header_encrypted = header.header_encrypted;
+    Some(info)
 }
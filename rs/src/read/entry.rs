@@ -1,5 +1,6 @@
 //! Archive entry types and selectors.
 
+use super::glob::GlobPattern;
 use crate::ArchivePath;
 use crate::ownership::UnixOwnership;
 use crate::timestamp::Timestamp;
@@ -71,6 +72,15 @@ pub struct Entry {
     pub is_anti: bool,
     /// Unix file ownership information.
     pub ownership: Option<UnixOwnership>,
+    /// Holes of a sparse file as `(offset, length)` pairs.
+    ///
+    /// Empty unless the file was sparse when it was archived. Extraction
+    /// leaves these ranges unallocated on disk.
+    pub sparse_holes: Vec<(u64, u64)>,
+    /// BSD file flags (`st_flags`, such as `UF_HIDDEN`), if they were stored.
+    ///
+    /// Restored on macOS and the BSDs when attributes are preserved.
+    pub bsd_flags: Option<u32>,
     /// Index in the internal entry list.
     #[allow(dead_code)] // Used for internal tracking
     pub(crate) index: usize,
@@ -78,6 +88,8 @@ pub struct Entry {
     pub(crate) folder_index: Option<usize>,
     /// Stream index within folder.
     pub(crate) stream_index: Option<usize>,
+    /// Whether the folder holds the data of other entries too.
+    pub(crate) solid_member: bool,
 }
 
 impl Entry {
@@ -91,6 +103,21 @@ impl Entry {
         !self.is_directory
     }
 
+    /// Returns true if this entry's folder also holds other entries.
+    ///
+    /// Data in a shared (solid) folder is compressed together, so
+    /// extracting one member decompresses the members stored before it.
+    /// Directories, empty files and entries in a folder of their own
+    /// return false.
+    pub fn is_solid_member(&self) -> bool {
+        self.solid_member
+    }
+
+    /// Returns true if this entry was a sparse file when it was archived.
+    pub fn is_sparse(&self) -> bool {
+        !self.sparse_holes.is_empty()
+    }
+
     /// Returns the modification time as a SystemTime (if available).
     ///
     /// For higher precision access, use [`modification_timestamp()`](Self::modification_timestamp).
@@ -181,6 +208,7 @@ impl Entry {
 /// | [`SelectByName`] | Selects by exact path match |
 /// | [`SelectByPredicate`] | Wraps a predicate closure |
 /// | [`SelectFilesOnly`] | Selects only files (excludes directories) |
+/// | [`SelectByGlob`] | Glob-based selection (`*`, `?`, `**`, `[a-z]`) |
 /// | `SelectByRegex` | Regex-based selection (requires `regex` feature) |
 ///
 /// # Example
@@ -297,6 +325,62 @@ impl EntrySelector for Vec<String> {
     }
 }
 
+/// Selector that matches entries by glob pattern.
+///
+/// The pattern must match the full entry path within the archive, so
+/// `*.txt` selects top-level text files only. Supported syntax:
+///
+/// | Pattern | Matches |
+/// |---------|---------|
+/// | `*` | Any characters within one path component |
+/// | `?` | One character other than `/` |
+/// | `[a-z]`, `[!a-z]` | One character in (or not in) a class |
+/// | `**` | Zero or more whole path components |
+///
+/// # Example
+///
+/// ```rust
+/// use zesven::read::SelectByGlob;
+///
+/// // Markdown files anywhere under docs/
+/// let selector = SelectByGlob::new("docs/**/*.md").unwrap();
+///
+/// // Text files at any depth
+/// let selector = SelectByGlob::new("**/*.txt").unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct SelectByGlob {
+    source: String,
+    pattern: GlobPattern,
+}
+
+impl SelectByGlob {
+    /// Creates a selector with the given glob pattern.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidGlob`] if the pattern is malformed.
+    ///
+    /// [`Error::InvalidGlob`]: crate::Error::InvalidGlob
+    pub fn new(pattern: &str) -> crate::Result<Self> {
+        Ok(Self {
+            source: pattern.to_string(),
+            pattern: GlobPattern::new(pattern)?,
+        })
+    }
+
+    /// Returns the glob pattern this selector was created from.
+    pub fn pattern(&self) -> &str {
+        &self.source
+    }
+}
+
+impl EntrySelector for SelectByGlob {
+    fn select(&self, entry: &Entry) -> bool {
+        self.pattern.matches(entry.path.as_str())
+    }
+}
+
 /// Selector that matches entries by regular expression pattern.
 ///
 /// This selector matches against the full entry path within the archive.
@@ -371,9 +455,12 @@ mod tests {
             is_symlink: false,
             is_anti: false,
             ownership: None,
+            sparse_holes: Vec::new(),
+            bsd_flags: None,
             index: 0,
             folder_index: None,
             stream_index: None,
+            solid_member: false,
         }
     }
 
@@ -394,6 +481,19 @@ mod tests {
         assert_eq!(entry.name(), "file.txt");
     }
 
+    #[test]
+    fn test_select_by_glob() {
+        let selector = SelectByGlob::new("docs/**/*.md").unwrap();
+        assert_eq!(selector.pattern(), "docs/**/*.md");
+        assert!(selector.select(&make_entry("docs/index.md", false)));
+        assert!(selector.select(&make_entry("docs/api/read/entry.md", false)));
+        assert!(!selector.select(&make_entry("other/docs/index.md", false)));
+
+        let selector = SelectByGlob::new("*.txt").unwrap();
+        assert!(selector.select(&make_entry("top.txt", false)));
+        assert!(!selector.select(&make_entry("nested/inner.txt", false)));
+    }
+
     #[test]
     fn test_select_all() {
         let entry = make_entry("test.txt", false);
@@ -0,0 +1,326 @@
+//! Seekable readers for single compressed entries.
+//!
+//! [`Archive::open_entry_reader`] returns a [`SeekableEntryReader`] for an
+//! entry stored alone in an LZMA2 folder. Seeking forward keeps decoding from
+//! the current position; seeking backward resumes from the nearest earlier
+//! checkpoint instead of the start of the entry.
+//!
+//! Checkpoints are the LZMA2 chunks that reset the dictionary, where decoding
+//! can start without any earlier state. They are found by scanning chunk
+//! headers when the reader is opened. Streams written with
+//! [`WriteOptions::cdc_chunking`](crate::write::WriteOptions::cdc_chunking),
+//! by the parallel LZMA2 encoder, or by multi-threaded 7-Zip contain one per
+//! block; a stream encoded as a single block only has a checkpoint at its
+//! start.
+
+use std::io::{self, Read, Seek, SeekFrom};
+use std::sync::Arc;
+
+use crate::codec::lzma::{Lzma2Decoder, lzma2_dict_reset_points};
+use crate::codec::method;
+use crate::{Error, ResourceLimits, Result};
+
+use super::Archive;
+
+/// Offsets at which decoding can restart.
+#[derive(Debug, Clone, Copy)]
+struct Checkpoint {
+    /// Offset of the chunk within the packed stream.
+    packed_offset: usize,
+    /// Number of unpacked bytes before the chunk.
+    unpacked_offset: u64,
+}
+
+/// Reader over shared packed data, starting at a given offset.
+struct PackedCursor {
+    data: Arc<[u8]>,
+    pos: usize,
+}
+
+impl Read for PackedCursor {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let remaining = &self.data[self.pos.min(self.data.len())..];
+        let n = remaining.len().min(buf.len());
+        buf[..n].copy_from_slice(&remaining[..n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+/// A `Read + Seek` reader over one LZMA2-compressed entry.
+///
+/// The entry's packed data is loaded into memory when the reader is opened;
+/// decompressed data is produced on demand. CRC verification is not performed
+/// because reads may cover only part of the entry.
+///
+/// Seeking backward, or far forward, restarts decoding at a checkpoint, and
+/// checkpoints only exist where the LZMA2 stream resets its dictionary. A
+/// stream encoded as a single block, which is what 7-Zip and zesven write by
+/// default, has one checkpoint at its start, so every backward seek decodes
+/// the entry again from the beginning. Entries written with
+/// [`WriteOptions::cdc_chunking`](crate::write::WriteOptions::cdc_chunking)
+/// or [`WriteOptions::parallel_lzma2`](crate::write::WriteOptions::parallel_lzma2)
+/// have one checkpoint per block; see
+/// [`checkpoint_count`](Self::checkpoint_count).
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use std::io::{Read, Seek, SeekFrom};
+/// use zesven::Archive;
+///
+/// let mut archive = Archive::open_path("archive.7z")?;
+/// let mut reader = archive.open_entry_reader("large.bin")?;
+/// reader.seek(SeekFrom::Start(10_000_000))?;
+/// let mut buf = [0u8; 4096];
+/// reader.read_exact(&mut buf)?;
+/// ```
+pub struct SeekableEntryReader {
+    packed: Arc<[u8]>,
+    properties: Vec<u8>,
+    size: u64,
+    checkpoints: Vec<Checkpoint>,
+    decoder: Lzma2Decoder<PackedCursor>,
+    /// Unpacked offset of the decoder's next output byte.
+    decoded: u64,
+    /// Logical read position.
+    position: u64,
+}
+
+impl std::fmt::Debug for SeekableEntryReader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SeekableEntryReader")
+            .field("size", &self.size)
+            .field("checkpoints", &self.checkpoints.len())
+            .field("position", &self.position)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SeekableEntryReader {
+    fn new(packed: Vec<u8>, properties: Vec<u8>, size: u64) -> Result<Self> {
+        let checkpoints: Vec<Checkpoint> = lzma2_dict_reset_points(&packed)?
+            .into_iter()
+            .map(|(packed_offset, unpacked_offset)| Checkpoint {
+                packed_offset,
+                unpacked_offset,
+            })
+            .collect();
+        if checkpoints.first().is_some_and(|c| c.packed_offset != 0) {
+            return Err(Error::InvalidFormat(
+                "LZMA2 stream does not start with a dictionary reset".into(),
+            ));
+        }
+
+        let packed: Arc<[u8]> = packed.into();
+        let decoder = Self::decoder_at(&packed, &properties, 0)?;
+        Ok(Self {
+            packed,
+            properties,
+            size,
+            checkpoints,
+            decoder,
+            decoded: 0,
+            position: 0,
+        })
+    }
+
+    fn decoder_at(
+        packed: &Arc<[u8]>,
+        properties: &[u8],
+        packed_offset: usize,
+    ) -> Result<Lzma2Decoder<PackedCursor>> {
+        let cursor = PackedCursor {
+            data: Arc::clone(packed),
+            pos: packed_offset,
+        };
+        Lzma2Decoder::new(cursor, properties)
+    }
+
+    /// Returns the uncompressed size of the entry.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Returns the number of checkpoints seeking can resume from.
+    pub fn checkpoint_count(&self) -> usize {
+        self.checkpoints.len()
+    }
+
+    /// Moves the decoder to the logical position.
+    fn sync_decoder(&mut self) -> io::Result<()> {
+        let target = self.position;
+        if target == self.decoded {
+            return Ok(());
+        }
+
+        // Restart at the nearest checkpoint if going backward, or if a
+        // checkpoint lies between the decoder and the target
+        let checkpoint = self
+            .checkpoints
+            .iter()
+            .rev()
+            .find(|c| c.unpacked_offset <= target)
+            .copied();
+        if let Some(checkpoint) = checkpoint {
+            if target < self.decoded || checkpoint.unpacked_offset > self.decoded {
+                self.decoder =
+                    Self::decoder_at(&self.packed, &self.properties, checkpoint.packed_offset)
+                        .map_err(io::Error::other)?;
+                self.decoded = checkpoint.unpacked_offset;
+            }
+        }
+
+        // Decode and discard up to the target
+        let mut skip = target - self.decoded;
+        let mut buf = [0u8; 8192];
+        while skip > 0 {
+            let n = self.decoder.read(&mut buf[..skip.min(8192) as usize])?;
+            if n == 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "LZMA2 stream ended before the entry size",
+                ));
+            }
+            skip -= n as u64;
+            self.decoded += n as u64;
+        }
+        Ok(())
+    }
+}
+
+impl Read for SeekableEntryReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.position >= self.size || buf.is_empty() {
+            return Ok(0);
+        }
+        self.sync_decoder()?;
+
+        let max = buf.len().min((self.size - self.position) as usize);
+        let n = self.decoder.read(&mut buf[..max])?;
+        self.decoded += n as u64;
+        self.position += n as u64;
+        Ok(n)
+    }
+}
+
+impl Seek for SeekableEntryReader {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let new_position = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(delta) => self.size.checked_add_signed(delta),
+            SeekFrom::Current(delta) => self.position.checked_add_signed(delta),
+        };
+        self.position = new_position.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })?;
+        Ok(self.position)
+    }
+}
+
+impl<R: Read + Seek> Archive<R> {
+    /// Opens a seekable reader over a single entry.
+    ///
+    /// The entry must be the only entry of a folder compressed with LZMA2
+    /// alone (no filters, no encryption). See [`SeekableEntryReader`] for
+    /// how seeking is handled.
+    ///
+    /// The default [`ResourceLimits`] apply; use
+    /// [`open_entry_reader_with_limits`](Self::open_entry_reader_with_limits)
+    /// to choose others.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the entry is not found,
+    /// [`Error::UnsupportedFeature`] if its folder does not qualify, or
+    /// [`Error::ResourceLimitExceeded`] if it exceeds the default limits.
+    pub fn open_entry_reader(&mut self, name: &str) -> Result<SeekableEntryReader> {
+        self.open_entry_reader_with_limits(name, &ResourceLimits::default())
+    }
+
+    /// Opens a seekable reader over a single entry with custom resource
+    /// limits.
+    ///
+    /// The entry's unpacked size and its packed size, which is held in
+    /// memory, must not exceed
+    /// [`max_entry_unpacked`](ResourceLimits::max_entry_unpacked), and its
+    /// compression ratio must satisfy
+    /// [`ratio_limit`](ResourceLimits::ratio_limit). Both are checked before
+    /// anything is read.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the entry is not found,
+    /// [`Error::UnsupportedFeature`] if its folder does not qualify, or
+    /// [`Error::ResourceLimitExceeded`] if it exceeds `limits`.
+    pub fn open_entry_reader_with_limits(
+        &mut self,
+        name: &str,
+        limits: &ResourceLimits,
+    ) -> Result<SeekableEntryReader> {
+        let unsupported = Error::UnsupportedFeature {
+            feature: "seekable reader for entries outside a single-entry LZMA2 folder",
+        };
+
+        let entry = self
+            .entries
+            .iter()
+            .find(|e| e.path.as_str() == name)
+            .ok_or_else(|| Error::InvalidFormat(format!("entry not found: {}", name)))?;
+        let size = entry.size;
+        let Some(folder_idx) = entry.folder_index else {
+            return Err(unsupported);
+        };
+        if self.is_solid_block(folder_idx) {
+            return Err(unsupported);
+        }
+
+        let folder = self
+            .header
+            .unpack_info
+            .as_ref()
+            .and_then(|ui| ui.folders.get(folder_idx))
+            .ok_or_else(|| {
+                Error::InvalidFormat(format!("folder index {} out of range", folder_idx))
+            })?;
+        let [coder] = folder.coders.as_slice() else {
+            return Err(unsupported);
+        };
+        if coder.method_id.as_slice() != method::LZMA2 {
+            return Err(unsupported);
+        }
+        let properties = coder.properties.clone().unwrap_or_default();
+
+        let pack_pos = self.calculate_pack_position(folder_idx)?;
+        let pack_size = self
+            .header
+            .pack_info
+            .as_ref()
+            .and_then(|pi| pi.pack_sizes.get(folder_idx))
+            .copied()
+            .ok_or_else(|| Error::InvalidFormat("missing pack size".into()))?;
+
+        for (what, bytes) in [("size", size), ("packed size", pack_size)] {
+            if bytes > limits.max_entry_unpacked {
+                return Err(Error::ResourceLimitExceeded(format!(
+                    "entry '{}' {} {} exceeds limit {}",
+                    name, what, bytes, limits.max_entry_unpacked
+                )));
+            }
+        }
+        if let Some(ratio_limit) = &limits.ratio_limit {
+            ratio_limit.check(pack_size, size)?;
+        }
+
+        self.reader
+            .seek(SeekFrom::Start(pack_pos))
+            .map_err(Error::Io)?;
+        let mut packed = vec![0u8; pack_size as usize];
+        self.reader.read_exact(&mut packed).map_err(Error::Io)?;
+
+        SeekableEntryReader::new(packed, properties, size)
+    }
+}
@@ -3,20 +3,53 @@
 //! This module provides methods for extracting entries from archives
 //! to various destinations (files, memory, custom destinations).
 
-use std::fs::File;
-use std::io::{Cursor, Read, Seek, SeekFrom, Write};
-use std::path::Path;
-
-use crate::format::streams::Folder;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufWriter, Cursor, Read, Seek, Write};
+use std::path::{Path, PathBuf};
+use std::sync::PoisonError;
+#[cfg(target_os = "linux")]
+use std::sync::atomic::Ordering;
+
+use crate::checksum::Crc32Writer;
+use crate::format::streams::{Folder, LimitMode};
+use crate::hardlink::create_hard_link;
+use crate::ntfs::parse_ads_path;
+use crate::ownership::UnixOwnership;
+use crate::sparse::SparseWriter;
 use crate::{Error, READ_BUFFER_SIZE, Result};
 
-use super::metadata::{apply_metadata, calculate_file_crc};
-use super::path_safety::{create_symlink, validate_path, validate_symlink_target};
+use super::metadata::{apply_metadata, apply_ownership, calculate_file_crc};
+use super::path_safety::{
+    create_symlink, extraction_path, resolve_symlink_target, validate_path, validate_symlink_target,
+};
+use super::read_ahead::ReadAheadBuffer;
 use super::{
-    Archive, EntrySelector, ExtractDestination, ExtractOptions, ExtractResult, ExtractionLimits,
-    LinkPolicy, OverwritePolicy,
+    Archive, CaseCollisionPolicy, EntryOutcome, EntrySelector, ExtractDestination, ExtractOptions,
+    ExtractResult, ExtractionLimits, LinkPolicy, OverwriteDecision, OverwritePolicy, PathSafety,
+    PreserveMetadata, VfsDestination,
 };
 
+/// Maximum number of links followed under [`LinkPolicy::Follow`].
+const MAX_FOLLOWED_LINKS: usize = 40;
+
+/// Creates the output file of an entry without following a link in its place.
+///
+/// Whatever is at `path` has already passed the overwrite checks, so it is
+/// removed first, and `create_new` refuses to open a symlink that appears
+/// in between instead of writing through it.
+fn create_output_file(path: &Path) -> Result<File> {
+    if path.symlink_metadata().is_ok_and(|m| !m.is_dir()) {
+        std::fs::remove_file(path).map_err(Error::Io)?;
+    }
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .map_err(Error::Io)
+}
+
 impl<R: Read + Seek> Archive<R> {
     /// Extracts entries to a destination directory.
     ///
@@ -56,9 +89,16 @@ impl<R: Read + Seek> Archive<R> {
     ) -> Result<ExtractResult> {
         let dest = dest.as_ref();
         let mut result = ExtractResult::default();
+        self.install_options(options);
 
         // Create extraction limits context with shared tracker for total bytes
-        let limits = ExtractionLimits::from_resource_limits(&options.limits);
+        let mut limits = ExtractionLimits {
+            entry_timeout: options.per_entry_timeout,
+            ..ExtractionLimits::from_resource_limits(&options.limits)
+        };
+        if options.strict {
+            limits.ratio_mode = LimitMode::HardError;
+        }
 
         // Validate destination
         if !dest.exists() {
@@ -74,6 +114,18 @@ impl<R: Read + Seek> Archive<R> {
             .map(|(idx, _)| idx)
             .collect();
 
+        if let Some(max) = options.max_ads_per_file {
+            self.check_ads_per_file(&entries_to_extract, max)?;
+        }
+
+        let mut state = ExtractState::default();
+        #[cfg(feature = "merkle")]
+        {
+            state.merkle = options
+                .compute_merkle
+                .then(super::merkle::MerkleBuilder::default);
+        }
+
         for idx in entries_to_extract {
             // Check for cancellation before each entry
             if let Some(ref progress) = options.progress {
@@ -86,26 +138,32 @@ impl<R: Read + Seek> Archive<R> {
 
             if entry.is_directory {
                 // Create directory
-                let dir_path = dest.join(entry.path.as_str());
-                if let Err(e) = std::fs::create_dir_all(&dir_path) {
-                    result.entries_failed += 1;
-                    result
-                        .failures
-                        .push((entry.path.as_str().to_string(), e.to_string()));
+                let dir_path = extraction_path(idx, entry.path.as_str(), options)
+                    .map(|path| dest.join(path.as_ref()))
+                    .and_then(|path| std::fs::create_dir_all(path).map_err(Error::Io));
+                let outcome = EntryOutcome::new(entry.path.as_str());
+                if let Err(e) = dir_path {
+                    strict_check(options, || {
+                        format!("entry '{}' failed: {}", entry.path.as_str(), e)
+                    })?;
+                    result.record(outcome.with_error(e));
                 } else {
-                    result.entries_extracted += 1;
+                    result.record(outcome);
                 }
             } else {
                 // Extract file
                 let entry_path = entry.path.as_str().to_string();
-                match self.extract_entry_by_index(idx, dest, options, &limits) {
-                    Ok(bytes) => {
-                        result.entries_extracted += 1;
-                        result.bytes_extracted += bytes;
+                match self.extract_entry_by_index(idx, dest, options, &limits, &mut state) {
+                    Ok(outcome) => {
+                        strict_skip_check(options, &outcome)?;
+                        result.record(outcome);
                     }
                     Err(Error::Cancelled) => {
                         // Cancellation requested - clean up any partial file and return
-                        let safe_path = dest.join(&entry_path);
+                        let safe_path = match extraction_path(idx, &entry_path, options) {
+                            Ok(path) => dest.join(path.as_ref()),
+                            Err(_) => return Err(Error::Cancelled),
+                        };
                         if safe_path.exists() {
                             if let Err(e) = std::fs::remove_file(&safe_path) {
                                 log::warn!(
@@ -119,15 +177,25 @@ impl<R: Read + Seek> Archive<R> {
                     }
                     Err(e) => {
                         if matches!(options.overwrite, OverwritePolicy::Skip) {
-                            result.entries_skipped += 1;
+                            strict_check(options, || {
+                                format!("entry '{}' skipped: {}", entry_path, e)
+                            })?;
+                            result.record(EntryOutcome {
+                                skipped: true,
+                                ..EntryOutcome::new(entry_path).with_error(e)
+                            });
                         } else {
-                            result.entries_failed += 1;
-                            result.failures.push((entry_path, e.to_string()));
+                            strict_check(options, || {
+                                format!("entry '{}' failed: {}", entry_path, e)
+                            })?;
+                            result.record(EntryOutcome::new(entry_path).with_error(e));
                         }
                     }
                 }
             }
 
+            strict_warning_check(options, &state.warnings)?;
+
             // Check for cancellation after each entry
             if let Some(ref progress) = options.progress {
                 if progress.should_cancel() {
@@ -136,6 +204,17 @@ impl<R: Read + Seek> Archive<R> {
             }
         }
 
+        for metadata in state.deferred_metadata {
+            metadata.apply(&options.preserve_metadata, &mut state.warnings);
+        }
+        strict_warning_check(options, &state.warnings)?;
+        result.warnings = state.warnings;
+
+        #[cfg(feature = "merkle")]
+        {
+            result.merkle_root = state.merkle.map(|m| m.root());
+        }
+
         Ok(result)
     }
 
@@ -165,7 +244,7 @@ impl<R: Read + Seek> Archive<R> {
 
             if entry.is_directory {
                 dest.on_directory(&entry)?;
-                result.entries_extracted += 1;
+                result.record(EntryOutcome::new(entry.path.as_str()));
             } else {
                 // Extract the entry to a writer
                 let mut writer = dest.create_writer(&entry)?;
@@ -174,16 +253,15 @@ impl<R: Read + Seek> Archive<R> {
                     Ok(bytes) => {
                         drop(writer); // Ensure writer is dropped before on_complete
                         dest.on_complete(&entry, true)?;
-                        result.entries_extracted += 1;
-                        result.bytes_extracted += bytes;
+                        result.record(EntryOutcome {
+                            bytes_written: bytes,
+                            ..EntryOutcome::new(entry.path.as_str())
+                        });
                     }
                     Err(e) => {
                         drop(writer);
                         dest.on_complete(&entry, false)?;
-                        result.entries_failed += 1;
-                        result
-                            .failures
-                            .push((entry.path.as_str().to_string(), e.to_string()));
+                        result.record(EntryOutcome::new(entry.path.as_str()).with_error(e));
                     }
                 }
             }
@@ -194,6 +272,161 @@ impl<R: Read + Seek> Archive<R> {
         Ok(result)
     }
 
+    /// Extracts entries into a virtual filesystem.
+    ///
+    /// Entries are replayed as [`VfsDestination`] operations: directories via
+    /// `create_dir`, files via `create_file` and `write`, and symlinks via
+    /// `symlink`, followed by `set_metadata` for each item. This allows
+    /// extraction into an [`InMemoryVfs`](super::InMemoryVfs) for tests or
+    /// sandboxing, or onto disk with
+    /// [`FilesystemDestination`](super::FilesystemDestination).
+    ///
+    /// The selector, link policy, backslash policy, cancellation and strict
+    /// mode from `options` are honored. Overwriting is up to the destination.
+    ///
+    /// # Arguments
+    ///
+    /// * `vfs` - The virtual filesystem to extract into
+    /// * `selector` - Selects which entries to extract
+    /// * `options` - Extraction options
+    pub fn extract_to_vfs<V: VfsDestination + ?Sized>(
+        &mut self,
+        vfs: &mut V,
+        selector: impl EntrySelector,
+        options: &ExtractOptions,
+    ) -> Result<ExtractResult> {
+        let mut result = ExtractResult::default();
+        self.install_options(options);
+
+        let entries_to_extract: Vec<_> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| selector.select(e))
+            .map(|(idx, _)| idx)
+            .collect();
+
+        for idx in entries_to_extract {
+            if let Some(ref progress) = options.progress {
+                if progress.should_cancel() {
+                    return Err(Error::Cancelled);
+                }
+            }
+
+            let entry_path = self.entries[idx].path.as_str().to_string();
+            match self.extract_entry_to_vfs(idx, vfs, options) {
+                Ok(outcome) => {
+                    strict_skip_check(options, &outcome)?;
+                    result.record(outcome);
+                }
+                Err(e) => {
+                    strict_check(options, || format!("entry '{}' failed: {}", entry_path, e))?;
+                    result.record(EntryOutcome::new(entry_path).with_error(e));
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Extracts a single entry into a virtual filesystem.
+    fn extract_entry_to_vfs<V: VfsDestination + ?Sized>(
+        &mut self,
+        entry_idx: usize,
+        vfs: &mut V,
+        options: &ExtractOptions,
+    ) -> Result<EntryOutcome> {
+        let entry = self.entries[entry_idx].clone();
+        let mut outcome = EntryOutcome::new(entry.path.as_str());
+        let relative_path = extraction_path(entry_idx, entry.path.as_str(), options)?;
+        let path = Path::new(relative_path.as_ref());
+
+        if entry.is_directory {
+            vfs.create_dir(path)?;
+        } else if entry.is_symlink {
+            if matches!(options.link_policy, LinkPolicy::Forbid) {
+                let rejected = Error::SymlinkRejected {
+                    entry_index: entry_idx,
+                    path: entry.path.as_str().to_string(),
+                };
+                outcome.skipped = true;
+                return Ok(outcome.with_error(rejected));
+            }
+
+            let target = self.symlink_target(entry_idx)?;
+            if matches!(options.link_policy, LinkPolicy::Follow) {
+                let source = self.followed_entry_index(entry_idx, target)?;
+                let data = self.extract_entry_to_vec_by_index(source)?;
+                vfs.create_file(path)?;
+                vfs.write(path, &data)?;
+                outcome.bytes_written = data.len() as u64;
+                outcome.crc_verified = self.entries[source].crc32.is_some();
+            } else {
+                if matches!(options.link_policy, LinkPolicy::ValidateTargets) {
+                    validate_symlink_target(entry_idx, &relative_path, &target)?;
+                }
+                vfs.symlink(path, &target)?;
+            }
+        } else {
+            let data = self.extract_entry_to_vec_by_index(entry_idx)?;
+            vfs.create_file(path)?;
+            vfs.write(path, &data)?;
+            outcome.bytes_written = data.len() as u64;
+            outcome.crc_verified = entry.crc32.is_some();
+        }
+
+        vfs.set_metadata(path, &entry)?;
+        Ok(outcome)
+    }
+
+    /// Reads the target stored as a symlink entry's content.
+    fn symlink_target(&mut self, entry_idx: usize) -> Result<String> {
+        String::from_utf8(self.extract_entry_to_vec_by_index(entry_idx)?).map_err(|_| {
+            Error::InvalidFormat(format!(
+                "symlink '{}' has non-UTF-8 target",
+                self.entries[entry_idx].path.as_str()
+            ))
+        })
+    }
+
+    /// Returns the index of the file entry the symlink at `entry_idx`
+    /// points to, following chains of links.
+    ///
+    /// Targets that leave the extraction directory are rejected.
+    fn followed_entry_index(&mut self, entry_idx: usize, target: String) -> Result<usize> {
+        let mut link_idx = entry_idx;
+        let mut target = target;
+        for _ in 0..MAX_FOLLOWED_LINKS {
+            let link_path = self.entries[link_idx].path.as_str().to_string();
+            let resolved = resolve_symlink_target(link_idx, &link_path, &target)?;
+            let source = self
+                .entries
+                .iter()
+                .position(|e| e.path.as_str() == resolved)
+                .ok_or_else(|| {
+                    Error::InvalidFormat(format!(
+                        "symlink '{}' points to '{}', which is not in the archive",
+                        link_path, target
+                    ))
+                })?;
+            if self.entries[source].is_directory {
+                return Err(Error::InvalidFormat(format!(
+                    "symlink '{}' points to directory '{}'",
+                    link_path, resolved
+                )));
+            }
+            if !self.entries[source].is_symlink {
+                return Ok(source);
+            }
+            target = self.symlink_target(source)?;
+            link_idx = source;
+        }
+        Err(Error::InvalidFormat(format!(
+            "too many levels of symbolic links at '{}'",
+            self.entries[entry_idx].path.as_str()
+        )))
+    }
+
     /// Extracts an entry to a writer by index.
     fn extract_entry_to_writer_by_index<W: std::io::Write + ?Sized>(
         &mut self,
@@ -206,34 +439,55 @@ impl<R: Read + Seek> Archive<R> {
         Ok(data.len() as u64)
     }
 
-    pub(crate) fn extract_entry_by_index(
+    /// Fails if any base file among `entries` has more than `max` alternate
+    /// data streams.
+    fn check_ads_per_file(&self, entries: &[usize], max: usize) -> Result<()> {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for &idx in entries {
+            if let Some((base, _)) = parse_ads_path(self.entries[idx].path.as_str()) {
+                let count = counts.entry(base).or_default();
+                *count += 1;
+                if *count > max {
+                    return Err(Error::ResourceLimitExceeded(format!(
+                        "'{}' has more than {} alternate data streams",
+                        base, max
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn extract_entry_by_index(
         &mut self,
         entry_idx: usize,
         dest: &Path,
         options: &ExtractOptions,
         limits: &ExtractionLimits,
-    ) -> Result<u64> {
-        // Copy needed data from entry to avoid borrow issues
+        state: &mut ExtractState,
+    ) -> Result<EntryOutcome> {
         let entry_path_str = self.entries[entry_idx].path.as_str().to_string();
-        let entry_size = self.entries[entry_idx].size;
-        let entry_crc = self.entries[entry_idx].crc32;
-        let folder_index = self.entries[entry_idx].folder_index;
-        let stream_index = self.entries[entry_idx].stream_index;
-        let is_symlink = self.entries[entry_idx].is_symlink;
-
-        // Copy metadata for preservation
-        let modification_time = self.entries[entry_idx].modification_time;
-        let creation_time = self.entries[entry_idx].creation_time;
-        let attributes = self.entries[entry_idx].attributes;
-
-        // Check symlink policy BEFORE doing any extraction work
-        if is_symlink {
+        let mut outcome = EntryOutcome::new(entry_path_str.as_str());
+
+        // Check symlink policy BEFORE doing any extraction work. Forbidden
+        // links (Unix symlinks and Windows reparse points alike) are skipped
+        // without writing anything, not even the stored target string.
+        // Followed links are extracted as a regular file holding the data
+        // and metadata of the entry they point to
+        let mut data_idx = entry_idx;
+        if self.entries[entry_idx].is_symlink {
             match options.link_policy {
                 LinkPolicy::Forbid => {
-                    return Err(Error::SymlinkRejected {
+                    let rejected = Error::SymlinkRejected {
                         entry_index: entry_idx,
                         path: entry_path_str,
-                    });
+                    };
+                    outcome.skipped = true;
+                    return Ok(outcome.with_error(rejected));
+                }
+                LinkPolicy::Follow => {
+                    let target = self.symlink_target(entry_idx)?;
+                    data_idx = self.followed_entry_index(entry_idx, target)?;
                 }
                 LinkPolicy::ValidateTargets | LinkPolicy::Allow => {
                     // Will handle symlink creation below after extracting target
@@ -241,16 +495,71 @@ impl<R: Read + Seek> Archive<R> {
             }
         }
 
+        // Copy needed data from entry to avoid borrow issues
+        let entry_size = self.entries[data_idx].size;
+        let entry_crc = self.entries[data_idx].crc32;
+        let folder_index = self.entries[data_idx].folder_index;
+        let stream_index = self.entries[data_idx].stream_index;
+        let is_symlink = self.entries[data_idx].is_symlink;
+
+        // Copy metadata for preservation
+        let modification_time = self.entries[data_idx].modification_time;
+        let creation_time = self.entries[data_idx].creation_time;
+        let attributes = self.entries[data_idx].attributes;
+        let bsd_flags = self.entries[data_idx].bsd_flags;
+        let sparse_holes = self.entries[data_idx].sparse_holes.clone();
+        let ownership = self.entries[data_idx].ownership.clone();
+
         // Validate path safety
-        let safe_path = validate_path(entry_idx, &entry_path_str, dest, &options.path_safety)?;
+        let relative_path = extraction_path(entry_idx, &entry_path_str, options)?;
+        let relative_path = state
+            .case_folded_paths
+            .claim(relative_path, options.case_collision_policy)?;
+        let mut safe_path = validate_path(entry_idx, &relative_path, dest, &options.path_safety)?;
+        let mut written_path = relative_path.to_string();
 
         // Create parent directories
         if let Some(parent) = safe_path.parent() {
             std::fs::create_dir_all(parent).map_err(Error::Io)?;
         }
 
-        // Check overwrite policy
-        if safe_path.exists() {
+        // Check overwrite policy, deferring to the conflict callback if set
+        let conflict_handler = options.on_conflict.as_ref().filter(|_| safe_path.exists());
+        if let Some(handler) = conflict_handler {
+            let decision = {
+                let mut handler = handler.lock().unwrap_or_else(PoisonError::into_inner);
+                (*handler)(&self.entries[entry_idx], &safe_path)
+            };
+            match decision {
+                OverwriteDecision::Overwrite => outcome.overwritten = true,
+                OverwriteDecision::Skip => {
+                    outcome.skipped = true;
+                    return Ok(outcome);
+                }
+                OverwriteDecision::Rename(path) => {
+                    // The new path is held to the same rules as archive paths
+                    let renamed = path.to_string_lossy();
+                    let relative = path.components().all(|c| {
+                        matches!(
+                            c,
+                            std::path::Component::Normal(_) | std::path::Component::CurDir
+                        )
+                    });
+                    if !relative && options.path_safety != PathSafety::Disabled {
+                        return Err(Error::PathTraversal {
+                            entry_index: entry_idx,
+                            path: renamed.into_owned(),
+                        });
+                    }
+                    safe_path = validate_path(entry_idx, &renamed, dest, &options.path_safety)?;
+                    written_path = renamed.replace('\\', "/");
+                    if let Some(parent) = safe_path.parent() {
+                        std::fs::create_dir_all(parent).map_err(Error::Io)?;
+                    }
+                    outcome.overwritten = safe_path.exists();
+                }
+            }
+        } else if safe_path.exists() {
             match options.overwrite {
                 OverwritePolicy::Error => {
                     return Err(Error::Io(std::io::Error::new(
@@ -259,12 +568,15 @@ impl<R: Read + Seek> Archive<R> {
                     )));
                 }
                 OverwritePolicy::Skip => {
-                    return Ok(0);
+                    outcome.skipped = true;
+                    return Ok(outcome);
                 }
-                OverwritePolicy::Overwrite => {}
+                OverwritePolicy::Overwrite => outcome.overwritten = true,
             }
         }
 
+        let mut leaf = state.start_leaf(&written_path);
+
         // Handle empty files (no folder assignment)
         let folder_idx = match folder_index {
             Some(idx) => idx,
@@ -277,8 +589,9 @@ impl<R: Read + Seek> Archive<R> {
                         entry_path_str
                     )));
                 }
-                File::create(&safe_path).map_err(Error::Io)?;
-                return Ok(0);
+                create_output_file(&safe_path)?;
+                state.finish_leaf(leaf);
+                return Ok(outcome);
             }
         };
 
@@ -302,23 +615,7 @@ impl<R: Read + Seek> Archive<R> {
         if is_symlink {
             // Extract symlink target content to memory
             let mut target_bytes = Vec::with_capacity(entry_size as usize);
-
-            #[cfg(feature = "lzma")]
-            if folder.uses_bcj2() {
-                self.extract_bcj2(&folder, folder_idx, stream_index, &mut target_bytes, limits)?;
-            } else {
-                self.extract_single_stream(
-                    &folder,
-                    folder_idx,
-                    stream_index,
-                    entry_size,
-                    &mut target_bytes,
-                    limits,
-                )?;
-            }
-
-            #[cfg(not(feature = "lzma"))]
-            self.extract_single_stream(
+            self.decode_entry(
                 &folder,
                 folder_idx,
                 stream_index,
@@ -334,48 +631,101 @@ impl<R: Read + Seek> Archive<R> {
 
             // Validate target if policy requires it
             if matches!(options.link_policy, LinkPolicy::ValidateTargets) {
-                validate_symlink_target(entry_idx, &entry_path_str, &target)?;
+                validate_symlink_target(entry_idx, &relative_path, &target)?;
             }
 
             // Create the symlink
-            return create_symlink(&safe_path, &target);
+            outcome.bytes_written = create_symlink(&safe_path, &target)?;
+            feed_leaf(&mut leaf, target.replace('\\', "/").as_bytes());
+            state.finish_leaf(leaf);
+            return Ok(outcome);
         }
 
         // Create output file (regular file path)
-        let mut file = File::create(&safe_path).map_err(Error::Io)?;
-
-        // Check for BCJ2 (multi-stream extraction)
-        #[cfg(feature = "lzma")]
-        let bytes_written = if folder.uses_bcj2() {
-            self.extract_bcj2(&folder, folder_idx, stream_index, &mut file, limits)?
+        let mut file = create_output_file(&safe_path)?;
+
+        // Stored entries of file-backed archives are copied in-kernel, unless
+        // they are sparse and the holes have to be skipped, or the data has
+        // to be hashed for the Merkle root
+        #[cfg(target_os = "linux")]
+        let copied = if sparse_holes.is_empty() && leaf.is_none() {
+            self.copy_stored_entry(&folder, folder_idx, stream_index, entry_size, &file, limits)?
         } else {
-            self.extract_single_stream(
-                &folder,
-                folder_idx,
-                stream_index,
-                entry_size,
-                &mut file,
-                limits,
-            )?
+            None
+        };
+        #[cfg(not(target_os = "linux"))]
+        let copied: Option<u64> = None;
+
+        // With write batching the CRC is computed while writing, so the file
+        // does not have to be read back
+        let (bytes_written, written_crc) = match copied {
+            Some(copied) => (copied, None),
+            None if options.write_batching => {
+                let output = SparseWriter::new(&mut file, &sparse_holes);
+                let mut output = batched_writer(output, entry_size);
+                let bytes_written = self.decode_entry(
+                    &folder,
+                    folder_idx,
+                    stream_index,
+                    entry_size,
+                    &mut LeafWriter {
+                        inner: &mut output,
+                        leaf: &mut leaf,
+                    },
+                    limits,
+                )?;
+                let crc = output.crc();
+                let output = output
+                    .into_inner()
+                    .into_inner()
+                    .map_err(|e| Error::Io(e.into_error()))?;
+                output.finish().map_err(Error::Io)?;
+                (bytes_written, Some(crc))
+            }
+            None => {
+                let mut output = SparseWriter::new(&mut file, &sparse_holes);
+                let bytes_written = self.decode_entry(
+                    &folder,
+                    folder_idx,
+                    stream_index,
+                    entry_size,
+                    &mut LeafWriter {
+                        inner: &mut output,
+                        leaf: &mut leaf,
+                    },
+                    limits,
+                )?;
+                output.finish().map_err(Error::Io)?;
+                (bytes_written, None)
+            }
         };
 
-        #[cfg(not(feature = "lzma"))]
-        let bytes_written = self.extract_single_stream(
-            &folder,
-            folder_idx,
-            stream_index,
-            entry_size,
-            &mut file,
-            limits,
-        )?;
+        // Verify the decoder produced exactly the declared number of bytes
+        if options.verify_size && bytes_written != entry_size {
+            drop(file);
+            if let Err(e) = std::fs::remove_file(&safe_path) {
+                log::warn!(
+                    "Failed to clean up truncated file '{}': {}",
+                    safe_path.display(),
+                    e
+                );
+            }
+            return Err(Error::InvalidFormat(format!(
+                "entry '{}' extracted to {} bytes but declares {} bytes",
+                entry_path_str, bytes_written, entry_size
+            )));
+        }
 
         // Verify CRC if available
         if let Some(expected_crc) = entry_crc {
-            // Re-read file and calculate CRC
+            // Re-read file and calculate CRC unless it was computed on write
             file.flush().map_err(Error::Io)?;
             drop(file);
 
-            let actual_crc = calculate_file_crc(&safe_path)?;
+            let actual_crc = match written_crc {
+                Some(crc) => crc,
+                None => calculate_file_crc(&safe_path)?,
+            };
             if actual_crc != expected_crc {
                 // Delete corrupted file
                 if let Err(e) = std::fs::remove_file(&safe_path) {
@@ -392,18 +742,59 @@ impl<R: Read + Seek> Archive<R> {
                     actual: actual_crc,
                 });
             }
+            outcome.crc_verified = true;
+        }
+
+        if options.dedup_on_extract {
+            let crc = match (written_crc, entry_crc) {
+                (Some(crc), _) | (None, Some(crc)) => crc,
+                (None, None) => calculate_file_crc(&safe_path)?,
+            };
+            state
+                .extracted_files
+                .link_or_insert(&safe_path, bytes_written, crc)?;
         }
 
         // Preserve metadata based on options
-        apply_metadata(
-            &safe_path,
-            &options.preserve_metadata,
+        let metadata = DeferredMetadata {
+            entry_path: entry_path_str,
+            path: safe_path,
             modification_time,
             creation_time,
             attributes,
-        );
+            bsd_flags,
+            ownership,
+        };
+        if options.write_batching {
+            state.deferred_metadata.push(metadata);
+        } else {
+            metadata.apply(&options.preserve_metadata, &mut state.warnings);
+        }
 
-        Ok(bytes_written)
+        state.finish_leaf(leaf);
+        outcome.bytes_written = bytes_written;
+        Ok(outcome)
+    }
+
+    /// Sets the dictionary for entries compressed with a ZSTD dictionary.
+    ///
+    /// Applies to all later extraction from this archive, including
+    /// [`extract_to_vec`](Self::extract_to_vec) and [`test`](Self::test).
+    /// Entries that record a different dictionary ID still fail to decode.
+    #[cfg(feature = "zstd")]
+    pub fn set_zstd_dictionary(&mut self, dict: crate::codec::ZstdDictionary) {
+        self.zstd_dictionary = Some(dict);
+    }
+
+    /// Installs the read-ahead buffer and ZSTD dictionary of `options` on
+    /// the archive.
+    fn install_options(&mut self, options: &ExtractOptions) {
+        self.read_ahead = options.read_ahead.map(ReadAheadBuffer::new);
+
+        #[cfg(feature = "zstd")]
+        if let Some(dict) = &options.zstd_dictionary {
+            self.set_zstd_dictionary(dict.clone());
+        }
     }
 
     /// Extracts an entry by name to a Vec.
@@ -431,6 +822,47 @@ impl<R: Read + Seek> Archive<R> {
         self.extract_entry_to_vec_by_index(entry_idx)
     }
 
+    /// Streams an entry's decompressed data to a writer.
+    ///
+    /// Data is written as it is decoded, so the entry never has to fit in
+    /// memory. The CRC is verified after the last byte has been written; on a
+    /// mismatch the writer has already received the corrupted data.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path of the entry to extract
+    /// * `out` - Writer receiving the entry data
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the entry is not found, is a directory, or extraction fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> zesven::Result<()> {
+    /// use zesven::Archive;
+    ///
+    /// let mut archive = Archive::open_path("archive.7z")?;
+    /// let mut stdout = std::io::stdout().lock();
+    /// archive.extract_entry_to("docs/readme.txt", &mut stdout)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn extract_entry_to(&mut self, path: &str, out: &mut impl Write) -> Result<u64> {
+        let entry_idx = self
+            .entries
+            .iter()
+            .position(|e| e.path.as_str() == path)
+            .ok_or_else(|| Error::InvalidFormat(format!("entry not found: {}", path)))?;
+
+        self.stream_entry_by_index(entry_idx, out, "writer")
+    }
+
     /// Extracts an entry by index to a Vec.
     ///
     /// # Arguments
@@ -441,24 +873,65 @@ impl<R: Read + Seek> Archive<R> {
     ///
     /// The decompressed entry data as a `Vec<u8>`.
     pub fn extract_entry_to_vec_by_index(&mut self, entry_idx: usize) -> Result<Vec<u8>> {
+        let capacity = self.entries.get(entry_idx).map_or(0, |e| e.size as usize);
+        let mut output = Vec::with_capacity(capacity);
+        self.stream_entry_by_index(entry_idx, &mut output, "vec")?;
+        Ok(output)
+    }
+
+    /// Decodes an entry into `output` and verifies its CRC.
+    ///
+    /// `target` names the destination in the error for directory entries.
+    pub(crate) fn stream_entry_by_index(
+        &mut self,
+        entry_idx: usize,
+        output: &mut impl Write,
+        target: &str,
+    ) -> Result<u64> {
+        let (bytes_written, actual_crc) = self.decode_entry_by_index(entry_idx, output, target)?;
+
+        // Verify CRC if available
+        if let Some(expected_crc) = self.entries[entry_idx].crc32 {
+            if actual_crc != expected_crc {
+                return Err(Error::CrcMismatch {
+                    entry_index: entry_idx,
+                    entry_name: Some(self.entries[entry_idx].path.as_str().to_string()),
+                    expected: expected_crc,
+                    actual: actual_crc,
+                });
+            }
+        }
+
+        Ok(bytes_written)
+    }
+
+    /// Decodes an entry into `output` without verifying its CRC.
+    ///
+    /// Returns the number of bytes written and the CRC of the decoded data.
+    pub(crate) fn decode_entry_by_index(
+        &mut self,
+        entry_idx: usize,
+        output: &mut impl Write,
+        target: &str,
+    ) -> Result<(u64, u32)> {
         let entry = self.entries.get(entry_idx).ok_or_else(|| {
             Error::InvalidFormat(format!("entry index {} out of range", entry_idx))
         })?;
 
         if entry.is_directory {
-            return Err(Error::InvalidFormat(
-                "cannot extract directory to vec".into(),
-            ));
+            return Err(Error::InvalidFormat(format!(
+                "cannot extract directory to {}",
+                target
+            )));
         }
 
-        // Empty files (size=0, no stream) can be extracted as empty Vec
+        // Empty files (size=0, no stream) produce no data
         // These have folder_index=None because they don't have data streams
         if entry.size == 0 && entry.folder_index.is_none() {
-            return Ok(Vec::new());
+            return Ok((0, crc32fast::hash(&[])));
         }
 
         let entry_size = entry.size;
-        let entry_crc = entry.crc32;
         let folder_idx = entry
             .folder_index
             .ok_or_else(|| Error::InvalidFormat("entry has no folder index".into()))?;
@@ -480,27 +953,10 @@ impl<R: Read + Seek> Archive<R> {
             })?
             .clone();
 
-        // Extract to Vec (use unlimited limits for public API backward compatibility)
+        // Use unlimited limits for public API backward compatibility
         let limits = ExtractionLimits::unlimited();
-        let mut output = Vec::with_capacity(entry_size as usize);
-
-        // Check for BCJ2 (multi-stream extraction)
-        #[cfg(feature = "lzma")]
-        if folder.uses_bcj2() {
-            self.extract_bcj2(&folder, folder_idx, stream_index, &mut output, &limits)?;
-        } else {
-            self.extract_single_stream(
-                &folder,
-                folder_idx,
-                stream_index,
-                entry_size,
-                &mut output,
-                &limits,
-            )?;
-        }
-
-        #[cfg(not(feature = "lzma"))]
-        self.extract_single_stream(
+        let mut output = Crc32Writer::new(output);
+        let bytes_written = self.decode_entry(
             &folder,
             folder_idx,
             stream_index,
@@ -509,20 +965,7 @@ impl<R: Read + Seek> Archive<R> {
             &limits,
         )?;
 
-        // Verify CRC if available
-        if let Some(expected_crc) = entry_crc {
-            let actual_crc = crc32fast::hash(&output);
-            if actual_crc != expected_crc {
-                return Err(Error::CrcMismatch {
-                    entry_index: entry_idx,
-                    entry_name: Some(self.entries[entry_idx].path.as_str().to_string()),
-                    expected: expected_crc,
-                    actual: actual_crc,
-                });
-            }
-        }
-
-        Ok(output)
+        Ok((bytes_written, output.crc()))
     }
 
     /// Extracts a non-solid entry directly.
@@ -543,6 +986,7 @@ impl<R: Read + Seek> Archive<R> {
 
         let cursor = Cursor::new(packed_data);
         let decoder = self.build_decoder_chain(cursor, folder, uncompressed_size)?;
+        let decoder = limits.wrap_timeout(decoder);
 
         // Wrap decoder with LimitedReader for resource limit enforcement
         let mut limited_decoder = limits.wrap_reader(decoder, compressed_size);
@@ -592,7 +1036,11 @@ impl<R: Read + Seek> Archive<R> {
         let compressed_size = packed_data.len() as u64;
 
         let cursor = Cursor::new(packed_data);
-        let mut decoder = self.build_decoder_chain(cursor, folder, uncompressed_size)?;
+        let decoder = self.build_decoder_chain(cursor, folder, uncompressed_size)?;
+        let mut decoder = limits.wrap_timeout(decoder);
+
+        // Hash the whole folder when its CRC is checkable (last stream only)
+        let mut folder_hasher = Self::folder_crc_hasher(folder, stream_index, entry_sizes.len());
 
         // Skip entries before the target (no limit enforcement on skipped data)
         for &skip_size in entry_sizes.iter().take(stream_index) {
@@ -600,10 +1048,15 @@ impl<R: Read + Seek> Archive<R> {
             let mut buf = [0u8; READ_BUFFER_SIZE];
             while remaining > 0 {
                 let to_read = buf.len().min(remaining as usize);
-                let n = decoder.read(&mut buf[..to_read]).map_err(Error::Io)?;
+                let n = decoder
+                    .read(&mut buf[..to_read])
+                    .map_err(super::map_io_error)?;
                 if n == 0 {
                     break;
                 }
+                if let Some(hasher) = folder_hasher.as_mut() {
+                    hasher.update(&buf[..n]);
+                }
                 remaining -= n as u64;
             }
         }
@@ -627,13 +1080,108 @@ impl<R: Read + Seek> Archive<R> {
                 break;
             }
             output.write_all(&buf[..n]).map_err(Error::Io)?;
+            if let Some(hasher) = folder_hasher.as_mut() {
+                hasher.update(&buf[..n]);
+            }
             total_written += n as u64;
             remaining -= n as u64;
         }
 
+        if let Some(hasher) = folder_hasher {
+            Self::verify_folder_crc(folder, folder_idx, hasher)?;
+        }
+
         Ok(total_written)
     }
 
+    /// Copies a stored (Copy-method) entry straight from the archive file
+    /// using `copy_file_range`, without passing the data through userspace.
+    ///
+    /// Returns `Ok(None)` when the fast path does not apply and the caller
+    /// should fall back to the buffered decoder: the archive was not opened
+    /// from a path, the folder is compressed or encrypted, the entry would hit
+    /// a resource limit or needs a folder CRC check, or the filesystem does
+    /// not support `copy_file_range`.
+    #[cfg(target_os = "linux")]
+    fn copy_stored_entry(
+        &self,
+        folder: &Folder,
+        folder_idx: usize,
+        stream_index: Option<usize>,
+        entry_size: u64,
+        output: &File,
+        limits: &ExtractionLimits,
+    ) -> Result<Option<u64>> {
+        let Some(source) = self.source_file.as_ref() else {
+            return Ok(None);
+        };
+        if folder.coders.len() != 1
+            || folder.coders[0].method_id != crate::codec::method::COPY
+            || folder.packed_streams.len() > 1
+        {
+            return Ok(None);
+        }
+
+        // Leave limit violations to the buffered path, which reports them
+        let total = limits.total_tracker.load(Ordering::Relaxed);
+        if entry_size > limits.max_entry_bytes
+            || total.saturating_add(entry_size) > limits.max_total_bytes
+        {
+            return Ok(None);
+        }
+
+        let Some(pack_size) = self
+            .header
+            .pack_info
+            .as_ref()
+            .and_then(|pack_info| pack_info.pack_sizes.get(folder_idx).copied())
+        else {
+            return Ok(None);
+        };
+
+        // Locate the entry within the folder: a stored solid block is just the
+        // concatenation of its streams
+        let mut offset_in_folder = 0u64;
+        if self.is_solid_block(folder_idx) {
+            let entry_sizes = self.get_solid_block_entry_sizes(folder_idx)?;
+            let stream_index = stream_index.unwrap_or(0);
+            if stream_index >= entry_sizes.len()
+                || Self::folder_crc_hasher(folder, stream_index, entry_sizes.len()).is_some()
+            {
+                return Ok(None);
+            }
+            offset_in_folder = entry_sizes[..stream_index].iter().sum();
+        }
+        if offset_in_folder.saturating_add(entry_size) > pack_size {
+            return Ok(None);
+        }
+
+        let offset = self.calculate_pack_position(folder_idx)? + offset_in_folder;
+        let copied = copy_file_range_all(source, offset, output, entry_size).map_err(Error::Io)?;
+        if let Some(copied) = copied {
+            limits.total_tracker.fetch_add(copied, Ordering::Relaxed);
+        }
+        Ok(copied)
+    }
+
+    /// Decodes a regular entry into `output`, handling BCJ2 folders.
+    fn decode_entry(
+        &mut self,
+        folder: &Folder,
+        folder_idx: usize,
+        stream_index: Option<usize>,
+        entry_size: u64,
+        output: &mut impl Write,
+        limits: &ExtractionLimits,
+    ) -> Result<u64> {
+        #[cfg(feature = "lzma")]
+        if folder.uses_bcj2() {
+            return self.extract_bcj2(folder, folder_idx, stream_index, output, limits);
+        }
+
+        self.extract_single_stream(folder, folder_idx, stream_index, entry_size, output, limits)
+    }
+
     /// Extracts a single-stream entry (non-BCJ2).
     pub(crate) fn extract_single_stream(
         &mut self,
@@ -660,16 +1208,8 @@ impl<R: Read + Seek> Archive<R> {
             .copied()
             .ok_or_else(|| Error::InvalidFormat("missing pack size".into()))?;
 
-        // Seek to pack position
-        self.reader
-            .seek(SeekFrom::Start(pack_pos))
-            .map_err(Error::Io)?;
-
         // Read packed data
-        let mut packed_data = vec![0u8; pack_size as usize];
-        self.reader
-            .read_exact(&mut packed_data)
-            .map_err(Error::Io)?;
+        let packed_data = self.read_pack_data(pack_pos, pack_size)?;
 
         // Check if this is a solid block (multiple entries in one folder)
         let is_solid_block = self.is_solid_block(folder_idx);
@@ -691,3 +1231,513 @@ impl<R: Read + Seek> Archive<R> {
         }
     }
 }
+
+/// Largest buffer used for a single file with write batching.
+const MAX_BATCH_BUFFER_SIZE: usize = 1024 * 1024;
+
+/// Wraps an entry's output in a buffer sized to hold the whole entry when it
+/// is small, and hashes the data on the way through.
+fn batched_writer<W: Write>(output: W, entry_size: u64) -> Crc32Writer<BufWriter<W>> {
+    let capacity = usize::try_from(entry_size)
+        .unwrap_or(usize::MAX)
+        .clamp(1, MAX_BATCH_BUFFER_SIZE);
+    Crc32Writer::new(BufWriter::with_capacity(capacity, output))
+}
+
+/// Timestamps, attributes and ownership of an extracted file, kept until the
+/// end of extraction when write batching is enabled.
+struct DeferredMetadata {
+    entry_path: String,
+    path: PathBuf,
+    modification_time: Option<u64>,
+    creation_time: Option<u64>,
+    attributes: Option<u32>,
+    bsd_flags: Option<u32>,
+    ownership: Option<UnixOwnership>,
+}
+
+impl DeferredMetadata {
+    /// Applies the metadata, recording a warning in `warnings` if ownership
+    /// could not be restored.
+    fn apply(self, options: &PreserveMetadata, warnings: &mut Vec<(String, String)>) {
+        // Ownership goes first: changing the owner clears setuid bits, and
+        // file flags set below may forbid further changes
+        if let Some(ownership) = self.ownership.as_ref().filter(|_| options.ownership) {
+            if let Some(warning) = apply_ownership(&self.path, ownership) {
+                warnings.push((self.entry_path.clone(), warning));
+            }
+        }
+        apply_metadata(
+            &self.path,
+            options,
+            self.modification_time,
+            self.creation_time,
+            self.attributes,
+            self.bsd_flags,
+        );
+    }
+}
+
+/// Bookkeeping shared by the entries of one [`Archive::extract`] call.
+#[derive(Default)]
+struct ExtractState {
+    /// Metadata of files written with write batching, applied at the end.
+    deferred_metadata: Vec<DeferredMetadata>,
+    /// Warnings for entries whose metadata could not be fully restored.
+    warnings: Vec<(String, String)>,
+    /// Files written so far, as hard link sources for deduplication.
+    extracted_files: ExtractedFiles,
+    /// Paths written so far, for detecting case collisions.
+    case_folded_paths: CaseFoldedPaths,
+    /// Leaves of the Merkle root, if one is computed.
+    #[cfg(feature = "merkle")]
+    merkle: Option<super::merkle::MerkleBuilder>,
+}
+
+impl ExtractState {
+    /// Starts the Merkle leaf of an entry written to `path`, relative to the
+    /// destination, if a Merkle root is computed.
+    fn start_leaf(&self, path: &str) -> Option<Leaf> {
+        #[cfg(feature = "merkle")]
+        return self.merkle.as_ref().map(|_| Leaf::new(path));
+        #[cfg(not(feature = "merkle"))]
+        {
+            let _ = path;
+            None
+        }
+    }
+
+    /// Adds the leaf of an entry that was written successfully.
+    fn finish_leaf(&mut self, leaf: Option<Leaf>) {
+        #[cfg(feature = "merkle")]
+        if let (Some(merkle), Some(leaf)) = (self.merkle.as_mut(), leaf) {
+            merkle.add_leaf(leaf);
+        }
+        #[cfg(not(feature = "merkle"))]
+        let _ = leaf;
+    }
+}
+
+#[cfg(feature = "merkle")]
+use super::merkle::Leaf;
+
+/// Stand-in for the Merkle leaf when the `merkle` feature is disabled.
+#[cfg(not(feature = "merkle"))]
+type Leaf = std::convert::Infallible;
+
+/// Adds `data` to the Merkle leaf, if there is one.
+fn feed_leaf(leaf: &mut Option<Leaf>, data: &[u8]) {
+    #[cfg(feature = "merkle")]
+    if let Some(leaf) = leaf {
+        leaf.update(data);
+    }
+    #[cfg(not(feature = "merkle"))]
+    let _ = (leaf, data);
+}
+
+/// Writer that feeds the entry data it writes to a Merkle leaf.
+struct LeafWriter<'a, W> {
+    inner: W,
+    leaf: &'a mut Option<Leaf>,
+}
+
+impl<W: Write> Write for LeafWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        feed_leaf(self.leaf, &buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Paths of one extraction keyed by their lowercase form, for
+/// [`ExtractOptions::case_collision_policy`].
+#[derive(Debug, Default)]
+struct CaseFoldedPaths {
+    paths: HashMap<String, String>,
+}
+
+impl CaseFoldedPaths {
+    /// Claims `path` for an entry, applying `policy` if an earlier entry
+    /// claimed a path that differs from it only in case.
+    fn claim<'a>(
+        &mut self,
+        path: Cow<'a, str>,
+        policy: CaseCollisionPolicy,
+    ) -> Result<Cow<'a, str>> {
+        let folded = path.to_lowercase();
+        let existing = match self.paths.get(&folded) {
+            Some(existing) if *existing != *path => existing,
+            _ => {
+                self.paths.insert(folded, path.to_string());
+                return Ok(path);
+            }
+        };
+
+        match policy {
+            CaseCollisionPolicy::Overwrite => Ok(path),
+            CaseCollisionPolicy::Error => Err(Error::Io(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                format!(
+                    "'{}' collides with '{}' when case is ignored",
+                    path, existing
+                ),
+            ))),
+            CaseCollisionPolicy::Rename => {
+                let renamed = (1..)
+                    .map(|n| numbered_path(&path, n))
+                    .find(|candidate| !self.paths.contains_key(&candidate.to_lowercase()))
+                    .expect("numbered paths are unbounded");
+                self.paths.insert(renamed.to_lowercase(), renamed.clone());
+                Ok(Cow::Owned(renamed))
+            }
+        }
+    }
+}
+
+/// Inserts ` (n)` before the extension of the last component of `path`.
+fn numbered_path(path: &str, n: usize) -> String {
+    let name_start = path.rfind('/').map_or(0, |i| i + 1);
+    let ext_start = path[name_start..]
+        .rfind('.')
+        .filter(|&i| i > 0)
+        .map_or(path.len(), |i| name_start + i);
+    format!("{} ({}){}", &path[..ext_start], n, &path[ext_start..])
+}
+
+/// Files written by one extraction, keyed by size and CRC, for
+/// [`ExtractOptions::dedup_on_extract`].
+#[derive(Debug, Default)]
+struct ExtractedFiles {
+    by_content: HashMap<(u64, u32), Vec<PathBuf>>,
+}
+
+impl ExtractedFiles {
+    /// Replaces the file at `path` with a hard link to an earlier file with
+    /// the same content, or records it as a link source if there is none.
+    ///
+    /// Returns whether the file was replaced. If the link cannot be created
+    /// the file is left as it is.
+    fn link_or_insert(&mut self, path: &Path, size: u64, crc: u32) -> Result<bool> {
+        let candidates = self.by_content.entry((size, crc)).or_default();
+        for original in candidates.iter().filter(|original| *original != path) {
+            if !same_file_contents(original, path)? {
+                continue;
+            }
+
+            // Link next to the file first so it is never missing
+            let mut temp_path = path.as_os_str().to_owned();
+            temp_path.push(".link.tmp");
+            let temp_path = PathBuf::from(temp_path);
+            let linked = create_hard_link(original, &temp_path)
+                .and_then(|()| std::fs::rename(&temp_path, path));
+            return match linked {
+                Ok(()) => Ok(true),
+                Err(e) => {
+                    log::debug!(
+                        "Keeping copy of '{}', hard link to '{}' failed: {}",
+                        path.display(),
+                        original.display(),
+                        e
+                    );
+                    let _ = std::fs::remove_file(&temp_path);
+                    Ok(false)
+                }
+            };
+        }
+        if !candidates.iter().any(|original| original == path) {
+            candidates.push(path.to_path_buf());
+        }
+        Ok(false)
+    }
+}
+
+/// Returns whether two files have the same bytes.
+fn same_file_contents(a: &Path, b: &Path) -> Result<bool> {
+    let mut a = std::io::BufReader::new(File::open(a).map_err(Error::Io)?);
+    let mut b = std::io::BufReader::new(File::open(b).map_err(Error::Io)?);
+    loop {
+        let chunk_a = a.fill_buf().map_err(Error::Io)?;
+        let chunk_b = b.fill_buf().map_err(Error::Io)?;
+        let n = chunk_a.len().min(chunk_b.len());
+        if n == 0 {
+            return Ok(chunk_a.is_empty() && chunk_b.is_empty());
+        }
+        if chunk_a[..n] != chunk_b[..n] {
+            return Ok(false);
+        }
+        a.consume(n);
+        b.consume(n);
+    }
+}
+
+/// Fails with [`Error::Strict`] when strict mode turns an entry warning into
+/// an error.
+fn strict_check(options: &ExtractOptions, message: impl FnOnce() -> String) -> Result<()> {
+    if options.strict {
+        return Err(Error::Strict(message()));
+    }
+    Ok(())
+}
+
+/// Fails in strict mode if `outcome` skipped its entry, such as a forbidden
+/// symlink or a file left in place by the overwrite policy.
+fn strict_skip_check(options: &ExtractOptions, outcome: &EntryOutcome) -> Result<()> {
+    if !outcome.skipped {
+        return Ok(());
+    }
+    strict_check(options, || {
+        let reason = outcome
+            .error
+            .as_deref()
+            .unwrap_or("destination already exists");
+        format!("entry '{}' skipped: {}", outcome.path, reason)
+    })
+}
+
+/// Fails in strict mode if a warning, such as ownership that could not be
+/// restored, has been recorded.
+fn strict_warning_check(options: &ExtractOptions, warnings: &[(String, String)]) -> Result<()> {
+    match warnings.first() {
+        Some((path, warning)) => strict_check(options, || format!("entry '{}': {}", path, warning)),
+        None => Ok(()),
+    }
+}
+
+/// Copies `len` bytes of `source` starting at `offset` to the current position
+/// of `output` with `copy_file_range(2)`.
+///
+/// Returns `Ok(None)` if the kernel or filesystem rejects the copy before any
+/// byte was transferred, so the caller can fall back to a buffered copy.
+/// The file offset of `source` is left untouched.
+#[cfg(target_os = "linux")]
+fn copy_file_range_all(
+    source: &File,
+    offset: u64,
+    output: &File,
+    len: u64,
+) -> std::io::Result<Option<u64>> {
+    use std::os::fd::AsRawFd;
+
+    unsafe extern "C" {
+        fn copy_file_range(
+            fd_in: i32,
+            off_in: *mut i64,
+            fd_out: i32,
+            off_out: *mut i64,
+            len: usize,
+            flags: u32,
+        ) -> isize;
+    }
+
+    let mut off_in = i64::try_from(offset).map_err(std::io::Error::other)?;
+    let mut copied = 0u64;
+    while copied < len {
+        let chunk = usize::try_from(len - copied).unwrap_or(usize::MAX);
+        let chunk = chunk.min(1 << 30);
+        // SAFETY: both descriptors are valid for the lifetime of the borrowed
+        // files, `off_in` points to a live i64 and a null `off_out` makes the
+        // kernel use (and advance) the output file offset.
+        let n = unsafe {
+            copy_file_range(
+                source.as_raw_fd(),
+                &mut off_in,
+                output.as_raw_fd(),
+                std::ptr::null_mut(),
+                chunk,
+                0,
+            )
+        };
+        if n < 0 {
+            let err = std::io::Error::last_os_error();
+            if err.kind() == std::io::ErrorKind::Interrupted {
+                continue;
+            }
+            // ENOSYS and EOPNOTSUPP, EXDEV, EINVAL: not supported for this
+            // pair of files
+            let unsupported = matches!(
+                err.kind(),
+                std::io::ErrorKind::Unsupported
+                    | std::io::ErrorKind::CrossesDevices
+                    | std::io::ErrorKind::InvalidInput
+            );
+            if copied == 0 && unsupported {
+                return Ok(None);
+            }
+            return Err(err);
+        }
+        if n == 0 {
+            if copied == 0 {
+                return Ok(None);
+            }
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "archive ended inside a stored entry",
+            ));
+        }
+        copied += n as u64;
+    }
+    Ok(Some(copied))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::CodecMethod;
+    use crate::format::streams::ResourceLimits;
+    use crate::{ArchivePath, WriteOptions, Writer};
+
+    /// Writer that counts the write calls reaching it.
+    #[derive(Debug, Default)]
+    struct StatsWriter {
+        data: Vec<u8>,
+        writes: usize,
+    }
+
+    impl Write for StatsWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.writes += 1;
+            self.data.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn small_files() -> Vec<(String, Vec<u8>)> {
+        (0..64)
+            .map(|i| {
+                let data = (0..20_000u32).map(|b| (b * 7 + i) as u8).collect();
+                (format!("dir/file{i:02}.bin"), data)
+            })
+            .collect()
+    }
+
+    /// Decodes every entry into its own [`StatsWriter`], returning the total
+    /// number of write calls and the decoded contents.
+    fn decode_all(archive: &mut Archive<Cursor<Vec<u8>>>, batching: bool) -> (usize, Vec<Vec<u8>>) {
+        let limits = ExtractionLimits::from_resource_limits(&ResourceLimits::default());
+        let mut writes = 0;
+        let mut contents = Vec::new();
+        for idx in 0..archive.entries.len() {
+            let entry = archive.entries[idx].clone();
+            let folder_idx = entry.folder_index.unwrap();
+            let folder = archive.header.unpack_info.as_ref().unwrap().folders[folder_idx].clone();
+            let mut sink = StatsWriter::default();
+            if batching {
+                let mut output = batched_writer(&mut sink, entry.size);
+                archive
+                    .decode_entry(
+                        &folder,
+                        folder_idx,
+                        entry.stream_index,
+                        entry.size,
+                        &mut output,
+                        &limits,
+                    )
+                    .unwrap();
+                assert_eq!(Some(output.crc()), entry.crc32);
+                output.into_inner().into_inner().unwrap();
+            } else {
+                archive
+                    .decode_entry(
+                        &folder,
+                        folder_idx,
+                        entry.stream_index,
+                        entry.size,
+                        &mut sink,
+                        &limits,
+                    )
+                    .unwrap();
+            }
+            writes += sink.writes;
+            contents.push(sink.data);
+        }
+        (writes, contents)
+    }
+
+    #[test]
+    fn test_write_batching_reduces_write_calls() {
+        let files = small_files();
+        let mut writer = Writer::create(Cursor::new(Vec::new()))
+            .unwrap()
+            .options(WriteOptions::new().method(CodecMethod::Copy));
+        for (path, data) in &files {
+            writer
+                .add_bytes(ArchivePath::new(path).unwrap(), data)
+                .unwrap();
+        }
+        let (_, cursor) = writer.finish_into_inner().unwrap();
+        let mut archive = Archive::open(Cursor::new(cursor.into_inner())).unwrap();
+
+        let (unbatched_writes, unbatched) = decode_all(&mut archive, false);
+        let (batched_writes, batched) = decode_all(&mut archive, true);
+
+        let expected: Vec<Vec<u8>> = files.into_iter().map(|(_, data)| data).collect();
+        assert_eq!(unbatched, expected);
+        assert_eq!(batched, expected);
+        assert_eq!(batched_writes, expected.len());
+        assert!(
+            batched_writes < unbatched_writes,
+            "batched {} writes, unbatched {}",
+            batched_writes,
+            unbatched_writes
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_extract_restores_ownership() {
+        use std::os::unix::fs::MetadataExt;
+
+        let mut writer = Writer::create(Cursor::new(Vec::new())).unwrap();
+        writer
+            .add_bytes(ArchivePath::new("owned.txt").unwrap(), b"owned content")
+            .unwrap();
+        let (_, cursor) = writer.finish_into_inner().unwrap();
+        let mut archive = Archive::open(Cursor::new(cursor.into_inner())).unwrap();
+
+        // Files created by this process belong to its effective user
+        let temp_dir = tempfile::tempdir().unwrap();
+        let is_root = std::fs::metadata(temp_dir.path()).unwrap().uid() == 0;
+
+        // Root can give files to anyone; other users cannot give them to root
+        let (uid, gid) = if is_root { (1234, 5678) } else { (0, 0) };
+        archive.entries[0].ownership = Some(UnixOwnership::from_ids(uid, gid));
+
+        for write_batching in [false, true] {
+            let dest = temp_dir.path().join(format!("batching-{write_batching}"));
+            let options = ExtractOptions::new()
+                .preserve_metadata(PreserveMetadata::all().ownership(true))
+                .write_batching(write_batching);
+            let result = archive.extract(&dest, (), &options).unwrap();
+            assert_eq!(result.entries_extracted, 1);
+            assert_eq!(result.entries_failed, 0);
+
+            let path = dest.join("owned.txt");
+            assert_eq!(std::fs::read(&path).unwrap(), b"owned content");
+            let metadata = std::fs::metadata(&path).unwrap();
+            if is_root {
+                assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+                assert_eq!((metadata.uid(), metadata.gid()), (uid, gid));
+            } else {
+                assert_eq!(result.warnings.len(), 1);
+                assert_eq!(result.warnings[0].0, "owned.txt");
+                assert!(result.warnings[0].1.contains("ownership"));
+            }
+        }
+    }
+
+    #[test]
+    fn test_numbered_path() {
+        assert_eq!(numbered_path("foo.txt", 1), "foo (1).txt");
+        assert_eq!(numbered_path("dir/foo.tar.gz", 2), "dir/foo.tar (2).gz");
+        assert_eq!(numbered_path("dir.d/foo", 1), "dir.d/foo (1)");
+        assert_eq!(numbered_path(".hidden", 1), ".hidden (1)");
+    }
+}
@@ -0,0 +1,72 @@
+//! Content fingerprints of archives.
+
+use std::io::{Read, Seek};
+
+use sha2::{Digest, Sha256};
+
+use super::{Archive, Entry};
+
+/// Kind byte of file entries.
+const KIND_FILE: u8 = 0x00;
+/// Kind byte of directory entries.
+const KIND_DIRECTORY: u8 = 0x01;
+/// Kind byte of symlink entries.
+const KIND_SYMLINK: u8 = 0x02;
+
+impl<R: Read + Seek> Archive<R> {
+    /// Returns a SHA-256 fingerprint of the archive's paths and contents.
+    ///
+    /// Every entry except anti-items contributes one record:
+    ///
+    /// ```text
+    /// record = len(path) as u64 LE || path || kind || size as u64 LE || crc32 as u32 LE
+    /// ```
+    ///
+    /// where `path` is the archive path in UTF-8 with `/` separators, `kind`
+    /// is `0x00` for files, `0x01` for directories and `0x02` for symlinks,
+    /// and `crc32` is `0` for entries without a CRC. Records are sorted by
+    /// path in byte order and the fingerprint is the SHA-256 of their
+    /// concatenation.
+    ///
+    /// Only the header is read, so this works for encrypted entries without
+    /// a password. The fingerprint does not depend on timestamps,
+    /// attributes, entry order, methods, solid blocks or encryption, so
+    /// archives holding the same files compare equal. Contents are compared
+    /// by CRC-32 only, which is enough to find duplicates but does not
+    /// protect against deliberate collisions.
+    pub fn content_fingerprint(&self) -> [u8; 32] {
+        let mut records: Vec<(&str, Vec<u8>)> = self
+            .entries
+            .iter()
+            .filter(|entry| !entry.is_anti)
+            .map(|entry| (entry.path.as_str(), fingerprint_record(entry)))
+            .collect();
+        records.sort();
+
+        let mut hasher = Sha256::new();
+        for (_, record) in records {
+            hasher.update(record);
+        }
+        hasher.finalize().into()
+    }
+}
+
+/// Encodes the fingerprint record of `entry`.
+fn fingerprint_record(entry: &Entry) -> Vec<u8> {
+    let path = entry.path.as_str();
+    let kind = if entry.is_directory {
+        KIND_DIRECTORY
+    } else if entry.is_symlink {
+        KIND_SYMLINK
+    } else {
+        KIND_FILE
+    };
+
+    let mut record = Vec::with_capacity(path.len() + 21);
+    record.extend_from_slice(&(path.len() as u64).to_le_bytes());
+    record.extend_from_slice(path.as_bytes());
+    record.push(kind);
+    record.extend_from_slice(&entry.size.to_le_bytes());
+    record.extend_from_slice(&entry.crc32.unwrap_or(0).to_le_bytes());
+    record
+}
@@ -0,0 +1,307 @@
+//! Glob pattern matching for archive entry paths.
+//!
+//! Patterns are matched against the whole `/`-separated entry path:
+//!
+//! - `*` matches any run of characters within a path component
+//! - `?` matches a single character other than `/`
+//! - `[a-z]` matches one character from a class; `[!a-z]` or `[^a-z]` negates it
+//! - `**` as a whole component matches zero or more components
+//!
+//! A `**` that is part of a longer component behaves like `*`.
+
+use crate::{Error, Result};
+
+/// A compiled glob pattern.
+#[derive(Debug, Clone)]
+pub(crate) struct GlobPattern {
+    segments: Vec<Segment>,
+}
+
+/// One `/`-separated component of a pattern.
+#[derive(Debug, Clone)]
+enum Segment {
+    /// `**`: zero or more path components.
+    AnyComponents,
+    /// A component matched token by token.
+    Component(Vec<Token>),
+}
+
+#[derive(Debug, Clone)]
+enum Token {
+    Literal(char),
+    /// `?`
+    AnyChar,
+    /// `*`
+    AnyRun,
+    /// `[...]`
+    Class {
+        negated: bool,
+        ranges: Vec<(char, char)>,
+    },
+}
+
+impl GlobPattern {
+    /// Compiles a glob pattern.
+    pub(crate) fn new(pattern: &str) -> Result<Self> {
+        let segments = pattern
+            .split('/')
+            .map(|component| match component {
+                "**" => Ok(Segment::AnyComponents),
+                _ => compile_component(component)
+                    .map(Segment::Component)
+                    .map_err(|reason| Error::InvalidGlob {
+                        pattern: pattern.to_string(),
+                        reason,
+                    }),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { segments })
+    }
+
+    /// Returns true if the pattern matches the whole path.
+    pub(crate) fn matches(&self, path: &str) -> bool {
+        let components: Vec<&str> = path.split('/').collect();
+        match_segments(&self.segments, &components)
+    }
+}
+
+fn compile_component(component: &str) -> std::result::Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = component.chars().peekable();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            '*' => {
+                // Consecutive stars within a component collapse into one
+                while chars.next_if_eq(&'*').is_some() {}
+                Token::AnyRun
+            }
+            '?' => Token::AnyChar,
+            '[' => {
+                let negated = chars.next_if(|&c| c == '!' || c == '^').is_some();
+                let mut ranges = Vec::new();
+                let mut closed = false;
+                // A `]` right after the opening bracket is a literal member
+                let mut first = true;
+                while let Some(start) = chars.next() {
+                    if start == ']' && !first {
+                        closed = true;
+                        break;
+                    }
+                    first = false;
+                    let end = match chars.peek() {
+                        Some('-') => {
+                            chars.next();
+                            match chars.next_if(|&c| c != ']') {
+                                Some(end) => end,
+                                None => {
+                                    // Trailing `-` is a literal member
+                                    ranges.push(('-', '-'));
+                                    start
+                                }
+                            }
+                        }
+                        _ => start,
+                    };
+                    if end < start {
+                        return Err(format!("invalid range '{}-{}'", start, end));
+                    }
+                    ranges.push((start, end));
+                }
+                if !closed {
+                    return Err("unclosed character class".into());
+                }
+                Token::Class { negated, ranges }
+            }
+            c => Token::Literal(c),
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+/// Matches components with the same backtracking as [`match_tokens`]: `**`
+/// plays the role of `*` and every other segment consumes one component, so
+/// only the most recent `**` needs to be retried and matching stays
+/// polynomial.
+fn match_segments(segments: &[Segment], components: &[&str]) -> bool {
+    let (mut seg, mut comp) = (0, 0);
+    // Segment after the last `**` and the component it was resumed from
+    let mut resume: Option<(usize, usize)> = None;
+    while comp < components.len() {
+        match segments.get(seg) {
+            Some(Segment::AnyComponents) => {
+                seg += 1;
+                resume = Some((seg, comp));
+                continue;
+            }
+            Some(Segment::Component(tokens)) if match_component(tokens, components[comp]) => {
+                seg += 1;
+                comp += 1;
+                continue;
+            }
+            _ => {}
+        }
+        match resume {
+            Some((star_seg, star_comp)) => {
+                seg = star_seg;
+                comp = star_comp + 1;
+                resume = Some((star_seg, comp));
+            }
+            None => return false,
+        }
+    }
+    segments[seg..]
+        .iter()
+        .all(|segment| matches!(segment, Segment::AnyComponents))
+}
+
+fn match_component(tokens: &[Token], component: &str) -> bool {
+    let chars: Vec<char> = component.chars().collect();
+    match_tokens(tokens, &chars)
+}
+
+/// Matches one component, retrying only from the most recent `*` on a
+/// mismatch. Earlier stars never need revisiting, because the most recent
+/// one can absorb anything they could, so this runs in O(tokens × chars).
+fn match_tokens(tokens: &[Token], chars: &[char]) -> bool {
+    let (mut tok, mut pos) = (0, 0);
+    // Token after the last `*` and the position it was resumed from
+    let mut resume: Option<(usize, usize)> = None;
+    while pos < chars.len() {
+        match tokens.get(tok) {
+            Some(Token::AnyRun) => {
+                tok += 1;
+                resume = Some((tok, pos));
+                continue;
+            }
+            Some(token) if token_matches(token, chars[pos]) => {
+                tok += 1;
+                pos += 1;
+                continue;
+            }
+            _ => {}
+        }
+        match resume {
+            Some((star_tok, star_pos)) => {
+                tok = star_tok;
+                pos = star_pos + 1;
+                resume = Some((star_tok, pos));
+            }
+            None => return false,
+        }
+    }
+    tokens[tok..]
+        .iter()
+        .all(|token| matches!(token, Token::AnyRun))
+}
+
+fn token_matches(token: &Token, c: char) -> bool {
+    match token {
+        Token::Literal(l) => *l == c,
+        Token::AnyChar => true,
+        Token::Class { negated, ranges } => {
+            ranges.iter().any(|&(lo, hi)| (lo..=hi).contains(&c)) != *negated
+        }
+        Token::AnyRun => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, path: &str) -> bool {
+        GlobPattern::new(pattern).unwrap().matches(path)
+    }
+
+    #[test]
+    fn test_star_is_anchored_to_one_component() {
+        assert!(matches("*.txt", "notes.txt"));
+        assert!(!matches("*.txt", "dir/notes.txt"));
+        assert!(!matches("*.txt", "notes.txt.bak"));
+        assert!(matches("dir/*", "dir/file"));
+        assert!(!matches("dir/*", "dir/sub/file"));
+        assert!(!matches("dir", "dir/file"));
+        assert!(!matches("file", "dir/file"));
+    }
+
+    #[test]
+    fn test_double_star_crosses_directories() {
+        assert!(matches("docs/**/*.md", "docs/readme.md"));
+        assert!(matches("docs/**/*.md", "docs/guide/intro.md"));
+        assert!(matches("docs/**/*.md", "docs/a/b/c/deep.md"));
+        assert!(!matches("docs/**/*.md", "src/docs/readme.md"));
+        assert!(!matches("docs/**/*.md", "docs/guide/intro.txt"));
+
+        assert!(matches("**/*.rs", "main.rs"));
+        assert!(matches("**/*.rs", "src/read/glob.rs"));
+        assert!(matches("src/**", "src"));
+        assert!(matches("src/**", "src/a/b"));
+        assert!(!matches("src/**", "srcx/a"));
+        assert!(matches("**", "anything/at/all"));
+    }
+
+    #[test]
+    fn test_double_star_inside_component_is_single_star() {
+        assert!(matches("a**b", "axyzb"));
+        assert!(!matches("a**b", "ax/yb"));
+    }
+
+    #[test]
+    fn test_question_mark_and_classes() {
+        assert!(matches("file?.txt", "file1.txt"));
+        assert!(!matches("file?.txt", "file10.txt"));
+        assert!(!matches("a?b", "a/b"));
+
+        assert!(matches("[a-c]*.log", "b-server.log"));
+        assert!(!matches("[a-c]*.log", "d-server.log"));
+        assert!(matches("[!a-c]*.log", "d-server.log"));
+        assert!(matches("[^a-c]*.log", "d-server.log"));
+        assert!(matches("v[0-9][0-9]", "v42"));
+        assert!(matches("[]]", "]"));
+        assert!(matches("[a-]", "-"));
+        assert!(matches("[*]", "*"));
+        assert!(!matches("[*]", "x"));
+    }
+
+    #[test]
+    fn test_pathological_patterns_match_quickly() {
+        let started = std::time::Instant::now();
+
+        let name = "a".repeat(60);
+        assert!(!matches(&format!("{}b", "a*".repeat(20)), &name));
+        assert!(matches(&format!("{}a", "*a".repeat(20)), &name));
+
+        let path = vec!["a"; 60].join("/");
+        assert!(!matches(&format!("{}b", "**/a/".repeat(20)), &path));
+        assert!(matches(&format!("{}a", "**/a/".repeat(20)), &path));
+        assert!(!matches(&format!("{}b", "*/**/".repeat(20)), &path));
+
+        // Exponential backtracking would take far longer than this
+        assert!(started.elapsed() < std::time::Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_star_backtracking() {
+        assert!(matches("*ab", "aab"));
+        assert!(matches("*a*b*c", "xaxbxbxc"));
+        assert!(!matches("*a*b*c", "xaxbxbx"));
+        assert!(matches("a*", "a"));
+        assert!(matches("**/x/**/y", "x/a/x/y"));
+        assert!(matches("**/x/y", "x/x/y"));
+        assert!(!matches("**/x/y", "x/y/z"));
+        assert!(matches("a/**/**/b", "a/b"));
+        assert!(matches("**", ""));
+    }
+
+    #[test]
+    fn test_invalid_patterns() {
+        let err = GlobPattern::new("[abc").unwrap_err();
+        assert!(
+            matches!(&err, Error::InvalidGlob { pattern, reason }
+                if pattern == "[abc" && reason.contains("unclosed")),
+            "{err:?}"
+        );
+        assert!(GlobPattern::new("[z-a]").is_err());
+    }
+}
@@ -0,0 +1,327 @@
+//! Caching of parsed archive headers.
+//!
+//! This module provides [`HeaderCache`], which lets a process that opens the
+//! same archives over and over skip reading and parsing their headers.
+
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::format::SIGNATURE_HEADER_SIZE;
+use crate::format::header::detect_sfx;
+use crate::format::parser::ArchiveHeader;
+use crate::s3fifo::S3FifoCache;
+use crate::{Error, Result};
+
+use super::{Archive, ArchiveInfo, Entry};
+
+/// Identifies an archive source by its size and signature header.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    /// Total size of the source in bytes.
+    size: u64,
+    /// Offset of the 7z signature (non-zero for SFX archives).
+    sfx_offset: u64,
+    /// The signature header, which records the position, size and CRC of
+    /// the archive header.
+    start_header: [u8; SIGNATURE_HEADER_SIZE as usize],
+}
+
+impl CacheKey {
+    /// Reads the key of the archive in `reader`.
+    fn read<R: Read + Seek>(reader: &mut R) -> Result<Self> {
+        let size = reader.seek(SeekFrom::End(0)).map_err(Error::Io)?;
+        let sfx_offset = detect_sfx(reader)?.map_or(0, |sfx| sfx.archive_offset);
+
+        let mut start_header = [0u8; SIGNATURE_HEADER_SIZE as usize];
+        reader
+            .seek(SeekFrom::Start(sfx_offset))
+            .map_err(Error::Io)?;
+        reader.read_exact(&mut start_header).map_err(Error::Io)?;
+
+        Ok(Self {
+            size,
+            sfx_offset,
+            start_header,
+        })
+    }
+}
+
+/// A parsed header with the entries and info built from it.
+#[derive(Debug)]
+struct CachedHeader {
+    header: ArchiveHeader,
+    entries: Vec<Entry>,
+    info: ArchiveInfo,
+}
+
+/// A bounded in-memory cache of parsed archive headers.
+///
+/// Used with [`Archive::open_cached`], which only reads the signature header
+/// of an archive whose header is already cached. Servers that repeatedly
+/// open the same set of archives avoid reading, decompressing and parsing
+/// their headers on every open.
+///
+/// # Invalidation
+///
+/// An archive is identified by the total size of its source and by its
+/// 32-byte signature header, which records the position, size and CRC of the
+/// archive header. The cache does not look at paths, file identities or
+/// modification times: a source of the same size whose signature header is
+/// unchanged is assumed to hold the same header. Rewriting an archive in
+/// place changes its header CRC, so a stale entry is only served if the new
+/// header collides with the old one in both size and CRC. Call
+/// [`clear`](Self::clear) if that risk is unacceptable after archives change.
+///
+/// Only headers opened without a password and with the default resource
+/// limits are cached, so archives with encrypted headers are never cached.
+///
+/// When full, the cache evicts headers with the same S3-FIFO policy as the
+/// other caches of this crate.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use zesven::read::{Archive, HeaderCache};
+///
+/// let cache = HeaderCache::new(64);
+///
+/// // The first open parses the header and caches it
+/// let archive = Archive::open_path_cached("archive.7z", &cache)?;
+///
+/// // Later opens of the same archive reuse the parsed header
+/// let archive = Archive::open_path_cached("archive.7z", &cache)?;
+/// ```
+pub struct HeaderCache {
+    cache: Mutex<S3FifoCache<CacheKey, Arc<CachedHeader>>>,
+}
+
+impl HeaderCache {
+    /// Creates a header cache holding up to `capacity` archive headers.
+    pub fn new(capacity: usize) -> Self {
+        let cap = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            cache: Mutex::new(S3FifoCache::new(cap)),
+        }
+    }
+
+    /// Clears all cached headers.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Returns the current number of cached headers.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns true if the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    fn get(&self, key: &CacheKey) -> Option<Arc<CachedHeader>> {
+        self.lock().get(key).cloned()
+    }
+
+    fn insert(&self, key: CacheKey, header: CachedHeader) {
+        self.lock().insert(key, Arc::new(header));
+    }
+
+    /// Locks the cache. Cached headers are never left half-written, so a
+    /// poisoned lock is safe to recover.
+    fn lock(&self) -> MutexGuard<'_, S3FifoCache<CacheKey, Arc<CachedHeader>>> {
+        self.cache.lock().unwrap_or_else(|poisoned| {
+            log::warn!("HeaderCache mutex was poisoned, recovering");
+            poisoned.into_inner()
+        })
+    }
+}
+
+impl std::fmt::Debug for HeaderCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HeaderCache")
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R: Read + Seek> Archive<R> {
+    /// Opens an archive from a reader, reusing its header from `cache`.
+    ///
+    /// If `cache` holds the header of this archive, only the signature
+    /// header is read and the parsed header is taken from the cache.
+    /// Otherwise the archive is opened like with [`open`](Self::open) and
+    /// its header is added to the cache. See [`HeaderCache`] for how
+    /// archives are identified.
+    ///
+    /// Like [`open`](Self::open), this has no file handle to copy stored
+    /// entries in-kernel from; use
+    /// [`open_path_cached`](Archive::open_path_cached) for archive files.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the archive is invalid or cannot be read.
+    pub fn open_cached(mut reader: R, cache: &HeaderCache) -> Result<Self> {
+        let key = CacheKey::read(&mut reader)?;
+
+        let Some(cached) = cache.get(&key) else {
+            reader.seek(SeekFrom::Start(0)).map_err(Error::Io)?;
+            let archive = Self::open(reader)?;
+            cache.insert(
+                key,
+                CachedHeader {
+                    header: archive.header.clone(),
+                    entries: archive.entries.clone(),
+                    info: archive.info.clone(),
+                },
+            );
+            return Ok(archive);
+        };
+
+        Ok(Self {
+            reader,
+            header: cached.header.clone(),
+            entries: cached.entries.clone(),
+            info: cached.info.clone(),
+            #[cfg(feature = "aes")]
+            password: None,
+            volume_info: None,
+            sfx_offset: key.sfx_offset,
+            read_ahead: None,
+            #[cfg(feature = "zstd")]
+            zstd_dictionary: None,
+            #[cfg(target_os = "linux")]
+            source_file: None,
+        })
+    }
+}
+
+impl Archive<BufReader<File>> {
+    /// Opens an archive file, reusing its header from `cache`.
+    ///
+    /// Works like [`open_cached`](Archive::open_cached), and like
+    /// [`open_path`](Self::open_path) keeps the file handle, so stored
+    /// entries are still copied in-kernel on Linux when the header comes
+    /// from the cache. Multi-volume archives are not detected.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened or the archive is
+    /// invalid.
+    pub fn open_path_cached(path: impl AsRef<Path>, cache: &HeaderCache) -> Result<Self> {
+        let file = File::open(path.as_ref()).map_err(Error::Io)?;
+        let source_file = file.try_clone().ok();
+        Self::open_cached(BufReader::new(file), cache)
+            .map(|archive| archive.with_source_file(source_file))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ArchivePath, Writer};
+    use std::io::Cursor;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Reader that counts the bytes read through it.
+    struct CountingReader {
+        inner: Cursor<Vec<u8>>,
+        bytes_read: Arc<AtomicUsize>,
+    }
+
+    impl Read for CountingReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let n = self.inner.read(buf)?;
+            self.bytes_read.fetch_add(n, Ordering::Relaxed);
+            Ok(n)
+        }
+    }
+
+    impl Seek for CountingReader {
+        fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+            self.inner.seek(pos)
+        }
+    }
+
+    fn create_archive(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut writer = Writer::create(Cursor::new(Vec::new())).unwrap();
+        for (path, data) in files {
+            writer
+                .add_bytes(ArchivePath::new(path).unwrap(), data)
+                .unwrap();
+        }
+        let (_, cursor) = writer.finish_into_inner().unwrap();
+        cursor.into_inner()
+    }
+
+    /// Opens `data` through `cache`, returning the archive and the number of
+    /// bytes read while opening it.
+    fn open_counted(data: &[u8], cache: &HeaderCache) -> (Archive<CountingReader>, usize) {
+        let bytes_read = Arc::new(AtomicUsize::new(0));
+        let reader = CountingReader {
+            inner: Cursor::new(data.to_vec()),
+            bytes_read: Arc::clone(&bytes_read),
+        };
+        let archive = Archive::open_cached(reader, cache).unwrap();
+        (archive, bytes_read.load(Ordering::Relaxed))
+    }
+
+    #[test]
+    fn test_open_cached_skips_header_parsing() {
+        let files: Vec<(String, Vec<u8>)> = (0..200)
+            .map(|i| {
+                (
+                    format!("dir/file{i:03}.txt"),
+                    format!("content {i}").into_bytes(),
+                )
+            })
+            .collect();
+        let files: Vec<(&str, &[u8])> = files
+            .iter()
+            .map(|(path, data)| (path.as_str(), data.as_slice()))
+            .collect();
+        let data = create_archive(&files);
+        let cache = HeaderCache::new(4);
+
+        let (_, first_read) = open_counted(&data, &cache);
+        assert_eq!(cache.len(), 1);
+
+        let (mut archive, second_read) = open_counted(&data, &cache);
+        assert_eq!(cache.len(), 1);
+        assert!(
+            second_read < first_read,
+            "cached open read {} bytes, uncached {}",
+            second_read,
+            first_read
+        );
+        assert_eq!(archive.len(), 200);
+        assert_eq!(
+            archive.extract_to_vec("dir/file123.txt").unwrap(),
+            b"content 123"
+        );
+
+        // A different archive is not served the cached header
+        let other = create_archive(&[("other.txt", b"other")]);
+        let (archive, _) = open_counted(&other, &cache);
+        assert_eq!(archive.len(), 1);
+    }
+
+    #[test]
+    fn test_open_path_cached_keeps_source_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("archive.7z");
+        std::fs::write(&path, create_archive(&[("a.txt", b"cached")])).unwrap();
+        let cache = HeaderCache::new(4);
+
+        for _ in 0..2 {
+            let mut archive = Archive::open_path_cached(&path, &cache).unwrap();
+            #[cfg(target_os = "linux")]
+            assert!(archive.source_file.is_some());
+            assert_eq!(archive.extract_to_vec("a.txt").unwrap(), b"cached");
+        }
+        assert_eq!(cache.len(), 1);
+    }
+}
@@ -1,6 +1,6 @@
 //! Archive information types.
 
-use crate::codec::CodecMethod;
+use crate::codec::{CodecMethod, FilterMethod};
 
 /// Encryption parameters for diagnostic purposes.
 ///
@@ -19,6 +19,8 @@ pub struct EncryptionInfo {
     pub salt_size: usize,
     /// Initialization vector size in bytes (0-16, padded to 16).
     pub iv_size: usize,
+    /// Whether the archive header (entry names and metadata) is encrypted.
+    pub header_encrypted: bool,
 }
 
 impl EncryptionInfo {
@@ -29,10 +31,73 @@ impl EncryptionInfo {
             num_cycles_power,
             salt_size,
             iv_size,
+            header_encrypted: false,
         }
     }
 }
 
+/// Methods an archive needs for extraction.
+///
+/// Returned by [`Archive::required_capabilities`](super::Archive::required_capabilities).
+/// Each method is listed once, in order of first use across folders.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CapabilitySet {
+    /// Compression codecs used by any folder.
+    pub codecs: Vec<CodecMethod>,
+    /// Filters used by any folder.
+    pub filters: Vec<FilterMethod>,
+    /// Whether any folder or the header is AES-encrypted.
+    pub aes: bool,
+    /// IDs of methods that are neither a [`CodecMethod`], a
+    /// [`FilterMethod`], nor AES, such as LZ5 or Lizard.
+    pub other_methods: Vec<Vec<u8>>,
+}
+
+impl CapabilitySet {
+    /// Returns true if the archive uses the given codec.
+    pub fn has_codec(&self, codec: CodecMethod) -> bool {
+        self.codecs.contains(&codec)
+    }
+
+    /// Returns true if the archive uses the given filter.
+    pub fn has_filter(&self, filter: FilterMethod) -> bool {
+        self.filters.contains(&filter)
+    }
+}
+
+/// Compact description of one folder (solid block).
+///
+/// Returned by [`Archive::folder_summaries`](super::Archive::folder_summaries).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FolderSummary {
+    /// Coder method names in decoding order, joined with `" → "`, for
+    /// example `"BCJ (x86) → LZMA2 → AES-256"`.
+    pub methods: String,
+    /// Total size of the folder's packed streams.
+    pub packed_size: u64,
+    /// Size of the folder's decoded output.
+    pub unpacked_size: u64,
+    /// Number of entries stored in the folder.
+    pub entry_count: usize,
+    /// Whether the folder is AES-encrypted.
+    pub encrypted: bool,
+}
+
+/// Per-codec totals over the folders of an archive.
+///
+/// Returned by [`Archive::codec_breakdown`](super::Archive::codec_breakdown).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CodecStat {
+    /// Name of the compression method, for example `"LZMA2"` or `"Copy"`.
+    pub method: String,
+    /// Total size of the packed streams of the folders using this method.
+    pub packed_size: u64,
+    /// Total decoded size of the folders using this method.
+    pub unpacked_size: u64,
+    /// Number of folders using this method.
+    pub folder_count: usize,
+}
+
 /// Information about an opened archive.
 #[derive(Debug, Clone, Default)]
 pub struct ArchiveInfo {
@@ -50,6 +115,8 @@ pub struct ArchiveInfo {
     pub has_encrypted_header: bool,
     /// Compression methods used in the archive.
     pub compression_methods: Vec<CodecMethod>,
+    /// Filters used in the archive.
+    pub filter_methods: Vec<FilterMethod>,
     /// Number of folders (compression blocks).
     pub folder_count: usize,
     /// Archive comment (if any).
@@ -84,6 +151,27 @@ impl ArchiveInfo {
     pub fn comment(&self) -> Option<&str> {
         self.comment.as_deref()
     }
+
+    /// Returns the compression codecs used by the archive's folders.
+    ///
+    /// Each codec is listed once, in order of first use. All coders of a
+    /// folder are included, so a BCJ2 folder reports the codecs of each of
+    /// its streams.
+    pub fn methods(&self) -> Vec<CodecMethod> {
+        self.compression_methods.clone()
+    }
+
+    /// Returns the filters used by the archive's folders.
+    ///
+    /// Each filter is listed once, in order of first use.
+    pub fn filters(&self) -> Vec<FilterMethod> {
+        self.filter_methods.clone()
+    }
+
+    /// Returns true if the archive's entries or header are AES-encrypted.
+    pub fn is_encrypted(&self) -> bool {
+        self.has_encrypted_entries || self.has_encrypted_header
+    }
 }
 
 /// Result of testing an archive for integrity.
@@ -101,6 +189,10 @@ pub struct TestResult {
     pub entries_failed: usize,
     /// Detailed failures (entry path and error message).
     pub failures: Vec<(String, String)>,
+    /// Per-entry status, in the order the entries were tested.
+    ///
+    /// Filled by [`Archive::test`](super::Archive::test).
+    pub entries: Vec<EntryTestStatus>,
 }
 
 impl TestResult {
@@ -115,10 +207,118 @@ impl TestResult {
     }
 }
 
+/// Integrity status of one tested entry.
+///
+/// Part of [`TestResult::entries`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryTestStatus {
+    /// Index of the entry in the archive.
+    pub index: usize,
+    /// Path of the entry.
+    pub path: String,
+    /// CRC stored in the archive, if any.
+    pub expected_crc: Option<u32>,
+    /// CRC of the decoded data, or `None` for directories and entries that
+    /// could not be decoded.
+    pub actual_crc: Option<u32>,
+    /// Whether the entry decoded and its CRC matched.
+    pub passed: bool,
+}
+
+/// A structural problem found by [`Archive::validate_structure`].
+///
+/// [`Archive::validate_structure`]: crate::read::Archive::validate_structure
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StructureIssue {
+    /// Folder the issue belongs to (`None` for archive-level issues).
+    pub folder_index: Option<usize>,
+    /// Description of the issue.
+    pub message: String,
+}
+
+/// Result of validating an archive's structure without decompressing data.
+#[must_use = "structure reports should be checked for issues"]
+#[derive(Debug, Clone, Default)]
+pub struct StructureReport {
+    /// Number of folders whose coder graph was checked.
+    pub folders_checked: usize,
+    /// Number of pack streams whose bounds were checked.
+    pub pack_streams_checked: usize,
+    /// Number of pack stream CRCs that were verified.
+    pub pack_crcs_checked: usize,
+    /// Issues found, in the order they were detected.
+    pub issues: Vec<StructureIssue>,
+}
+
+impl StructureReport {
+    /// Returns true if no issues were found.
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Records an issue.
+    pub(crate) fn push(&mut self, folder_index: Option<usize>, message: impl Into<String>) {
+        self.issues.push(StructureIssue {
+            folder_index,
+            message: message.into(),
+        });
+    }
+}
+
+/// Size shortfall of a truncated archive, from [`Archive::check_truncation`].
+///
+/// All sizes are measured from the start of the underlying stream, so they
+/// include any SFX stub.
+///
+/// [`Archive::check_truncation`]: crate::read::Archive::check_truncation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TruncationInfo {
+    /// Length the stream needs to hold all pack streams and the header.
+    pub expected_size: u64,
+    /// Actual length of the stream.
+    pub actual_size: u64,
+    /// Number of bytes missing from the end of the stream.
+    pub missing_bytes: u64,
+}
+
+/// Outcome of extracting a single entry.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EntryOutcome {
+    /// Path of the entry within the archive.
+    pub path: String,
+    /// Number of bytes written to the destination.
+    pub bytes_written: u64,
+    /// Whether the written data was checked against the entry's stored CRC.
+    pub crc_verified: bool,
+    /// Whether the entry was skipped instead of extracted.
+    pub skipped: bool,
+    /// Whether an existing file at the destination was overwritten.
+    pub overwritten: bool,
+    /// Error message if the entry could not be extracted.
+    pub error: Option<String>,
+}
+
+impl EntryOutcome {
+    /// Creates an outcome for the entry at `path` with nothing written yet.
+    pub(crate) fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns this outcome with the given error recorded.
+    pub(crate) fn with_error(mut self, error: impl ToString) -> Self {
+        self.error = Some(error.to_string());
+        self
+    }
+}
+
 /// Result of extracting entries from an archive.
 ///
 /// This struct contains information about how many entries were extracted,
-/// skipped, or failed, along with details about any failures.
+/// skipped, or failed, along with details about any failures. The counts
+/// summarize [`ExtractResult::entries`], which lists each entry's outcome.
 #[must_use = "extraction results should be checked for warnings or partial failures"]
 #[derive(Debug, Clone, Default)]
 pub struct ExtractResult {
@@ -132,9 +332,55 @@ pub struct ExtractResult {
     pub bytes_extracted: u64,
     /// Detailed failures (entry path and error message).
     pub failures: Vec<(String, String)>,
+    /// Problems that did not stop an entry from being extracted, such as
+    /// ownership that could not be restored (entry path and message).
+    pub warnings: Vec<(String, String)>,
+    /// Per-entry outcomes, in extraction order.
+    pub entries: Vec<EntryOutcome>,
+    /// Merkle root over the extracted entries.
+    ///
+    /// Set by [`Archive::extract`](super::Archive::extract) when
+    /// [`ExtractOptions::compute_merkle`](super::ExtractOptions::compute_merkle)
+    /// is enabled. All hashes are SHA-256. Each extracted file or symlink
+    /// contributes one leaf:
+    ///
+    /// ```text
+    /// leaf = SHA-256(0x00 || len(path) as u64 LE || path || content)
+    /// ```
+    ///
+    /// where `path` is where the entry was written, relative to the
+    /// destination in UTF-8 with `/` separators (the archive path unless a
+    /// conflict handler renamed it), and `content` is the decoded data as it
+    /// was written, or the link target for symlinks. Directories, skipped
+    /// entries and failed entries contribute no leaf.
+    ///
+    /// Leaves are sorted by path in byte order, so the root does not depend
+    /// on the order of entries in the archive. Adjacent pairs are combined
+    /// level by level as `SHA-256(0x01 || left || right)`, and an odd node at
+    /// the end of a level is carried up unchanged. A single leaf is its own
+    /// root, and the root of no leaves is `SHA-256("")`.
+    #[cfg(feature = "merkle")]
+    pub merkle_root: Option<[u8; 32]>,
 }
 
 impl ExtractResult {
+    /// Records an entry outcome and updates the aggregate counts.
+    ///
+    /// Skipped entries count as skipped even when they carry an error;
+    /// otherwise an error counts the entry as failed.
+    pub(crate) fn record(&mut self, outcome: EntryOutcome) {
+        if outcome.skipped {
+            self.entries_skipped += 1;
+        } else if let Some(error) = &outcome.error {
+            self.entries_failed += 1;
+            self.failures.push((outcome.path.clone(), error.clone()));
+        } else {
+            self.entries_extracted += 1;
+            self.bytes_extracted += outcome.bytes_written;
+        }
+        self.entries.push(outcome);
+    }
+
     /// Returns true if all selected entries were extracted successfully.
     pub fn is_ok(&self) -> bool {
         self.entries_failed == 0
@@ -168,6 +414,31 @@ mod tests {
         assert!((info.space_savings() - 0.0).abs() < 0.001);
     }
 
+    #[test]
+    fn test_extract_result_record() {
+        let mut result = ExtractResult::default();
+        result.record(EntryOutcome {
+            bytes_written: 10,
+            crc_verified: true,
+            ..EntryOutcome::new("a.txt")
+        });
+        result.record(EntryOutcome {
+            skipped: true,
+            ..EntryOutcome::new("b.txt")
+        });
+        result.record(EntryOutcome::new("c.txt").with_error("boom"));
+
+        assert_eq!(result.entries_extracted, 1);
+        assert_eq!(result.entries_skipped, 1);
+        assert_eq!(result.entries_failed, 1);
+        assert_eq!(result.bytes_extracted, 10);
+        assert_eq!(
+            result.failures,
+            vec![("c.txt".to_string(), "boom".to_string())]
+        );
+        assert_eq!(result.entries.len(), 3);
+    }
+
     #[test]
     fn test_test_result() {
         let mut result = TestResult::default();
@@ -0,0 +1,142 @@
+//! Merkle roots over extracted entries.
+//!
+//! The scheme is documented on
+//! [`ExtractResult::merkle_root`](super::ExtractResult::merkle_root).
+
+use sha2::{Digest, Sha256};
+
+/// Prefix for leaf hashes.
+const LEAF_PREFIX: u8 = 0x00;
+/// Prefix for inner node hashes.
+const NODE_PREFIX: u8 = 0x01;
+
+/// Collects entry hashes and computes their Merkle root.
+#[derive(Debug, Default)]
+pub(crate) struct MerkleBuilder {
+    leaves: Vec<(String, [u8; 32])>,
+}
+
+impl MerkleBuilder {
+    /// Adds a leaf whose content was fed to it as it was written.
+    pub fn add_leaf(&mut self, leaf: Leaf) {
+        self.leaves.push((leaf.path, leaf.hasher.finalize().into()));
+    }
+
+    /// Returns the Merkle root over all added leaves.
+    pub fn root(mut self) -> [u8; 32] {
+        self.leaves.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut level: Vec<[u8; 32]> = self.leaves.into_iter().map(|(_, hash)| hash).collect();
+        if level.is_empty() {
+            return Sha256::digest([]).into();
+        }
+
+        while level.len() > 1 {
+            level = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => {
+                        let mut hasher = Sha256::new();
+                        hasher.update([NODE_PREFIX]);
+                        hasher.update(left);
+                        hasher.update(right);
+                        hasher.finalize().into()
+                    }
+                    [single] => *single,
+                    _ => unreachable!("chunks(2) yields one or two items"),
+                })
+                .collect();
+        }
+        level[0]
+    }
+
+    fn leaf_hasher(path: &str) -> Sha256 {
+        let mut hasher = Sha256::new();
+        hasher.update([LEAF_PREFIX]);
+        hasher.update((path.len() as u64).to_le_bytes());
+        hasher.update(path.as_bytes());
+        hasher
+    }
+}
+
+/// The leaf of one entry, hashed while its content is written.
+pub(crate) struct Leaf {
+    path: String,
+    hasher: Sha256,
+}
+
+impl Leaf {
+    /// Starts the leaf of the entry written to `path`.
+    pub fn new(path: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            hasher: MerkleBuilder::leaf_hasher(path),
+        }
+    }
+
+    /// Adds content written for the entry.
+    pub fn update(&mut self, data: &[u8]) {
+        self.hasher.update(data);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaf(path: &str, content: &[u8]) -> [u8; 32] {
+        let mut hasher = MerkleBuilder::leaf_hasher(path);
+        hasher.update(content);
+        hasher.finalize().into()
+    }
+
+    fn content_leaf(path: &str, content: &[u8]) -> Leaf {
+        let mut leaf = Leaf::new(path);
+        leaf.update(content);
+        leaf
+    }
+
+    fn node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update([NODE_PREFIX]);
+        hasher.update(left);
+        hasher.update(right);
+        hasher.finalize().into()
+    }
+
+    #[test]
+    fn test_merkle_root_scheme() {
+        let mut builder = MerkleBuilder::default();
+        builder.add_leaf(content_leaf("c.txt", b"three"));
+        builder.add_leaf(content_leaf("a.txt", b"one"));
+        builder.add_leaf(content_leaf("b.txt", b"two"));
+
+        let a = leaf("a.txt", b"one");
+        let b = leaf("b.txt", b"two");
+        let c = leaf("c.txt", b"three");
+        assert_eq!(builder.root(), node(&node(&a, &b), &c));
+    }
+
+    #[test]
+    fn test_merkle_root_edge_cases() {
+        let empty: [u8; 32] = Sha256::digest([]).into();
+        assert_eq!(MerkleBuilder::default().root(), empty);
+
+        let mut builder = MerkleBuilder::default();
+        builder.add_leaf(content_leaf("only", b"data"));
+        assert_eq!(builder.root(), leaf("only", b"data"));
+
+        // Content fed in pieces hashes like content read at once
+        let mut builder = MerkleBuilder::default();
+        let mut streamed = Leaf::new("only");
+        streamed.update(b"da");
+        streamed.update(b"ta");
+        builder.add_leaf(streamed);
+        assert_eq!(builder.root(), leaf("only", b"data"));
+    }
+
+    #[test]
+    fn test_merkle_leaf_binds_path_to_content() {
+        // Moving bytes between path and content must change the leaf
+        assert_ne!(leaf("ab", b"c"), leaf("a", b"bc"));
+    }
+}
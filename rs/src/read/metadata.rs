@@ -7,6 +7,7 @@ use std::fs::File;
 use std::io::Read;
 use std::path::Path;
 
+use crate::ownership::UnixOwnership;
 use crate::{Error, READ_BUFFER_SIZE, Result};
 
 use super::PreserveMetadata;
@@ -14,12 +15,15 @@ use super::PreserveMetadata;
 /// Applies metadata to an extracted file based on options.
 ///
 /// This sets file timestamps and attributes as configured in `PreserveMetadata`.
+/// BSD file flags count as attributes and are set last, since flags such as
+/// `uchg` block any further change to the file.
 pub(crate) fn apply_metadata(
     path: &Path,
     options: &PreserveMetadata,
     modification_time: Option<u64>,
     creation_time: Option<u64>,
     attributes: Option<u32>,
+    bsd_flags: Option<u32>,
 ) {
     use filetime::FileTime;
 
@@ -57,15 +61,9 @@ pub(crate) fn apply_metadata(
     #[cfg(any(windows, target_os = "macos"))]
     if options.creation_time {
         if let Some(ctime) = creation_time.and_then(filetime_to_unix) {
-            // filetime crate doesn't support setting creation time directly,
-            // we'd need platform-specific code. For now, log a warning.
-            // On Windows, this would use SetFileTime.
-            // On macOS, this would use setattrlist.
-            log::debug!(
-                "Creation time preservation requested but not yet implemented for '{}': {:?}",
-                path.display(),
-                ctime
-            );
+            if let Err(e) = set_creation_time(path, ctime) {
+                log::warn!("Failed to set creation time on '{}': {}", path.display(), e);
+            }
         }
     }
 
@@ -77,9 +75,43 @@ pub(crate) fn apply_metadata(
         if let Some(attrs) = attributes {
             apply_file_attributes(path, attrs);
         }
+        if let Some(flags) = bsd_flags {
+            if let Err(e) = crate::bsd_flags::apply_flags(path, flags) {
+                log::warn!("Failed to set file flags on '{}': {}", path.display(), e);
+            }
+        }
     }
 }
 
+/// Restores the owner and group of an extracted file.
+///
+/// Returns a warning message if ownership could not be changed, typically
+/// because the process lacks the privilege to give files away.
+pub(crate) fn apply_ownership(path: &Path, ownership: &UnixOwnership) -> Option<String> {
+    let e = ownership.apply_to_path(path).err()?;
+    log::warn!("Failed to set ownership on '{}': {}", path.display(), e);
+    Some(format!("failed to restore ownership: {}", e))
+}
+
+/// Sets the creation time of a file.
+///
+/// Windows stores a settable creation time (applied with `SetFileTime`);
+/// on macOS this is the file's birth time.
+#[cfg(any(windows, target_os = "macos"))]
+fn set_creation_time(path: &Path, ctime: filetime::FileTime) -> std::io::Result<()> {
+    use std::fs::{FileTimes, OpenOptions};
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[cfg(target_os = "macos")]
+    use std::os::macos::fs::FileTimesExt;
+    #[cfg(windows)]
+    use std::os::windows::fs::FileTimesExt;
+
+    let created = UNIX_EPOCH + Duration::new(ctime.unix_seconds() as u64, ctime.nanoseconds());
+    let file = OpenOptions::new().write(true).open(path)?;
+    file.set_times(FileTimes::new().set_created(created))
+}
+
 /// Applies file attributes to an extracted file.
 #[cfg(unix)]
 pub(crate) fn apply_file_attributes(path: &Path, attrs: u32) {
@@ -24,6 +24,8 @@
 mod destination;
 pub(crate) mod entries;
 mod entry;
+#[cfg(feature = "lzma")]
+mod entry_reader;
 mod info;
 mod options;
 
@@ -33,28 +35,46 @@ mod archive_query;
 mod archive_test;
 mod decompression;
 mod extraction;
+#[cfg(feature = "merkle")]
+mod fingerprint;
+mod glob;
+mod header_cache;
+#[cfg(feature = "merkle")]
+mod merkle;
 mod metadata;
 mod multivolume;
 mod path_safety;
+mod read_ahead;
 mod solid_blocks;
+mod vfs;
 
 // Re-exports from core modules
 pub use destination::{
-    ExtractDestination, FilesystemDestination, MemoryDestination, NullDestination,
+    AlignedBuffer, ExtractDestination, FilesystemDestination, MemoryDestination, NullDestination,
 };
 #[cfg(feature = "regex")]
 pub use entry::SelectByRegex;
 pub use entry::{
-    Entry, EntrySelector, SelectAll, SelectByName, SelectByPredicate, SelectFilesOnly,
+    Entry, EntrySelector, SelectAll, SelectByGlob, SelectByName, SelectByPredicate, SelectFilesOnly,
+};
+#[cfg(feature = "lzma")]
+pub use entry_reader::SeekableEntryReader;
+pub use header_cache::HeaderCache;
+pub use info::{
+    ArchiveInfo, CapabilitySet, CodecStat, EncryptionInfo, EntryOutcome, EntryTestStatus,
+    ExtractResult, FolderSummary, StructureIssue, StructureReport, TestResult, TruncationInfo,
 };
-pub use info::{ArchiveInfo, EncryptionInfo, ExtractResult, TestResult};
 pub use options::{
-    ExtractOptions, FilterPolicy, LinkPolicy, OverwritePolicy, PathSafety, PreserveMetadata,
-    TestOptions, Threads,
+    BackslashPolicy, CaseCollisionPolicy, ConflictHandler, ExtractOptions, FilterPolicy,
+    LinkPolicy, OverwriteDecision, OverwritePolicy, PathSafety, PreserveMetadata, TestOptions,
+    Threads,
 };
+pub use read_ahead::BufferedSeekReader;
+pub use vfs::{InMemoryVfs, VfsDestination, VfsEntry, VfsNode};
 
 // Re-exports from refactored modules
 pub(crate) use archive_open::{ExtractionLimits, map_io_error};
+pub(crate) use path_safety::extraction_path;
 
 use std::path::PathBuf;
 
@@ -80,10 +100,19 @@ pub struct Archive<R> {
     /// Password for encrypted extraction (used by extraction methods).
     #[cfg(feature = "aes")]
     pub(crate) password: Option<Password>,
+    /// Dictionary for ZSTD folders compressed with one.
+    #[cfg(feature = "zstd")]
+    pub(crate) zstd_dictionary: Option<crate::codec::ZstdDictionary>,
     /// Volume information for multi-volume archives.
     pub(crate) volume_info: Option<VolumeInfo>,
     /// Offset to the 7z signature (non-zero for SFX archives).
     pub(crate) sfx_offset: u64,
+    /// Read-ahead buffer for packed data, installed during extraction.
+    pub(crate) read_ahead: Option<read_ahead::ReadAheadBuffer>,
+    /// Duplicate handle of the archive file, used for in-kernel copies of
+    /// stored entries (only set for archives opened from a path).
+    #[cfg(target_os = "linux")]
+    pub(crate) source_file: Option<std::fs::File>,
 }
 
 #[cfg(test)]
@@ -83,6 +83,11 @@ impl Archive<MultiVolumeReader> {
                 paths: volume_paths,
             }),
             sfx_offset: 0, // Multi-volume archives don't have SFX stubs
+            read_ahead: None,
+            #[cfg(feature = "zstd")]
+            zstd_dictionary: None,
+            #[cfg(target_os = "linux")]
+            source_file: None,
         })
     }
 }
@@ -128,5 +133,10 @@ pub(crate) fn open_multivolume_as_single(base_path: &Path) -> Result<Archive<Buf
             paths: volume_paths,
         }),
         sfx_offset: 0, // Multi-volume archives don't have SFX stubs
+        read_ahead: None,
+        #[cfg(feature = "zstd")]
+        zstd_dictionary: None,
+        #[cfg(target_os = "linux")]
+        source_file: None,
     })
 }
@@ -1,5 +1,10 @@
 //! Extraction and test options for archive operations.
 
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use super::Entry;
 use crate::format::streams::ResourceLimits;
 use crate::progress::ProgressReporter;
 
@@ -21,16 +26,93 @@ pub enum OverwritePolicy {
     Overwrite,
 }
 
+/// Decision returned by an [`ExtractOptions::on_conflict`] callback.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OverwriteDecision {
+    /// Replace the existing file.
+    Overwrite,
+    /// Leave the existing file untouched and skip the entry.
+    Skip,
+    /// Write the entry to another path instead.
+    ///
+    /// The path is resolved against the extraction directory and must stay
+    /// inside it: absolute paths and `..` components fail the entry with
+    /// [`Error::PathTraversal`](crate::Error::PathTraversal) unless
+    /// [`PathSafety::Disabled`] is set.
+    Rename(PathBuf),
+}
+
+/// Callback deciding what to do when an extracted file already exists.
+///
+/// Receives the archive entry and the path of the existing file.
+pub type ConflictHandler = dyn FnMut(&Entry, &Path) -> OverwriteDecision + Send;
+
 /// Policy for handling symbolic links.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum LinkPolicy {
     /// Forbid symbolic links (safest).
+    ///
+    /// Symlink entries, including entries with the Windows reparse point
+    /// attribute, are skipped: nothing is written for them and they are
+    /// counted in [`ExtractResult::entries_skipped`](super::ExtractResult::entries_skipped).
     #[default]
     Forbid,
     /// Allow symbolic links but validate their targets.
     ValidateTargets,
     /// Allow all symbolic links (use with caution).
     Allow,
+    /// Replace symbolic links with a copy of the entry they point to.
+    ///
+    /// The target must be a file in the archive that stays within the
+    /// extraction directory. Chains of links are followed. The copy is
+    /// written like any other file, with the target's metadata and under
+    /// the same overwrite policy and limits. [`Allow`](Self::Allow) and
+    /// [`ValidateTargets`](Self::ValidateTargets) recreate links as
+    /// symlinks instead.
+    Follow,
+}
+
+/// Policy for entries whose paths differ only in case.
+///
+/// On case-insensitive filesystems such entries would be written to the
+/// same file. Paths are compared against those already extracted in the
+/// same [`Archive::extract`](super::Archive::extract) call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CaseCollisionPolicy {
+    /// Fail the later entry.
+    Error,
+    /// Extract the later entry under a numbered name, such as `foo (1).txt`.
+    Rename,
+    /// Extract the later entry to its own path.
+    ///
+    /// On a case-insensitive filesystem it replaces the earlier file, subject
+    /// to the [`OverwritePolicy`].
+    #[default]
+    Overwrite,
+}
+
+/// Policy for interpreting backslashes in entry names during extraction.
+///
+/// Entry names are read from the archive verbatim, so a name such as
+/// `dir\file.txt` is reported unchanged by [`Entry::path`](super::Entry::path).
+/// This policy only decides how such names map onto the filesystem.
+///
+/// It is applied by the methods that take [`ExtractOptions`]:
+/// [`Archive::extract`](super::Archive::extract),
+/// [`Archive::extract_to_vfs`](super::Archive::extract_to_vfs) and
+/// [`StreamingArchive::extract_all`](crate::streaming::StreamingArchive::extract_all).
+/// The parallel and async extractors have their own options and always
+/// keep backslashes as literal filename characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BackslashPolicy {
+    /// Keep backslashes as literal filename characters.
+    #[default]
+    Preserve,
+    /// Treat backslashes as directory separators.
+    ///
+    /// Useful for archives created by tools that stored Windows-style paths.
+    /// The converted path is re-validated unless path safety is disabled.
+    Separator,
 }
 
 /// Policy for filtering entries based on selector matches.
@@ -154,18 +236,40 @@ pub struct PreserveMetadata {
     pub creation_time: bool,
     /// Preserve file attributes (read-only, hidden, etc.).
     pub attributes: bool,
+    /// Restore Unix file ownership (UID and GID) when entries carry it.
+    ///
+    /// Not included in [`all`](Self::all); enable it with
+    /// [`ownership`](Self::ownership). Changing a file's owner usually requires root privileges. Files whose
+    /// ownership cannot be restored are still extracted, and the failure is
+    /// recorded in [`ExtractResult::warnings`](super::ExtractResult::warnings).
+    pub ownership: bool,
 }
 
 impl PreserveMetadata {
-    /// Preserve all available metadata.
+    /// Preserve timestamps and attributes.
+    ///
+    /// Ownership is not restored; add it with [`ownership`](Self::ownership).
     pub fn all() -> Self {
         Self {
             modification_time: true,
             creation_time: true,
             attributes: true,
+            ownership: false,
         }
     }
 
+    /// Sets whether Unix file ownership is restored.
+    ///
+    /// ```rust
+    /// use zesven::read::PreserveMetadata;
+    ///
+    /// let preserve = PreserveMetadata::all().ownership(true);
+    /// ```
+    pub fn ownership(mut self, enabled: bool) -> Self {
+        self.ownership = enabled;
+        self
+    }
+
     /// Preserve no metadata.
     pub fn none() -> Self {
         Self::default()
@@ -180,6 +284,7 @@ impl PreserveMetadata {
             modification_time: true,
             creation_time: true,
             attributes: false,
+            ownership: false,
         }
     }
 
@@ -192,12 +297,12 @@ impl PreserveMetadata {
             modification_time: true,
             creation_time: false,
             attributes: false,
+            ownership: false,
         }
     }
 }
 
 /// Options for extraction operations.
-#[derive(Default)]
 pub struct ExtractOptions {
     /// Policy for handling existing files.
     pub overwrite: OverwritePolicy,
@@ -205,28 +310,113 @@ pub struct ExtractOptions {
     pub path_safety: PathSafety,
     /// Symbolic link handling policy.
     pub link_policy: LinkPolicy,
+    /// Policy for entries whose paths differ only in case.
+    pub case_collision_policy: CaseCollisionPolicy,
+    /// Backslash handling policy for entry names.
+    pub backslashes: BackslashPolicy,
     /// Resource limits for extraction.
     pub limits: ResourceLimits,
     /// Thread configuration.
     pub threads: Threads,
     /// Metadata preservation options.
     pub preserve_metadata: PreserveMetadata,
+    /// Whether to check that each extracted file has its declared size.
+    pub verify_size: bool,
+    /// Whether to buffer file writes and defer metadata to the end of extraction.
+    pub write_batching: bool,
+    /// Whether files with identical content are hard-linked to each other.
+    pub dedup_on_extract: bool,
+    /// Whether entry failures, skipped entries and warnings abort extraction
+    /// with [`Error::Strict`].
+    ///
+    /// [`Error::Strict`]: crate::Error::Strict
+    pub strict: bool,
+    /// Maximum time to spend decompressing a single entry.
+    pub per_entry_timeout: Option<Duration>,
+    /// Maximum number of NTFS alternate data streams per base file.
+    pub max_ads_per_file: Option<usize>,
+    /// Whether to sanitize control characters in extracted file names.
+    ///
+    /// `None` sanitizes exactly when the path safety policy is
+    /// [`PathSafety::Strict`].
+    pub sanitize_control_chars: Option<bool>,
+    /// Alignment in bytes of in-memory output buffers.
+    pub output_alignment: usize,
+    /// Block size for read-ahead of packed data (None = no read-ahead).
+    pub read_ahead: Option<usize>,
+    /// Whether to compute a Merkle root over the extracted entries.
+    #[cfg(feature = "merkle")]
+    pub compute_merkle: bool,
     /// Password for encrypted archives.
     #[cfg(feature = "aes")]
     pub password: Option<Password>,
+    /// Dictionary for entries compressed with a ZSTD dictionary.
+    #[cfg(feature = "zstd")]
+    pub zstd_dictionary: Option<crate::codec::ZstdDictionary>,
     /// Progress reporter for tracking extraction progress (optional).
     pub progress: Option<Box<dyn ProgressReporter>>,
+    /// Callback consulted when an extracted file already exists (optional).
+    ///
+    /// Takes precedence over [`overwrite`](Self::overwrite) when set.
+    pub on_conflict: Option<Mutex<Box<ConflictHandler>>>,
+}
+
+impl Default for ExtractOptions {
+    fn default() -> Self {
+        Self {
+            overwrite: OverwritePolicy::default(),
+            path_safety: PathSafety::default(),
+            link_policy: LinkPolicy::default(),
+            case_collision_policy: CaseCollisionPolicy::default(),
+            backslashes: BackslashPolicy::default(),
+            limits: ResourceLimits::default(),
+            threads: Threads::default(),
+            preserve_metadata: PreserveMetadata::default(),
+            verify_size: true,
+            write_batching: false,
+            dedup_on_extract: false,
+            strict: false,
+            per_entry_timeout: None,
+            max_ads_per_file: None,
+            sanitize_control_chars: None,
+            output_alignment: 1,
+            read_ahead: None,
+            #[cfg(feature = "merkle")]
+            compute_merkle: false,
+            #[cfg(feature = "aes")]
+            password: None,
+            #[cfg(feature = "zstd")]
+            zstd_dictionary: None,
+            progress: None,
+            on_conflict: None,
+        }
+    }
 }
 
 impl std::fmt::Debug for ExtractOptions {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("ExtractOptions")
-            .field("overwrite", &self.overwrite)
+        let mut s = f.debug_struct("ExtractOptions");
+        s.field("overwrite", &self.overwrite)
             .field("path_safety", &self.path_safety)
             .field("link_policy", &self.link_policy)
+            .field("case_collision_policy", &self.case_collision_policy)
+            .field("backslashes", &self.backslashes)
             .field("threads", &self.threads)
             .field("preserve_metadata", &self.preserve_metadata)
-            .finish_non_exhaustive()
+            .field("verify_size", &self.verify_size)
+            .field("write_batching", &self.write_batching)
+            .field("dedup_on_extract", &self.dedup_on_extract)
+            .field("strict", &self.strict)
+            .field("per_entry_timeout", &self.per_entry_timeout)
+            .field("max_ads_per_file", &self.max_ads_per_file)
+            .field("sanitize_control_chars", &self.sanitize_control_chars)
+            .field("output_alignment", &self.output_alignment)
+            .field("read_ahead", &self.read_ahead);
+        #[cfg(feature = "merkle")]
+        s.field("compute_merkle", &self.compute_merkle);
+        #[cfg(feature = "zstd")]
+        s.field("zstd_dictionary", &self.zstd_dictionary);
+        s.finish_non_exhaustive()
     }
 }
 
@@ -254,6 +444,18 @@ impl ExtractOptions {
         self
     }
 
+    /// Sets the policy for entries whose paths differ only in case.
+    pub fn case_collision_policy(mut self, policy: CaseCollisionPolicy) -> Self {
+        self.case_collision_policy = policy;
+        self
+    }
+
+    /// Sets the backslash handling policy.
+    pub fn backslashes(mut self, policy: BackslashPolicy) -> Self {
+        self.backslashes = policy;
+        self
+    }
+
     /// Sets the resource limits.
     pub fn limits(mut self, limits: ResourceLimits) -> Self {
         self.limits = limits;
@@ -272,6 +474,166 @@ impl ExtractOptions {
         self
     }
 
+    /// Sets whether extracted file sizes are checked against the entry size.
+    ///
+    /// Enabled by default. When a decoder produces fewer or more bytes than
+    /// the archive declares, extraction fails with [`Error::InvalidFormat`]
+    /// even if the CRC of the produced data matches.
+    ///
+    /// [`Error::InvalidFormat`]: crate::Error::InvalidFormat
+    pub fn verify_size(mut self, verify: bool) -> Self {
+        self.verify_size = verify;
+        self
+    }
+
+    /// Sets whether small-file writes are batched to reduce syscalls.
+    ///
+    /// Disabled by default. When enabled, each extracted file is written
+    /// through a buffer sized to the entry (up to 1 MiB), its CRC is computed
+    /// while writing instead of by reading the file back, and timestamps and
+    /// attributes are applied once after all entries have been written.
+    /// This mostly helps archives with many small files; the extracted
+    /// content is the same either way.
+    pub fn write_batching(mut self, enabled: bool) -> Self {
+        self.write_batching = enabled;
+        self
+    }
+
+    /// Sets whether files with identical content are hard-linked on disk.
+    ///
+    /// Disabled by default. When enabled, [`Archive::extract`] writes the
+    /// first file of each distinct content normally and replaces later files
+    /// with the same content by hard links to it. Candidates are matched by
+    /// size and CRC (computed if the archive stores none) and compared byte
+    /// for byte before linking. Where hard links cannot be created, the
+    /// extracted copy is kept.
+    ///
+    /// Linked files share one inode, so they also share timestamps and
+    /// permissions, and writing to one changes all of them.
+    ///
+    /// [`Archive::extract`]: super::Archive::extract
+    pub fn dedup_on_extract(mut self, enabled: bool) -> Self {
+        self.dedup_on_extract = enabled;
+        self
+    }
+
+    /// Sets whether warnings abort extraction.
+    ///
+    /// By default an entry that cannot be extracted (for example because
+    /// its path is rejected by the path safety policy) is recorded in
+    /// [`ExtractResult::failures`], a skipped entry (an existing file kept by
+    /// the overwrite policy, a forbidden symlink or a case collision) is
+    /// marked as skipped, metadata that could not be restored is recorded in
+    /// [`ExtractResult::warnings`], and extraction continues. In strict mode
+    /// the first of these stops extraction with [`Error::Strict`], and a
+    /// [`RatioLimit`] in [`LimitMode::Warn`] is enforced as a hard error.
+    ///
+    /// [`ExtractResult::failures`]: crate::read::ExtractResult::failures
+    /// [`ExtractResult::warnings`]: crate::read::ExtractResult::warnings
+    /// [`RatioLimit`]: crate::RatioLimit
+    /// [`LimitMode::Warn`]: crate::LimitMode::Warn
+    /// [`Error::Strict`]: crate::Error::Strict
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Sets a deadline for decompressing each entry.
+    ///
+    /// Each entry is decoded on a worker thread; if it is not done within
+    /// `timeout`, its extraction fails with [`Error::Timeout`] and extraction
+    /// moves on to the next entry. This guards against maliciously slow
+    /// inputs. Entries of a solid block are decoded together with the entries
+    /// before them, so the deadline covers that whole decode. Entries stored
+    /// without compression and copied in-kernel are not affected.
+    ///
+    /// A decoder stuck inside a single call keeps its worker thread busy
+    /// until that call returns; the thread then stops.
+    ///
+    /// [`Error::Timeout`]: crate::Error::Timeout
+    pub fn per_entry_timeout(mut self, timeout: Duration) -> Self {
+        self.per_entry_timeout = Some(timeout);
+        self
+    }
+
+    /// Limits the number of NTFS alternate data streams per base file.
+    ///
+    /// Alternate streams are entries named `base:stream_name`. When any
+    /// selected base file carries more than `max` of them, [`Archive::extract`]
+    /// fails with [`Error::ResourceLimitExceeded`] before writing anything.
+    /// This guards against archives that attach huge numbers of streams to
+    /// one file. No limit is applied by default.
+    ///
+    /// [`Archive::extract`]: super::Archive::extract
+    /// [`Error::ResourceLimitExceeded`]: crate::Error::ResourceLimitExceeded
+    pub fn max_ads_per_file(mut self, max: usize) -> Self {
+        self.max_ads_per_file = Some(max);
+        self
+    }
+
+    /// Sets whether control characters in entry names are sanitized.
+    ///
+    /// Names containing newlines, escape sequences, or other control
+    /// characters can inject terminal commands or confuse scripts that list
+    /// the extracted files. When enabled, each control character is replaced
+    /// with `_` and the original name is logged; in [strict](Self::strict)
+    /// mode such entries are rejected instead. By default names are
+    /// sanitized under [`PathSafety::Strict`] and kept verbatim otherwise.
+    pub fn sanitize_control_chars(mut self, sanitize: bool) -> Self {
+        self.sanitize_control_chars = Some(sanitize);
+        self
+    }
+
+    /// Returns whether control characters in entry names are sanitized.
+    pub fn sanitizes_control_chars(&self) -> bool {
+        self.sanitize_control_chars
+            .unwrap_or(self.path_safety == PathSafety::Strict)
+    }
+
+    /// Aligns in-memory output buffers to `alignment` bytes.
+    ///
+    /// Useful when extracted data is post-processed with SIMD loads that
+    /// expect, for example, 32- or 64-byte alignment. The value is rounded up
+    /// to a power of two. This only applies to in-memory destinations
+    /// created with [`MemoryDestination::from_options`]; files on disk and
+    /// other destinations are unaffected. The default is 1 (no alignment).
+    ///
+    /// [`MemoryDestination::from_options`]: super::MemoryDestination::from_options
+    pub fn output_alignment(mut self, alignment: usize) -> Self {
+        self.output_alignment = alignment.max(1).next_power_of_two();
+        self
+    }
+
+    /// Enables read-ahead of packed data in blocks of `block_size` bytes.
+    ///
+    /// Packed data is then fetched from the archive source in blocks aligned
+    /// to multiples of `block_size`, so extracting many small entries costs
+    /// a few large reads instead of a seek and a read per entry. Useful for
+    /// sources where each read or seek is expensive, such as network-backed
+    /// readers. Packed streams of a block or more are read directly. To
+    /// buffer the reads made while opening an archive, wrap the source in a
+    /// [`BufferedSeekReader`](super::BufferedSeekReader).
+    pub fn read_ahead(mut self, block_size: usize) -> Self {
+        self.read_ahead = Some(block_size);
+        self
+    }
+
+    /// Sets whether to compute a Merkle root over the extracted entries.
+    ///
+    /// When enabled, [`Archive::extract`] hashes each file and symlink after
+    /// writing it and reports the root in [`ExtractResult::merkle_root`].
+    /// Two extractions yield the same root exactly when they produced the
+    /// same paths with the same contents. The hashing and ordering scheme is
+    /// described on [`ExtractResult::merkle_root`].
+    ///
+    /// [`Archive::extract`]: super::Archive::extract
+    /// [`ExtractResult::merkle_root`]: super::ExtractResult::merkle_root
+    #[cfg(feature = "merkle")]
+    pub fn compute_merkle(mut self, compute: bool) -> Self {
+        self.compute_merkle = compute;
+        self
+    }
+
     /// Sets the password for encrypted archives.
     #[cfg(feature = "aes")]
     pub fn password(mut self, password: impl Into<Password>) -> Self {
@@ -279,13 +641,57 @@ impl ExtractOptions {
         self
     }
 
+    /// Sets the dictionary for entries compressed with a ZSTD dictionary.
+    ///
+    /// Needed for archives written with
+    /// [`WriteOptions::zstd_dictionary`](crate::WriteOptions::zstd_dictionary).
+    /// The dictionary's ID must match the one recorded in the archive.
+    /// Extraction installs it on the archive with
+    /// [`Archive::set_zstd_dictionary`](super::Archive::set_zstd_dictionary),
+    /// so later extractions keep using it.
+    #[cfg(feature = "zstd")]
+    pub fn zstd_dictionary(mut self, dict: crate::codec::ZstdDictionary) -> Self {
+        self.zstd_dictionary = Some(dict);
+        self
+    }
+
     /// Sets the progress reporter.
     pub fn progress(mut self, reporter: impl ProgressReporter + 'static) -> Self {
         self.progress = Some(Box::new(reporter));
         self
     }
 
-    /// Clones all settings except the progress reporter.
+    /// Sets a callback that decides what happens to existing files.
+    ///
+    /// The callback is invoked once for each file whose destination already
+    /// exists, before anything is written, and its [`OverwriteDecision`]
+    /// replaces the [`overwrite`](Self::overwrite) policy for that file.
+    /// Without a callback the overwrite policy applies.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use zesven::read::{ExtractOptions, OverwriteDecision};
+    ///
+    /// // Only replace files that are older than the archive entry
+    /// let options = ExtractOptions::new().on_conflict(|entry, existing| {
+    ///     let on_disk = std::fs::metadata(existing).and_then(|m| m.modified()).ok();
+    ///     match (entry.modified(), on_disk) {
+    ///         (Some(archived), Some(on_disk)) if archived > on_disk => OverwriteDecision::Overwrite,
+    ///         _ => OverwriteDecision::Skip,
+    ///     }
+    /// });
+    /// # let _ = options;
+    /// ```
+    pub fn on_conflict(
+        mut self,
+        handler: impl FnMut(&Entry, &Path) -> OverwriteDecision + Send + 'static,
+    ) -> Self {
+        self.on_conflict = Some(Mutex::new(Box::new(handler)));
+        self
+    }
+
+    /// Clones all settings except the progress reporter and conflict callback.
     ///
     /// This is useful when you need to extract multiple archives with the same
     /// settings but can't clone the options due to the non-Clone progress reporter.
@@ -304,12 +710,28 @@ impl ExtractOptions {
             overwrite: self.overwrite,
             path_safety: self.path_safety,
             link_policy: self.link_policy,
+            case_collision_policy: self.case_collision_policy,
+            backslashes: self.backslashes,
             limits: self.limits.clone(),
             threads: self.threads,
             preserve_metadata: self.preserve_metadata.clone(),
+            verify_size: self.verify_size,
+            write_batching: self.write_batching,
+            dedup_on_extract: self.dedup_on_extract,
+            strict: self.strict,
+            per_entry_timeout: self.per_entry_timeout,
+            max_ads_per_file: self.max_ads_per_file,
+            sanitize_control_chars: self.sanitize_control_chars,
+            output_alignment: self.output_alignment,
+            read_ahead: self.read_ahead,
+            #[cfg(feature = "merkle")]
+            compute_merkle: self.compute_merkle,
             #[cfg(feature = "aes")]
             password: self.password.clone(),
+            #[cfg(feature = "zstd")]
+            zstd_dictionary: self.zstd_dictionary.clone(),
             progress: None, // Cannot clone Box<dyn ProgressReporter>
+            on_conflict: None,
         }
     }
 }
@@ -387,6 +809,11 @@ mod tests {
         assert_eq!(LinkPolicy::default(), LinkPolicy::Forbid);
     }
 
+    #[test]
+    fn test_backslash_policy_default() {
+        assert_eq!(BackslashPolicy::default(), BackslashPolicy::Preserve);
+    }
+
     #[test]
     fn test_threads_count() {
         use std::num::NonZeroUsize;
@@ -410,11 +837,14 @@ mod tests {
         assert!(all.modification_time);
         assert!(all.creation_time);
         assert!(all.attributes);
+        assert!(!all.ownership);
+        assert!(PreserveMetadata::all().ownership(true).ownership);
 
         let none = PreserveMetadata::none();
         assert!(!none.modification_time);
         assert!(!none.creation_time);
         assert!(!none.attributes);
+        assert!(!none.ownership);
     }
 
     #[test]
@@ -438,11 +868,37 @@ mod tests {
         let opts = ExtractOptions::new()
             .overwrite(OverwritePolicy::Skip)
             .path_safety(PathSafety::Relaxed)
-            .threads(Threads::count_or_single(2));
+            .threads(Threads::count_or_single(2))
+            .verify_size(false)
+            .strict(true);
 
         assert_eq!(opts.overwrite, OverwritePolicy::Skip);
         assert_eq!(opts.path_safety, PathSafety::Relaxed);
         assert_eq!(opts.threads.count(), 2);
+        assert!(!opts.verify_size);
+        assert!(ExtractOptions::default().verify_size);
+        assert!(!ExtractOptions::default().write_batching);
+        assert!(
+            ExtractOptions::default()
+                .write_batching(true)
+                .write_batching
+        );
+        assert!(opts.strict);
+        assert!(!ExtractOptions::default().strict);
+    }
+
+    #[test]
+    fn test_sanitize_control_chars_default() {
+        assert!(ExtractOptions::new().sanitizes_control_chars());
+        let relaxed = ExtractOptions::new().path_safety(PathSafety::Relaxed);
+        assert!(!relaxed.sanitizes_control_chars());
+        assert!(
+            relaxed
+                .sanitize_control_chars(true)
+                .sanitizes_control_chars()
+        );
+        let disabled = ExtractOptions::new().sanitize_control_chars(false);
+        assert!(!disabled.sanitizes_control_chars());
     }
 
     #[test]
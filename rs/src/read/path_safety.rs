@@ -3,11 +3,60 @@
 //! This module provides functions for validating extraction paths against
 //! path traversal attacks and symlink escapes.
 
+use std::borrow::Cow;
 use std::path::Path;
 
-use crate::{Error, Result};
+use crate::{ArchivePath, Error, Result};
 
-use super::PathSafety;
+use super::{BackslashPolicy, ExtractOptions, PathSafety};
+
+/// Maps a stored entry name to the relative path used on disk.
+///
+/// Entry names are kept verbatim when reading the archive; separator
+/// normalization happens here, at extraction time. With
+/// [`BackslashPolicy::Separator`], backslashes become `/` and the result is
+/// validated again so that `..\` sequences cannot escape the destination.
+///
+/// When control characters are sanitized (see
+/// [`ExtractOptions::sanitize_control_chars`]), each one is replaced with
+/// `_`, or the entry is rejected in strict mode.
+pub(crate) fn extraction_path<'a>(
+    entry_idx: usize,
+    entry_path: &'a str,
+    options: &ExtractOptions,
+) -> Result<Cow<'a, str>> {
+    let path = if options.backslashes == BackslashPolicy::Preserve || !entry_path.contains('\\') {
+        Cow::Borrowed(entry_path)
+    } else {
+        let converted = entry_path.replace('\\', "/");
+        if options.path_safety != PathSafety::Disabled && ArchivePath::new(&converted).is_err() {
+            return Err(Error::PathTraversal {
+                entry_index: entry_idx,
+                path: entry_path.to_string(),
+            });
+        }
+        Cow::Owned(converted)
+    };
+
+    if !options.sanitizes_control_chars() || !path.chars().any(char::is_control) {
+        return Ok(path);
+    }
+    if options.strict {
+        return Err(Error::InvalidArchivePath(format!(
+            "entry {} name contains control characters: {:?}",
+            entry_idx, entry_path
+        )));
+    }
+    let sanitized: String = path
+        .chars()
+        .map(|c| if c.is_control() { '_' } else { c })
+        .collect();
+    log::warn!(
+        "Replaced control characters in entry name {:?} with '_'",
+        entry_path
+    );
+    Ok(Cow::Owned(sanitized))
+}
 
 /// Validates that an extraction path is safe according to the configured policy.
 ///
@@ -135,6 +184,31 @@ pub(crate) fn validate_symlink_target(
     Ok(())
 }
 
+/// Resolves a symlink target to the archive path it points to.
+///
+/// The target is validated with [`validate_symlink_target`] first, so the
+/// resolved path never leaves the extraction directory.
+pub(crate) fn resolve_symlink_target(
+    entry_idx: usize,
+    entry_path: &str,
+    target: &str,
+) -> Result<String> {
+    validate_symlink_target(entry_idx, entry_path, target)?;
+
+    let mut components: Vec<&str> = entry_path.split('/').collect();
+    components.pop();
+    for component in target.split('/') {
+        match component {
+            "" | "." => {}
+            ".." => {
+                components.pop();
+            }
+            name => components.push(name),
+        }
+    }
+    Ok(components.join("/"))
+}
+
 /// Creates a symbolic link at the specified path pointing to the target.
 #[cfg(unix)]
 pub(crate) fn create_symlink(link_path: &Path, target: &str) -> Result<u64> {
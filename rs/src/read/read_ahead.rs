@@ -0,0 +1,317 @@
+//! Read-ahead buffering for sources where small reads and seeks are costly.
+//!
+//! This module provides [`BufferedSeekReader`], which turns the many small
+//! reads made while parsing an archive into fewer block-aligned fetches, and
+//! the buffer behind [`ExtractOptions::read_ahead`].
+//!
+//! [`ExtractOptions::read_ahead`]: super::ExtractOptions::read_ahead
+
+use std::io::{self, Read, Seek, SeekFrom};
+
+use super::Archive;
+use crate::{Error, Result};
+
+/// Caches one block-aligned block of a source.
+#[derive(Debug)]
+pub(crate) struct ReadAheadBuffer {
+    block_size: usize,
+    /// Offset of `data` in the source.
+    start: u64,
+    data: Vec<u8>,
+}
+
+impl ReadAheadBuffer {
+    /// Creates an empty buffer fetching `block_size` bytes at a time.
+    pub(crate) fn new(block_size: usize) -> Self {
+        Self {
+            block_size: block_size.max(1),
+            start: 0,
+            data: Vec::new(),
+        }
+    }
+
+    /// Reads from `inner` at `pos` into `buf`, returning the bytes read.
+    ///
+    /// Reads of at least a block bypass the buffer. Smaller reads outside
+    /// the cached block fetch the aligned block containing `pos`.
+    /// `inner_pos` tracks the position of `inner` (`None` if unknown) so
+    /// that seeks to where `inner` already is are skipped.
+    fn read_at<R: Read + Seek>(
+        &mut self,
+        inner: &mut R,
+        inner_pos: &mut Option<u64>,
+        pos: u64,
+        buf: &mut [u8],
+    ) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        if let Some(n) = self.copy_cached(pos, buf) {
+            return Ok(n);
+        }
+
+        if buf.len() >= self.block_size {
+            seek_to(inner, inner_pos, pos)?;
+            let n = inner.read(buf)?;
+            *inner_pos = Some(pos + n as u64);
+            return Ok(n);
+        }
+
+        let block_start = pos - pos % self.block_size as u64;
+        seek_to(inner, inner_pos, block_start)?;
+        self.data.clear();
+        self.data.resize(self.block_size, 0);
+        let mut filled = 0;
+        while filled < self.block_size {
+            match inner.read(&mut self.data[filled..]) {
+                Ok(0) => break,
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => {
+                    self.data.clear();
+                    *inner_pos = None;
+                    return Err(e);
+                }
+            }
+        }
+        self.data.truncate(filled);
+        self.start = block_start;
+        *inner_pos = Some(block_start + filled as u64);
+
+        Ok(self.copy_cached(pos, buf).unwrap_or(0))
+    }
+
+    /// Fills `buf` from `inner` at `pos`.
+    fn read_exact_at<R: Read + Seek>(
+        &mut self,
+        inner: &mut R,
+        pos: u64,
+        buf: &mut [u8],
+    ) -> io::Result<()> {
+        let mut inner_pos = None;
+        let mut done = 0;
+        while done < buf.len() {
+            let n = self.read_at(inner, &mut inner_pos, pos + done as u64, &mut buf[done..])?;
+            if n == 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "failed to fill whole buffer",
+                ));
+            }
+            done += n;
+        }
+        Ok(())
+    }
+
+    /// Copies cached bytes at `pos` into `buf`, if `pos` is cached.
+    fn copy_cached(&self, pos: u64, buf: &mut [u8]) -> Option<usize> {
+        let offset = usize::try_from(pos.checked_sub(self.start)?).ok()?;
+        let cached = self.data.get(offset..).filter(|c| !c.is_empty())?;
+        let n = cached.len().min(buf.len());
+        buf[..n].copy_from_slice(&cached[..n]);
+        Some(n)
+    }
+}
+
+/// Seeks `inner` to `pos` unless it is known to be there already.
+fn seek_to<R: Seek>(inner: &mut R, inner_pos: &mut Option<u64>, pos: u64) -> io::Result<()> {
+    if *inner_pos != Some(pos) {
+        inner.seek(SeekFrom::Start(pos))?;
+        *inner_pos = Some(pos);
+    }
+    Ok(())
+}
+
+/// A reader that coalesces small reads into block-aligned fetches.
+///
+/// Parsing an archive header makes many small reads and seeks, which are
+/// expensive on network-backed sources such as HTTP range readers. This
+/// wrapper fetches whole blocks of `block_size` bytes, aligned to multiples
+/// of the block size, and serves small reads from the last fetched block.
+/// Seeks only update the logical position; the source is sought once data
+/// outside the cached block is needed. Reads of a block or more go straight
+/// to the source.
+///
+/// The source must not change while it is wrapped, since cached data is not
+/// revalidated.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use zesven::read::{Archive, BufferedSeekReader};
+///
+/// let source = HttpRangeReader::new("https://example.com/archive.7z")?;
+/// let archive = Archive::open(BufferedSeekReader::new(source, 64 * 1024))?;
+/// ```
+pub struct BufferedSeekReader<R> {
+    inner: R,
+    buffer: ReadAheadBuffer,
+    /// Logical position (`None` until first needed: the position of `inner`).
+    pos: Option<u64>,
+    /// Position of `inner` (`None` if unknown).
+    inner_pos: Option<u64>,
+}
+
+impl<R> BufferedSeekReader<R> {
+    /// Wraps `inner`, fetching `block_size` bytes at a time.
+    ///
+    /// Reading starts at the current position of `inner`. A `block_size`
+    /// of zero is treated as one.
+    pub fn new(inner: R, block_size: usize) -> Self {
+        Self {
+            inner,
+            buffer: ReadAheadBuffer::new(block_size),
+            pos: None,
+            inner_pos: None,
+        }
+    }
+
+    /// Returns the block size.
+    pub fn block_size(&self) -> usize {
+        self.buffer.block_size
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Consumes the wrapper and returns the underlying reader.
+    ///
+    /// The position of the returned reader is unspecified.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Seek> BufferedSeekReader<R> {
+    /// Returns the logical position, taking it from `inner` the first time.
+    fn position(&mut self) -> io::Result<u64> {
+        match self.pos {
+            Some(pos) => Ok(pos),
+            None => {
+                let pos = self.inner.stream_position()?;
+                self.pos = Some(pos);
+                self.inner_pos = Some(pos);
+                Ok(pos)
+            }
+        }
+    }
+}
+
+impl<R: Read + Seek> Read for BufferedSeekReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let pos = self.position()?;
+        let n = self
+            .buffer
+            .read_at(&mut self.inner, &mut self.inner_pos, pos, buf)?;
+        self.pos = Some(pos + n as u64);
+        Ok(n)
+    }
+}
+
+impl<R: Read + Seek> Seek for BufferedSeekReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let new_pos = match pos {
+            SeekFrom::Start(offset) => offset,
+            SeekFrom::Current(delta) => {
+                let current = self.position()?;
+                current.checked_add_signed(delta).ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "invalid seek to a negative or overflowing position",
+                    )
+                })?
+            }
+            SeekFrom::End(_) => {
+                let end = self.inner.seek(pos)?;
+                self.inner_pos = Some(end);
+                end
+            }
+        };
+        self.pos = Some(new_pos);
+        Ok(new_pos)
+    }
+}
+
+impl<R: std::fmt::Debug> std::fmt::Debug for BufferedSeekReader<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BufferedSeekReader")
+            .field("inner", &self.inner)
+            .field("block_size", &self.buffer.block_size)
+            .field("pos", &self.pos)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R: Read + Seek> Archive<R> {
+    /// Reads `size` bytes of packed data at `offset`.
+    ///
+    /// Goes through the read-ahead buffer installed from
+    /// [`ExtractOptions::read_ahead`](super::ExtractOptions::read_ahead),
+    /// if any.
+    pub(crate) fn read_pack_data(&mut self, offset: u64, size: u64) -> Result<Vec<u8>> {
+        let mut data = vec![0u8; size as usize];
+        match &mut self.read_ahead {
+            Some(buffer) => buffer
+                .read_exact_at(&mut self.reader, offset, &mut data)
+                .map_err(Error::Io)?,
+            None => {
+                self.reader
+                    .seek(SeekFrom::Start(offset))
+                    .map_err(Error::Io)?;
+                self.reader.read_exact(&mut data).map_err(Error::Io)?;
+            }
+        }
+        Ok(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_buffered_seek_reader_matches_source() {
+        let data: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        let mut reader = BufferedSeekReader::new(Cursor::new(data.clone()), 1024);
+
+        let mut buf = [0u8; 100];
+        reader.seek(SeekFrom::Start(1000)).unwrap();
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf[..], &data[1000..1100]);
+
+        // Small reads across a block boundary and backwards seeks
+        reader.seek(SeekFrom::Current(-50)).unwrap();
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf[..], &data[1050..1150]);
+
+        // Reads of a whole block bypass the buffer
+        let mut large = vec![0u8; 3000];
+        reader.seek(SeekFrom::Start(5000)).unwrap();
+        reader.read_exact(&mut large).unwrap();
+        assert_eq!(&large[..], &data[5000..8000]);
+
+        // Seeking from the end and reading to EOF
+        let pos = reader.seek(SeekFrom::End(-10)).unwrap();
+        assert_eq!(pos, 9990);
+        let mut tail = Vec::new();
+        reader.read_to_end(&mut tail).unwrap();
+        assert_eq!(&tail[..], &data[9990..]);
+
+        assert!(reader.seek(SeekFrom::Current(-20_000)).is_err());
+    }
+
+    #[test]
+    fn test_buffered_seek_reader_starts_at_inner_position() {
+        let mut inner = Cursor::new(b"0123456789".to_vec());
+        inner.set_position(4);
+        let mut reader = BufferedSeekReader::new(inner, 4);
+        let mut buf = [0u8; 3];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"456");
+        assert_eq!(reader.stream_position().unwrap(), 7);
+    }
+}
@@ -3,9 +3,9 @@
 //! This module provides functions for working with solid archives where
 //! multiple files are compressed together in a single block.
 
-use std::io::{Read, Seek};
 #[cfg(feature = "lzma")]
-use std::io::{SeekFrom, Write};
+use std::io::Write;
+use std::io::{Read, Seek};
 
 use crate::format::SIGNATURE_HEADER_SIZE;
 #[cfg(feature = "lzma")]
@@ -86,6 +86,44 @@ impl<R: Read + Seek> Archive<R> {
         Ok(sizes)
     }
 
+    /// Returns a hasher for the whole-folder CRC if it should be verified.
+    ///
+    /// A folder CRC covers the concatenated output of every stream in the
+    /// folder, so it can only be checked when the target stream is the last
+    /// one and the whole folder is decoded as a side effect.
+    ///
+    /// Archives written by zesven 1.1 and earlier store a placeholder CRC of
+    /// zero for multi-stream folders, so a zero folder CRC is not verified.
+    pub(crate) fn folder_crc_hasher(
+        folder: &crate::format::streams::Folder,
+        stream_index: usize,
+        num_streams: usize,
+    ) -> Option<crc32fast::Hasher> {
+        folder
+            .unpack_crc
+            .filter(|&crc| crc != 0 && stream_index + 1 == num_streams)
+            .map(|_| crc32fast::Hasher::new())
+    }
+
+    /// Verifies the CRC of a fully decoded folder against its folder CRC.
+    pub(crate) fn verify_folder_crc(
+        folder: &crate::format::streams::Folder,
+        folder_idx: usize,
+        hasher: crc32fast::Hasher,
+    ) -> Result<()> {
+        if let Some(expected) = folder.unpack_crc {
+            let actual = hasher.finalize();
+            if actual != expected {
+                return Err(Error::FolderCrcMismatch {
+                    folder_index: folder_idx,
+                    expected,
+                    actual,
+                });
+            }
+        }
+        Ok(())
+    }
+
     /// Calculates the pack stream base index for a folder.
     ///
     /// For multi-stream folders (like BCJ2), we need to know where this folder's
@@ -134,25 +172,21 @@ impl<R: Read + Seek> Archive<R> {
             }
         }
 
-        let mut pack_data = Vec::with_capacity(num_pack_streams);
-
-        for i in 0..num_pack_streams {
-            let pack_idx = pack_base + i;
-            let pack_size = pack_info.pack_sizes.get(pack_idx).copied().ok_or_else(|| {
-                Error::InvalidFormat(format!(
-                    "missing pack size for stream {} (pack_idx {})",
-                    i, pack_idx
-                ))
-            })?;
-
-            self.reader
-                .seek(SeekFrom::Start(pack_offset))
-                .map_err(Error::Io)?;
-
-            let mut data = vec![0u8; pack_size as usize];
-            self.reader.read_exact(&mut data).map_err(Error::Io)?;
+        let pack_sizes = (0..num_pack_streams)
+            .map(|i| {
+                let pack_idx = pack_base + i;
+                pack_info.pack_sizes.get(pack_idx).copied().ok_or_else(|| {
+                    Error::InvalidFormat(format!(
+                        "missing pack size for stream {} (pack_idx {})",
+                        i, pack_idx
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
 
-            pack_data.push(data);
+        let mut pack_data = Vec::with_capacity(num_pack_streams);
+        for pack_size in pack_sizes {
+            pack_data.push(self.read_pack_data(pack_offset, pack_size)?);
             pack_offset += pack_size;
         }
 
@@ -178,7 +212,8 @@ impl<R: Read + Seek> Archive<R> {
         let compressed_size: u64 = pack_data.iter().map(|p| p.len() as u64).sum();
 
         // Build BCJ2 decoder
-        let mut decoder = codec::build_bcj2_folder_decoder(folder, &pack_data)?;
+        let decoder = codec::build_bcj2_folder_decoder(folder, &pack_data)?;
+        let mut decoder = limits.wrap_timeout(decoder);
 
         // Check if this is a solid block (multiple files in one folder)
         // Use is_solid_block() first to avoid requiring SubStreamsInfo for non-solid BCJ2
@@ -201,7 +236,7 @@ impl<R: Read + Seek> Archive<R> {
                 let mut remaining = skip_size;
                 while remaining > 0 {
                     let to_read = buf.len().min(remaining as usize);
-                    let n = decoder.read(&mut buf[..to_read]).map_err(Error::Io)?;
+                    let n = decoder.read(&mut buf[..to_read]).map_err(map_io_error)?;
                     if n == 0 {
                         return Err(Error::InvalidFormat(
                             "unexpected end of BCJ2 stream while skipping".into(),
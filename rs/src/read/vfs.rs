@@ -0,0 +1,247 @@
+//! Virtual filesystem extraction targets.
+//!
+//! This module provides the [`VfsDestination`] trait, which models the
+//! filesystem operations extraction performs (creating directories, files and
+//! symlinks, writing data, applying metadata), and [`InMemoryVfs`], an
+//! implementation that builds the extracted tree in memory.
+//!
+//! Use [`Archive::extract_to_vfs`](super::Archive::extract_to_vfs) to extract
+//! into any implementation. [`FilesystemDestination`](super::FilesystemDestination)
+//! implements the trait over the real filesystem.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use zesven::read::{Archive, ExtractOptions, InMemoryVfs, VfsNode};
+//!
+//! let mut archive = Archive::open_path("archive.7z")?;
+//! let mut vfs = InMemoryVfs::new();
+//! archive.extract_to_vfs(&mut vfs, (), &ExtractOptions::default())?;
+//!
+//! for (path, entry) in vfs.entries() {
+//!     if let VfsNode::File(data) = &entry.node {
+//!         println!("{}: {} bytes", path.display(), data.len());
+//!     }
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::read::Entry;
+use crate::{Error, Result};
+
+/// Filesystem operations used by extraction.
+///
+/// Paths passed to these methods are relative to the extraction root and use
+/// the archive's `/` separators. Parent directories are not guaranteed to have
+/// been created explicitly; implementations should create them as needed.
+pub trait VfsDestination: Send {
+    /// Creates a directory, including any missing parents.
+    fn create_dir(&mut self, path: &Path) -> Result<()>;
+
+    /// Creates an empty file, replacing any existing file at `path`.
+    fn create_file(&mut self, path: &Path) -> Result<()>;
+
+    /// Appends `data` to a file previously created with [`Self::create_file`].
+    fn write(&mut self, path: &Path, data: &[u8]) -> Result<()>;
+
+    /// Applies the metadata of `entry` to an extracted item.
+    ///
+    /// Called once per entry after its content is complete. The default
+    /// implementation does nothing.
+    fn set_metadata(&mut self, path: &Path, entry: &Entry) -> Result<()> {
+        let _ = (path, entry);
+        Ok(())
+    }
+
+    /// Creates a symbolic link at `path` pointing to `target`.
+    fn symlink(&mut self, path: &Path, target: &str) -> Result<()>;
+}
+
+/// A node in an [`InMemoryVfs`] tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VfsNode {
+    /// A directory.
+    Directory,
+    /// A regular file with its contents.
+    File(Vec<u8>),
+    /// A symbolic link with its target.
+    Symlink(String),
+}
+
+/// An item stored in an [`InMemoryVfs`], with the metadata applied to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VfsEntry {
+    /// The node kind and its content.
+    pub node: VfsNode,
+    /// Modification time as Windows FILETIME, if set.
+    pub modification_time: Option<u64>,
+    /// File attributes, if set.
+    pub attributes: Option<u32>,
+}
+
+impl VfsEntry {
+    fn new(node: VfsNode) -> Self {
+        Self {
+            node,
+            modification_time: None,
+            attributes: None,
+        }
+    }
+}
+
+/// In-memory virtual filesystem.
+///
+/// Records every directory, file and symlink created during extraction,
+/// keyed by its relative path. Unlike [`MemoryDestination`](super::MemoryDestination),
+/// it keeps directories and symlinks, so the whole extracted tree can be
+/// inspected without touching the real filesystem.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryVfs {
+    entries: BTreeMap<PathBuf, VfsEntry>,
+}
+
+impl InMemoryVfs {
+    /// Creates an empty virtual filesystem.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns all items, ordered by path.
+    pub fn entries(&self) -> &BTreeMap<PathBuf, VfsEntry> {
+        &self.entries
+    }
+
+    /// Takes ownership of the items.
+    pub fn into_entries(self) -> BTreeMap<PathBuf, VfsEntry> {
+        self.entries
+    }
+
+    /// Returns the item at `path`.
+    pub fn get(&self, path: impl AsRef<Path>) -> Option<&VfsEntry> {
+        self.entries.get(path.as_ref())
+    }
+
+    /// Returns the contents of the file at `path`.
+    pub fn file(&self, path: impl AsRef<Path>) -> Option<&[u8]> {
+        match &self.get(path)?.node {
+            VfsNode::File(data) => Some(data),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of items.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if nothing has been created.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn create_parents(&mut self, path: &Path) -> Result<()> {
+        match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => self.create_dir(parent),
+            _ => Ok(()),
+        }
+    }
+
+    fn get_mut(&mut self, path: &Path) -> Result<&mut VfsEntry> {
+        self.entries.get_mut(path).ok_or_else(|| {
+            Error::Io(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no such item: {}", path.display()),
+            ))
+        })
+    }
+}
+
+impl VfsDestination for InMemoryVfs {
+    fn create_dir(&mut self, path: &Path) -> Result<()> {
+        match self.entries.get(path).map(|e| &e.node) {
+            Some(VfsNode::Directory) => Ok(()),
+            Some(_) => Err(Error::Io(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("not a directory: {}", path.display()),
+            ))),
+            None => {
+                self.create_parents(path)?;
+                self.entries
+                    .insert(path.to_path_buf(), VfsEntry::new(VfsNode::Directory));
+                Ok(())
+            }
+        }
+    }
+
+    fn create_file(&mut self, path: &Path) -> Result<()> {
+        self.create_parents(path)?;
+        self.entries
+            .insert(path.to_path_buf(), VfsEntry::new(VfsNode::File(Vec::new())));
+        Ok(())
+    }
+
+    fn write(&mut self, path: &Path, data: &[u8]) -> Result<()> {
+        match &mut self.get_mut(path)?.node {
+            VfsNode::File(contents) => {
+                contents.extend_from_slice(data);
+                Ok(())
+            }
+            _ => Err(Error::Io(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("not a file: {}", path.display()),
+            ))),
+        }
+    }
+
+    fn set_metadata(&mut self, path: &Path, entry: &Entry) -> Result<()> {
+        let item = self.get_mut(path)?;
+        item.modification_time = entry.modification_time;
+        item.attributes = entry.attributes;
+        Ok(())
+    }
+
+    fn symlink(&mut self, path: &Path, target: &str) -> Result<()> {
+        self.create_parents(path)?;
+        self.entries.insert(
+            path.to_path_buf(),
+            VfsEntry::new(VfsNode::Symlink(target.to_string())),
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_in_memory_vfs_creates_parents() {
+        let mut vfs = InMemoryVfs::new();
+        vfs.create_file(Path::new("a/b/file.txt")).unwrap();
+        vfs.write(Path::new("a/b/file.txt"), b"hello ").unwrap();
+        vfs.write(Path::new("a/b/file.txt"), b"world").unwrap();
+        vfs.symlink(Path::new("a/link"), "b/file.txt").unwrap();
+
+        assert_eq!(vfs.get("a").unwrap().node, VfsNode::Directory);
+        assert_eq!(vfs.get("a/b").unwrap().node, VfsNode::Directory);
+        assert_eq!(vfs.file("a/b/file.txt"), Some(&b"hello world"[..]));
+        assert_eq!(
+            vfs.get("a/link").unwrap().node,
+            VfsNode::Symlink("b/file.txt".into())
+        );
+        assert_eq!(vfs.len(), 4);
+    }
+
+    #[test]
+    fn test_in_memory_vfs_rejects_invalid_operations() {
+        let mut vfs = InMemoryVfs::new();
+        assert!(vfs.write(Path::new("missing"), b"data").is_err());
+
+        vfs.create_file(Path::new("file")).unwrap();
+        assert!(vfs.create_dir(Path::new("file")).is_err());
+        assert!(vfs.create_dir(Path::new("file/child")).is_err());
+    }
+}
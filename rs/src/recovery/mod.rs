@@ -10,6 +10,9 @@
 //! 1. **Signature Scanning**: Search for 7z signatures in corrupted files
 //! 2. **Header Recovery**: Attempt to parse headers with relaxed validation
 //! 3. **Entry-by-Entry Recovery**: Extract individual entries, skipping corrupt ones
+//! 4. **Recovery Records**: Rebuild damaged regions from parity data written
+//!    with [`WriteOptions::recovery_record`](crate::WriteOptions::recovery_record),
+//!    using [`repair_with_record`]
 //!
 //! # Example
 //!
@@ -28,15 +31,22 @@
 //!             result.failed_entries.len());
 //!     }
 //!     RecoveryStatus::Failed => println!("Could not recover archive"),
+//!     RecoveryStatus::Cancelled => println!("Recovery was cancelled"),
 //! }
 //! ```
 
+mod record;
 mod scanner;
 
+pub(crate) use record::RecordEncoder;
+pub use record::{RecordRepair, repair_with_record};
 pub use scanner::SignatureScanner;
 
+use crate::format::streams::{LimitMode, ResourceLimits};
 use crate::{Archive, Error, Result};
 use std::io::{Read, Seek, SeekFrom};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 /// Options for archive recovery operations.
 #[derive(Debug, Clone)]
@@ -49,6 +59,10 @@ pub struct RecoveryOptions {
     pub skip_corrupt_entries: bool,
     /// Whether to try multiple header locations (default: false).
     pub try_multiple_headers: bool,
+    /// Flag that cancels the recovery when set to `true` (default: none).
+    pub cancellation: Option<Arc<AtomicBool>>,
+    /// Whether warnings fail the recovery with [`Error::Strict`] (default: false).
+    pub strict: bool,
 }
 
 impl Default for RecoveryOptions {
@@ -58,6 +72,8 @@ impl Default for RecoveryOptions {
             validate_crcs: true,
             skip_corrupt_entries: false,
             try_multiple_headers: false,
+            cancellation: None,
+            strict: false,
         }
     }
 }
@@ -81,6 +97,19 @@ impl RecoveryOptions {
     }
 
     /// Sets whether to skip corrupt entries.
+    ///
+    /// When enabled, every file entry is decoded during recovery. Entries
+    /// that fail to decode, or fail their CRC check when
+    /// [`validate_crcs`](Self::validate_crcs) is set, are reported as
+    /// [`FailedEntry`]; the data of the others is kept and available from
+    /// [`RecoveredEntry::extract`]. Pack data that extends past the end of
+    /// the file no longer prevents the archive from opening.
+    ///
+    /// The decoded data of every recovered entry is held in the
+    /// [`RecoveryResult`], so recovery needs as much memory as the
+    /// uncompressed size of all recovered entries together. For large
+    /// archives, use this to find the intact entries and extract them from
+    /// [`RecoveryResult::archive`] instead.
     pub fn skip_corrupt_entries(mut self, skip: bool) -> Self {
         self.skip_corrupt_entries = skip;
         self
@@ -91,6 +120,41 @@ impl RecoveryOptions {
         self.try_multiple_headers = try_multiple;
         self
     }
+
+    /// Sets a flag that cancels the recovery when set to `true`.
+    ///
+    /// The flag is checked periodically during the signature scan and
+    /// before the archive is opened.
+    pub fn cancellation(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.cancellation = Some(flag);
+        self
+    }
+
+    /// Sets whether warnings fail the recovery.
+    ///
+    /// In strict mode any condition that would be added to
+    /// [`RecoveryResult::warnings`] is returned as [`Error::Strict`] instead.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Passes a warning through, or converts it to [`Error::Strict`] in
+    /// strict mode.
+    fn warning(&self, message: String) -> Result<String> {
+        if self.strict {
+            Err(Error::Strict(message))
+        } else {
+            Ok(message)
+        }
+    }
+
+    /// Returns true if the cancellation flag is set.
+    fn is_cancelled(&self) -> bool {
+        self.cancellation
+            .as_ref()
+            .is_some_and(|flag| flag.load(Ordering::Relaxed))
+    }
 }
 
 /// Status of a recovery operation.
@@ -104,10 +168,12 @@ pub enum RecoveryStatus {
     HeaderRecovered,
     /// Recovery failed completely.
     Failed,
+    /// Recovery was cancelled before it completed.
+    Cancelled,
 }
 
 /// Information about a successfully recovered entry.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct RecoveredEntry {
     /// Path of the entry in the archive.
     pub path: String,
@@ -117,6 +183,37 @@ pub struct RecoveredEntry {
     pub crc_valid: bool,
     /// Index of the entry in the archive.
     pub index: usize,
+    /// Decoded data, kept when corrupt entries are skipped.
+    data: Option<Vec<u8>>,
+}
+
+impl RecoveredEntry {
+    /// Returns the recovered data of the entry.
+    ///
+    /// Data is decoded during recovery only with
+    /// [`RecoveryOptions::skip_corrupt_entries`]; otherwise, and for
+    /// directories, this returns [`Error::InvalidFormat`]. The data stays
+    /// owned by the entry; copy it if it must outlive the recovery result.
+    pub fn extract(&self) -> Result<&[u8]> {
+        self.data.as_deref().ok_or_else(|| {
+            Error::InvalidFormat(format!(
+                "data of '{}' was not decoded during recovery",
+                self.path
+            ))
+        })
+    }
+}
+
+impl std::fmt::Debug for RecoveredEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RecoveredEntry")
+            .field("path", &self.path)
+            .field("size", &self.size)
+            .field("crc_valid", &self.crc_valid)
+            .field("index", &self.index)
+            .field("has_data", &self.data.is_some())
+            .finish()
+    }
 }
 
 /// Information about a failed entry recovery.
@@ -148,6 +245,18 @@ pub struct RecoveryResult<R> {
 }
 
 impl<R> RecoveryResult<R> {
+    /// Creates the result of a cancelled recovery.
+    fn cancelled(archive_offset: u64) -> Self {
+        Self {
+            archive: None,
+            status: RecoveryStatus::Cancelled,
+            recovered_entries: Vec::new(),
+            failed_entries: Vec::new(),
+            warnings: vec!["Recovery cancelled".to_string()],
+            archive_offset,
+        }
+    }
+
     /// Returns the number of successfully recovered entries.
     pub fn recovered_count(&self) -> usize {
         self.recovered_entries.len()
@@ -199,14 +308,22 @@ pub fn recover_archive<R: Read + Seek + Send + 'static>(
     // First, try to find the 7z signature
     reader.seek(SeekFrom::Start(0)).map_err(Error::Io)?;
     let mut scanner = SignatureScanner::new(&mut reader, options.search_limit);
+    if let Some(flag) = &options.cancellation {
+        scanner = scanner.with_cancellation(Arc::clone(flag));
+    }
 
-    let signature_offset = match scanner.find_next_signature()? {
+    let found = scanner.find_valid_signature()?;
+    if scanner.is_cancelled() {
+        return Ok(RecoveryResult::cancelled(0));
+    }
+
+    let signature_offset = match found {
         Some(offset) => {
             if offset > 0 {
-                warnings.push(format!(
+                warnings.push(options.warning(format!(
                     "Archive signature found at offset {} (possible SFX or corruption)",
                     offset
-                ));
+                ))?);
             }
             offset
         }
@@ -216,12 +333,16 @@ pub fn recover_archive<R: Read + Seek + Send + 'static>(
                 status: RecoveryStatus::Failed,
                 recovered_entries: Vec::new(),
                 failed_entries: Vec::new(),
-                warnings: vec!["No 7z signature found in file".to_string()],
+                warnings: vec![options.warning("No 7z signature found in file".to_string())?],
                 archive_offset: 0,
             });
         }
     };
 
+    if options.is_cancelled() {
+        return Ok(RecoveryResult::cancelled(signature_offset));
+    }
+
     // Try to open the archive at the found offset
     reader
         .seek(SeekFrom::Start(signature_offset))
@@ -256,7 +377,7 @@ pub fn recover_archive<R: Read + Seek + Send + 'static>(
             })
         }
         Err(e) => {
-            warnings.push(format!("Failed to open archive: {}", e));
+            warnings.push(options.warning(format!("Failed to open archive: {}", e))?);
             Ok(RecoveryResult {
                 archive: None,
                 status: RecoveryStatus::Failed,
@@ -281,18 +402,77 @@ fn try_open_archive<R: Read + Seek + Send + 'static>(
 )> {
     // Try opening with standard parsing
     // Archive::open handles SFX detection automatically - we've already seeked to the offset
-    let archive = Archive::open(reader)?;
+    // When entries are decoded, truncated pack data fails only the affected entries
+    let mut archive = if options.skip_corrupt_entries {
+        let limits = ResourceLimits::default().pack_bounds(LimitMode::Warn);
+        Archive::open_with_limits(reader, limits)?
+    } else {
+        Archive::open(reader)?
+    };
 
     // Validate entries
     let mut results = Vec::new();
-    for (index, entry) in archive.entries().iter().enumerate() {
-        let entry_result = validate_entry(&archive, index, entry, options);
+    for index in 0..archive.entries().len() {
+        let entry_result = if options.skip_corrupt_entries {
+            recover_entry(&mut archive, index, options)
+        } else {
+            validate_entry(&archive, index, &archive.entries()[index], options)
+        };
         results.push(entry_result);
     }
 
     Ok((archive, results))
 }
 
+/// Decodes a single entry and returns its recovered data.
+fn recover_entry<R: Read + Seek>(
+    archive: &mut Archive<R>,
+    index: usize,
+    options: &RecoveryOptions,
+) -> std::result::Result<RecoveredEntry, FailedEntry> {
+    let entry = &archive.entries()[index];
+    if entry.is_directory {
+        return validate_entry(archive, index, entry, options);
+    }
+    let path = entry.path.as_str().to_string();
+    let expected_crc = entry.crc32;
+    let failed = |reason: String| FailedEntry {
+        path: Some(path.clone()),
+        reason,
+        index,
+    };
+
+    // The entry size comes from a possibly damaged header, so the buffer
+    // grows with the decoded data instead of being reserved up front
+    let mut data = Vec::new();
+    let (_, actual_crc) = archive
+        .decode_entry_by_index(index, &mut data, "vec")
+        .map_err(|e| failed(e.to_string()))?;
+
+    let crc_valid = if options.validate_crcs {
+        match expected_crc {
+            Some(expected) if expected != actual_crc => {
+                return Err(failed(format!(
+                    "CRC mismatch: expected {:08x}, got {:08x}",
+                    expected, actual_crc
+                )));
+            }
+            Some(_) => true,
+            None => false,
+        }
+    } else {
+        true
+    };
+
+    Ok(RecoveredEntry {
+        size: data.len() as u64,
+        crc_valid,
+        index,
+        data: Some(data),
+        path,
+    })
+}
+
 /// Validates a single entry and returns recovery information.
 fn validate_entry<R>(
     _archive: &Archive<R>,
@@ -307,6 +487,7 @@ fn validate_entry<R>(
             size: 0,
             crc_valid: true,
             index,
+            data: None,
         });
     }
 
@@ -324,6 +505,7 @@ fn validate_entry<R>(
         size: entry.size,
         crc_valid,
         index,
+        data: None,
     })
 }
 
@@ -458,12 +640,14 @@ mod tests {
                     size: 100,
                     crc_valid: true,
                     index: 0,
+                    data: None,
                 },
                 RecoveredEntry {
                     path: "b.txt".into(),
                     size: 200,
                     crc_valid: true,
                     index: 1,
+                    data: None,
                 },
             ],
             failed_entries: vec![FailedEntry {
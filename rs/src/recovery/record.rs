@@ -0,0 +1,550 @@
+//! Recovery records: Reed-Solomon parity appended to an archive.
+//!
+//! A recovery record is written by [`WriteOptions::recovery_record`] after
+//! the end of the 7z structure. Readers that follow the header offsets still
+//! extract the archive, but 7-Zip warns "There are some data after the end
+//! of the payload data" when it finds the record. [`repair_with_record`]
+//! uses it to reconstruct damaged regions of the archive in place.
+//!
+//! # Layout
+//!
+//! The archive data after the signature header is split into 512-byte
+//! blocks, and every 100 consecutive blocks form a group. Each group gets
+//! `percent` parity blocks (fewer for a shorter last group, at least one),
+//! computed with a Cauchy Reed-Solomon code over GF(2^8), so any `percent`
+//! damaged blocks of a group can be rebuilt. The record holds, in order:
+//!
+//! - the parity blocks of every group
+//! - the CRC32 of every data block and of every parity block
+//! - a copy of the 32-byte signature header
+//! - a 28-byte footer: magic, version, percent, block size, covered length,
+//!   and a CRC32 of the CRC tables, signature header copy and footer
+//!
+//! [`WriteOptions::recovery_record`]: crate::WriteOptions::recovery_record
+
+use std::io::{Read, Seek, SeekFrom, Write};
+
+use crate::format::SIGNATURE_HEADER_SIZE;
+use crate::{Error, Result};
+
+/// Size of the blocks the archive data is split into.
+const BLOCK_SIZE: usize = 512;
+
+/// Number of data blocks per parity group.
+const GROUP_BLOCKS: usize = 100;
+
+/// Magic bytes at the start of the footer.
+const MAGIC: [u8; 8] = *b"zsvnRREC";
+
+/// Version of the record layout.
+const VERSION: u8 = 1;
+
+/// Size of the footer at the end of the record.
+const FOOTER_SIZE: usize = 28;
+
+/// Exponent and logarithm tables of GF(2^8) with polynomial 0x11d.
+static GF_TABLES: ([u8; 512], [u8; 256]) = gf_tables();
+
+const fn gf_tables() -> ([u8; 512], [u8; 256]) {
+    let mut exp = [0u8; 512];
+    let mut log = [0u8; 256];
+    let mut x: u16 = 1;
+    let mut i = 0;
+    while i < 255 {
+        exp[i] = x as u8;
+        log[x as usize] = i as u8;
+        x <<= 1;
+        if x & 0x100 != 0 {
+            x ^= 0x11d;
+        }
+        i += 1;
+    }
+    while i < 512 {
+        exp[i] = exp[i - 255];
+        i += 1;
+    }
+    (exp, log)
+}
+
+fn gf_mul(a: u8, b: u8) -> u8 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    let (exp, log) = &GF_TABLES;
+    exp[log[a as usize] as usize + log[b as usize] as usize]
+}
+
+fn gf_inv(a: u8) -> u8 {
+    debug_assert_ne!(a, 0);
+    let (exp, log) = &GF_TABLES;
+    exp[255 - log[a as usize] as usize]
+}
+
+/// Adds `c * src` to `dst`.
+fn gf_mul_add(dst: &mut [u8], src: &[u8], c: u8) {
+    if c == 0 {
+        return;
+    }
+    let mut row = [0u8; 256];
+    for (s, r) in row.iter_mut().enumerate() {
+        *r = gf_mul(c, s as u8);
+    }
+    for (d, &s) in dst.iter_mut().zip(src) {
+        *d ^= row[s as usize];
+    }
+}
+
+/// Coefficient of data block `data` in parity block `parity` of a group.
+///
+/// The entries `1 / (x_i + y_j)` with distinct `x_i = i` and
+/// `y_j = GROUP_BLOCKS + j` form a Cauchy matrix, every square submatrix of
+/// which is invertible.
+fn coefficient(parity: usize, data: usize) -> u8 {
+    gf_inv(parity as u8 ^ (GROUP_BLOCKS + data) as u8)
+}
+
+/// Number of parity blocks for a group of `blocks` data blocks.
+fn parity_blocks(blocks: usize, percent: usize) -> usize {
+    (blocks * percent).div_ceil(100).max(1)
+}
+
+/// Block geometry of a record covering `body_len` bytes.
+struct Geometry {
+    data_blocks: usize,
+    /// Number of parity blocks of each group.
+    group_parity: Vec<usize>,
+}
+
+impl Geometry {
+    fn new(body_len: u64, percent: usize) -> Result<Self> {
+        let data_blocks = usize::try_from(body_len.div_ceil(BLOCK_SIZE as u64))
+            .map_err(|_| Error::InvalidFormat("recovery record is too large".into()))?;
+        let group_parity = (0..data_blocks.div_ceil(GROUP_BLOCKS))
+            .map(|group| {
+                let blocks = (data_blocks - group * GROUP_BLOCKS).min(GROUP_BLOCKS);
+                parity_blocks(blocks, percent)
+            })
+            .collect();
+        Ok(Self {
+            data_blocks,
+            group_parity,
+        })
+    }
+
+    fn parity_blocks(&self) -> usize {
+        self.group_parity.iter().sum()
+    }
+
+    /// Size of the CRC tables, signature header copy and footer.
+    fn metadata_len(&self) -> usize {
+        (self.data_blocks + self.parity_blocks()) * 4 + SIGNATURE_HEADER_SIZE as usize + FOOTER_SIZE
+    }
+}
+
+/// Computes a recovery record from the archive data as it is written.
+pub(crate) struct RecordEncoder {
+    percent: usize,
+    /// Data of the current, incomplete block.
+    block: Vec<u8>,
+    /// Parity accumulated for the current group.
+    group_parity: Vec<u8>,
+    /// Data blocks added to the current group.
+    group_blocks: usize,
+    /// Parity blocks of completed groups.
+    parity: Vec<u8>,
+    /// CRC32 of every completed data block.
+    crcs: Vec<u32>,
+    body_len: u64,
+}
+
+impl RecordEncoder {
+    /// Creates an encoder adding `percent` parity blocks per 100 data blocks.
+    pub(crate) fn new(percent: u8) -> Self {
+        let percent = usize::from(percent.clamp(1, 100));
+        Self {
+            percent,
+            block: Vec::with_capacity(BLOCK_SIZE),
+            group_parity: vec![0; percent * BLOCK_SIZE],
+            group_blocks: 0,
+            parity: Vec::new(),
+            crcs: Vec::new(),
+            body_len: 0,
+        }
+    }
+
+    /// Adds archive data following the data added so far.
+    pub(crate) fn update(&mut self, mut data: &[u8]) {
+        self.body_len += data.len() as u64;
+        while !data.is_empty() {
+            let n = (BLOCK_SIZE - self.block.len()).min(data.len());
+            self.block.extend_from_slice(&data[..n]);
+            data = &data[n..];
+            if self.block.len() == BLOCK_SIZE {
+                self.add_block();
+            }
+        }
+    }
+
+    fn add_block(&mut self) {
+        self.crcs.push(crc32fast::hash(&self.block));
+        self.block.resize(BLOCK_SIZE, 0);
+        for (i, parity) in self.group_parity.chunks_mut(BLOCK_SIZE).enumerate() {
+            gf_mul_add(parity, &self.block, coefficient(i, self.group_blocks));
+        }
+        self.block.clear();
+        self.group_blocks += 1;
+        if self.group_blocks == GROUP_BLOCKS {
+            self.flush_group();
+        }
+    }
+
+    fn flush_group(&mut self) {
+        let count = parity_blocks(self.group_blocks, self.percent);
+        self.parity
+            .extend_from_slice(&self.group_parity[..count * BLOCK_SIZE]);
+        self.group_parity.fill(0);
+        self.group_blocks = 0;
+    }
+
+    /// Completes the record, given the final signature header.
+    pub(crate) fn finish(mut self, signature_header: &[u8]) -> Vec<u8> {
+        if !self.block.is_empty() {
+            self.add_block();
+        }
+        if self.group_blocks > 0 {
+            self.flush_group();
+        }
+
+        let mut record = std::mem::take(&mut self.parity);
+        let metadata_start = record.len();
+        let parity_crcs: Vec<u32> = record.chunks(BLOCK_SIZE).map(crc32fast::hash).collect();
+        for crc in self.crcs.iter().chain(&parity_crcs) {
+            record.extend_from_slice(&crc.to_le_bytes());
+        }
+        record.extend_from_slice(signature_header);
+        record.extend_from_slice(&MAGIC);
+        record.push(VERSION);
+        record.push(self.percent as u8);
+        record.extend_from_slice(&[0, 0]);
+        record.extend_from_slice(&(BLOCK_SIZE as u32).to_le_bytes());
+        record.extend_from_slice(&self.body_len.to_le_bytes());
+        let crc = crc32fast::hash(&record[metadata_start..]);
+        record.extend_from_slice(&crc.to_le_bytes());
+        record
+    }
+}
+
+/// Outcome of [`repair_with_record`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RecordRepair {
+    /// Number of damaged 512-byte blocks found in the archive data.
+    pub damaged_blocks: usize,
+    /// Number of damaged blocks that were reconstructed.
+    pub repaired_blocks: usize,
+    /// Whether the signature header was restored from the record.
+    pub signature_header_restored: bool,
+}
+
+impl RecordRepair {
+    /// Returns true if every damaged block was reconstructed.
+    pub fn is_complete(&self) -> bool {
+        self.repaired_blocks == self.damaged_blocks
+    }
+}
+
+/// Repairs an archive in place using its recovery record.
+///
+/// Damaged blocks are found by their CRC and rebuilt from the parity of
+/// their group, as long as a group has no more damaged blocks than intact
+/// parity blocks. The signature header is restored from its copy in the
+/// record. Blocks that cannot be rebuilt are left as they are and counted
+/// in the returned [`RecordRepair`].
+///
+/// # Errors
+///
+/// Returns an error if the archive has no recovery record, if the record
+/// itself is damaged beyond its parity blocks, or on I/O errors.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use zesven::recovery::repair_with_record;
+///
+/// let mut file = std::fs::OpenOptions::new().read(true).write(true).open("damaged.7z")?;
+/// let repair = repair_with_record(&mut file)?;
+/// println!("repaired {} of {} damaged blocks", repair.repaired_blocks, repair.damaged_blocks);
+/// ```
+pub fn repair_with_record<F: Read + Write + Seek>(file: &mut F) -> Result<RecordRepair> {
+    let file_len = file.seek(SeekFrom::End(0)).map_err(Error::Io)?;
+    if file_len < FOOTER_SIZE as u64 {
+        return Err(Error::InvalidFormat("no recovery record found".into()));
+    }
+
+    let mut footer = [0u8; FOOTER_SIZE];
+    file.seek(SeekFrom::Start(file_len - FOOTER_SIZE as u64))
+        .map_err(Error::Io)?;
+    file.read_exact(&mut footer).map_err(Error::Io)?;
+    if footer[..8] != MAGIC {
+        return Err(Error::InvalidFormat("no recovery record found".into()));
+    }
+    if footer[8] != VERSION {
+        return Err(Error::UnsupportedFeature {
+            feature: "recovery record version",
+        });
+    }
+    let percent = usize::from(footer[9]);
+    let block_size = u32::from_le_bytes(footer[12..16].try_into().unwrap_or_default());
+    let body_len = u64::from_le_bytes(footer[16..24].try_into().unwrap_or_default());
+    if !(1..=100).contains(&percent) || block_size as usize != BLOCK_SIZE {
+        return Err(Error::InvalidFormat("recovery record is damaged".into()));
+    }
+
+    // The footer may be damaged too, so check the body length against the
+    // file before sizing anything by it
+    let size_mismatch =
+        || Error::InvalidFormat("recovery record does not match the file size".into());
+    if body_len > file_len.saturating_sub(SIGNATURE_HEADER_SIZE + FOOTER_SIZE as u64) {
+        return Err(size_mismatch());
+    }
+
+    let geometry = Geometry::new(body_len, percent)?;
+    let parity_len = (geometry.parity_blocks() * BLOCK_SIZE) as u64;
+    let metadata_len = geometry.metadata_len() as u64;
+    let body_start = SIGNATURE_HEADER_SIZE;
+    let expected_len = body_start
+        .checked_add(body_len)
+        .and_then(|len| len.checked_add(parity_len))
+        .and_then(|len| len.checked_add(metadata_len));
+    if expected_len != Some(file_len) {
+        return Err(size_mismatch());
+    }
+
+    let mut metadata = vec![0u8; metadata_len as usize];
+    file.seek(SeekFrom::Start(file_len - metadata_len))
+        .map_err(Error::Io)?;
+    file.read_exact(&mut metadata).map_err(Error::Io)?;
+    let (covered, stored_crc) = metadata.split_at(metadata.len() - 4);
+    if crc32fast::hash(covered) != u32::from_le_bytes(stored_crc.try_into().unwrap_or_default()) {
+        return Err(Error::InvalidFormat("recovery record is damaged".into()));
+    }
+    let crcs: Vec<u32> = metadata[..(geometry.data_blocks + geometry.parity_blocks()) * 4]
+        .chunks_exact(4)
+        .map(|c| u32::from_le_bytes(c.try_into().unwrap_or_default()))
+        .collect();
+    let (data_crcs, parity_crcs) = crcs.split_at(geometry.data_blocks);
+
+    let mut repair = RecordRepair::default();
+
+    let header_copy_start = crcs.len() * 4;
+    let header_copy =
+        &metadata[header_copy_start..header_copy_start + SIGNATURE_HEADER_SIZE as usize];
+    let mut signature_header = [0u8; SIGNATURE_HEADER_SIZE as usize];
+    file.seek(SeekFrom::Start(0)).map_err(Error::Io)?;
+    file.read_exact(&mut signature_header).map_err(Error::Io)?;
+    if signature_header[..] != *header_copy {
+        file.seek(SeekFrom::Start(0)).map_err(Error::Io)?;
+        file.write_all(header_copy).map_err(Error::Io)?;
+        repair.signature_header_restored = true;
+    }
+
+    let parity_start = body_start + body_len;
+    let mut first_parity = 0;
+    for (group, &parity_count) in geometry.group_parity.iter().enumerate() {
+        let first_block = group * GROUP_BLOCKS;
+        let blocks = (geometry.data_blocks - first_block).min(GROUP_BLOCKS);
+        let group_start = body_start + (first_block * BLOCK_SIZE) as u64;
+        let group_len = (body_len - (first_block * BLOCK_SIZE) as u64)
+            .min((GROUP_BLOCKS * BLOCK_SIZE) as u64) as usize;
+
+        let mut data = vec![0u8; blocks * BLOCK_SIZE];
+        file.seek(SeekFrom::Start(group_start)).map_err(Error::Io)?;
+        file.read_exact(&mut data[..group_len]).map_err(Error::Io)?;
+
+        let damaged: Vec<usize> = (0..blocks)
+            .filter(|&i| {
+                let len = BLOCK_SIZE.min(group_len - i * BLOCK_SIZE);
+                let block = &data[i * BLOCK_SIZE..i * BLOCK_SIZE + len];
+                crc32fast::hash(block) != data_crcs[first_block + i]
+            })
+            .collect();
+        let group_first_parity = first_parity;
+        first_parity += parity_count;
+        if damaged.is_empty() {
+            continue;
+        }
+        repair.damaged_blocks += damaged.len();
+
+        let mut parity = vec![0u8; parity_count * BLOCK_SIZE];
+        file.seek(SeekFrom::Start(
+            parity_start + (group_first_parity * BLOCK_SIZE) as u64,
+        ))
+        .map_err(Error::Io)?;
+        file.read_exact(&mut parity).map_err(Error::Io)?;
+        let rows: Vec<usize> = (0..parity_count)
+            .filter(|&i| {
+                crc32fast::hash(&parity[i * BLOCK_SIZE..(i + 1) * BLOCK_SIZE])
+                    == parity_crcs[group_first_parity + i]
+            })
+            .take(damaged.len())
+            .collect();
+        if rows.len() < damaged.len() {
+            continue;
+        }
+
+        let rebuilt = rebuild_blocks(&data, &parity, &damaged, &rows);
+        for (&i, block) in damaged.iter().zip(rebuilt.chunks(BLOCK_SIZE)) {
+            let len = BLOCK_SIZE.min(group_len - i * BLOCK_SIZE);
+            if crc32fast::hash(&block[..len]) != data_crcs[first_block + i] {
+                continue;
+            }
+            file.seek(SeekFrom::Start(group_start + (i * BLOCK_SIZE) as u64))
+                .map_err(Error::Io)?;
+            file.write_all(&block[..len]).map_err(Error::Io)?;
+            repair.repaired_blocks += 1;
+        }
+    }
+
+    file.flush().map_err(Error::Io)?;
+    Ok(repair)
+}
+
+/// Rebuilds the `damaged` data blocks of a group from the parity `rows`.
+///
+/// Returns the rebuilt blocks, concatenated in the order of `damaged`.
+fn rebuild_blocks(data: &[u8], parity: &[u8], damaged: &[usize], rows: &[usize]) -> Vec<u8> {
+    let n = damaged.len();
+
+    // Syndromes: parity minus the contribution of the intact blocks
+    let mut syndromes = vec![0u8; n * BLOCK_SIZE];
+    for (r, &row) in rows.iter().enumerate() {
+        let syndrome = &mut syndromes[r * BLOCK_SIZE..(r + 1) * BLOCK_SIZE];
+        syndrome.copy_from_slice(&parity[row * BLOCK_SIZE..(row + 1) * BLOCK_SIZE]);
+        for (i, block) in data.chunks(BLOCK_SIZE).enumerate() {
+            if !damaged.contains(&i) {
+                gf_mul_add(syndrome, block, coefficient(row, i));
+            }
+        }
+    }
+
+    // Invert the Cauchy submatrix by Gauss-Jordan elimination
+    let mut matrix: Vec<Vec<u8>> = rows
+        .iter()
+        .map(|&row| damaged.iter().map(|&i| coefficient(row, i)).collect())
+        .collect();
+    let mut inverse: Vec<Vec<u8>> = (0..n)
+        .map(|r| (0..n).map(|c| u8::from(r == c)).collect())
+        .collect();
+    for col in 0..n {
+        let pivot = (col..n)
+            .find(|&r| matrix[r][col] != 0)
+            .expect("Cauchy submatrices are invertible");
+        matrix.swap(col, pivot);
+        inverse.swap(col, pivot);
+        let scale = gf_inv(matrix[col][col]);
+        for c in 0..n {
+            matrix[col][c] = gf_mul(matrix[col][c], scale);
+            inverse[col][c] = gf_mul(inverse[col][c], scale);
+        }
+        for r in 0..n {
+            let factor = matrix[r][col];
+            if r == col || factor == 0 {
+                continue;
+            }
+            for c in 0..n {
+                matrix[r][c] ^= gf_mul(factor, matrix[col][c]);
+                inverse[r][c] ^= gf_mul(factor, inverse[col][c]);
+            }
+        }
+    }
+
+    let mut rebuilt = vec![0u8; n * BLOCK_SIZE];
+    for (d, block) in rebuilt.chunks_mut(BLOCK_SIZE).enumerate() {
+        for (r, syndrome) in syndromes.chunks(BLOCK_SIZE).enumerate() {
+            gf_mul_add(block, syndrome, inverse[d][r]);
+        }
+    }
+    rebuilt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Builds a fake archive of `body_len` bytes with a recovery record.
+    fn protected(body_len: usize, percent: u8) -> Vec<u8> {
+        let header: Vec<u8> = (0..SIGNATURE_HEADER_SIZE as u8).collect();
+        let body: Vec<u8> = (0..body_len).map(|i| (i * 7 + i / 300) as u8).collect();
+        let mut encoder = RecordEncoder::new(percent);
+        for chunk in body.chunks(333) {
+            encoder.update(chunk);
+        }
+        let mut file = header.clone();
+        file.extend_from_slice(&body);
+        file.extend_from_slice(&encoder.finish(&header));
+        file
+    }
+
+    #[test]
+    fn test_gf_inverse() {
+        for a in 1..=255u8 {
+            assert_eq!(gf_mul(a, gf_inv(a)), 1);
+        }
+    }
+
+    #[test]
+    fn test_repair_with_record_rebuilds_blocks() {
+        let original = protected(120_000, 10);
+        let mut damaged = original.clone();
+        // Ten blocks of the first group, one of the last and the signature header
+        for byte in &mut damaged[1000..1000 + 9 * BLOCK_SIZE] {
+            *byte ^= 0x5a;
+        }
+        damaged[119_900] ^= 1;
+        damaged[3] = 0;
+
+        let mut file = Cursor::new(damaged);
+        let repair = repair_with_record(&mut file).unwrap();
+        assert_eq!(repair.damaged_blocks, 11);
+        assert!(repair.is_complete());
+        assert!(repair.signature_header_restored);
+        assert_eq!(file.into_inner(), original);
+
+        let mut intact = Cursor::new(original.clone());
+        assert_eq!(
+            repair_with_record(&mut intact).unwrap(),
+            RecordRepair::default()
+        );
+    }
+
+    #[test]
+    fn test_repair_with_record_reports_excess_damage() {
+        let original = protected(60_000, 1);
+        let mut damaged = original.clone();
+        damaged[100] ^= 1;
+        damaged[5000] ^= 1;
+
+        let repair = repair_with_record(&mut Cursor::new(damaged)).unwrap();
+        assert_eq!(repair.damaged_blocks, 2);
+        assert_eq!(repair.repaired_blocks, 0);
+        assert!(!repair.is_complete());
+    }
+
+    #[test]
+    fn test_repair_with_damaged_body_len() {
+        let original = protected(60_000, 5);
+        let body_len_at = original.len() - FOOTER_SIZE + 16;
+        for body_len in [1u64 << 56, u64::MAX, 60_001] {
+            let mut damaged = original.clone();
+            damaged[body_len_at..body_len_at + 8].copy_from_slice(&body_len.to_le_bytes());
+            let err = repair_with_record(&mut Cursor::new(damaged)).unwrap_err();
+            assert!(matches!(err, Error::InvalidFormat(_)), "{err:?}");
+        }
+    }
+
+    #[test]
+    fn test_repair_without_record() {
+        let mut file = Cursor::new(vec![0u8; 100]);
+        assert!(repair_with_record(&mut file).is_err());
+    }
+}
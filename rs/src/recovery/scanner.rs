@@ -7,17 +7,29 @@
 use crate::format::SIGNATURE;
 use crate::{Error, Result};
 use std::io::{Read, Seek, SeekFrom};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Number of bytes read or searched between cancellation checks.
+const CANCEL_CHECK_INTERVAL: usize = 1024 * 1024;
 
 /// Scanner for finding 7z signatures in binary data.
 ///
 /// The scanner buffers data and searches for the 7z signature pattern,
 /// validating version bytes to reduce false positives.
+///
+/// A long scan can be aborted with [`with_cancellation`](Self::with_cancellation):
+/// the flag is checked after every mebibyte read or searched, and a cancelled
+/// scan returns the signatures found so far (possibly none) with
+/// [`is_cancelled`](Self::is_cancelled) reporting `true`.
 pub struct SignatureScanner<'a, R: Read + Seek> {
     reader: &'a mut R,
     search_limit: usize,
     buffer: Vec<u8>,
     current_offset: u64,
     bytes_read: usize,
+    cancel_flag: Option<Arc<AtomicBool>>,
+    cancelled: bool,
 }
 
 impl<'a, R: Read + Seek> SignatureScanner<'a, R> {
@@ -34,79 +46,58 @@ impl<'a, R: Read + Seek> SignatureScanner<'a, R> {
             buffer: Vec::new(),
             current_offset: 0,
             bytes_read: 0,
+            cancel_flag: None,
+            cancelled: false,
         }
     }
 
+    /// Sets a flag that aborts the scan when it becomes `true`.
+    pub fn with_cancellation(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.cancel_flag = Some(flag);
+        self
+    }
+
+    /// Returns true if the last scan stopped early because it was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled
+    }
+
     /// Finds the next 7z signature starting from the current position.
     ///
     /// # Returns
     ///
     /// Returns `Ok(Some(offset))` if a signature is found, where offset
     /// is the absolute position in the file. Returns `Ok(None)` if no
-    /// signature is found within the search limit.
+    /// signature is found within the search limit or the scan was cancelled.
     pub fn find_next_signature(&mut self) -> Result<Option<u64>> {
         self.ensure_buffer_loaded()?;
+        Ok(self.search_buffer(true).first().copied())
+    }
 
-        // Search for signature in buffer
-        let mut search_start = 0;
-        while search_start + 8 <= self.buffer.len() {
-            if let Some(rel_pos) = self.buffer[search_start..]
-                .windows(6)
-                .position(|w| w == SIGNATURE)
-            {
-                let pos = search_start + rel_pos;
-                // Validate version bytes
-                if pos + 8 <= self.buffer.len() {
-                    let version_major = self.buffer[pos + 6];
-                    let version_minor = self.buffer[pos + 7];
-                    // Valid 7z versions: major = 0, minor <= 10
-                    if version_major == 0 && version_minor <= 10 {
-                        return Ok(Some(self.current_offset + pos as u64));
-                    }
-                }
-                // False positive, continue searching
-                search_start = pos + 1;
-            } else {
-                break;
-            }
-        }
-
-        Ok(None)
+    /// Finds the first signature whose start header CRC validates.
+    ///
+    /// SFX stubs can contain signature bytes of their own, so a validated
+    /// start header is preferred over the first raw match. Falls back to
+    /// the first raw match when no start header validates.
+    pub fn find_valid_signature(&mut self) -> Result<Option<u64>> {
+        self.ensure_buffer_loaded()?;
+        let signatures = self.search_buffer(false);
+        let valid = signatures.iter().copied().find(|&offset| {
+            let pos = (offset - self.current_offset) as usize;
+            validate_start_header(&self.buffer[pos..])
+        });
+        Ok(valid.or_else(|| signatures.first().copied()))
     }
 
     /// Finds all 7z signatures in the search range.
     ///
     /// # Returns
     ///
-    /// Returns a vector of all signature offsets found.
+    /// Returns a vector of all signature offsets found. If the scan was
+    /// cancelled, only the signatures found before cancellation are returned.
     pub fn find_all_signatures(&mut self) -> Result<Vec<u64>> {
         self.ensure_buffer_loaded()?;
-
-        let mut signatures = Vec::new();
-        let mut search_start = 0;
-
-        while search_start + 8 <= self.buffer.len() {
-            if let Some(rel_pos) = self.buffer[search_start..]
-                .windows(6)
-                .position(|w| w == SIGNATURE)
-            {
-                let pos = search_start + rel_pos;
-                // Validate version bytes
-                if pos + 8 <= self.buffer.len() {
-                    let version_major = self.buffer[pos + 6];
-                    let version_minor = self.buffer[pos + 7];
-                    // Valid 7z versions: major = 0, minor <= 10
-                    if version_major == 0 && version_minor <= 10 {
-                        signatures.push(self.current_offset + pos as u64);
-                    }
-                }
-                search_start = pos + 1;
-            } else {
-                break;
-            }
-        }
-
-        Ok(signatures)
+        Ok(self.search_buffer(false))
     }
 
     /// Scans backwards from the end of the file.
@@ -130,12 +121,10 @@ impl<'a, R: Read + Seek> SignatureScanner<'a, R> {
             .seek(SeekFrom::Start(start_pos))
             .map_err(Error::Io)?;
         self.current_offset = start_pos;
-        self.buffer.clear();
-        self.buffer.resize(read_size, 0);
-        self.reader
-            .read_exact(&mut self.buffer)
-            .map_err(Error::Io)?;
-        self.bytes_read = read_size;
+        self.fill_buffer(read_size)?;
+        if self.buffer.len() < read_size && !self.cancelled {
+            return Err(Error::Io(std::io::ErrorKind::UnexpectedEof.into()));
+        }
 
         // Find all signatures and reverse the order
         let mut signatures = self.find_all_signatures()?;
@@ -154,14 +143,74 @@ impl<'a, R: Read + Seek> SignatureScanner<'a, R> {
         self.current_offset = self.reader.stream_position().map_err(Error::Io)?;
 
         // Read up to search_limit bytes
-        self.buffer.resize(self.search_limit, 0);
-        let bytes_read = self.reader.read(&mut self.buffer).map_err(Error::Io)?;
-        self.buffer.truncate(bytes_read);
-        self.bytes_read = bytes_read;
+        self.fill_buffer(self.search_limit)
+    }
 
+    /// Reads up to `limit` bytes into the buffer, checking for cancellation
+    /// between chunks.
+    fn fill_buffer(&mut self, limit: usize) -> Result<()> {
+        self.buffer.clear();
+        while self.buffer.len() < limit {
+            if self.check_cancelled() {
+                break;
+            }
+            let start = self.buffer.len();
+            let chunk = CANCEL_CHECK_INTERVAL.min(limit - start);
+            self.buffer.resize(start + chunk, 0);
+            let n = self
+                .reader
+                .read(&mut self.buffer[start..])
+                .map_err(Error::Io)?;
+            self.buffer.truncate(start + n);
+            if n == 0 {
+                break;
+            }
+        }
+        self.bytes_read = self.buffer.len();
         Ok(())
     }
 
+    /// Searches the buffer for signatures with valid version bytes,
+    /// checking for cancellation between chunks.
+    fn search_buffer(&mut self, first_only: bool) -> Vec<u64> {
+        let mut signatures = Vec::new();
+        // A match needs 8 bytes: the 6-byte signature plus 2 version bytes
+        let end = self.buffer.len().saturating_sub(7);
+        let mut chunk_start = 0;
+
+        while chunk_start < end {
+            if self.check_cancelled() {
+                break;
+            }
+            let chunk_end = (chunk_start + CANCEL_CHECK_INTERVAL).min(end);
+            for (i, window) in self.buffer[chunk_start..chunk_end + 7]
+                .windows(8)
+                .enumerate()
+            {
+                // Valid 7z versions: major = 0, minor <= 10
+                if &window[..6] == SIGNATURE && window[6] == 0 && window[7] <= 10 {
+                    signatures.push(self.current_offset + (chunk_start + i) as u64);
+                    if first_only {
+                        return signatures;
+                    }
+                }
+            }
+            chunk_start = chunk_end;
+        }
+
+        signatures
+    }
+
+    /// Checks the cancellation flag, remembering if it was set.
+    fn check_cancelled(&mut self) -> bool {
+        if let Some(flag) = &self.cancel_flag {
+            if flag.load(Ordering::Relaxed) {
+                self.cancelled = true;
+            }
+        }
+        self.cancelled
+    }
+
     /// Returns the number of bytes that were read.
     pub fn bytes_scanned(&self) -> usize {
         self.bytes_read
@@ -171,6 +220,7 @@ impl<'a, R: Read + Seek> SignatureScanner<'a, R> {
     pub fn reset(&mut self) -> Result<()> {
         self.buffer.clear();
         self.bytes_read = 0;
+        self.cancelled = false;
         self.reader.seek(SeekFrom::Start(0)).map_err(Error::Io)?;
         self.current_offset = 0;
         Ok(())
@@ -180,7 +230,6 @@ impl<'a, R: Read + Seek> SignatureScanner<'a, R> {
 /// Checks if data at the given position looks like a valid 7z start header.
 ///
 /// This performs additional validation beyond just the signature.
-#[allow(dead_code)] // Part of recovery API for archive scanning
 pub fn validate_start_header(data: &[u8]) -> bool {
     // Need at least 32 bytes for a start header
     if data.len() < 32 {
@@ -296,6 +345,22 @@ mod tests {
         assert_eq!(offset, None);
     }
 
+    #[test]
+    fn test_scanner_prefers_valid_start_header() {
+        // A stub containing signature and version bytes without a valid header
+        let mut data = vec![0xFFu8; 16];
+        data.extend_from_slice(SIGNATURE);
+        data.extend_from_slice(&[0x00, 0x04]);
+        data.extend_from_slice(&[0xAAu8; 40]);
+        let real_offset = data.len() as u64;
+        data.extend_from_slice(&create_valid_header());
+
+        let mut cursor = Cursor::new(data);
+        let mut scanner = SignatureScanner::new(&mut cursor, 1024);
+        assert_eq!(scanner.find_next_signature().unwrap(), Some(16));
+        assert_eq!(scanner.find_valid_signature().unwrap(), Some(real_offset));
+    }
+
     #[test]
     fn test_scanner_find_all() {
         let header = create_valid_header();
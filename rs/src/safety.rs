@@ -7,8 +7,11 @@ use std::io::{self, Read};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::{Duration, Instant};
 
-use crate::{ArchivePath, Error, Result};
+use crate::format::streams::LimitMode;
+use crate::{ArchivePath, Error, READ_BUFFER_SIZE, Result};
 
 /// Policy for validating extraction paths.
 ///
@@ -242,6 +245,10 @@ pub struct LimitedReader<R> {
     compressed_size: u64,
     /// Maximum compression ratio allowed.
     max_ratio: Option<u32>,
+    /// How to handle exceeding `max_ratio`.
+    ratio_mode: LimitMode,
+    /// Whether an exceeded ratio has already been logged in warn mode.
+    ratio_warned: bool,
     /// Shared counter for total bytes across all entries.
     total_tracker: Option<Arc<AtomicU64>>,
     /// Maximum total bytes.
@@ -257,6 +264,8 @@ impl<R> LimitedReader<R> {
             bytes_read: 0,
             compressed_size: 0,
             max_ratio: None,
+            ratio_mode: LimitMode::HardError,
+            ratio_warned: false,
             total_tracker: None,
             max_total_bytes: u64::MAX,
         }
@@ -280,6 +289,15 @@ impl<R> LimitedReader<R> {
         self
     }
 
+    /// Sets how exceeding the maximum compression ratio is handled.
+    ///
+    /// Defaults to [`LimitMode::HardError`]. [`LimitMode::Warn`] logs a
+    /// warning once and keeps reading.
+    pub fn ratio_mode(mut self, mode: LimitMode) -> Self {
+        self.ratio_mode = mode;
+        self
+    }
+
     /// Sets a shared tracker for total bytes.
     pub fn total_tracker(mut self, tracker: Arc<AtomicU64>, max_total: u64) -> Self {
         self.total_tracker = Some(tracker);
@@ -322,13 +340,20 @@ impl<R: Read> Read for LimitedReader<R> {
             if self.compressed_size > 0 {
                 // Use saturating_mul to handle potential overflow safely
                 let max_allowed = (max_ratio as u64).saturating_mul(self.compressed_size);
-                if self.bytes_read > max_allowed {
+                if self.bytes_read > max_allowed && self.ratio_mode != LimitMode::Ignore {
                     // Calculate actual ratio for error message (truncation OK for display)
                     let actual_ratio = self.bytes_read / self.compressed_size;
-                    return Err(io::Error::other(Error::ResourceLimitExceeded(format!(
+                    let message = format!(
                         "Compression ratio {}:1 exceeds limit {}:1 (compressed: {}, uncompressed: {})",
                         actual_ratio, max_ratio, self.compressed_size, self.bytes_read
-                    ))));
+                    );
+                    if self.ratio_mode == LimitMode::HardError {
+                        return Err(io::Error::other(Error::ResourceLimitExceeded(message)));
+                    }
+                    if !self.ratio_warned {
+                        log::warn!("{}", message);
+                        self.ratio_warned = true;
+                    }
                 }
             }
         }
@@ -355,6 +380,115 @@ impl<R> std::fmt::Debug for LimitedReader<R> {
             .field("bytes_read", &self.bytes_read)
             .field("compressed_size", &self.compressed_size)
             .field("max_ratio", &self.max_ratio)
+            .field("ratio_mode", &self.ratio_mode)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Number of decoded chunks a [`DeadlineReader`] worker may buffer ahead.
+const DEADLINE_CHANNEL_CAPACITY: usize = 4;
+
+/// A reader wrapper that decodes on a worker thread with a deadline.
+///
+/// The inner reader runs on a spawned thread that hands decoded chunks over a
+/// bounded channel. Once `timeout` has elapsed since creation, reads fail with
+/// an [`io::ErrorKind::TimedOut`] error wrapping [`Error::Timeout`].
+///
+/// Dropping the reader disconnects the channel; the worker stops as soon as
+/// its current read of the inner reader returns.
+pub struct DeadlineReader {
+    receiver: Receiver<io::Result<Vec<u8>>>,
+    chunk: Vec<u8>,
+    pos: usize,
+    deadline: Instant,
+    timeout: Duration,
+    finished: bool,
+}
+
+impl DeadlineReader {
+    /// Starts reading `inner` on a worker thread.
+    pub fn new<R: Read + Send + 'static>(mut inner: R, timeout: Duration) -> Self {
+        let (sender, receiver) = mpsc::sync_channel(DEADLINE_CHANNEL_CAPACITY);
+        std::thread::spawn(move || {
+            loop {
+                let mut buf = vec![0u8; READ_BUFFER_SIZE];
+                let message = match inner.read(&mut buf) {
+                    Ok(0) => break,
+                    Ok(n) => {
+                        buf.truncate(n);
+                        Ok(buf)
+                    }
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(e) => Err(e),
+                };
+                let failed = message.is_err();
+                if sender.send(message).is_err() || failed {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            receiver,
+            chunk: Vec::new(),
+            pos: 0,
+            deadline: Instant::now() + timeout,
+            timeout,
+            finished: false,
+        }
+    }
+
+    fn timed_out(&self) -> io::Error {
+        io::Error::new(
+            io::ErrorKind::TimedOut,
+            Error::Timeout {
+                timeout: self.timeout,
+            },
+        )
+    }
+}
+
+impl Read for DeadlineReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        while self.pos >= self.chunk.len() {
+            if self.finished {
+                return Ok(0);
+            }
+            let remaining = self
+                .deadline
+                .checked_duration_since(Instant::now())
+                .filter(|d| !d.is_zero())
+                .ok_or_else(|| self.timed_out())?;
+            match self.receiver.recv_timeout(remaining) {
+                Ok(Ok(chunk)) => {
+                    self.chunk = chunk;
+                    self.pos = 0;
+                }
+                Ok(Err(e)) => {
+                    self.finished = true;
+                    return Err(e);
+                }
+                Err(RecvTimeoutError::Timeout) => return Err(self.timed_out()),
+                Err(RecvTimeoutError::Disconnected) => self.finished = true,
+            }
+        }
+
+        let n = buf.len().min(self.chunk.len() - self.pos);
+        buf[..n].copy_from_slice(&self.chunk[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+impl std::fmt::Debug for DeadlineReader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DeadlineReader")
+            .field("timeout", &self.timeout)
+            .field("finished", &self.finished)
             .finish_non_exhaustive()
     }
 }
@@ -515,6 +649,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_limited_reader_ratio_warn_mode_keeps_reading() {
+        let data = vec![0u8; 1000];
+        let mut reader = LimitedReader::new(Cursor::new(data))
+            .compressed_size(1)
+            .max_ratio(10)
+            .ratio_mode(LimitMode::Warn);
+
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf).unwrap();
+        assert_eq!(buf.len(), 1000);
+    }
+
     #[test]
     fn test_limited_reader_ratio_one_over_boundary() {
         // Test one byte over boundary should fail
@@ -531,4 +678,50 @@ mod tests {
             "Ratio 100.1:1 should exceed limit of 100:1"
         );
     }
+
+    /// Reader that sleeps before every read, standing in for a slow decoder.
+    struct SlowReader<R> {
+        inner: R,
+        delay: Duration,
+    }
+
+    impl<R: Read> Read for SlowReader<R> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            std::thread::sleep(self.delay);
+            self.inner.read(buf)
+        }
+    }
+
+    #[test]
+    fn test_deadline_reader_times_out() {
+        let slow = SlowReader {
+            inner: Cursor::new(vec![0u8; 1000]),
+            delay: Duration::from_secs(5),
+        };
+        let mut reader = DeadlineReader::new(slow, Duration::from_millis(50));
+
+        let mut buf = Vec::new();
+        let err = reader.read_to_end(&mut buf).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+        let inner = err.get_ref().and_then(|e| e.downcast_ref::<Error>());
+        assert!(
+            matches!(inner, Some(Error::Timeout { timeout }) if *timeout == Duration::from_millis(50)),
+            "expected Error::Timeout, got {:?}",
+            inner
+        );
+    }
+
+    #[test]
+    fn test_deadline_reader_passes_data_through() {
+        let data: Vec<u8> = (0..200_000u32).map(|i| i as u8).collect();
+        let slow = SlowReader {
+            inner: Cursor::new(data.clone()),
+            delay: Duration::from_millis(1),
+        };
+        let mut reader = DeadlineReader::new(slow, Duration::from_secs(60));
+
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf).unwrap();
+        assert_eq!(buf, data);
+    }
 }
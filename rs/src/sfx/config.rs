@@ -38,6 +38,9 @@ pub struct SfxConfig {
 
     /// Error title shown in error dialogs.
     pub error_title: Option<String>,
+
+    /// Whether the encoded config starts with a UTF-8 byte order mark.
+    pub bom: bool,
 }
 
 impl SfxConfig {
@@ -88,6 +91,16 @@ impl SfxConfig {
         self
     }
 
+    /// Sets whether the encoded config starts with a UTF-8 byte order mark.
+    ///
+    /// The config is always UTF-8. Some SFX stubs only recognize it as
+    /// UTF-8 when it starts with a BOM; others reject the BOM, so it is
+    /// off by default.
+    pub fn with_bom(mut self, bom: bool) -> Self {
+        self.bom = bom;
+        self
+    }
+
     /// Returns true if any configuration settings are set.
     pub fn has_settings(&self) -> bool {
         self.title.is_some()
@@ -102,13 +115,18 @@ impl SfxConfig {
 
     /// Encodes the configuration into 7-Zip SFX config format.
     ///
-    /// Format: `;!@Install@!UTF-8!` marker followed by key=value pairs.
+    /// Format: `;!@Install@!UTF-8!` marker followed by key=value pairs,
+    /// encoded as UTF-8 and preceded by a byte order mark if
+    /// [`with_bom`](Self::with_bom) is set.
     pub fn encode(&self) -> Vec<u8> {
         if !self.has_settings() {
             return Vec::new();
         }
 
         let mut config = String::new();
+        if self.bom {
+            config.push('\u{FEFF}');
+        }
         config.push_str(";!@Install@!UTF-8!\n");
 
         if let Some(ref title) = self.title {
@@ -214,4 +232,23 @@ mod tests {
         assert_eq!(config.run_program, Some("./install.sh".to_string()));
         assert!(config.progress);
     }
+
+    #[test]
+    fn test_config_with_bom_and_non_ascii_title() {
+        let config = SfxConfig::new()
+            .title("Установщик 安装程序")
+            .run_program("démarrer.exe")
+            .with_bom(true);
+        let encoded = config.encode();
+
+        assert!(encoded.starts_with(&[0xEF, 0xBB, 0xBF]));
+        assert!(encoded[3..].starts_with(b";!@Install@!UTF-8!\n"));
+        let title = "Title=\"Установщик 安装程序\"".as_bytes();
+        assert!(encoded.windows(title.len()).any(|w| w == title));
+        let text = std::str::from_utf8(&encoded).unwrap();
+        assert!(text.contains("RunProgram=\"démarrer.exe\""));
+
+        let without_bom = config.with_bom(false).encode();
+        assert_eq!(without_bom, encoded[3..]);
+    }
 }
@@ -0,0 +1,218 @@
+//! Sparse file support.
+//!
+//! Files with holes (ranges the filesystem has not allocated, which read as
+//! zeros) are detected when they are archived from disk with
+//! [`WriteOptions::preserve_sparse`](crate::WriteOptions::preserve_sparse),
+//! and the hole map is stored in the archive header. On extraction, [`SparseWriter`] seeks over
+//! those ranges instead of writing zeros, so the output is sparse as well.
+
+use std::fs::File;
+use std::io::{self, Seek, SeekFrom, Write};
+
+/// Returns the holes of `file` as `(offset, length)` pairs.
+///
+/// Holes are found with `SEEK_DATA`/`SEEK_HOLE` on the 64-bit targets in
+/// [`sys`]. The list is empty on every other target and on filesystems
+/// that do not report holes.
+/// The file offset is reset to the start of the file.
+pub(crate) fn detect_holes(file: &mut File, len: u64) -> io::Result<Vec<(u64, u64)>> {
+    let holes = seek_holes(file, len).unwrap_or_default();
+    file.seek(SeekFrom::Start(0))?;
+    Ok(holes)
+}
+
+/// `lseek(2)` and the constants it needs, on the targets whose values have
+/// been checked against the system headers.
+///
+/// `off_t` is 64 bits wide on all of them. Other targets get no bindings and
+/// report no holes.
+#[cfg(all(
+    target_pointer_width = "64",
+    any(target_os = "linux", target_os = "android", target_os = "macos")
+))]
+mod sys {
+    use std::ffi::c_int;
+
+    unsafe extern "C" {
+        pub fn lseek(fd: c_int, offset: i64, whence: c_int) -> i64;
+    }
+
+    /// `<sys/unistd.h>` on macOS.
+    #[cfg(target_os = "macos")]
+    pub const SEEK_HOLE: c_int = 3;
+    #[cfg(target_os = "macos")]
+    pub const SEEK_DATA: c_int = 4;
+
+    /// `<linux/fs.h>` (`uapi`), shared by every Linux architecture.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub const SEEK_DATA: c_int = 3;
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub const SEEK_HOLE: c_int = 4;
+
+    /// No data at or after the requested offset.
+    pub const ENXIO: c_int = 6;
+}
+
+#[cfg(all(
+    target_pointer_width = "64",
+    any(target_os = "linux", target_os = "android", target_os = "macos")
+))]
+fn seek_holes(file: &File, len: u64) -> io::Result<Vec<(u64, u64)>> {
+    use std::os::fd::AsRawFd;
+    use sys::{ENXIO, SEEK_DATA, SEEK_HOLE};
+
+    let seek = |offset: u64, whence: std::ffi::c_int| -> io::Result<Option<u64>> {
+        let offset = i64::try_from(offset).map_err(io::Error::other)?;
+        // SAFETY: the descriptor is valid for the lifetime of the borrowed file
+        // and lseek only moves its offset.
+        let pos = unsafe { sys::lseek(file.as_raw_fd(), offset, whence) };
+        if pos >= 0 {
+            return Ok(Some(pos as u64));
+        }
+        let err = io::Error::last_os_error();
+        if err.raw_os_error() == Some(ENXIO) {
+            Ok(None)
+        } else {
+            Err(err)
+        }
+    };
+
+    let mut holes = Vec::new();
+    let mut pos = 0;
+    while pos < len {
+        let Some(data) = seek(pos, SEEK_DATA)? else {
+            holes.push((pos, len - pos));
+            break;
+        };
+        let data = data.min(len);
+        if data > pos {
+            holes.push((pos, data - pos));
+        }
+        pos = seek(data, SEEK_HOLE)?.unwrap_or(len);
+    }
+    Ok(holes)
+}
+
+#[cfg(not(all(
+    target_pointer_width = "64",
+    any(target_os = "linux", target_os = "android", target_os = "macos")
+)))]
+fn seek_holes(_file: &File, _len: u64) -> io::Result<Vec<(u64, u64)>> {
+    Ok(Vec::new())
+}
+
+/// Writes entry data to a file, leaving the given holes unallocated.
+///
+/// Zero bytes inside a hole are skipped with a seek; anything else is written
+/// as usual, so a hole map that does not match the data cannot corrupt the
+/// output. [`finish`](Self::finish) sets the final length, which also creates
+/// a hole at the end of the file.
+pub(crate) struct SparseWriter<'a> {
+    file: &'a mut File,
+    holes: &'a [(u64, u64)],
+    pos: u64,
+}
+
+impl<'a> SparseWriter<'a> {
+    /// Creates a writer starting at the current (zero) offset of `file`.
+    pub fn new(file: &'a mut File, holes: &'a [(u64, u64)]) -> Self {
+        Self {
+            file,
+            holes,
+            pos: 0,
+        }
+    }
+
+    /// Extends the file to the number of bytes written.
+    ///
+    /// Does nothing when there are no holes.
+    pub fn finish(self) -> io::Result<()> {
+        if self.holes.is_empty() {
+            return Ok(());
+        }
+        self.file.set_len(self.pos)
+    }
+}
+
+impl Write for SparseWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        let pos = self.pos;
+        let hole_end = self
+            .holes
+            .iter()
+            .map(|&(offset, len)| (offset, offset.saturating_add(len)))
+            .find(|&(start, end)| start <= pos && pos < end)
+            .map(|(_, end)| end);
+
+        let n = match hole_end {
+            Some(end) => {
+                let n = buf
+                    .len()
+                    .min(usize::try_from(end - pos).unwrap_or(usize::MAX));
+                if buf[..n].iter().all(|&b| b == 0) {
+                    self.file.seek(SeekFrom::Current(n as i64))?;
+                } else {
+                    self.file.write_all(&buf[..n])?;
+                }
+                n
+            }
+            None => {
+                let next_hole = self
+                    .holes
+                    .iter()
+                    .map(|&(offset, _)| offset)
+                    .filter(|&offset| offset > pos)
+                    .min()
+                    .unwrap_or(u64::MAX);
+                let n = buf
+                    .len()
+                    .min(usize::try_from(next_hole - pos).unwrap_or(usize::MAX));
+                self.file.write_all(&buf[..n])?;
+                n
+            }
+        };
+
+        self.pos += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn test_sparse_writer_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.bin");
+        let mut data = vec![0u8; 3000];
+        data[..10].fill(1);
+        // Non-zero byte inside a declared hole must still be written
+        data[1500] = 7;
+
+        let holes = [(100, 1900), (2500, 500)];
+        let mut file = File::create(&path).unwrap();
+        let mut writer = SparseWriter::new(&mut file, &holes);
+        for chunk in data.chunks(256) {
+            writer.write_all(chunk).unwrap();
+        }
+        writer.finish().unwrap();
+        drop(file);
+
+        let mut written = Vec::new();
+        File::open(&path)
+            .unwrap()
+            .read_to_end(&mut written)
+            .unwrap();
+        assert_eq!(written, data);
+    }
+}
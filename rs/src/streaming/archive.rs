@@ -8,9 +8,9 @@ use std::io::{BufReader, Read, Seek};
 use std::path::Path;
 
 use crate::format::header::StartHeader;
-use crate::format::parser::{ArchiveHeader, read_archive_header};
+use crate::format::parser::ArchiveHeader;
 use crate::format::streams::ResourceLimits;
-use crate::read::{Entry, ExtractOptions};
+use crate::read::{Entry, ExtractOptions, extraction_path};
 use crate::{ArchivePath, Error, Result};
 
 #[cfg(feature = "aes")]
@@ -71,7 +71,6 @@ pub struct StreamingArchive<R> {
     /// Source reader
     reader: R,
     /// Start header
-    #[allow(dead_code)] // Header metadata preserved for archive info
     start_header: StartHeader,
     /// Archive header
     header: ArchiveHeader,
@@ -154,7 +153,7 @@ impl<R: Read + Seek + Send> StreamingArchive<R> {
                 config.max_compression_ratio,
             )));
 
-        let (start_header, header) = read_archive_header(&mut reader, Some(limits))?;
+        let (start_header, header) = super::read_headers(&mut reader, limits)?;
         let (entries, skipped_entries) = Self::build_entries(&header);
         let is_solid = super::check_is_solid(&header);
         let memory_tracker = MemoryTracker::new(config.max_memory_buffer);
@@ -187,7 +186,7 @@ impl<R: Read + Seek + Send> StreamingArchive<R> {
                 config.max_compression_ratio,
             )));
 
-        let (start_header, header) = read_archive_header(&mut reader, Some(limits))?;
+        let (start_header, header) = super::read_headers(&mut reader, limits)?;
         let (entries, skipped_entries) = Self::build_entries(&header);
         let is_solid = super::check_is_solid(&header);
         let memory_tracker = MemoryTracker::new(config.max_memory_buffer);
@@ -249,9 +248,17 @@ impl<R: Read + Seek + Send> StreamingArchive<R> {
                 }
             };
 
-            let (folder_index, stream_index) = if archive_entry.is_directory {
+            // Directories and empty files have no stream
+            let (folder_index, stream_index) = if !archive_entry.has_stream {
                 (None, None)
             } else {
+                // Folders without unpack streams (e.g. alignment padding) hold no entries
+                if let Some(ss) = substreams {
+                    while ss.num_unpack_streams_in_folders.get(folder_idx) == Some(&0) {
+                        folder_idx += 1;
+                    }
+                }
+
                 let fi = folder_idx;
                 let si = stream_idx;
 
@@ -300,9 +307,12 @@ impl<R: Read + Seek + Send> StreamingArchive<R> {
                 is_symlink,
                 is_anti: archive_entry.is_anti,
                 ownership: None,
+                sparse_holes: archive_entry.sparse_holes.clone(),
+                bsd_flags: archive_entry.bsd_flags,
                 index: idx,
                 folder_index,
                 stream_index,
+                solid_member: crate::read::entries::is_solid_member(header, folder_index),
             });
         }
 
@@ -479,6 +489,7 @@ impl<R: Read + Seek + Send> StreamingArchive<R> {
             &mut self.reader,
             &self.password,
             self.config.clone(),
+            self.start_header.sfx_offset,
         )
     }
 
@@ -490,18 +501,43 @@ impl<R: Read + Seek + Send> StreamingArchive<R> {
             &self.entries,
             &mut self.reader,
             self.config.clone(),
+            self.start_header.sfx_offset,
         )
     }
 
+    /// Returns an iterator over entries in decode order.
+    ///
+    /// Entries without data (directories and empty files) are yielded
+    /// first, then the entries of each folder in the order their data
+    /// appears in the compressed stream. Each folder is decompressed once,
+    /// whatever the order of the file list.
+    ///
+    /// Archives written by this crate and by 7-Zip store their file list
+    /// in stream order, so for those this only differs from
+    /// [`entries`](Self::entries) in where entries without data appear.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// for entry_result in archive.entries_decode_order()? {
+    ///     let mut entry = entry_result?;
+    ///     entry.extract_to(&mut output)?;
+    /// }
+    /// ```
+    pub fn entries_decode_order(&mut self) -> Result<EntryIterator<'_, R>> {
+        Ok(self.entries()?.in_decode_order())
+    }
+
     /// Extracts all entries to a directory with bounded memory.
     ///
     /// This method extracts all entries using the streaming API,
     /// suitable for large archives that shouldn't be fully loaded
-    /// into memory.
+    /// into memory. Entry names are mapped onto `dest` following the
+    /// backslash and control-character settings of `options`.
     pub fn extract_all(
         &mut self,
         dest: impl AsRef<Path>,
-        _options: &ExtractOptions,
+        options: &ExtractOptions,
     ) -> Result<ExtractAllResult> {
         let dest = dest.as_ref();
 
@@ -511,12 +547,22 @@ impl<R: Read + Seek + Send> StreamingArchive<R> {
 
         let mut result = ExtractAllResult::default();
         let mut iter = self.entries()?;
+        let mut next_idx = 0;
 
         while let Some(entry_result) = iter.next() {
+            let entry_idx = next_idx;
+            next_idx += 1;
             match entry_result {
                 Ok(entry) => {
                     let entry_name = entry.name().to_string();
-                    let entry_path = dest.join(&entry_name);
+                    let entry_path = match extraction_path(entry_idx, &entry_name, options) {
+                        Ok(path) => dest.join(path.as_ref()),
+                        Err(e) => {
+                            result.entries_failed += 1;
+                            result.failures.push((entry_name, e.to_string()));
+                            continue;
+                        }
+                    };
 
                     if entry.is_directory() {
                         if let Err(e) = std::fs::create_dir_all(&entry_path) {
@@ -627,7 +673,10 @@ impl<R: Read + Seek + Send> StreamingArchive<R> {
     /// # Important
     ///
     /// - **Non-solid archives**: Extracted in parallel (2-4x speedup on 4+ cores)
-    /// - **Solid archives**: Returns an error; use [`extract_all`](Self::extract_all) instead
+    /// - **Solid archives with several folders**: Folders are extracted in
+    ///   parallel, the files within a folder sequentially
+    /// - **Solid archives with one folder**: Returns an error; use
+    ///   [`extract_all`](Self::extract_all) instead
     ///
     /// # Example
     ///
@@ -637,7 +686,7 @@ impl<R: Read + Seek + Send> StreamingArchive<R> {
     ///
     /// let mut archive = StreamingArchive::open_path("archive.7z", "")?;
     ///
-    /// if !archive.is_solid() {
+    /// if archive.supports_parallel_extraction() {
     ///     let options = ParallelExtractionOptions::new()
     ///         .threads(Threads::count_or_single(4))
     ///         .verify_crc(true);
@@ -653,30 +702,32 @@ impl<R: Read + Seek + Send> StreamingArchive<R> {
     ///
     /// # Errors
     ///
-    /// Returns [`Error::UnsupportedFeature`] if called on a solid archive.
+    /// Returns [`Error::UnsupportedFeature`] if called on a solid archive with
+    /// a single folder.
     pub fn extract_all_parallel(
         &mut self,
         dest: impl AsRef<Path>,
         options: &ParallelExtractionOptions,
     ) -> Result<ParallelExtractionResult> {
-        if self.is_solid {
+        if !self.supports_parallel_extraction() {
             return Err(Error::UnsupportedFeature {
-                feature: "parallel extraction for solid archives",
+                feature: "parallel extraction for single-folder solid archives",
             });
         }
 
-        let extractor = ParallelFolderExtractor::new(&self.header, &self.entries, options.clone());
+        let extractor = ParallelFolderExtractor::new(&self.header, &self.entries, options.clone())
+            .with_sfx_offset(self.start_header.sfx_offset);
 
         extractor.extract_to_directory(&mut self.reader, dest)
     }
 
     /// Checks if parallel extraction is available for this archive.
     ///
-    /// Returns `true` for non-solid archives where files can be extracted
-    /// in parallel, `false` for solid archives where sequential extraction
-    /// is required.
+    /// Returns `true` for non-solid archives and for solid archives split
+    /// into several folders, `false` for solid archives with a single folder
+    /// where sequential extraction is required.
     pub fn supports_parallel_extraction(&self) -> bool {
-        !self.is_solid
+        super::check_supports_parallel(&self.header)
     }
 }
 
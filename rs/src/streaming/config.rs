@@ -60,6 +60,9 @@ pub struct StreamingConfig {
     ///
     /// Provides protection against compression bombs.
     /// A ratio of 1000 means 1 byte compressed can expand to at most 1000 bytes.
+    /// Checked against the bytes each folder actually decodes, so reads fail
+    /// with [`ResourceLimitExceeded`](crate::Error::ResourceLimitExceeded)
+    /// once a folder's output passes the limit.
     /// Default: 1000.
     pub max_compression_ratio: u32,
 
@@ -609,7 +612,10 @@ impl From<crate::codec::CodecMethod> for CompressionMethod {
             crate::codec::CodecMethod::Copy => Self::Copy,
             crate::codec::CodecMethod::Lzma => Self::Lzma,
             crate::codec::CodecMethod::Lzma2 => Self::Lzma2,
-            crate::codec::CodecMethod::Deflate => Self::Deflate,
+            // Deflate64 differs only by its 64 KiB window
+            crate::codec::CodecMethod::Deflate | crate::codec::CodecMethod::Deflate64 => {
+                Self::Deflate
+            }
             crate::codec::CodecMethod::BZip2 => Self::Bzip2,
             crate::codec::CodecMethod::PPMd => Self::Ppmd,
             crate::codec::CodecMethod::Lz4 => Self::Lz4,
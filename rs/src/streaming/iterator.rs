@@ -8,13 +8,14 @@ use std::io::{self, Read, Seek, SeekFrom};
 
 use crate::format::parser::ArchiveHeader;
 use crate::format::streams::Folder;
-use crate::read::Entry;
+use crate::read::{Entry, map_io_error};
 use crate::{Error, READ_BUFFER_SIZE, Result};
 
 #[cfg(feature = "aes")]
 use crate::Password;
 
 use super::config::StreamingConfig;
+use super::sink::Crc32Sink;
 
 /// Iterator that yields archive entries one at a time with streaming decompression.
 ///
@@ -50,7 +51,9 @@ pub struct EntryIterator<'a, R: Read + Seek> {
     password: &'a Password,
     /// Streaming configuration
     config: StreamingConfig,
-    /// Current entry index
+    /// Indices into `entries`, in the order they are yielded
+    order: Vec<usize>,
+    /// Position in `order` of the next entry
     current_index: usize,
     /// Current folder index being processed
     current_folder: Option<usize>,
@@ -60,12 +63,22 @@ pub struct EntryIterator<'a, R: Read + Seek> {
     stream_position_in_folder: usize,
     /// Bytes remaining in current entry
     bytes_remaining: u64,
+    /// Running CRC of the current entry, when it is being verified
+    crc_check: Option<CrcCheck>,
     /// Pack data start position in the archive
     pack_start: u64,
     /// Whether the iterator is exhausted
     finished: bool,
 }
 
+/// Running CRC of an entry, compared against the stored CRC once the
+/// entry's last byte has been read.
+struct CrcCheck {
+    sink: Crc32Sink,
+    expected: u32,
+    entry_index: usize,
+}
+
 impl<'a, R: Read + Seek + Send> EntryIterator<'a, R> {
     /// Creates a new entry iterator.
     #[cfg(feature = "aes")]
@@ -75,8 +88,9 @@ impl<'a, R: Read + Seek + Send> EntryIterator<'a, R> {
         source: &'a mut R,
         password: &'a Password,
         config: StreamingConfig,
+        sfx_offset: u64,
     ) -> Result<Self> {
-        let pack_start = super::calculate_pack_start(header);
+        let pack_start = super::calculate_pack_start(header, sfx_offset);
 
         Ok(Self {
             header,
@@ -84,11 +98,13 @@ impl<'a, R: Read + Seek + Send> EntryIterator<'a, R> {
             source,
             password,
             config,
+            order: (0..entries.len()).collect(),
             current_index: 0,
             current_folder: None,
             folder_decoder: None,
             stream_position_in_folder: 0,
             bytes_remaining: 0,
+            crc_check: None,
             pack_start,
             finished: false,
         })
@@ -101,24 +117,39 @@ impl<'a, R: Read + Seek + Send> EntryIterator<'a, R> {
         entries: &'a [Entry],
         source: &'a mut R,
         config: StreamingConfig,
+        sfx_offset: u64,
     ) -> Result<Self> {
-        let pack_start = super::calculate_pack_start(header);
+        let pack_start = super::calculate_pack_start(header, sfx_offset);
 
         Ok(Self {
             header,
             entries,
             source,
             config,
+            order: (0..entries.len()).collect(),
             current_index: 0,
             current_folder: None,
             folder_decoder: None,
             stream_position_in_folder: 0,
             bytes_remaining: 0,
+            crc_check: None,
             pack_start,
             finished: false,
         })
     }
 
+    /// Reorders the iterator to yield entries in decode order.
+    ///
+    /// Entries without data (directories and empty files) come first,
+    /// followed by the entries of each folder in pack order, each folder's
+    /// entries in stream order.
+    pub(crate) fn in_decode_order(mut self) -> Self {
+        let entries = self.entries;
+        self.order
+            .sort_by_key(|&i| (entries[i].folder_index, entries[i].stream_index));
+        self
+    }
+
     /// Returns the total number of entries.
     pub fn len(&self) -> usize {
         self.entries.len()
@@ -144,8 +175,10 @@ impl<'a, R: Read + Seek + Send> EntryIterator<'a, R> {
             return Ok(None);
         }
 
-        let entry = &self.entries[self.current_index];
+        let entry_index = self.order[self.current_index];
+        let entry = &self.entries[entry_index];
         self.current_index += 1;
+        self.crc_check = None;
 
         // Handle directories (no data to extract)
         if entry.is_directory {
@@ -163,8 +196,10 @@ impl<'a, R: Read + Seek + Send> EntryIterator<'a, R> {
 
         let stream_index = entry.stream_index.unwrap_or(0);
 
-        // Check if we need to switch folders
-        if self.current_folder != Some(folder_index) {
+        // Switch folders, or restart the folder if the stream was already passed
+        if self.current_folder != Some(folder_index)
+            || stream_index < self.stream_position_in_folder
+        {
             self.init_folder_decoder(folder_index)?;
             self.stream_position_in_folder = 0;
         }
@@ -180,6 +215,13 @@ impl<'a, R: Read + Seek + Send> EntryIterator<'a, R> {
         let size = self.get_stream_size(folder_index, stream_index);
         self.bytes_remaining = size;
         self.stream_position_in_folder = stream_index + 1;
+        if self.config.verify_crc && size > 0 {
+            self.crc_check = entry.crc32.map(|expected| CrcCheck {
+                sink: Crc32Sink::new(),
+                expected,
+                entry_index,
+            });
+        }
 
         // Create streaming entry
         Ok(Some(StreamingEntry::with_size(entry, size)))
@@ -187,7 +229,7 @@ impl<'a, R: Read + Seek + Send> EntryIterator<'a, R> {
 
     fn skip_bytes(&mut self, bytes: u64) -> Result<()> {
         if let Some(decoder) = &mut self.folder_decoder {
-            io::copy(&mut decoder.take(bytes), &mut io::sink()).map_err(Error::Io)?;
+            io::copy(&mut decoder.take(bytes), &mut io::sink()).map_err(map_io_error)?;
         }
         Ok(())
     }
@@ -214,7 +256,7 @@ impl<'a, R: Read + Seek + Send> EntryIterator<'a, R> {
             .map_err(Error::Io)?;
 
         // Build decoder chain
-        let decoder = self.build_folder_decoder(folder)?;
+        let decoder = self.build_folder_decoder(folder_index, folder)?;
 
         self.folder_decoder = Some(decoder);
         self.current_folder = Some(folder_index);
@@ -273,7 +315,11 @@ impl<'a, R: Read + Seek + Send> EntryIterator<'a, R> {
         Ok(offset)
     }
 
-    fn build_folder_decoder(&mut self, folder: &Folder) -> Result<Box<dyn Read + Send + 'static>> {
+    fn build_folder_decoder(
+        &mut self,
+        folder_index: usize,
+        folder: &Folder,
+    ) -> Result<Box<dyn Read + Send + 'static>> {
         if folder.coders.is_empty() {
             return Err(Error::InvalidFormat("folder has no coders".into()));
         }
@@ -283,7 +329,6 @@ impl<'a, R: Read + Seek + Send> EntryIterator<'a, R> {
         let uncompressed_size = folder.final_unpack_size().unwrap_or(0);
 
         // Calculate pack size for this folder
-        let folder_index = self.current_folder.unwrap_or(0);
         let pack_size = self
             .header
             .pack_info
@@ -300,6 +345,7 @@ impl<'a, R: Read + Seek + Send> EntryIterator<'a, R> {
         // Create cursor and build decoder
         let cursor = std::io::Cursor::new(packed_data);
         let decoder = crate::codec::build_decoder(cursor, coder, uncompressed_size)?;
+        let decoder = super::limit_folder_ratio(decoder, pack_size, &self.config);
         // Decoder implements Read, so we can box it as dyn Read
         Ok(Box::new(decoder) as Box<dyn Read + Send + 'static>)
     }
@@ -316,7 +362,23 @@ impl<'a, R: Read + Seek + Send> EntryIterator<'a, R> {
     /// # Returns
     ///
     /// The number of bytes read, or 0 if the entry has been fully read.
+    ///
+    /// # Errors
+    ///
+    /// When [`StreamingConfig::verify_crc`] is set, the read that returns the
+    /// entry's final bytes fails with [`ErrorKind::InvalidData`] wrapping an
+    /// [`Error::CrcMismatch`] if the data does not match the stored CRC.
+    ///
+    /// [`ErrorKind::InvalidData`]: io::ErrorKind::InvalidData
     pub fn read_entry_data(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.read_verified(buf).map_err(|e| match e {
+            Error::Io(e) => e,
+            e => io::Error::new(io::ErrorKind::InvalidData, e),
+        })
+    }
+
+    /// Reads data from the current entry, checking its CRC as data flows.
+    fn read_verified(&mut self, buf: &mut [u8]) -> Result<usize> {
         if self.bytes_remaining == 0 {
             return Ok(0);
         }
@@ -327,9 +389,27 @@ impl<'a, R: Read + Seek + Send> EntryIterator<'a, R> {
         };
 
         let to_read = buf.len().min(self.bytes_remaining as usize);
-        let n = decoder.read(&mut buf[..to_read])?;
+        let n = decoder.read(&mut buf[..to_read]).map_err(map_io_error)?;
         self.bytes_remaining -= n as u64;
 
+        if let Some(check) = &mut self.crc_check {
+            io::Write::write_all(&mut check.sink, &buf[..n]).map_err(Error::Io)?;
+        }
+        if self.bytes_remaining == 0 {
+            if let Some(check) = self.crc_check.take() {
+                let actual = check.sink.finalize();
+                if actual != check.expected {
+                    let entry = &self.entries[check.entry_index];
+                    return Err(Error::CrcMismatch {
+                        entry_index: entry.index,
+                        entry_name: Some(entry.path.as_str().to_string()),
+                        expected: check.expected,
+                        actual,
+                    });
+                }
+            }
+        }
+
         Ok(n)
     }
 
@@ -338,6 +418,7 @@ impl<'a, R: Read + Seek + Send> EntryIterator<'a, R> {
     pub(crate) fn skip_remaining(&mut self) -> Result<()> {
         self.skip_bytes(self.bytes_remaining)?;
         self.bytes_remaining = 0;
+        self.crc_check = None;
         Ok(())
     }
 
@@ -356,6 +437,13 @@ impl<'a, R: Read + Seek + Send> EntryIterator<'a, R> {
     ///
     /// The number of bytes written.
     ///
+    /// # Errors
+    ///
+    /// Returns [`Error::CrcMismatch`] as soon as the entry's final bytes are
+    /// read if they do not match the stored CRC and
+    /// [`StreamingConfig::verify_crc`] is set. Those bytes are not written
+    /// to `sink`.
+    ///
     /// # Example
     ///
     /// ```rust,ignore
@@ -374,7 +462,7 @@ impl<'a, R: Read + Seek + Send> EntryIterator<'a, R> {
         let mut buf = [0u8; READ_BUFFER_SIZE];
 
         loop {
-            let n = self.read_entry_data(&mut buf)?;
+            let n = self.read_verified(&mut buf)?;
             if n == 0 {
                 break;
             }
@@ -409,7 +497,7 @@ impl<'a, R: Read + Seek + Send> EntryIterator<'a, R> {
         let mut buf = [0u8; READ_BUFFER_SIZE];
 
         loop {
-            let n = self.read_entry_data(&mut buf)?;
+            let n = self.read_verified(&mut buf)?;
             if n == 0 {
                 break;
             }
@@ -581,9 +669,12 @@ mod tests {
             is_symlink: false,
             is_anti: false,
             ownership: None,
+            sparse_holes: Vec::new(),
+            bsd_flags: None,
             index: 0,
             folder_index: None,
             stream_index: None,
+            solid_member: false,
         };
 
         let streaming = StreamingEntry::directory(&entry);
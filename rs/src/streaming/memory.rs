@@ -31,11 +31,31 @@ use crate::{Error, Result};
 /// drop(guard);
 /// # Ok::<(), zesven::Error>(())
 /// ```
-#[derive(Debug)]
 pub struct MemoryTracker {
     current_usage: AtomicUsize,
     peak_usage: AtomicUsize,
     limit: usize,
+    pressure_thresholds: Vec<u8>,
+    pressure_callback: Option<PressureCallback>,
+}
+
+/// Callback invoked with `(current_usage, limit)` when usage crosses a
+/// pressure threshold.
+type PressureCallback = Box<dyn Fn(usize, usize) + Send + Sync>;
+
+/// Default pressure thresholds, in percent of the limit.
+const DEFAULT_PRESSURE_THRESHOLDS: [u8; 2] = [80, 95];
+
+impl std::fmt::Debug for MemoryTracker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MemoryTracker")
+            .field("current_usage", &self.current_usage)
+            .field("peak_usage", &self.peak_usage)
+            .field("limit", &self.limit)
+            .field("pressure_thresholds", &self.pressure_thresholds)
+            .field("has_pressure_callback", &self.pressure_callback.is_some())
+            .finish()
+    }
 }
 
 impl MemoryTracker {
@@ -49,9 +69,42 @@ impl MemoryTracker {
             current_usage: AtomicUsize::new(0),
             peak_usage: AtomicUsize::new(0),
             limit,
+            pressure_thresholds: DEFAULT_PRESSURE_THRESHOLDS.to_vec(),
+            pressure_callback: None,
         }
     }
 
+    /// Sets a callback invoked when usage crosses a pressure threshold.
+    ///
+    /// The callback receives the usage after the allocation and the limit.
+    /// It fires once per upward crossing of each threshold, so usage that
+    /// drops below a threshold and rises again notifies again. It runs on
+    /// the allocating thread and must not allocate from this tracker.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use zesven::streaming::MemoryTracker;
+    ///
+    /// let tracker = MemoryTracker::new(1000).on_pressure(|current, limit| {
+    ///     eprintln!("memory pressure: {} of {} bytes", current, limit);
+    /// });
+    /// let _guard = tracker.allocate(900)?; // crosses 80%
+    /// # Ok::<(), zesven::Error>(())
+    /// ```
+    pub fn on_pressure(mut self, callback: impl Fn(usize, usize) + Send + Sync + 'static) -> Self {
+        self.pressure_callback = Some(Box::new(callback));
+        self
+    }
+
+    /// Sets the pressure thresholds in percent of the limit (default: 80, 95).
+    ///
+    /// Values above 100 are clamped to 100.
+    pub fn pressure_thresholds(mut self, percents: &[u8]) -> Self {
+        self.pressure_thresholds = percents.iter().map(|&p| p.min(100)).collect();
+        self
+    }
+
     /// Creates an unlimited memory tracker.
     ///
     /// This tracker will never fail allocations due to limits.
@@ -118,6 +171,7 @@ impl MemoryTracker {
             {
                 // Update peak usage
                 self.peak_usage.fetch_max(new_usage, Ordering::SeqCst);
+                self.notify_pressure(current, new_usage);
 
                 return Ok(MemoryGuard {
                     tracker: self,
@@ -196,6 +250,20 @@ impl MemoryTracker {
             .store(self.current_usage(), Ordering::SeqCst);
     }
 
+    /// Invokes the pressure callback if usage rose across a threshold.
+    fn notify_pressure(&self, previous: usize, current: usize) {
+        let Some(callback) = &self.pressure_callback else {
+            return;
+        };
+        let crossed = self.pressure_thresholds.iter().any(|&percent| {
+            let mark = (self.limit as u128 * u128::from(percent) / 100) as usize;
+            previous < mark && mark <= current
+        });
+        if crossed {
+            callback(current, self.limit);
+        }
+    }
+
     // Internal method to release memory (called by MemoryGuard)
     fn release(&self, bytes: usize) {
         self.current_usage.fetch_sub(bytes, Ordering::SeqCst);
@@ -491,6 +559,47 @@ mod tests {
         assert_eq!(guard.bytes(), 1024 * 1024 * 1024);
     }
 
+    #[test]
+    fn test_pressure_callback() {
+        use std::sync::{Arc, Mutex};
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&events);
+        let tracker = MemoryTracker::new(1000)
+            .on_pressure(move |current, limit| recorded.lock().unwrap().push((current, limit)));
+
+        let below = tracker.allocate(700).unwrap();
+        assert!(events.lock().unwrap().is_empty());
+
+        let _high = tracker.allocate(150).unwrap(); // 850 crosses 80%
+        let _critical = tracker.allocate(100).unwrap(); // 950 crosses 95%
+        assert!(tracker.allocate(100).is_err()); // Rejected allocations do not notify
+        assert_eq!(*events.lock().unwrap(), vec![(850, 1000), (950, 1000)]);
+
+        // Dropping below a threshold and crossing it again notifies again
+        drop(below);
+        let _again = tracker.allocate(700).unwrap();
+        assert_eq!(events.lock().unwrap().len(), 3);
+        assert_eq!(events.lock().unwrap()[2], (950, 1000));
+    }
+
+    #[test]
+    fn test_pressure_custom_thresholds() {
+        use std::sync::Arc;
+        use std::sync::atomic::AtomicUsize;
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let tracker = MemoryTracker::new(100)
+            .pressure_thresholds(&[50])
+            .on_pressure(move |_, _| {
+                counter.fetch_add(1, Ordering::SeqCst);
+            });
+
+        let _guard = tracker.allocate(90).unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
     #[test]
     fn test_reset_peak() {
         let tracker = MemoryTracker::new(1024);
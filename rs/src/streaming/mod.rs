@@ -107,16 +107,38 @@ mod random;
 mod sink;
 mod solid;
 
+use std::io::{Read, Seek, SeekFrom};
+
 use crate::format::SIGNATURE_HEADER_SIZE;
-use crate::format::parser::ArchiveHeader;
+use crate::format::header::{StartHeader, detect_sfx};
+use crate::format::parser::{ArchiveHeader, read_archive_header_with_offset};
+use crate::format::streams::ResourceLimits;
+use crate::safety::LimitedReader;
+use crate::{Error, Result};
+
+/// Reads the archive headers, skipping an SFX stub in front of the archive.
+///
+/// The returned start header records the archive's offset in
+/// [`StartHeader::sfx_offset`].
+pub(crate) fn read_headers<R: Read + Seek>(
+    reader: &mut R,
+    limits: ResourceLimits,
+) -> Result<(StartHeader, ArchiveHeader)> {
+    let sfx_offset = detect_sfx(reader)?.map_or(0, |sfx| sfx.archive_offset);
+    reader
+        .seek(SeekFrom::Start(sfx_offset))
+        .map_err(Error::Io)?;
+    read_archive_header_with_offset(reader, Some(limits), sfx_offset)
+}
 
 /// Calculates the starting offset of packed data in the archive.
 ///
 /// Pack data starts after the signature header plus the pack_pos
-/// offset from the PackInfo structure.
-pub(crate) fn calculate_pack_start(header: &ArchiveHeader) -> u64 {
+/// offset from the PackInfo structure. Both are relative to the start of
+/// the archive, which is `sfx_offset` bytes into the file for SFX archives.
+pub(crate) fn calculate_pack_start(header: &ArchiveHeader, sfx_offset: u64) -> u64 {
     let pack_pos = header.pack_info.as_ref().map(|pi| pi.pack_pos).unwrap_or(0);
-    SIGNATURE_HEADER_SIZE + pack_pos
+    sfx_offset + SIGNATURE_HEADER_SIZE + pack_pos
 }
 
 /// Checks if an archive uses solid compression (multiple streams per folder).
@@ -132,6 +154,35 @@ pub(crate) fn check_is_solid(header: &ArchiveHeader) -> bool {
         .unwrap_or(false)
 }
 
+/// Checks if the folders of an archive can be decoded in parallel: either
+/// every file has a folder of its own, or the solid blocks are spread over
+/// several folders.
+pub(crate) fn check_supports_parallel(header: &ArchiveHeader) -> bool {
+    !check_is_solid(header)
+        || header
+            .unpack_info
+            .as_ref()
+            .is_some_and(|ui| ui.folders.len() > 1)
+}
+
+/// Wraps a folder decoder so that decoding fails once its output exceeds
+/// [`StreamingConfig::max_compression_ratio`] times the folder's packed size.
+///
+/// The header sizes are only claims; this bounds what a crafted stream can
+/// actually expand to, with a [`ResourceLimitExceeded`] error wrapped in
+/// the [`io::Error`](std::io::Error) of the failing read.
+///
+/// [`ResourceLimitExceeded`]: crate::Error::ResourceLimitExceeded
+pub(crate) fn limit_folder_ratio<D: Read>(
+    decoder: D,
+    pack_size: u64,
+    config: &StreamingConfig,
+) -> LimitedReader<D> {
+    LimitedReader::new(decoder)
+        .compressed_size(pack_size)
+        .max_ratio(config.max_compression_ratio)
+}
+
 // Re-export main types
 pub use archive::{ExtractAllResult, StreamingArchive};
 pub use config::{CompressionMethod, MemoryEstimate, StreamingConfig, SystemMemoryInfo};
@@ -6,9 +6,13 @@
 //!
 //! # Important
 //!
-//! Solid archives (where multiple files share a compression block) **cannot** be
-//! extracted in parallel due to compression dependencies. Use sequential extraction
-//! via [`super::EntryIterator`] for solid archives.
+//! The files of a solid block (a folder shared by multiple files) **cannot** be
+//! extracted in parallel due to compression dependencies. Solid archives split
+//! into several folders (see [`WriteOptions::min_folders`]) are extracted one
+//! folder per thread. Use sequential extraction via [`super::EntryIterator`]
+//! for solid archives with a single folder.
+//!
+//! [`WriteOptions::min_folders`]: crate::WriteOptions::min_folders
 //!
 //! # Example
 //!
@@ -29,7 +33,7 @@ use std::io::{Read, Seek, SeekFrom};
 use std::path::Path;
 use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 #[allow(unused_imports)]
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Condvar, Mutex};
 
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
@@ -50,6 +54,8 @@ pub struct ParallelExtractionOptions {
     /// Maximum number of folders to process per batch.
     /// Larger batches have better throughput but use more memory.
     pub batch_size: usize,
+    /// Maximum number of output files open at once (None = one per thread).
+    pub max_open_files: Option<usize>,
 }
 
 impl Default for ParallelExtractionOptions {
@@ -59,6 +65,7 @@ impl Default for ParallelExtractionOptions {
             verify_crc: true,
             skip_existing: false,
             batch_size: 64,
+            max_open_files: None,
         }
     }
 }
@@ -92,6 +99,16 @@ impl ParallelExtractionOptions {
         self.batch_size = size.max(1);
         self
     }
+
+    /// Sets the maximum number of output files open at once.
+    ///
+    /// Threads wait for a free slot before creating a file, so extracting
+    /// many files on many threads stays within the process descriptor limit.
+    /// Values below 1 are raised to 1.
+    pub fn max_open_files(mut self, max: usize) -> Self {
+        self.max_open_files = Some(max.max(1));
+        self
+    }
 }
 
 /// Result of parallel extraction.
@@ -111,6 +128,8 @@ pub struct ParallelExtractionResult {
     pub threads_used: usize,
     /// Whether parallel extraction was actually used.
     pub used_parallel: bool,
+    /// Largest number of output files that were open at the same time.
+    pub peak_open_files: usize,
 }
 
 impl ParallelExtractionResult {
@@ -161,6 +180,47 @@ struct ProgressCounters {
     cancelled: AtomicBool,
 }
 
+/// Counting semaphore that bounds the number of open output files.
+struct OpenFileLimit {
+    max: Option<usize>,
+    open: Mutex<usize>,
+    released: Condvar,
+    peak: AtomicUsize,
+}
+
+impl OpenFileLimit {
+    fn new(max: Option<usize>) -> Self {
+        Self {
+            max,
+            open: Mutex::new(0),
+            released: Condvar::new(),
+            peak: AtomicUsize::new(0),
+        }
+    }
+
+    /// Blocks until a file may be opened; the slot is freed when the guard drops.
+    fn acquire(&self) -> OpenFileGuard<'_> {
+        let mut open = self.open.lock().unwrap();
+        while self.max.is_some_and(|max| *open >= max) {
+            open = self.released.wait(open).unwrap();
+        }
+        *open += 1;
+        self.peak.fetch_max(*open, Ordering::Relaxed);
+        OpenFileGuard { limit: self }
+    }
+}
+
+struct OpenFileGuard<'a> {
+    limit: &'a OpenFileLimit,
+}
+
+impl Drop for OpenFileGuard<'_> {
+    fn drop(&mut self) {
+        *self.limit.open.lock().unwrap() -= 1;
+        self.limit.released.notify_one();
+    }
+}
+
 impl Default for ProgressCounters {
     fn default() -> Self {
         Self {
@@ -204,7 +264,7 @@ impl<'a> ParallelFolderExtractor<'a> {
         entries: &'a [Entry],
         options: ParallelExtractionOptions,
     ) -> Self {
-        let pack_start = super::calculate_pack_start(header);
+        let pack_start = super::calculate_pack_start(header, 0);
         Self {
             header,
             entries,
@@ -213,11 +273,19 @@ impl<'a> ParallelFolderExtractor<'a> {
         }
     }
 
+    /// Sets the offset of the archive within the source, for archives
+    /// behind an SFX stub.
+    pub fn with_sfx_offset(mut self, sfx_offset: u64) -> Self {
+        self.pack_start = super::calculate_pack_start(self.header, sfx_offset);
+        self
+    }
+
     /// Checks if the archive is suitable for parallel extraction.
     ///
-    /// Returns `false` for solid archives where files share compression blocks.
+    /// Returns `false` for solid archives where all files share a single
+    /// compression block.
     pub fn can_extract_parallel(&self) -> bool {
-        !super::check_is_solid(self.header)
+        super::check_supports_parallel(self.header)
     }
 
     /// Builds work items grouped by folder, pre-loading packed data.
@@ -305,7 +373,7 @@ impl<'a> ParallelFolderExtractor<'a> {
 
         if !self.can_extract_parallel() {
             return Err(Error::UnsupportedFeature {
-                feature: "parallel extraction for solid archives",
+                feature: "parallel extraction for single-folder solid archives",
             });
         }
 
@@ -350,14 +418,24 @@ impl<'a> ParallelFolderExtractor<'a> {
         let header = self.header;
         let counters_ref = Arc::clone(&counters);
         let failures_ref = Arc::clone(&failures);
+        let open_files = OpenFileLimit::new(self.options.max_open_files);
 
         pool.install(|| {
-            work_items.par_iter().for_each(|work_item| {
+            work_items.into_par_iter().for_each(|mut work_item| {
                 if counters_ref.cancelled.load(Ordering::Relaxed) {
                     return;
                 }
 
-                match Self::process_folder(work_item, header, &dest_arc, &options, &counters_ref) {
+                let packed_data = std::mem::take(&mut work_item.packed_data);
+                match Self::process_folder(
+                    &work_item,
+                    packed_data,
+                    header,
+                    &dest_arc,
+                    &options,
+                    &counters_ref,
+                    &open_files,
+                ) {
                     Ok(()) => {}
                     Err(e) => {
                         counters_ref
@@ -377,9 +455,10 @@ impl<'a> ParallelFolderExtractor<'a> {
             entries_skipped: counters.entries_skipped.load(Ordering::Relaxed),
             entries_failed: counters.entries_failed.load(Ordering::Relaxed),
             bytes_extracted: counters.bytes_extracted.load(Ordering::Relaxed),
-            failures: Arc::try_unwrap(failures).unwrap().into_inner().unwrap(),
+            failures: std::mem::take(&mut *failures.lock().unwrap()),
             threads_used: thread_count,
             used_parallel: true,
+            peak_open_files: open_files.peak.load(Ordering::Relaxed),
         };
 
         // Add directory count to extracted
@@ -412,13 +491,22 @@ impl<'a> ParallelFolderExtractor<'a> {
         let work_items = self.build_work_items(source)?;
         let counters = Arc::new(ProgressCounters::default());
         let mut failures = Vec::new();
+        let open_files = OpenFileLimit::new(self.options.max_open_files);
 
         let dest_arc = Arc::new(dest.to_path_buf());
 
         // Process folders sequentially
-        for work_item in &work_items {
-            match Self::process_folder(work_item, self.header, &dest_arc, &self.options, &counters)
-            {
+        for mut work_item in work_items {
+            let packed_data = std::mem::take(&mut work_item.packed_data);
+            match Self::process_folder(
+                &work_item,
+                packed_data,
+                self.header,
+                &dest_arc,
+                &self.options,
+                &counters,
+                &open_files,
+            ) {
                 Ok(()) => {}
                 Err(e) => {
                     counters
@@ -439,6 +527,7 @@ impl<'a> ParallelFolderExtractor<'a> {
             failures,
             threads_used: 1,
             used_parallel: false,
+            peak_open_files: open_files.peak.load(Ordering::Relaxed),
         };
 
         result.entries_extracted += self.entries.iter().filter(|e| e.is_directory).count();
@@ -447,12 +536,17 @@ impl<'a> ParallelFolderExtractor<'a> {
     }
 
     /// Processes a single folder's entries using pre-loaded packed data.
+    ///
+    /// Takes ownership of the folder's packed data so the decoder reads it in
+    /// place instead of from a copy.
     fn process_folder(
         work_item: &FolderWorkItem,
+        packed_data: Vec<u8>,
         header: &ArchiveHeader,
         dest: &Arc<std::path::PathBuf>,
         options: &ParallelExtractionOptions,
         counters: &ProgressCounters,
+        open_files: &OpenFileLimit,
     ) -> Result<()> {
         // Get folder info
         let folder = header
@@ -463,7 +557,7 @@ impl<'a> ParallelFolderExtractor<'a> {
 
         // Build decoder from pre-loaded packed data
         let uncompressed_size = folder.final_unpack_size().unwrap_or(0);
-        let cursor = std::io::Cursor::new(work_item.packed_data.clone());
+        let cursor = std::io::Cursor::new(packed_data);
 
         if folder.coders.is_empty() {
             return Err(Error::InvalidFormat("folder has no coders".into()));
@@ -513,6 +607,8 @@ impl<'a> ParallelFolderExtractor<'a> {
                 .get(entry.stream_index)
                 .copied()
                 .unwrap_or(entry.size);
+            // Declared before the file so the slot is freed after it closes
+            let _slot = open_files.acquire();
             let mut file = std::fs::File::create(&entry_path).map_err(Error::Io)?;
 
             // Use CRC verification if enabled
@@ -635,6 +731,7 @@ mod tests {
         assert!(opts.verify_crc);
         assert!(!opts.skip_existing);
         assert_eq!(opts.batch_size, 64);
+        assert_eq!(opts.max_open_files, None);
     }
 
     #[test]
@@ -643,12 +740,14 @@ mod tests {
             .threads(Threads::count_or_single(4))
             .verify_crc(false)
             .skip_existing(true)
-            .batch_size(32);
+            .batch_size(32)
+            .max_open_files(0);
 
         assert_eq!(opts.threads.count(), 4);
         assert!(!opts.verify_crc);
         assert!(opts.skip_existing);
         assert_eq!(opts.batch_size, 32);
+        assert_eq!(opts.max_open_files, Some(1));
     }
 
     #[test]
@@ -661,6 +760,7 @@ mod tests {
             failures: Vec::new(),
             threads_used: 4,
             used_parallel: true,
+            peak_open_files: 4,
         };
 
         assert!(result.is_success());
@@ -677,6 +777,7 @@ mod tests {
             failures: vec![("file.txt".to_string(), "error".to_string())],
             threads_used: 4,
             used_parallel: true,
+            peak_open_files: 4,
         };
 
         assert!(!result.is_success());
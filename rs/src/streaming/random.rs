@@ -5,11 +5,10 @@
 
 use std::io::{self, Read, Seek, SeekFrom};
 
-use crate::format::SIGNATURE_HEADER_SIZE;
 use crate::format::header::StartHeader;
-use crate::format::parser::{ArchiveHeader, read_archive_header};
+use crate::format::parser::ArchiveHeader;
 use crate::format::streams::ResourceLimits;
-use crate::read::Entry;
+use crate::read::{Entry, map_io_error};
 use crate::{Error, Result};
 
 #[cfg(feature = "aes")]
@@ -55,10 +54,8 @@ pub struct RandomAccessReader<R: Read + Seek> {
     skipped_entries: Vec<super::SkippedEntry>,
     /// Password for encrypted archives
     #[cfg(feature = "aes")]
-    #[allow(dead_code)] // Reserved for encrypted random access
     password: Password,
     /// Configuration
-    #[allow(dead_code)] // Reserved for configuration usage
     config: StreamingConfig,
     /// Pack data start position
     pack_start: u64,
@@ -78,10 +75,10 @@ impl<R: Read + Seek> RandomAccessReader<R> {
                 config.max_compression_ratio,
             )));
 
-        let (start_header, header) = read_archive_header(&mut source, Some(limits))?;
+        let (start_header, header) = super::read_headers(&mut source, limits)?;
         let (entries, skipped_entries) = Self::build_entries(&header);
         let is_solid = super::check_is_solid(&header);
-        let pack_start = Self::calculate_pack_start(&start_header);
+        let pack_start = super::calculate_pack_start(&header, start_header.sfx_offset);
 
         Ok(Self {
             source,
@@ -107,10 +104,10 @@ impl<R: Read + Seek> RandomAccessReader<R> {
                 config.max_compression_ratio,
             )));
 
-        let (start_header, header) = read_archive_header(&mut source, Some(limits))?;
+        let (start_header, header) = super::read_headers(&mut source, limits)?;
         let (entries, skipped_entries) = Self::build_entries(&header);
         let is_solid = super::check_is_solid(&header);
-        let pack_start = Self::calculate_pack_start(&start_header);
+        let pack_start = super::calculate_pack_start(&header, start_header.sfx_offset);
 
         Ok(Self {
             source,
@@ -155,6 +152,13 @@ impl<R: Read + Seek> RandomAccessReader<R> {
             let (folder_index, stream_index) = if archive_entry.is_directory {
                 (None, None)
             } else {
+                // Folders without unpack streams (e.g. alignment padding) hold no entries
+                if let Some(ss) = substreams {
+                    while ss.num_unpack_streams_in_folders.get(folder_idx) == Some(&0) {
+                        folder_idx += 1;
+                    }
+                }
+
                 let fi = folder_idx;
                 let si = stream_idx;
 
@@ -203,9 +207,12 @@ impl<R: Read + Seek> RandomAccessReader<R> {
                 is_symlink,
                 is_anti: archive_entry.is_anti,
                 ownership: None,
+                sparse_holes: archive_entry.sparse_holes.clone(),
+                bsd_flags: archive_entry.bsd_flags,
                 index: idx,
                 folder_index,
                 stream_index,
+                solid_member: crate::read::entries::is_solid_member(header, folder_index),
             });
         }
 
@@ -223,11 +230,6 @@ impl<R: Read + Seek> RandomAccessReader<R> {
         )
     }
 
-    fn calculate_pack_start(start_header: &StartHeader) -> u64 {
-        // Pack data starts after signature header + next header offset
-        SIGNATURE_HEADER_SIZE + start_header.next_header_offset
-    }
-
     /// Returns true if random access is supported (non-solid archive).
     pub fn supports_random_access(&self) -> bool {
         !self.is_solid
@@ -299,31 +301,19 @@ impl<R: Read + Seek> RandomAccessReader<R> {
 
         let folder_index = folder_index.unwrap();
 
-        // Get folder info (need to clone coders to avoid borrow issues)
-        let (coder_id, coder_properties, uncompressed_size) = {
-            let folders = self
-                .header
-                .unpack_info
-                .as_ref()
-                .map(|ui| &ui.folders)
-                .ok_or_else(|| Error::InvalidFormat("missing unpack info".into()))?;
-
-            let folder = folders.get(folder_index).ok_or_else(|| {
+        // Clone the folder to avoid holding a borrow of the header
+        let folder = self
+            .header
+            .unpack_info
+            .as_ref()
+            .map(|ui| &ui.folders)
+            .ok_or_else(|| Error::InvalidFormat("missing unpack info".into()))?
+            .get(folder_index)
+            .cloned()
+            .ok_or_else(|| {
                 Error::InvalidFormat(format!("folder index {} out of range", folder_index))
             })?;
 
-            if folder.coders.is_empty() {
-                return Err(Error::InvalidFormat("folder has no coders".into()));
-            }
-
-            let coder = &folder.coders[0];
-            (
-                coder.method_id.clone(),
-                coder.properties.clone(),
-                folder.final_unpack_size().unwrap_or(0),
-            )
-        };
-
         // Calculate folder offset
         let folder_offset = self.calculate_folder_offset(folder_index)?;
 
@@ -335,7 +325,8 @@ impl<R: Read + Seek> RandomAccessReader<R> {
             .and_then(|pi| pi.pack_sizes.get(folder_index).copied())
             .unwrap_or(0);
 
-        // Seek and read packed data
+        // Seek and read packed data; earlier folders are never read. Each
+        // encrypted folder carries its own IV, so decryption starts here too.
         self.source
             .seek(SeekFrom::Start(folder_offset))
             .map_err(Error::Io)?;
@@ -345,16 +336,10 @@ impl<R: Read + Seek> RandomAccessReader<R> {
             .read_exact(&mut packed_data)
             .map_err(Error::Io)?;
 
-        // Build decoder with copied coder info
         let cursor = std::io::Cursor::new(packed_data);
-        let temp_coder = crate::format::streams::Coder {
-            method_id: coder_id,
-            properties: coder_properties,
-            num_in_streams: 1,
-            num_out_streams: 1,
-        };
-        let decoder = crate::codec::build_decoder(cursor, &temp_coder, uncompressed_size)?;
-        let boxed_decoder: Box<dyn Read + Send + 'static> = Box::new(decoder);
+        let decoder = self.build_decoder(cursor, &folder)?;
+        let boxed_decoder: Box<dyn Read + Send + 'static> =
+            Box::new(super::limit_folder_ratio(decoder, pack_size, &self.config));
 
         // Now get entry reference for return
         let entry = &self.entries[index];
@@ -375,7 +360,7 @@ impl<R: Read + Seek> RandomAccessReader<R> {
     /// Extracts a specific entry to a Write sink.
     pub fn extract_entry_to<W: io::Write>(&mut self, index: usize, sink: &mut W) -> Result<u64> {
         let mut reader = self.entry_reader(index)?;
-        let written = io::copy(&mut reader, sink).map_err(Error::Io)?;
+        let written = io::copy(&mut reader, sink).map_err(map_io_error)?;
         Ok(written)
     }
 
@@ -386,10 +371,31 @@ impl<R: Read + Seek> RandomAccessReader<R> {
         sink: &mut W,
     ) -> Result<u64> {
         let mut reader = self.entry_reader_by_name(name)?;
-        let written = io::copy(&mut reader, sink).map_err(Error::Io)?;
+        let written = io::copy(&mut reader, sink).map_err(map_io_error)?;
         Ok(written)
     }
 
+    /// Builds the decoder chain for a folder, decrypting it if needed.
+    fn build_decoder(
+        &self,
+        input: std::io::Cursor<Vec<u8>>,
+        folder: &crate::format::streams::Folder,
+    ) -> Result<Box<dyn Read + Send + 'static>> {
+        let uncompressed_size = folder.final_unpack_size().unwrap_or(0);
+
+        #[cfg(feature = "aes")]
+        if crate::read::entries::folder_uses_encryption(folder) {
+            return crate::codec::build_encrypted_folder_decoder(
+                input,
+                folder,
+                uncompressed_size,
+                &self.password,
+            );
+        }
+
+        crate::codec::build_decoder_chain(input, folder, uncompressed_size)
+    }
+
     fn calculate_folder_offset(&self, folder_index: usize) -> Result<u64> {
         let pack_info = self
             .header
@@ -397,7 +403,7 @@ impl<R: Read + Seek> RandomAccessReader<R> {
             .as_ref()
             .ok_or_else(|| Error::InvalidFormat("missing pack info".into()))?;
 
-        let mut offset = self.pack_start + pack_info.pack_pos;
+        let mut offset = self.pack_start;
 
         // Sum up pack sizes for previous folders
         for i in 0..folder_index {
@@ -596,9 +602,12 @@ mod tests {
                 is_symlink: false,
                 is_anti: false,
                 ownership: None,
+                sparse_holes: Vec::new(),
+                bsd_flags: None,
                 index: 0,
                 folder_index: Some(0),
                 stream_index: Some(0),
+                solid_member: false,
             },
             Entry {
                 path: ArchivePath::new("src/main.rs").unwrap(),
@@ -614,9 +623,12 @@ mod tests {
                 is_symlink: false,
                 is_anti: false,
                 ownership: None,
+                sparse_holes: Vec::new(),
+                bsd_flags: None,
                 index: 1,
                 folder_index: Some(1),
                 stream_index: Some(0),
+                solid_member: false,
             },
             Entry {
                 path: ArchivePath::new("src").unwrap(),
@@ -632,9 +644,12 @@ mod tests {
                 is_symlink: false,
                 is_anti: false,
                 ownership: None,
+                sparse_holes: Vec::new(),
+                bsd_flags: None,
                 index: 2,
                 folder_index: None,
                 stream_index: None,
+                solid_member: false,
             },
         ]
     }
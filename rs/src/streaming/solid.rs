@@ -8,6 +8,7 @@ use std::io::{self, Read, Seek, SeekFrom};
 use crate::format::SIGNATURE_HEADER_SIZE;
 use crate::format::parser::ArchiveHeader;
 use crate::format::streams::Folder;
+use crate::read::map_io_error;
 use crate::{Error, READ_BUFFER_SIZE, Result};
 
 #[cfg(feature = "aes")]
@@ -63,7 +64,6 @@ pub struct SolidBlockStreamReader<'a, R: Read + Seek> {
     #[allow(dead_code)] // Reserved for encrypted solid block support
     password: &'a Password,
     /// Configuration
-    #[allow(dead_code)] // Reserved for configuration usage
     config: StreamingConfig,
     /// The decoder for the solid block
     decoder: Option<Box<dyn Read + Send + 'a>>,
@@ -241,7 +241,12 @@ impl<'a, R: Read + Seek + Send> SolidBlockStreamReader<'a, R> {
 
         let cursor = std::io::Cursor::new(packed_data);
         // Build decoder chain to handle filter+codec combinations (e.g., BCJ + LZMA2)
-        crate::codec::build_decoder_chain(cursor, folder, uncompressed_size)
+        let decoder = crate::codec::build_decoder_chain(cursor, folder, uncompressed_size)?;
+        Ok(Box::new(super::limit_folder_ratio(
+            decoder,
+            pack_size,
+            &self.config,
+        )))
     }
 
     /// Returns the number of entries in this block.
@@ -320,7 +325,7 @@ impl<'a, R: Read + Seek + Send> SolidBlockStreamReader<'a, R> {
             };
 
             let remaining = self.bytes_remaining_in_entry;
-            io::copy(&mut decoder.take(remaining), &mut io::sink()).map_err(Error::Io)?;
+            io::copy(&mut decoder.take(remaining), &mut io::sink()).map_err(map_io_error)?;
             self.total_decompressed += remaining;
             self.bytes_remaining_in_entry = 0;
         }
@@ -347,7 +352,7 @@ impl<'a, R: Read + Seek + Send> SolidBlockStreamReader<'a, R> {
 
         loop {
             let mut buf = [0u8; READ_BUFFER_SIZE];
-            let n = self.read_entry_data(&mut buf)?;
+            let n = self.read_entry_data(&mut buf).map_err(map_io_error)?;
             if n == 0 {
                 break;
             }
@@ -368,7 +373,7 @@ impl<'a, R: Read + Seek + Send> SolidBlockStreamReader<'a, R> {
         let mut buf = [0u8; READ_BUFFER_SIZE];
 
         loop {
-            let n = self.read_entry_data(&mut buf)?;
+            let n = self.read_entry_data(&mut buf).map_err(map_io_error)?;
             if n == 0 {
                 break;
             }
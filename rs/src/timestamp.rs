@@ -16,6 +16,9 @@
 //! - Unix timestamps with microseconds: loses 100ns precision
 //! - Unix timestamps with nanoseconds: preserves full precision (as multiples of 100ns)
 //!
+//! With the `chrono` feature, [`Timestamp`] also converts to and from
+//! `chrono::DateTime<Utc>`.
+//!
 //! # Example
 //!
 //! ```rust
@@ -87,11 +90,12 @@ impl Timestamp {
 
     /// Creates a timestamp from Unix seconds (since January 1, 1970).
     ///
-    /// Returns `None` if the timestamp would overflow.
+    /// Returns `None` if the timestamp would overflow or falls before
+    /// January 1, 1601.
     pub fn from_unix_secs(secs: i64) -> Option<Self> {
         if secs < 0 {
             // Handle times before Unix epoch
-            let neg_secs = (-secs) as u64;
+            let neg_secs = secs.unsigned_abs();
             let neg_intervals = neg_secs.checked_mul(INTERVALS_PER_SECOND)?;
             FILETIME_UNIX_DIFF
                 .checked_sub(neg_intervals)
@@ -108,12 +112,15 @@ impl Timestamp {
     ///
     /// Note: Only 100-nanosecond precision is preserved. The nanoseconds value
     /// is rounded down to the nearest 100ns.
+    ///
+    /// Returns `None` if the timestamp would overflow or falls before
+    /// January 1, 1601.
     pub fn from_unix_secs_nanos(secs: i64, nanos: u32) -> Option<Self> {
         // Convert nanos to 100ns intervals (truncating)
         let nano_intervals = (nanos as u64) / 100;
 
         if secs < 0 {
-            let neg_secs = (-secs) as u64;
+            let neg_secs = secs.unsigned_abs();
             let neg_intervals = neg_secs.checked_mul(INTERVALS_PER_SECOND)?;
             let base = FILETIME_UNIX_DIFF.checked_sub(neg_intervals)?;
             // For negative times, we subtract the remaining nanosecond portion
@@ -130,14 +137,27 @@ impl Timestamp {
     }
 
     /// Creates a timestamp from a `SystemTime`.
+    ///
+    /// Only 100-nanosecond precision is preserved; finer precision is
+    /// rounded towards the past. Returns `None` for times before January 1,
+    /// 1601, which FILETIME cannot represent, or too far in the future.
     pub fn from_system_time(time: SystemTime) -> Option<Self> {
         match time.duration_since(UNIX_EPOCH) {
             Ok(duration) => {
-                Self::from_unix_secs_nanos(duration.as_secs() as i64, duration.subsec_nanos())
+                let secs = duration.as_secs().checked_mul(INTERVALS_PER_SECOND)?;
+                let intervals = secs.checked_add(u64::from(duration.subsec_nanos()) / 100)?;
+                FILETIME_UNIX_DIFF
+                    .checked_add(intervals)
+                    .map(Self::from_filetime)
             }
             Err(e) => {
                 let duration = e.duration();
-                Self::from_unix_secs_nanos(-(duration.as_secs() as i64), duration.subsec_nanos())
+                let secs = duration.as_secs().checked_mul(INTERVALS_PER_SECOND)?;
+                let intervals =
+                    secs.checked_add(u64::from(duration.subsec_nanos()).div_ceil(100))?;
+                FILETIME_UNIX_DIFF
+                    .checked_sub(intervals)
+                    .map(Self::from_filetime)
             }
         }
     }
@@ -232,6 +252,11 @@ impl Timestamp {
     /// Converts to a `SystemTime`.
     ///
     /// Preserves full 100-nanosecond precision.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the platform's `SystemTime` cannot represent the timestamp.
+    /// Use [`to_system_time`](Self::to_system_time) to handle that case.
     pub fn as_system_time(&self) -> SystemTime {
         if self.filetime >= FILETIME_UNIX_DIFF {
             let intervals = self.filetime - FILETIME_UNIX_DIFF;
@@ -248,6 +273,40 @@ impl Timestamp {
         }
     }
 
+    /// Converts to a `SystemTime`, or `None` if the platform's `SystemTime`
+    /// cannot represent the timestamp.
+    ///
+    /// Preserves full 100-nanosecond precision.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zesven::Timestamp;
+    /// use std::time::{Duration, SystemTime};
+    ///
+    /// let ts = Timestamp::from_unix_secs(60).unwrap();
+    /// assert_eq!(
+    ///     ts.to_system_time(),
+    ///     Some(SystemTime::UNIX_EPOCH + Duration::from_secs(60))
+    /// );
+    /// ```
+    pub fn to_system_time(&self) -> Option<SystemTime> {
+        let (intervals, after_epoch) = if self.filetime >= FILETIME_UNIX_DIFF {
+            (self.filetime - FILETIME_UNIX_DIFF, true)
+        } else {
+            (FILETIME_UNIX_DIFF - self.filetime, false)
+        };
+        let secs = intervals / INTERVALS_PER_SECOND;
+        let nanos = ((intervals % INTERVALS_PER_SECOND) * 100) as u32;
+        let duration = Duration::new(secs, nanos);
+
+        if after_epoch {
+            UNIX_EPOCH.checked_add(duration)
+        } else {
+            UNIX_EPOCH.checked_sub(duration)
+        }
+    }
+
     /// Returns the sub-second portion as 100-nanosecond intervals (0-9999999).
     ///
     /// This provides the maximum precision available in the FILETIME format.
@@ -314,6 +373,37 @@ impl From<Timestamp> for SystemTime {
     }
 }
 
+/// Converts a chrono `DateTime<Utc>`, saturating at the FILETIME range.
+///
+/// Times before January 1, 1601 become FILETIME 0; times beyond the
+/// largest FILETIME (in the year 60056) become `u64::MAX`. Sub-100ns
+/// precision is rounded towards the past.
+#[cfg(feature = "chrono")]
+impl From<chrono::DateTime<chrono::Utc>> for Timestamp {
+    fn from(time: chrono::DateTime<chrono::Utc>) -> Self {
+        let secs = time.timestamp();
+        Self::from_unix_secs_nanos(secs, time.timestamp_subsec_nanos()).unwrap_or(if secs < 0 {
+            Self::from_filetime(0)
+        } else {
+            Self::from_filetime(u64::MAX)
+        })
+    }
+}
+
+/// Converts to a chrono `DateTime<Utc>`, preserving 100ns precision.
+///
+/// Every FILETIME value lies within chrono's range, so this cannot fail.
+#[cfg(feature = "chrono")]
+impl From<Timestamp> for chrono::DateTime<chrono::Utc> {
+    fn from(ts: Timestamp) -> Self {
+        let intervals = i128::from(ts.filetime) - i128::from(FILETIME_UNIX_DIFF);
+        let secs = intervals.div_euclid(i128::from(INTERVALS_PER_SECOND)) as i64;
+        let nanos = (intervals.rem_euclid(i128::from(INTERVALS_PER_SECOND)) * 100) as u32;
+        chrono::DateTime::from_timestamp(secs, nanos)
+            .unwrap_or(chrono::DateTime::<chrono::Utc>::MAX_UTC)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -398,6 +488,78 @@ mod tests {
         assert_eq!(back, 132456789012345678);
     }
 
+    #[test]
+    fn test_system_time_before_unix_epoch() {
+        // 1.5 seconds before the Unix epoch
+        let time = UNIX_EPOCH - Duration::new(1, 500_000_000);
+        let ts = Timestamp::from_system_time(time).unwrap();
+        assert_eq!(ts.as_filetime(), FILETIME_UNIX_DIFF - 15_000_000);
+        assert_eq!(ts.to_system_time(), Some(time));
+
+        // Sub-100ns remainders round towards the past
+        let ts = Timestamp::from_system_time(UNIX_EPOCH - Duration::from_nanos(50)).unwrap();
+        assert_eq!(ts.as_filetime(), FILETIME_UNIX_DIFF - 1);
+    }
+
+    #[test]
+    fn test_filetime_epoch_boundary() {
+        let filetime_epoch =
+            UNIX_EPOCH - Duration::from_secs(FILETIME_UNIX_DIFF / INTERVALS_PER_SECOND);
+
+        let ts = Timestamp::from_system_time(filetime_epoch).unwrap();
+        assert_eq!(ts.as_filetime(), 0);
+        assert_eq!(ts.to_system_time(), Some(filetime_epoch));
+
+        // One tick before 1601 is out of range rather than wrapping
+        assert_eq!(
+            Timestamp::from_system_time(filetime_epoch - Duration::from_nanos(100)),
+            None
+        );
+        assert_eq!(Timestamp::from_unix_secs(-11_644_473_601), None);
+        assert_eq!(Timestamp::from_unix_secs(i64::MIN), None);
+        assert_eq!(Timestamp::from_unix_secs_nanos(i64::MIN, 0), None);
+    }
+
+    #[test]
+    fn test_known_filetime() {
+        // 2009-02-13 23:31:30.1234567 UTC
+        let ts = Timestamp::from_filetime(128_790_414_901_234_567);
+        assert_eq!(ts.as_unix_secs(), 1_234_567_890);
+        assert_eq!(ts.sub_second_100ns(), 1_234_567);
+        assert_eq!(
+            ts.to_system_time(),
+            Some(UNIX_EPOCH + Duration::new(1_234_567_890, 123_456_700))
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_chrono_conversions() {
+        use chrono::{DateTime, TimeZone, Utc};
+
+        let time = Utc.with_ymd_and_hms(2009, 2, 13, 23, 31, 30).unwrap()
+            + chrono::Duration::nanoseconds(123_456_700);
+        let ts = Timestamp::from(time);
+        assert_eq!(ts.as_filetime(), 128_790_414_901_234_567);
+        assert_eq!(DateTime::<Utc>::from(ts), time);
+
+        let filetime_epoch = Utc.with_ymd_and_hms(1601, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(Timestamp::from(filetime_epoch).as_filetime(), 0);
+        assert_eq!(
+            DateTime::<Utc>::from(Timestamp::from_filetime(0)),
+            filetime_epoch
+        );
+
+        // Out-of-range times saturate
+        let before = Utc.with_ymd_and_hms(1600, 12, 31, 23, 59, 59).unwrap();
+        assert_eq!(Timestamp::from(before).as_filetime(), 0);
+        assert_eq!(
+            Timestamp::from(DateTime::<Utc>::MAX_UTC).as_filetime(),
+            u64::MAX
+        );
+        let _ = DateTime::<Utc>::from(Timestamp::from_filetime(u64::MAX));
+    }
+
     #[test]
     fn test_default() {
         let ts = Timestamp::default();
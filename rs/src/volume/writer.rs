@@ -1,6 +1,6 @@
 //! Multi-volume archive writer.
 
-use std::fs::File;
+use std::fs::{File, OpenOptions};
 use std::io::{self, BufWriter, Seek, SeekFrom, Write};
 use std::path::PathBuf;
 
@@ -92,6 +92,83 @@ impl MultiVolumeWriter {
         })
     }
 
+    /// Continues an existing volume set, appending after its last volume.
+    ///
+    /// The volumes `base.001`, `base.002`, ... described by `config` are
+    /// discovered in order; writing resumes at the end of the last one and
+    /// new volumes are created once it reaches the configured size. This lets
+    /// a byte stream that was split into volumes be resumed later, e.g. after
+    /// the producing process restarted.
+    ///
+    /// Volume 1 is reopened as well, so seeking back for header rewrites keeps
+    /// working as with [`create`](Self::create).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::VolumeMissing`] if the first volume does not exist,
+    /// or an I/O error if an existing volume cannot be opened for writing.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use zesven::volume::{VolumeConfig, MultiVolumeWriter};
+    ///
+    /// let config = VolumeConfig::new("stream.7z", 100 * 1024 * 1024);
+    /// let mut writer = MultiVolumeWriter::continue_from(config)?;
+    /// writer.write_all(&more_data)?;
+    /// writer.finish()?;
+    /// ```
+    pub fn continue_from(config: VolumeConfig) -> Result<Self> {
+        let first_path = config.volume_path(1);
+        let open_existing = |path: &PathBuf, volume: u32| {
+            OpenOptions::new()
+                .read(true)
+                .write(true)
+                .open(path)
+                .map_err(|source| Error::VolumeMissing {
+                    volume,
+                    path: path.display().to_string(),
+                    source,
+                })
+        };
+
+        let mut first_file = open_existing(&first_path, 1)?;
+        let first_volume_written = first_file.seek(SeekFrom::End(0))?;
+
+        // Discover the remaining volumes; all but the last are complete
+        let mut completed_sizes = Vec::new();
+        let mut current_volume = 1;
+        let mut current_volume_written = first_volume_written;
+        let mut total_written = first_volume_written;
+        while let Ok(metadata) = std::fs::metadata(config.volume_path(current_volume + 1)) {
+            completed_sizes.push(current_volume_written);
+            current_volume += 1;
+            current_volume_written = metadata.len();
+            total_written += current_volume_written;
+        }
+
+        let current_file = if current_volume > 1 {
+            let mut file = open_existing(&config.volume_path(current_volume), current_volume)?;
+            file.seek(SeekFrom::End(0))?;
+            Some(BufWriter::new(file))
+        } else {
+            None
+        };
+
+        Ok(Self {
+            config,
+            first_volume: Some(BufWriter::new(first_file)),
+            current_file,
+            current_volume,
+            current_volume_written,
+            first_volume_written,
+            total_written,
+            completed_sizes,
+            first_volume_position: 0,
+            header_rewrite_mode: false,
+        })
+    }
+
     /// Opens the next volume file.
     fn open_next_volume(&mut self) -> Result<()> {
         // Close current volume if open (but not first volume - it stays open)
@@ -457,6 +534,40 @@ mod tests {
         assert_eq!(writer.remaining_in_volume(), 0);
     }
 
+    #[test]
+    fn test_continue_from_existing_volumes() {
+        let dir = TempDir::new().unwrap();
+        let base_path = dir.path().join("test.7z");
+
+        let mut writer = MultiVolumeWriter::create(VolumeConfig::new(&base_path, 100)).unwrap();
+        writer.write_all(&[1u8; 150]).unwrap();
+        writer.finish().unwrap();
+
+        let mut writer =
+            MultiVolumeWriter::continue_from(VolumeConfig::new(&base_path, 100)).unwrap();
+        assert_eq!(writer.current_volume(), 2);
+        assert_eq!(writer.total_written(), 150);
+        assert_eq!(writer.remaining_in_volume(), 50);
+        writer.write_all(&[2u8; 100]).unwrap();
+
+        let sizes = writer.finish().unwrap();
+        assert_eq!(sizes, vec![100, 100, 50]);
+
+        let volume_path = PathBuf::from(format!("{}.002", base_path.display()));
+        let data = std::fs::read(volume_path).unwrap();
+        assert_eq!(&data[..50], &[1u8; 50][..]);
+        assert_eq!(&data[50..], &[2u8; 50][..]);
+    }
+
+    #[test]
+    fn test_continue_from_missing_volume_set() {
+        let dir = TempDir::new().unwrap();
+        let config = VolumeConfig::new(dir.path().join("absent.7z"), 100);
+
+        let err = MultiVolumeWriter::continue_from(config).unwrap_err();
+        assert!(matches!(err, Error::VolumeMissing { volume: 1, .. }));
+    }
+
     #[test]
     fn test_seek_to_start_first_volume() {
         let dir = TempDir::new().unwrap();
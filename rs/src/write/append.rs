@@ -24,10 +24,12 @@
 
 use std::collections::HashSet;
 use std::fs::File;
-use std::io::{BufReader, BufWriter};
+use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write};
 use std::path::Path;
 
+use crate::format::SIGNATURE_HEADER_SIZE;
 use crate::read::Archive;
+use crate::write::raw_copy::RawFolder;
 use crate::write::{EntryMeta, WriteOptions, Writer};
 use crate::{ArchivePath, Error, Result};
 
@@ -229,9 +231,14 @@ impl ArchiveAppender {
     ///
     /// This operation:
     /// 1. Opens the original archive
-    /// 2. Creates a new archive with all original entries
-    /// 3. Adds all new entries
-    /// 4. Replaces the original file with the new archive
+    /// 2. Copies the packed data of its folders verbatim into a new archive
+    /// 3. Compresses all new entries into new folders
+    /// 4. Writes a header covering both and replaces the original file
+    ///
+    /// Existing entries are never decompressed, so the cost of appending
+    /// is independent of how expensive the original data was to compress.
+    /// Folders whose entries lack CRCs are the exception: they are decoded
+    /// and recompressed with the current options.
     ///
     /// Note: This is implemented by creating a new archive rather than
     /// true in-place append, which ensures data integrity.
@@ -256,7 +263,6 @@ impl ArchiveAppender {
         let temp_file = File::create(&temp_path).map_err(Error::Io)?;
         let temp_writer = BufWriter::new(temp_file);
 
-        // Use the editor approach: copy existing entries + add new ones
         let result = {
             // Open original archive
             let original_file = File::open(&self.path).map_err(Error::Io)?;
@@ -266,16 +272,7 @@ impl ArchiveAppender {
             // Create new writer
             let mut writer = Writer::create(temp_writer)?.options(self.options.clone());
 
-            // Copy all existing entries by extracting and re-adding
-            let entries: Vec<_> = original_archive.entries().to_vec();
-            for (idx, entry) in entries.iter().enumerate() {
-                if entry.is_directory {
-                    writer.add_directory(entry.path.clone(), EntryMeta::default())?;
-                } else {
-                    let data = original_archive.extract_entry_to_vec_by_index(idx)?;
-                    writer.add_bytes(entry.path.clone(), &data)?;
-                }
-            }
+            copy_existing_entries(&mut original_archive, &mut writer)?;
 
             // Add new entries
             for pending in self.new_entries {
@@ -300,6 +297,120 @@ impl ArchiveAppender {
     }
 }
 
+/// Copies every entry of `archive` into `writer`, keeping folders packed.
+///
+/// Entries are added in their original order, and each folder is copied
+/// when its first entry is reached, so entries map to the same streams
+/// as in the original archive.
+fn copy_existing_entries<R: Read + Seek, W: Write + Seek>(
+    archive: &mut Archive<R>,
+    writer: &mut Writer<W>,
+) -> Result<()> {
+    let mut raw_folders = plan_raw_folders(archive)?;
+    let copyable: Vec<bool> = raw_folders.iter().map(Option::is_some).collect();
+
+    let entries: Vec<_> = archive.entries().to_vec();
+    for (idx, entry) in entries.into_iter().enumerate() {
+        let meta = EntryMeta {
+            is_directory: entry.is_directory,
+            size: entry.size,
+            modification_time: entry.modification_time,
+            creation_time: entry.creation_time,
+            access_time: entry.access_time,
+            attributes: entry.attributes,
+            is_anti: entry.is_anti,
+            sparse_holes: entry.sparse_holes,
+            bsd_flags: entry.bsd_flags,
+        };
+
+        let Some(folder_idx) = entry.folder_index else {
+            writer.add_copied_entry(entry.path, meta)?;
+            continue;
+        };
+
+        if !copyable.get(folder_idx).copied().unwrap_or(false) {
+            let data = archive.extract_entry_to_vec_by_index(idx)?;
+            writer.add_stream(entry.path, &mut data.as_slice(), meta)?;
+            continue;
+        }
+
+        if let Some((offset, raw)) = raw_folders[folder_idx].take() {
+            archive
+                .reader
+                .seek(SeekFrom::Start(offset))
+                .map_err(Error::Io)?;
+            writer.copy_raw_folder(raw, &mut archive.reader)?;
+        }
+        writer.add_copied_entry(entry.path, meta)?;
+    }
+
+    Ok(())
+}
+
+/// Locates the packed streams of each folder in `archive`.
+///
+/// Returns the offset of each folder's first packed stream alongside the
+/// folder to copy, or `None` for folders that cannot be copied verbatim
+/// because a stream has no CRC or is not mapped to an entry.
+pub(crate) fn plan_raw_folders<R: Read + Seek>(
+    archive: &Archive<R>,
+) -> Result<Vec<Option<(u64, RawFolder)>>> {
+    let folders = archive.header.folders();
+    let Some(pack_info) = archive.header.pack_info.as_ref() else {
+        return Ok(vec![None; folders.len()]);
+    };
+
+    // Size and CRC of each stream, collected from the entries
+    let mut streams: Vec<Vec<(u64, Option<u32>)>> = vec![Vec::new(); folders.len()];
+    for entry in archive.entries() {
+        if let Some(folder_streams) = entry.folder_index.and_then(|idx| streams.get_mut(idx)) {
+            folder_streams.push((entry.size, entry.crc32));
+        }
+    }
+
+    let mut offset = archive.sfx_offset + SIGNATURE_HEADER_SIZE + pack_info.pack_pos;
+    let mut pack_idx = 0;
+    let mut plans = Vec::with_capacity(folders.len());
+    for (folder_idx, (folder, folder_streams)) in folders.iter().zip(streams).enumerate() {
+        let num_packed = folder.packed_streams.len();
+        let pack_sizes = pack_info
+            .pack_sizes
+            .get(pack_idx..pack_idx + num_packed)
+            .ok_or_else(|| {
+                Error::InvalidFormat(format!("missing pack sizes for folder {}", folder_idx))
+            })?
+            .to_vec();
+        let folder_offset = offset;
+        pack_idx += num_packed;
+        offset += pack_sizes.iter().sum::<u64>();
+
+        let num_streams = archive
+            .header
+            .substreams_info
+            .as_ref()
+            .and_then(|ss| ss.num_unpack_streams_in_folders.get(folder_idx).copied())
+            .unwrap_or(1);
+        let streams: Option<Vec<(u64, u32)>> = folder_streams
+            .into_iter()
+            .map(|(size, crc)| crc.map(|crc| (size, crc)))
+            .collect();
+
+        plans.push(match streams {
+            Some(streams) if streams.len() as u64 == num_streams => Some((
+                folder_offset,
+                RawFolder {
+                    folder: folder.clone(),
+                    pack_sizes,
+                    streams,
+                },
+            )),
+            _ => None,
+        });
+    }
+
+    Ok(plans)
+}
+
 #[cfg(all(test, feature = "lzma"))]
 mod tests {
     use super::*;
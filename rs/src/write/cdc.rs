@@ -0,0 +1,211 @@
+//! Content-defined chunking for dedup-friendly LZMA2 streams.
+//!
+//! With [`WriteOptions::cdc_chunking`](super::WriteOptions::cdc_chunking),
+//! data compressed with LZMA2 is split at boundaries chosen by a rolling gear
+//! hash over the content, and every chunk is encoded independently (starting
+//! with a dictionary reset). The chunk encodings are concatenated into a
+//! single LZMA2 stream.
+//!
+//! Because boundaries depend only on nearby bytes, an insertion or edit in a
+//! large file only changes the chunks around it; the compressed bytes of all
+//! other chunks stay identical between archive versions. Backup tools that
+//! deduplicate at the byte level can then share those chunks across archives.
+//!
+//! # Format
+//!
+//! No format extension is needed: a chunked stream is a standard LZMA2 stream
+//! that uses dictionary resets at chunk starts, so the archive remains
+//! readable by 7-Zip and any other 7z implementation. Each file is still a
+//! single substream, and extraction needs no reassembly step.
+
+/// Gear hash table: one pseudo-random 64-bit value per byte value.
+const GEAR: [u64; 256] = gear_table();
+
+/// Builds the gear table with SplitMix64, so chunk boundaries are stable
+/// across platforms and releases.
+const fn gear_table() -> [u64; 256] {
+    let mut table = [0u64; 256];
+    let mut state: u64 = 0x7a65_7376_656e_4344;
+    let mut i = 0;
+    while i < 256 {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        table[i] = z ^ (z >> 31);
+        i += 1;
+    }
+    table
+}
+
+/// Content-defined chunking parameters.
+///
+/// Chunks are at least `min_size` and at most `max_size` bytes; in between,
+/// a boundary is placed where the rolling hash matches, which happens on
+/// average every `avg_size` bytes (rounded to a power of two). Data no larger
+/// than `min_size` is never split.
+///
+/// # Example
+///
+/// ```rust
+/// use zesven::write::{CdcConfig, WriteOptions};
+///
+/// let options = WriteOptions::new().cdc_chunking(Some(CdcConfig::new(
+///     64 * 1024,
+///     256 * 1024,
+///     1024 * 1024,
+/// )));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CdcConfig {
+    /// Minimum chunk size in bytes.
+    pub min_size: usize,
+    /// Target average chunk size in bytes.
+    pub avg_size: usize,
+    /// Maximum chunk size in bytes.
+    pub max_size: usize,
+}
+
+impl Default for CdcConfig {
+    fn default() -> Self {
+        Self {
+            min_size: 256 * 1024,
+            avg_size: 1024 * 1024,
+            max_size: 4 * 1024 * 1024,
+        }
+    }
+}
+
+impl CdcConfig {
+    /// Creates a configuration, adjusting sizes so that
+    /// `1 <= min_size <= avg_size <= max_size`.
+    pub fn new(min_size: usize, avg_size: usize, max_size: usize) -> Self {
+        let min_size = min_size.max(1);
+        let avg_size = avg_size.max(min_size);
+        Self {
+            min_size,
+            avg_size,
+            max_size: max_size.max(avg_size),
+        }
+    }
+
+    /// Returns an iterator over the content-defined chunks of `data`.
+    ///
+    /// The chunks are consecutive, non-empty slices that together cover
+    /// `data` exactly.
+    pub fn chunks<'a>(&self, data: &'a [u8]) -> CdcChunks<'a> {
+        let config = Self::new(self.min_size, self.avg_size, self.max_size);
+        let bits = config.avg_size.next_power_of_two().trailing_zeros();
+        CdcChunks {
+            data,
+            config,
+            // Boundary when the top `bits` bits of the hash are zero
+            mask: if bits == 0 { 0 } else { !(u64::MAX >> bits) },
+        }
+    }
+}
+
+/// Iterator over content-defined chunks, created by [`CdcConfig::chunks`].
+#[derive(Debug, Clone)]
+pub struct CdcChunks<'a> {
+    data: &'a [u8],
+    config: CdcConfig,
+    mask: u64,
+}
+
+impl<'a> CdcChunks<'a> {
+    /// Returns the length of the next chunk of the remaining data.
+    fn next_boundary(&self) -> usize {
+        let data = self.data;
+        if data.len() <= self.config.min_size {
+            return data.len();
+        }
+
+        let end = data.len().min(self.config.max_size);
+        let mut hash: u64 = 0;
+        for (i, &byte) in data[..end].iter().enumerate() {
+            hash = (hash << 1).wrapping_add(GEAR[byte as usize]);
+            if i + 1 >= self.config.min_size && hash & self.mask == 0 {
+                return i + 1;
+            }
+        }
+        end
+    }
+}
+
+impl<'a> Iterator for CdcChunks<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.data.is_empty() {
+            return None;
+        }
+        let (chunk, rest) = self.data.split_at(self.next_boundary());
+        self.data = rest;
+        Some(chunk)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pseudo_random(len: usize, seed: u64) -> Vec<u8> {
+        let mut state = seed;
+        (0..len)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (state >> 33) as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_chunks_cover_data_within_bounds() {
+        let config = CdcConfig::new(1024, 4096, 16384);
+        let data = pseudo_random(200_000, 1);
+        let chunks: Vec<&[u8]> = config.chunks(&data).collect();
+
+        assert!(chunks.len() > 1);
+        assert_eq!(chunks.concat(), data);
+        for chunk in &chunks[..chunks.len() - 1] {
+            assert!(chunk.len() >= 1024 && chunk.len() <= 16384);
+        }
+    }
+
+    #[test]
+    fn test_chunks_resynchronize_after_edit() {
+        let config = CdcConfig::new(1024, 4096, 16384);
+        let original = pseudo_random(200_000, 2);
+        let mut edited = original.clone();
+        edited.splice(100_000..100_000, b"inserted bytes".iter().copied());
+
+        let before: Vec<&[u8]> = config.chunks(&original).collect();
+        let after: Vec<&[u8]> = config.chunks(&edited).collect();
+        let shared = after.iter().filter(|c| before.contains(c)).count();
+        assert!(shared + 2 >= before.len(), "{} of {}", shared, before.len());
+    }
+
+    #[test]
+    fn test_small_and_empty_data() {
+        let config = CdcConfig::default();
+        assert_eq!(config.chunks(&[]).count(), 0);
+        assert_eq!(config.chunks(b"small").collect::<Vec<_>>(), vec![b"small"]);
+    }
+
+    #[test]
+    fn test_new_orders_sizes() {
+        let config = CdcConfig::new(0, 0, 0);
+        assert_eq!(
+            (config.min_size, config.avg_size, config.max_size),
+            (1, 1, 1)
+        );
+        let config = CdcConfig::new(4096, 1024, 2048);
+        assert_eq!(
+            (config.min_size, config.avg_size, config.max_size),
+            (4096, 4096, 4096)
+        );
+    }
+}
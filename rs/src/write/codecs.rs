@@ -11,14 +11,55 @@ use super::Writer;
 
 impl<W: Write + Seek> Writer<W> {
     /// Compresses data using LZMA2.
+    ///
+    /// With content-defined chunking enabled, each chunk is encoded as its
+    /// own dictionary-reset stream and the streams are concatenated.
     #[cfg(feature = "lzma2")]
     pub(crate) fn compress_lzma2(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let Some(cdc) = &self.options.cdc_chunking else {
+            return self.encode_lzma2(data);
+        };
+
+        let mut output = Vec::new();
+        for chunk in cdc.chunks(data) {
+            let encoded = self.encode_lzma2(chunk)?;
+            // Drop each chunk's end-of-stream marker; one is appended at the end
+            output.extend_from_slice(encoded.strip_suffix(&[0x00]).unwrap_or(&encoded));
+        }
+        output.push(0x00);
+        Ok(output)
+    }
+
+    /// Encodes data as a single LZMA2 stream.
+    ///
+    /// With [`WriteOptions::parallel_lzma2`](super::WriteOptions::parallel_lzma2),
+    /// data larger than one block is compressed in parallel blocks.
+    #[cfg(feature = "lzma2")]
+    fn encode_lzma2(&self, data: &[u8]) -> Result<Vec<u8>> {
         use crate::codec::lzma::{Lzma2Encoder, Lzma2EncoderOptions};
 
         let opts = Lzma2EncoderOptions {
             dict_size: Some(1 << (16 + self.options.level.min(7))),
+            match_finder: self.options.match_finder,
             ..Default::default()
         };
+
+        #[cfg(feature = "parallel")]
+        if self.options.parallel_lzma2 && data.len() > self.options.lzma2_block_size {
+            use crate::codec::lzma2_parallel::{ParallelLzma2Encoder, ParallelLzma2Options};
+
+            let parallel_opts = ParallelLzma2Options {
+                level: opts.preset,
+                dict_size: opts.dict_size,
+                threads: None,
+                block_size: self.options.lzma2_block_size,
+                match_finder: opts.match_finder,
+            };
+            return Ok(ParallelLzma2Encoder::new(parallel_opts)
+                .compress(data)?
+                .data);
+        }
+
         let mut output = Vec::new();
         {
             let mut encoder = Lzma2Encoder::new(&mut output, &opts);
@@ -28,6 +69,102 @@ impl<W: Write + Seek> Writer<W> {
         Ok(output)
     }
 
+    /// Builds an encoder that compresses an entry of `size` bytes into
+    /// `output` as the data arrives.
+    ///
+    /// Produces the same kind of stream as [`compress_data`](Self::compress_data).
+    /// Returns `None` when the configured pipeline needs the whole entry up
+    /// front: with a filter, encryption, or content-defined chunking.
+    pub(crate) fn stream_encoder<'a, S: Write + Send + 'a>(
+        &self,
+        output: S,
+        size: u64,
+    ) -> Result<Option<Box<dyn crate::codec::Encoder + 'a>>> {
+        use crate::codec::CodecMethod;
+
+        if self.options.filter.is_active() || self.options.is_data_encrypted() {
+            return Ok(None);
+        }
+
+        #[cfg(feature = "zstd")]
+        if let (CodecMethod::Zstd, Some(dict)) =
+            (self.options.method, &self.options.zstd_dictionary)
+        {
+            use crate::codec::ZSTD_LEVEL_MAP;
+            use crate::codec::zstd::{ZstdEncoderOptions, ZstdStreamEncoder};
+
+            let opts = ZstdEncoderOptions {
+                level: ZSTD_LEVEL_MAP[self.options.level.min(9) as usize],
+            };
+            return Ok(Some(Box::new(ZstdStreamEncoder::with_dictionary(
+                output, &opts, dict,
+            )?)));
+        }
+
+        match self.options.method {
+            #[cfg(feature = "lzma2")]
+            CodecMethod::Lzma2 => {
+                use crate::codec::lzma::{Lzma2Encoder, Lzma2EncoderOptions};
+
+                if self.options.cdc_chunking.is_some() {
+                    return Ok(None);
+                }
+                let opts = Lzma2EncoderOptions {
+                    dict_size: Some(1 << (16 + self.options.level.min(7))),
+                    match_finder: self.options.match_finder,
+                    ..Default::default()
+                };
+
+                #[cfg(feature = "parallel")]
+                if self.options.parallel_lzma2 && size > self.options.lzma2_block_size as u64 {
+                    use crate::codec::lzma2_parallel::{
+                        ParallelLzma2Options, StreamingParallelLzma2Encoder,
+                    };
+
+                    let parallel_opts = ParallelLzma2Options {
+                        level: opts.preset,
+                        dict_size: opts.dict_size,
+                        threads: None,
+                        block_size: self.options.lzma2_block_size,
+                        match_finder: opts.match_finder,
+                    };
+                    return Ok(Some(Box::new(StreamingParallelLzma2Encoder::new(
+                        output,
+                        parallel_opts,
+                    ))));
+                }
+                #[cfg(not(feature = "parallel"))]
+                let _ = size;
+
+                Ok(Some(Box::new(Lzma2Encoder::new(output, &opts))))
+            }
+            #[cfg(feature = "lzma")]
+            CodecMethod::Lzma => {
+                use crate::codec::lzma::{LzmaEncoder, LzmaEncoderOptions};
+
+                let opts = LzmaEncoderOptions {
+                    dict_size: Some(1 << (16 + self.options.level.min(7))),
+                    match_finder: self.options.match_finder,
+                    ..Default::default()
+                };
+                Ok(Some(Box::new(LzmaEncoder::new(output, &opts)?)))
+            }
+            #[cfg(feature = "bzip2")]
+            CodecMethod::BZip2 => {
+                use crate::codec::bzip2::{Bzip2Encoder, Bzip2EncoderOptions};
+
+                let opts = Bzip2EncoderOptions {
+                    level: self.options.level,
+                    block_size: self.options.bzip2_block_size,
+                };
+                Ok(Some(Box::new(Bzip2Encoder::new(output, &opts))))
+            }
+            method => method
+                .build_encoder(output, self.options.level.min(9) as u8)
+                .map(Some),
+        }
+    }
+
     /// Compresses data using LZMA.
     #[cfg(feature = "lzma")]
     pub(crate) fn compress_lzma(&self, data: &[u8]) -> Result<Vec<u8>> {
@@ -35,6 +172,7 @@ impl<W: Write + Seek> Writer<W> {
 
         let opts = LzmaEncoderOptions {
             dict_size: Some(1 << (16 + self.options.level.min(7))),
+            match_finder: self.options.match_finder,
             ..Default::default()
         };
         let mut output = Vec::new();
@@ -63,6 +201,23 @@ impl<W: Write + Seek> Writer<W> {
         Ok(output)
     }
 
+    /// Compresses data using Deflate64.
+    #[cfg(feature = "deflate")]
+    pub(crate) fn compress_deflate64(&self, data: &[u8]) -> Result<Vec<u8>> {
+        use crate::codec::deflate::{Deflate64Encoder, DeflateEncoderOptions};
+
+        let opts = DeflateEncoderOptions {
+            level: self.options.level,
+        };
+        let mut output = Vec::new();
+        {
+            let mut encoder = Deflate64Encoder::new(&mut output, &opts);
+            encoder.write_all(data).map_err(crate::Error::Io)?;
+            encoder.try_finish().map_err(crate::Error::Io)?;
+        }
+        Ok(output)
+    }
+
     /// Compresses data using BZip2.
     #[cfg(feature = "bzip2")]
     pub(crate) fn compress_bzip2(&self, data: &[u8]) -> Result<Vec<u8>> {
@@ -70,6 +225,7 @@ impl<W: Write + Seek> Writer<W> {
 
         let opts = Bzip2EncoderOptions {
             level: self.options.level,
+            block_size: self.options.bzip2_block_size,
         };
         let mut output = Vec::new();
         {
@@ -83,7 +239,7 @@ impl<W: Write + Seek> Writer<W> {
     /// Compresses data using Zstd.
     #[cfg(feature = "zstd")]
     pub(crate) fn compress_zstd(&self, data: &[u8]) -> Result<Vec<u8>> {
-        use super::ZSTD_LEVEL_MAP;
+        use crate::codec::ZSTD_LEVEL_MAP;
         use crate::codec::zstd::{ZstdEncoderOptions, ZstdStreamEncoder};
 
         let zstd_level = ZSTD_LEVEL_MAP[self.options.level.min(9) as usize];
@@ -91,8 +247,11 @@ impl<W: Write + Seek> Writer<W> {
         let opts = ZstdEncoderOptions { level: zstd_level };
         let mut output = Vec::new();
         {
-            let mut encoder = ZstdStreamEncoder::new(&mut output, &opts)
-                .map_err(|e| crate::Error::Io(std::io::Error::other(e)))?;
+            let mut encoder = match &self.options.zstd_dictionary {
+                Some(dict) => ZstdStreamEncoder::with_dictionary(&mut output, &opts, dict),
+                None => ZstdStreamEncoder::new(&mut output, &opts),
+            }
+            .map_err(|e| crate::Error::Io(std::io::Error::other(e)))?;
             encoder.write_all(data).map_err(crate::Error::Io)?;
             encoder.try_finish().map_err(crate::Error::Io)?;
         }
@@ -117,7 +276,7 @@ impl<W: Write + Seek> Writer<W> {
     /// Compresses data using Brotli.
     #[cfg(feature = "brotli")]
     pub(crate) fn compress_brotli(&self, data: &[u8]) -> Result<Vec<u8>> {
-        use super::BROTLI_QUALITY_MAP;
+        use crate::codec::BROTLI_QUALITY_MAP;
         use crate::codec::brotli::{BrotliEncoder, BrotliEncoderOptions};
 
         let quality = BROTLI_QUALITY_MAP[self.options.level.min(9) as usize];
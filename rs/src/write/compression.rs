@@ -26,6 +26,8 @@ impl<W: Write + Seek> Writer<W> {
             CodecMethod::Lzma => self.compress_lzma(data),
             #[cfg(feature = "deflate")]
             CodecMethod::Deflate => self.compress_deflate(data),
+            #[cfg(feature = "deflate")]
+            CodecMethod::Deflate64 => self.compress_deflate64(data),
             #[cfg(feature = "bzip2")]
             CodecMethod::BZip2 => self.compress_bzip2(data),
             #[cfg(feature = "zstd")]
@@ -43,6 +45,40 @@ impl<W: Write + Seek> Writer<W> {
         }
     }
 
+    /// Compresses the data of a solid block made of members of `member_sizes` bytes.
+    ///
+    /// By default the block is compressed as one stream, so the dictionary is
+    /// carried across members. When [`SolidOptions::reset_dictionary_between_members`]
+    /// is set and the method is LZMA2, each member is encoded separately and the
+    /// chunk streams are concatenated, so every member starts with a dictionary
+    /// reset chunk. Other methods ignore the setting.
+    ///
+    /// [`SolidOptions::reset_dictionary_between_members`]: super::SolidOptions::reset_dictionary_between_members
+    pub(crate) fn compress_solid_data(&self, data: &[u8], member_sizes: &[u64]) -> Result<Vec<u8>> {
+        #[cfg(feature = "lzma2")]
+        if self.options.solid.reset_dictionary_between_members
+            && self.options.method == crate::codec::CodecMethod::Lzma2
+            && member_sizes.len() > 1
+        {
+            let mut output = Vec::new();
+            let mut offset = 0usize;
+            for &size in member_sizes {
+                let end = offset + size as usize;
+                let member = self.compress_lzma2(&data[offset..end])?;
+                // Drop each member's end-of-stream marker; one is appended at the end
+                output.extend_from_slice(member.strip_suffix(&[0x00]).unwrap_or(&member));
+                offset = end;
+            }
+            output.push(0x00);
+            return Ok(output);
+        }
+
+        #[cfg(not(feature = "lzma2"))]
+        let _ = member_sizes;
+
+        self.compress_data(data)
+    }
+
     /// Applies the configured filter to data.
     ///
     /// Returns the filtered data. If no filter is configured, returns None.
@@ -123,9 +159,13 @@ impl<W: Write + Seek> Writer<W> {
     }
 
     /// Filters and compresses data, returning the compressed data and filter info.
+    ///
+    /// `member_sizes` lists the entry sizes of a solid block (empty for a
+    /// single entry); see [`Self::compress_solid_data`].
     pub(crate) fn filter_and_compress_data(
         &self,
         data: &[u8],
+        member_sizes: &[u64],
     ) -> Result<(Vec<u8>, Option<FilteredFolderInfo>)> {
         // Apply filter if configured
         let (data_to_compress, filter_info) = if self.options.filter.is_active() {
@@ -142,7 +182,7 @@ impl<W: Write + Seek> Writer<W> {
         };
 
         // Compress the (possibly filtered) data
-        let compressed = self.compress_data(&data_to_compress)?;
+        let compressed = self.compress_solid_data(&data_to_compress, member_sizes)?;
         Ok((compressed, filter_info))
     }
 
@@ -151,6 +191,7 @@ impl<W: Write + Seek> Writer<W> {
     pub(crate) fn filter_compress_and_encrypt_data(
         &self,
         data: &[u8],
+        member_sizes: &[u64],
     ) -> Result<(Vec<u8>, Option<FilteredFolderInfo>, EncryptedFolderInfo)> {
         use crate::crypto::{Aes256Encoder, AesProperties, derive_key};
 
@@ -169,7 +210,7 @@ impl<W: Write + Seek> Writer<W> {
         };
 
         // Compress the (possibly filtered) data
-        let compressed = self.compress_data(&data_to_compress)?;
+        let compressed = self.compress_solid_data(&data_to_compress, member_sizes)?;
         let compressed_size = compressed.len() as u64;
 
         // Encrypt
@@ -0,0 +1,107 @@
+//! Content digests over written entries.
+//!
+//! The scheme is documented on
+//! [`WriteResult::content_digest`](super::WriteResult::content_digest).
+
+use std::io::{self, Read};
+
+use sha2::{Digest, Sha256};
+
+/// Collects per-entry content hashes and combines them into one digest.
+#[derive(Debug, Default)]
+pub(crate) struct ContentDigest {
+    entries: Vec<(String, [u8; 32])>,
+}
+
+impl ContentDigest {
+    /// Records the content hash of the entry at `path`.
+    pub fn add(&mut self, path: &str, hash: [u8; 32]) {
+        self.entries.push((path.to_string(), hash));
+    }
+
+    /// Hashes `data` and records it for the entry at `path`.
+    pub fn add_bytes(&mut self, path: &str, data: &[u8]) {
+        self.add(path, Sha256::digest(data).into());
+    }
+
+    /// Returns the digest over all recorded entries in path order.
+    pub fn digest(&self) -> [u8; 32] {
+        let mut entries: Vec<_> = self.entries.iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut hasher = Sha256::new();
+        for (path, hash) in entries {
+            hasher.update((path.len() as u64).to_le_bytes());
+            hasher.update(path.as_bytes());
+            hasher.update(hash);
+        }
+        hasher.finalize().into()
+    }
+}
+
+/// Reader that hashes everything read through it.
+pub(crate) struct HashingReader<'a> {
+    inner: &'a mut dyn Read,
+    hasher: Sha256,
+}
+
+impl<'a> HashingReader<'a> {
+    /// Wraps `inner`.
+    pub fn new(inner: &'a mut dyn Read) -> Self {
+        Self {
+            inner,
+            hasher: Sha256::new(),
+        }
+    }
+
+    /// Returns the SHA-256 of the bytes read so far.
+    pub fn finish(self) -> [u8; 32] {
+        self.hasher.finalize().into()
+    }
+}
+
+impl Read for HashingReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.hasher.update(&buf[..n]);
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_digest_is_order_independent() {
+        let mut a = ContentDigest::default();
+        a.add_bytes("b.txt", b"two");
+        a.add_bytes("a.txt", b"one");
+
+        let mut b = ContentDigest::default();
+        b.add_bytes("a.txt", b"one");
+        b.add_bytes("b.txt", b"two");
+
+        assert_eq!(a.digest(), b.digest());
+    }
+
+    #[test]
+    fn test_content_digest_binds_path_to_content() {
+        let mut a = ContentDigest::default();
+        a.add_bytes("ab", b"c");
+        let mut b = ContentDigest::default();
+        b.add_bytes("a", b"bc");
+        assert_ne!(a.digest(), b.digest());
+    }
+
+    #[test]
+    fn test_hashing_reader_matches_direct_hash() {
+        let data = b"hello world".repeat(100);
+        let mut source = &data[..];
+        let mut reader = HashingReader::new(&mut source);
+        let mut out = Vec::new();
+        reader.read_to_end(&mut out).unwrap();
+        assert_eq!(out, data);
+        assert_eq!(reader.finish(), <[u8; 32]>::from(Sha256::digest(&data)));
+    }
+}
@@ -3,12 +3,14 @@
 //! This module provides functions for compressing individual entries,
 //! including solid and non-solid compression modes, and BCJ2 filter handling.
 
-use std::io::{Read, Seek, Write};
+use std::io::{self, Read, Seek, Write};
+use std::sync::{Arc, Mutex, PoisonError};
 
-use crate::{ArchivePath, Error, Result};
+use crate::format::SIGNATURE_HEADER_SIZE;
+use crate::{ArchivePath, Error, READ_BUFFER_SIZE, Result};
 
 use super::options::EntryMeta;
-use super::{Bcj2FolderInfo, PendingEntry, SolidBufferEntry, Writer};
+use super::{Bcj2FolderInfo, PendingEntry, SolidBufferEntry, Writer, WriterState};
 
 impl<W: Write + Seek> Writer<W> {
     /// Compresses an entry in non-solid mode.
@@ -21,6 +23,7 @@ impl<W: Write + Seek> Writer<W> {
         // Read all data and compute CRC
         let mut data = Vec::new();
         source.read_to_end(&mut data).map_err(Error::Io)?;
+        self.progress_add_total(data.len() as u64);
 
         // Check if BCJ2 filter is active - route to dedicated method
         if self.options.filter.is_bcj2() {
@@ -53,29 +56,30 @@ impl<W: Write + Seek> Writer<W> {
         #[cfg(feature = "aes")]
         let (output_data, filter_info, encryption_info) = if self.options.is_data_encrypted() {
             let (encrypted, filter_info, enc_info) =
-                self.filter_compress_and_encrypt_data(&data)?;
+                self.filter_compress_and_encrypt_data(&data, &[])?;
             (encrypted, filter_info, Some(enc_info))
         } else {
-            let (compressed, filter_info) = self.filter_and_compress_data(&data)?;
+            let (compressed, filter_info) = self.filter_and_compress_data(&data, &[])?;
             (compressed, filter_info, None)
         };
 
         #[cfg(not(feature = "aes"))]
         let (output_data, filter_info, encryption_info) = {
-            let (compressed, filter_info) = self.filter_and_compress_data(&data)?;
+            let (compressed, filter_info) = self.filter_and_compress_data(&data, &[])?;
             (compressed, filter_info, Option::<()>::None)
         };
 
         let packed_size = output_data.len() as u64;
 
         // Write compressed (and possibly encrypted) data
-        self.sink.write_all(&output_data).map_err(Error::Io)?;
+        self.align_pack_data()?;
+        self.write_pack_data(&output_data)?;
         self.compressed_bytes += packed_size;
 
         // Track stream info (only for non-empty files)
         self.stream_info.pack_sizes.push(packed_size);
         self.stream_info.unpack_sizes.push(uncompressed_size);
-        self.stream_info.crcs.push(crc);
+        self.stream_info.crcs.push(Some(crc));
 
         // Track encryption info for header writing
         #[cfg(feature = "aes")]
@@ -86,6 +90,7 @@ impl<W: Write + Seek> Writer<W> {
 
         // Track that this is not a BCJ2 folder
         self.stream_info.bcj2_folder_info.push(None);
+        self.stream_info.raw_folder_info.push(None);
 
         // Suppress unused variable warning when aes feature is disabled
         #[cfg(not(feature = "aes"))]
@@ -94,9 +99,104 @@ impl<W: Write + Seek> Writer<W> {
         // Track that this is a non-solid folder (1 stream per folder)
         self.stream_info.num_unpack_streams_per_folder.push(1);
 
+        self.progress_advance(uncompressed_size)
+    }
+
+    /// Compresses an entry in non-solid mode without buffering its data.
+    ///
+    /// Data is read in [`READ_BUFFER_SIZE`] chunks and the compressed output
+    /// of each chunk is written to the sink before the next one is read.
+    /// `meta.size` must be the exact size of the data. Falls back to
+    /// [`compress_entry_non_solid`](Self::compress_entry_non_solid) when the
+    /// configured pipeline cannot stream.
+    ///
+    /// If reading or compressing fails after part of the packed stream has
+    /// reached the sink, the writer is marked failed: the partial stream
+    /// would shift every later folder, so no further entries are accepted
+    /// and the archive cannot be finished.
+    pub(crate) fn compress_entry_streaming(
+        &mut self,
+        archive_path: ArchivePath,
+        source: &mut dyn Read,
+        meta: EntryMeta,
+    ) -> Result<()> {
+        let uncompressed_size = meta.size;
+        let output = SharedBuffer::default();
+        let Some(encoder) = self.stream_encoder(output.clone(), uncompressed_size)? else {
+            return self.compress_entry_non_solid(archive_path, source, meta);
+        };
+
+        if uncompressed_size > 0 {
+            self.progress_add_total(uncompressed_size);
+            self.align_pack_data()?;
+
+            let mut packed_size = 0u64;
+            let crc = match self.stream_pack_data(source, encoder, &output, &mut packed_size) {
+                Ok(crc) => crc,
+                Err(e) => {
+                    if packed_size > 0 {
+                        self.state = WriterState::Failed;
+                    }
+                    return Err(e);
+                }
+            };
+            self.compressed_bytes += packed_size;
+
+            self.stream_info.pack_sizes.push(packed_size);
+            self.stream_info.unpack_sizes.push(uncompressed_size);
+            self.stream_info.crcs.push(Some(crc));
+            #[cfg(feature = "aes")]
+            self.stream_info.encryption_info.push(None);
+            self.stream_info.filter_info.push(None);
+            self.stream_info.bcj2_folder_info.push(None);
+            self.stream_info.raw_folder_info.push(None);
+            self.stream_info.num_unpack_streams_per_folder.push(1);
+        }
+
+        self.entries.push(PendingEntry {
+            path: archive_path,
+            meta,
+            uncompressed_size,
+        });
         Ok(())
     }
 
+    /// Feeds `source` through `encoder` and writes the packed stream to the
+    /// sink, returning the CRC of the data read.
+    ///
+    /// `packed_size` is updated as packed bytes are written, so it is
+    /// accurate even when an error is returned.
+    fn stream_pack_data(
+        &mut self,
+        source: &mut dyn Read,
+        mut encoder: Box<dyn crate::codec::Encoder + '_>,
+        output: &SharedBuffer,
+        packed_size: &mut u64,
+    ) -> Result<u32> {
+        let mut hasher = crc32fast::Hasher::new();
+        let mut buf = vec![0u8; READ_BUFFER_SIZE];
+        loop {
+            let n = source.read(&mut buf).map_err(Error::Io)?;
+            if n == 0 {
+                break;
+            }
+            hasher.update(&buf[..n]);
+            encoder.write_all(&buf[..n]).map_err(Error::Io)?;
+            *packed_size += self.drain_pack_output(output)?;
+            self.progress_advance(n as u64)?;
+        }
+        encoder.finish().map_err(Error::Io)?;
+        *packed_size += self.drain_pack_output(output)?;
+        Ok(hasher.finalize())
+    }
+
+    /// Moves compressed output collected by a streaming encoder to the sink.
+    fn drain_pack_output(&mut self, output: &SharedBuffer) -> Result<u64> {
+        let data = output.take();
+        self.write_pack_data(&data)?;
+        Ok(data.len() as u64)
+    }
+
     /// Compresses an entry using BCJ2 4-stream filter.
     ///
     /// BCJ2 splits x86 code into 4 streams for improved compression:
@@ -132,10 +232,11 @@ impl<W: Write + Seek> Writer<W> {
         let streams = bcj2_encode(data);
 
         // Write all 4 streams sequentially to output
-        self.sink.write_all(&streams.main).map_err(Error::Io)?;
-        self.sink.write_all(&streams.call).map_err(Error::Io)?;
-        self.sink.write_all(&streams.jump).map_err(Error::Io)?;
-        self.sink.write_all(&streams.range).map_err(Error::Io)?;
+        self.align_pack_data()?;
+        self.write_pack_data(&streams.main)?;
+        self.write_pack_data(&streams.call)?;
+        self.write_pack_data(&streams.jump)?;
+        self.write_pack_data(&streams.range)?;
 
         let total_packed = streams.total_size() as u64;
         self.compressed_bytes += total_packed;
@@ -153,13 +254,14 @@ impl<W: Write + Seek> Writer<W> {
         // For BCJ2, we don't use pack_sizes (handled separately)
         // Store unpack_size and CRC
         self.stream_info.unpack_sizes.push(uncompressed_size);
-        self.stream_info.crcs.push(crc);
+        self.stream_info.crcs.push(Some(crc));
 
         // Track filter info as None (BCJ2 handled separately)
         self.stream_info.filter_info.push(None);
 
         // Track BCJ2 folder info
         self.stream_info.bcj2_folder_info.push(Some(bcj2_info));
+        self.stream_info.raw_folder_info.push(None);
 
         // Track encryption info (BCJ2 + encryption not supported yet)
         #[cfg(feature = "aes")]
@@ -168,7 +270,7 @@ impl<W: Write + Seek> Writer<W> {
         // Track that this is a non-solid folder (1 stream per folder)
         self.stream_info.num_unpack_streams_per_folder.push(1);
 
-        Ok(())
+        self.progress_advance(uncompressed_size)
     }
 
     /// Buffers an entry for solid compression.
@@ -181,9 +283,21 @@ impl<W: Write + Seek> Writer<W> {
         // Read all data and compute CRC
         let mut data = Vec::new();
         source.read_to_end(&mut data).map_err(Error::Io)?;
-        let crc = crc32fast::hash(&data);
         let data_size = data.len() as u64;
 
+        // Large files get their own folder instead of joining the solid block
+        if self
+            .options
+            .solid
+            .small_file_threshold
+            .is_some_and(|threshold| data_size >= threshold)
+        {
+            return self.compress_entry_non_solid(archive_path, &mut data.as_slice(), meta);
+        }
+
+        let crc = crc32fast::hash(&data);
+        self.progress_add_total(data_size);
+
         // Buffer the entry
         self.solid_buffer_size += data_size;
         self.solid_buffer.push(SolidBufferEntry {
@@ -212,6 +326,57 @@ impl<W: Write + Seek> Writer<W> {
         Ok(())
     }
 
+    /// Flushes the solid buffer when the archive is finished.
+    ///
+    /// If fewer folders than [`SolidOptions::min_folders`] have been written,
+    /// the buffered entries are partitioned into blocks of similar size to
+    /// make up the difference. Every block gets at least one non-empty entry.
+    ///
+    /// [`SolidOptions::min_folders`]: crate::write::SolidOptions::min_folders
+    pub(crate) fn flush_final_solid_buffer(&mut self) -> Result<()> {
+        let folders_written = self.stream_info.unpack_sizes.len();
+        let mut parts_left = self
+            .options
+            .solid
+            .min_folders
+            .map_or(1, |min| min.saturating_sub(folders_written))
+            .max(1);
+        if parts_left == 1 {
+            return self.flush_solid_buffer();
+        }
+
+        let buffered = std::mem::take(&mut self.solid_buffer);
+        self.solid_buffer_size = 0;
+
+        let mut unflushed_bytes: u64 = buffered.iter().map(|e| e.data.len() as u64).sum();
+        let mut streams_left = buffered.iter().filter(|e| !e.data.is_empty()).count();
+        let mut target = unflushed_bytes.div_ceil(parts_left as u64);
+
+        for entry in buffered {
+            let is_stream = !entry.data.is_empty();
+            self.solid_buffer_size += entry.data.len() as u64;
+            self.solid_buffer.push(entry);
+            if !is_stream {
+                continue;
+            }
+            streams_left -= 1;
+
+            // Flush once the block reaches its share, or when the remaining
+            // non-empty entries are only just enough for the remaining blocks
+            if parts_left > 1
+                && streams_left > 0
+                && (self.solid_buffer_size >= target || streams_left < parts_left)
+            {
+                unflushed_bytes -= self.solid_buffer_size;
+                self.flush_solid_buffer()?;
+                parts_left -= 1;
+                target = unflushed_bytes.div_ceil(parts_left as u64);
+            }
+        }
+
+        self.flush_solid_buffer()
+    }
+
     /// Flushes the solid buffer, compressing all buffered entries as one block.
     pub(crate) fn flush_solid_buffer(&mut self) -> Result<()> {
         if self.solid_buffer.is_empty() {
@@ -242,23 +407,24 @@ impl<W: Write + Seek> Writer<W> {
         #[cfg(feature = "aes")]
         let (output_data, filter_info, encryption_info) = if self.options.is_data_encrypted() {
             let (encrypted, filter_info, enc_info) =
-                self.filter_compress_and_encrypt_data(&combined)?;
+                self.filter_compress_and_encrypt_data(&combined, &sizes)?;
             (encrypted, filter_info, Some(enc_info))
         } else {
-            let (compressed, filter_info) = self.filter_and_compress_data(&combined)?;
+            let (compressed, filter_info) = self.filter_and_compress_data(&combined, &sizes)?;
             (compressed, filter_info, None)
         };
 
         #[cfg(not(feature = "aes"))]
         let (output_data, filter_info, encryption_info) = {
-            let (compressed, filter_info) = self.filter_and_compress_data(&combined)?;
+            let (compressed, filter_info) = self.filter_and_compress_data(&combined, &sizes)?;
             (compressed, filter_info, Option::<()>::None)
         };
 
         let packed_size = output_data.len() as u64;
 
         // Write compressed (and possibly encrypted) data
-        self.sink.write_all(&output_data).map_err(Error::Io)?;
+        self.align_pack_data()?;
+        self.write_pack_data(&output_data)?;
         self.compressed_bytes += packed_size;
 
         // Record ONE folder with streams for non-empty entries only
@@ -274,6 +440,7 @@ impl<W: Write + Seek> Writer<W> {
 
         // Track that this is not a BCJ2 folder
         self.stream_info.bcj2_folder_info.push(None);
+        self.stream_info.raw_folder_info.push(None);
 
         // Suppress unused variable warning when aes feature is disabled
         #[cfg(not(feature = "aes"))]
@@ -288,13 +455,11 @@ impl<W: Write + Seek> Writer<W> {
         // For solid blocks with exactly 1 stream, use folder CRC directly (no SubStreamsInfo needed).
         if num_streams == 1 {
             // Single non-empty file: use folder CRC, no substreams needed
-            self.stream_info
-                .crcs
-                .push(crcs.first().copied().unwrap_or(0));
+            self.stream_info.crcs.push(crcs.first().copied());
             // Don't add to substream_sizes/crcs - not needed for single stream
         } else {
             // Multiple non-empty files: use substream CRCs
-            self.stream_info.crcs.push(0);
+            self.stream_info.crcs.push(None);
             self.stream_info.substream_sizes.extend_from_slice(&sizes);
             self.stream_info.substream_crcs.extend_from_slice(&crcs);
         }
@@ -311,6 +476,102 @@ impl<W: Write + Seek> Writer<W> {
 
         self.solid_buffer_size = 0;
 
+        self.progress_advance(total_uncompressed)
+    }
+
+    /// Pads the packed data so the next folder starts at the configured
+    /// alignment.
+    ///
+    /// The zero-filled gap is recorded as a Copy folder with no unpack
+    /// streams, which readers skip when mapping entries to folders. In
+    /// header-first mode `pack_pos` is aligned when the header is written,
+    /// so only the offset within the packed data matters here.
+    pub(crate) fn align_pack_data(&mut self) -> Result<()> {
+        let Some(alignment) = self.options.effective_pack_alignment() else {
+            return Ok(());
+        };
+
+        let bcj2_packed: u64 = self
+            .stream_info
+            .bcj2_folder_info
+            .iter()
+            .flatten()
+            .map(|info| info.pack_sizes.iter().sum::<u64>())
+            .sum();
+        let raw_packed: u64 = self
+            .stream_info
+            .raw_folder_info
+            .iter()
+            .flatten()
+            .map(|info| info.pack_sizes.iter().sum::<u64>())
+            .sum();
+        let packed = self.stream_info.pack_sizes.iter().sum::<u64>() + bcj2_packed + raw_packed;
+        let start = if self.options.header_first {
+            0
+        } else {
+            SIGNATURE_HEADER_SIZE
+        };
+
+        let offset = start + packed;
+        let gap = offset.next_multiple_of(alignment) - offset;
+        if gap == 0 {
+            return Ok(());
+        }
+
+        let padding = vec![0u8; gap as usize];
+        self.write_pack_data(&padding)?;
+
+        self.stream_info.pack_sizes.push(gap);
+        self.stream_info.unpack_sizes.push(gap);
+        self.stream_info.crcs.push(Some(crc32fast::hash(&padding)));
+        #[cfg(feature = "aes")]
+        self.stream_info.encryption_info.push(None);
+        self.stream_info.filter_info.push(None);
+        self.stream_info.bcj2_folder_info.push(None);
+        self.stream_info.raw_folder_info.push(None);
+        self.stream_info.num_unpack_streams_per_folder.push(0);
+
+        Ok(())
+    }
+
+    /// Writes packed stream data to the sink.
+    ///
+    /// In header-first mode the data is buffered instead, since it must be
+    /// placed after a header whose size is not yet known.
+    pub(crate) fn write_pack_data(&mut self, data: &[u8]) -> Result<()> {
+        if self.options.header_first {
+            self.pack_buffer.extend_from_slice(data);
+            Ok(())
+        } else {
+            self.write_body(data)
+        }
+    }
+}
+
+/// Output buffer shared between a streaming encoder and the writer.
+///
+/// The encoder owns one handle and appends compressed bytes to it; the
+/// writer drains it into the sink after every input chunk.
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl SharedBuffer {
+    /// Takes the bytes collected so far.
+    fn take(&self) -> Vec<u8> {
+        std::mem::take(&mut *self.0.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
         Ok(())
     }
 }
@@ -3,7 +3,6 @@
 //! This module provides methods for adding entries to an archive from
 //! various sources: files, streams, and byte slices.
 
-use std::fs::File;
 use std::io::{BufReader, Read, Seek, Write};
 use std::path::Path;
 
@@ -31,12 +30,19 @@ impl<W: Write + Seek> Writer<W> {
         self.ensure_accepting_entries()?;
 
         let disk_path = disk_path.as_ref();
-        let meta = EntryMeta::from_path(disk_path)?;
+        let mut meta = EntryMeta::from_path(disk_path)?;
+        if self.options.preserve_bsd_flags {
+            meta.bsd_flags = crate::bsd_flags::read_flags(disk_path).map_err(Error::Io)?;
+        }
 
         if meta.is_directory {
             self.add_directory(archive_path, meta)
         } else {
-            let file = File::open(disk_path).map_err(Error::Io)?;
+            let mut file = self.options.share_mode.open(disk_path).map_err(Error::Io)?;
+            if self.options.preserve_sparse {
+                meta.sparse_holes =
+                    crate::sparse::detect_holes(&mut file, meta.size).map_err(Error::Io)?;
+            }
             let mut reader = BufReader::new(file);
             self.add_stream(archive_path, &mut reader, meta)
         }
@@ -132,14 +138,86 @@ impl<W: Write + Seek> Writer<W> {
         archive_path: ArchivePath,
         source: &mut dyn Read,
         meta: EntryMeta,
+    ) -> Result<()> {
+        self.add_entry_data(archive_path, source, meta, false)
+    }
+
+    /// Adds `size` bytes of data from a reader, streaming them through the codec.
+    ///
+    /// Unlike [`add_stream`](Self::add_stream), which reads the whole entry
+    /// into memory before compressing it, this method compresses non-solid
+    /// entries as the data arrives: it holds one read buffer and the codec's
+    /// working state, and writes compressed output to the sink after every
+    /// read. Large sources such as network streams can therefore be added
+    /// without buffering them.
+    ///
+    /// The entry is still buffered in full when the configured pipeline
+    /// needs all of it up front: in solid mode, with a filter, with
+    /// encryption, or with content-defined chunking. In header-first mode
+    /// the compressed output is kept in memory until the archive is
+    /// finished, as for every other entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `archive_path` - Path within the archive
+    /// * `reader` - Reader providing the data
+    /// * `size` - Exact number of bytes to read; overrides `meta.size`
+    /// * `meta` - Entry metadata
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the reader ends before `size` bytes, if
+    /// compression fails, or if the writer is in an invalid state. If the
+    /// reader fails after part of the compressed entry has been written,
+    /// the writer rejects further entries and cannot be finished.
+    pub fn add_reader(
+        &mut self,
+        archive_path: ArchivePath,
+        reader: impl Read,
+        size: u64,
+        meta: EntryMeta,
+    ) -> Result<()> {
+        let mut source = ExactSizeReader {
+            inner: reader,
+            size,
+            remaining: size,
+        };
+        let meta = EntryMeta { size, ..meta };
+        self.add_entry_data(archive_path, &mut source, meta, true)
+    }
+
+    /// Adds a file entry whose data comes from `source`.
+    ///
+    /// With `streaming`, non-solid entries go through
+    /// [`compress_entry_streaming`](Self::compress_entry_streaming), which
+    /// relies on `meta.size` being exact.
+    fn add_entry_data(
+        &mut self,
+        archive_path: ArchivePath,
+        source: &mut dyn Read,
+        meta: EntryMeta,
+        streaming: bool,
     ) -> Result<()> {
         self.ensure_accepting_entries()?;
 
+        #[cfg(feature = "merkle")]
+        let path = archive_path.as_str().to_string();
+        #[cfg(feature = "merkle")]
+        let mut hashing = super::content_digest::HashingReader::new(source);
+        #[cfg(feature = "merkle")]
+        let source: &mut dyn Read = &mut hashing;
+
         if self.options.solid.is_solid() {
-            self.buffer_entry_solid(archive_path, source, meta)
+            self.buffer_entry_solid(archive_path, source, meta)?;
+        } else if streaming {
+            self.compress_entry_streaming(archive_path, source, meta)?;
         } else {
-            self.compress_entry_non_solid(archive_path, source, meta)
+            self.compress_entry_non_solid(archive_path, source, meta)?;
         }
+
+        #[cfg(feature = "merkle")]
+        self.content_digest.add(&path, hashing.finish());
+        Ok(())
     }
 
     /// Adds data from a byte slice.
@@ -158,3 +236,37 @@ impl<W: Write + Seek> Writer<W> {
         self.add_stream(archive_path, &mut cursor, meta)
     }
 }
+
+/// Reader that yields exactly `size` bytes of its inner reader.
+///
+/// Reading past `size` returns end of stream; running out of data before
+/// `size` bytes is an [`UnexpectedEof`](std::io::ErrorKind::UnexpectedEof) error.
+struct ExactSizeReader<R> {
+    inner: R,
+    size: u64,
+    remaining: u64,
+}
+
+impl<R: Read> Read for ExactSizeReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.remaining == 0 || buf.is_empty() {
+            return Ok(0);
+        }
+        let limit = buf
+            .len()
+            .min(usize::try_from(self.remaining).unwrap_or(usize::MAX));
+        let n = self.inner.read(&mut buf[..limit])?;
+        if n == 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                format!(
+                    "reader ended after {} of {} bytes",
+                    self.size - self.remaining,
+                    self.size
+                ),
+            ));
+        }
+        self.remaining -= n as u64;
+        Ok(n)
+    }
+}
@@ -8,60 +8,81 @@ use std::io::{Seek, Write};
 use crate::Result;
 use crate::format::property_id;
 use crate::format::reader::write_variable_u64;
+use crate::format::streams::Folder;
 
 use super::encoding_utils::encode_bool_vector;
 use super::{FilteredFolderInfo, Writer};
 
 impl<W: Write + Seek> Writer<W> {
     /// Encodes the archive header.
-    pub(crate) fn encode_header(&self) -> Result<Vec<u8>> {
+    ///
+    /// `pack_pos` is the offset of the first packed stream relative to the
+    /// end of the signature header.
+    pub(crate) fn encode_header(&self, pack_pos: u64) -> Result<Vec<u8>> {
         let mut header = Vec::new();
 
         // Header marker
         header.push(property_id::HEADER);
 
-        // Check if we have BCJ2 folders
+        // Check if we have BCJ2 or copied folders
         let has_bcj2 = self
             .stream_info
             .bcj2_folder_info
             .iter()
             .any(|f| f.is_some());
+        let has_raw = self.stream_info.raw_folder_info.iter().any(|f| f.is_some());
 
         // Count total pack streams:
         // - Regular folders: 1 pack stream each
         // - BCJ2 folders: 4 pack streams each
+        // - Copied folders: as many as the source folder had
         let total_pack_streams: usize = self
             .stream_info
             .bcj2_folder_info
             .iter()
-            .map(|f| if f.is_some() { 4 } else { 1 })
+            .zip(&self.stream_info.raw_folder_info)
+            .map(|(bcj2, raw)| match (bcj2, raw) {
+                (Some(_), _) => 4,
+                (None, Some(raw)) => raw.pack_sizes.len(),
+                (None, None) => 1,
+            })
             .sum();
 
         // MainStreamsInfo (if we have data)
-        let has_streams = !self.stream_info.pack_sizes.is_empty() || has_bcj2;
+        let has_streams = !self.stream_info.pack_sizes.is_empty() || has_bcj2 || has_raw;
         if has_streams {
             header.push(property_id::MAIN_STREAMS_INFO);
 
             // PackInfo
             header.push(property_id::PACK_INFO);
-            write_variable_u64(&mut header, 0)?; // pack_pos (relative to data start)
+            write_variable_u64(&mut header, pack_pos)?; // pack_pos (relative to data start)
             write_variable_u64(&mut header, total_pack_streams as u64)?;
 
             // Pack sizes - write all pack sizes for all folders
             header.push(property_id::SIZE);
-            let mut non_bcj2_pack_idx = 0;
-            for bcj2_info in self.stream_info.bcj2_folder_info.iter() {
+            let mut regular_pack_idx = 0;
+            for (bcj2_info, raw_info) in self
+                .stream_info
+                .bcj2_folder_info
+                .iter()
+                .zip(&self.stream_info.raw_folder_info)
+            {
                 if let Some(info) = bcj2_info {
                     // BCJ2 folder: 4 pack sizes
                     for &size in &info.pack_sizes {
                         write_variable_u64(&mut header, size)?;
                     }
+                } else if let Some(info) = raw_info {
+                    // Copied folder: the source folder's pack sizes
+                    for &size in &info.pack_sizes {
+                        write_variable_u64(&mut header, size)?;
+                    }
                 } else {
                     // Regular folder: 1 pack size
-                    if let Some(&size) = self.stream_info.pack_sizes.get(non_bcj2_pack_idx) {
+                    if let Some(&size) = self.stream_info.pack_sizes.get(regular_pack_idx) {
                         write_variable_u64(&mut header, size)?;
                     }
-                    non_bcj2_pack_idx += 1;
+                    regular_pack_idx += 1;
                 }
             }
             header.push(property_id::END);
@@ -85,16 +106,25 @@ impl<W: Write + Seek> Writer<W> {
                 self.encode_unpack_sizes(&mut header, i, unpack_size)?;
             }
 
-            // CRCs for folders
-            header.push(property_id::CRC);
-            header.push(1); // all defined
-            for &crc in &self.stream_info.crcs {
-                header.extend_from_slice(&crc.to_le_bytes());
+            // CRCs for folders (multi-stream solid folders have none)
+            if self.stream_info.crcs.iter().any(Option::is_some) {
+                header.push(property_id::CRC);
+                if self.stream_info.crcs.iter().all(Option::is_some) {
+                    header.push(1); // all defined
+                } else {
+                    header.push(0);
+                    let defined: Vec<bool> =
+                        self.stream_info.crcs.iter().map(Option::is_some).collect();
+                    header.extend_from_slice(&encode_bool_vector(&defined));
+                }
+                for crc in self.stream_info.crcs.iter().flatten() {
+                    header.extend_from_slice(&crc.to_le_bytes());
+                }
             }
 
             header.push(property_id::END); // End UnpackInfo
 
-            // SubStreamsInfo - needed if any folder has more than 1 stream
+            // SubStreamsInfo - needed if any folder has other than 1 stream
             self.encode_substreams_info(&mut header)?;
 
             header.push(property_id::END); // End MainStreamsInfo
@@ -110,6 +140,25 @@ impl<W: Write + Seek> Writer<W> {
 
     /// Encodes a single folder's coder chain.
     fn encode_folder(&self, header: &mut Vec<u8>, folder_idx: usize) -> Result<()> {
+        // Copied folders keep the coder chain of the source archive
+        if let Some(Some(info)) = self.stream_info.raw_folder_info.get(folder_idx) {
+            return encode_raw_folder(header, &info.folder);
+        }
+
+        // Padding folders (no unpack streams) hold zeros stored with Copy
+        if self
+            .stream_info
+            .num_unpack_streams_per_folder
+            .get(folder_idx)
+            == Some(&0)
+        {
+            let method_id = crate::codec::method::COPY;
+            header.push(0x01); // num_coders = 1
+            header.push(method_id.len() as u8);
+            header.extend_from_slice(method_id);
+            return Ok(());
+        }
+
         // Check if this is a BCJ2 folder
         let bcj2_info = self
             .stream_info
@@ -245,6 +294,14 @@ impl<W: Write + Seek> Writer<W> {
         folder_idx: usize,
         unpack_size: u64,
     ) -> Result<()> {
+        // Copied folder: one size per coder output, as in the source archive
+        if let Some(Some(info)) = self.stream_info.raw_folder_info.get(folder_idx) {
+            for &size in &info.folder.unpack_sizes {
+                write_variable_u64(header, size)?;
+            }
+            return Ok(());
+        }
+
         // Check for BCJ2 folder
         let bcj2_info = self
             .stream_info
@@ -314,12 +371,15 @@ impl<W: Write + Seek> Writer<W> {
     }
 
     /// Encodes SubStreamsInfo section.
+    ///
+    /// Written when any folder has other than one stream: solid blocks, or
+    /// alignment padding folders that hold none.
     fn encode_substreams_info(&self, header: &mut Vec<u8>) -> Result<()> {
         let has_substreams = self
             .stream_info
             .num_unpack_streams_per_folder
             .iter()
-            .any(|&n| n > 1);
+            .any(|&n| n != 1);
 
         if !has_substreams {
             return Ok(());
@@ -337,10 +397,14 @@ impl<W: Write + Seek> Writer<W> {
         if !self.stream_info.substream_sizes.is_empty() {
             header.push(property_id::SIZE);
 
+            // substream_sizes only holds streams of multi-stream folders
             let mut stream_idx = 0;
             for &count in &self.stream_info.num_unpack_streams_per_folder {
+                if count <= 1 {
+                    continue;
+                }
                 // Write all sizes except the last one in each folder
-                for i in 0..(count as usize).saturating_sub(1) {
+                for i in 0..(count as usize) - 1 {
                     if stream_idx + i < self.stream_info.substream_sizes.len() {
                         write_variable_u64(
                             header,
@@ -426,6 +490,19 @@ impl<W: Write + Seek> Writer<W> {
         header.push(0); // external = 0
         header.extend_from_slice(&names_data);
 
+        // CTime (if any entries have it)
+        let has_ctime: Vec<_> = self
+            .entries
+            .iter()
+            .map(|e| e.meta.creation_time.is_some())
+            .collect();
+        if has_ctime.iter().any(|&x| x) {
+            header.push(property_id::CTIME);
+            let ctime_data = self.encode_times(&has_ctime, |e| e.meta.creation_time);
+            write_variable_u64(header, ctime_data.len() as u64)?;
+            header.extend_from_slice(&ctime_data);
+        }
+
         // MTime (if any entries have it)
         let has_mtime: Vec<_> = self
             .entries
@@ -439,6 +516,32 @@ impl<W: Write + Seek> Writer<W> {
             header.extend_from_slice(&mtime_data);
         }
 
+        // Sparse file hole maps
+        let has_holes: Vec<_> = self
+            .entries
+            .iter()
+            .map(|e| !e.meta.sparse_holes.is_empty())
+            .collect();
+        if has_holes.iter().any(|&x| x) {
+            header.push(property_id::SPARSE_HOLES);
+            let holes_data = self.encode_sparse_holes(&has_holes)?;
+            write_variable_u64(header, holes_data.len() as u64)?;
+            header.extend_from_slice(&holes_data);
+        }
+
+        // BSD file flags
+        let has_flags: Vec<_> = self
+            .entries
+            .iter()
+            .map(|e| e.meta.bsd_flags.is_some())
+            .collect();
+        if has_flags.iter().any(|&x| x) {
+            header.push(property_id::BSD_FLAGS);
+            let flags_data = self.encode_bsd_flags(&has_flags);
+            write_variable_u64(header, flags_data.len() as u64)?;
+            header.extend_from_slice(&flags_data);
+        }
+
         // Comment (if set in options)
         if let Some(ref comment) = self.options.comment {
             header.push(property_id::COMMENT);
@@ -455,6 +558,11 @@ impl<W: Write + Seek> Writer<W> {
     /// Returns whether the method has properties to encode.
     pub(crate) fn method_has_properties(&self) -> bool {
         use crate::codec::CodecMethod;
+
+        #[cfg(feature = "zstd")]
+        if self.options.method == CodecMethod::Zstd && self.options.zstd_dictionary.is_some() {
+            return true;
+        }
         matches!(
             self.options.method,
             CodecMethod::Lzma | CodecMethod::Lzma2 | CodecMethod::PPMd
@@ -493,6 +601,13 @@ impl<W: Write + Seek> Writer<W> {
                 props.extend_from_slice(&mem_size.to_le_bytes());
                 props
             }
+            #[cfg(feature = "zstd")]
+            CodecMethod::Zstd => self
+                .options
+                .zstd_dictionary
+                .as_ref()
+                .map(|dict| dict.coder_properties())
+                .unwrap_or_default(),
             _ => Vec::new(),
         }
     }
@@ -555,3 +670,41 @@ impl<W: Write + Seek> Writer<W> {
         Ok(())
     }
 }
+
+/// Encodes a folder read from another archive, coder for coder.
+fn encode_raw_folder(header: &mut Vec<u8>, folder: &Folder) -> Result<()> {
+    write_variable_u64(header, folder.coders.len() as u64)?;
+
+    for coder in &folder.coders {
+        let is_complex = coder.num_in_streams != 1 || coder.num_out_streams != 1;
+        let flags = (coder.method_id.len() as u8)
+            | if is_complex { 0x10 } else { 0 }
+            | if coder.properties.is_some() { 0x20 } else { 0 };
+        header.push(flags);
+        header.extend_from_slice(&coder.method_id);
+
+        if is_complex {
+            write_variable_u64(header, coder.num_in_streams)?;
+            write_variable_u64(header, coder.num_out_streams)?;
+        }
+
+        if let Some(props) = &coder.properties {
+            write_variable_u64(header, props.len() as u64)?;
+            header.extend_from_slice(props);
+        }
+    }
+
+    for bind_pair in &folder.bind_pairs {
+        write_variable_u64(header, bind_pair.in_index)?;
+        write_variable_u64(header, bind_pair.out_index)?;
+    }
+
+    // A single packed stream is implied by the unbound input
+    if folder.packed_streams.len() > 1 {
+        for &index in &folder.packed_streams {
+            write_variable_u64(header, index)?;
+        }
+    }
+
+    Ok(())
+}
@@ -1,10 +1,13 @@
 //! Metadata encoding for archive entries.
 //!
-//! This module provides functions for encoding file names, comments, and timestamps
-//! in the 7z archive format.
+//! This module provides functions for encoding file names, comments, timestamps
+//! and sparse file hole maps in the 7z archive format.
 
 use std::io::{Seek, Write};
 
+use crate::Result;
+use crate::format::reader::write_variable_u64;
+
 use super::encoding_utils::encode_bool_vector;
 use super::{PendingEntry, Writer};
 
@@ -72,4 +75,51 @@ impl<W: Write + Seek> Writer<W> {
 
         data
     }
+
+    /// Encodes the hole maps of sparse entries.
+    pub(crate) fn encode_sparse_holes(&self, defined: &[bool]) -> Result<Vec<u8>> {
+        let mut data = Vec::new();
+
+        // AllDefined flag
+        if defined.iter().all(|&x| x) {
+            data.push(1);
+        } else {
+            data.push(0);
+            data.extend_from_slice(&encode_bool_vector(defined));
+        }
+
+        for entry in &self.entries {
+            let holes = &entry.meta.sparse_holes;
+            if !holes.is_empty() {
+                write_variable_u64(&mut data, holes.len() as u64)?;
+                for &(offset, len) in holes {
+                    write_variable_u64(&mut data, offset)?;
+                    write_variable_u64(&mut data, len)?;
+                }
+            }
+        }
+
+        Ok(data)
+    }
+
+    /// Encodes BSD file flags as little-endian u32 values.
+    pub(crate) fn encode_bsd_flags(&self, defined: &[bool]) -> Vec<u8> {
+        let mut data = Vec::new();
+
+        // AllDefined flag
+        if defined.iter().all(|&x| x) {
+            data.push(1);
+        } else {
+            data.push(0);
+            data.extend_from_slice(&encode_bool_vector(defined));
+        }
+
+        for entry in &self.entries {
+            if let Some(flags) = entry.meta.bsd_flags {
+                data.extend_from_slice(&flags.to_le_bytes());
+            }
+        }
+
+        data
+    }
 }
@@ -21,51 +21,40 @@
 
 // Existing modules
 mod append;
+mod cdc;
 pub(crate) mod options;
 
 // Refactored modules
 mod codecs;
 mod compression;
+#[cfg(feature = "merkle")]
+pub(crate) mod content_digest;
 mod encoding_utils;
 mod entry_compression;
 mod entry_input;
 mod header_encode;
 mod header_encryption;
 mod metadata_encode;
+mod progress;
+mod raw_copy;
+mod streaming_sink;
 mod writer_init;
 
 // Re-exports
 pub use append::{AppendResult, ArchiveAppender};
-pub use options::{EntryMeta, Lzma2Variant, SolidOptions, WriteFilter, WriteOptions, WriteResult};
+pub use cdc::{CdcChunks, CdcConfig};
+pub use options::{
+    EntryMeta, Lzma2Variant, ShareMode, SolidOptions, WriteFilter, WriteOptions, WriteResult,
+};
+pub use streaming_sink::StreamingSink;
 
-use crate::ArchivePath;
+#[cfg(feature = "aes")]
+pub(crate) use append::plan_raw_folders;
+#[cfg(feature = "aes")]
+pub(crate) use raw_copy::RawFolder;
 
-/// Maps zesven compression level (0-9) to Zstd level (1-22).
-///
-/// | Input | Zstd | Characteristic |
-/// |-------|------|----------------|
-/// | 0-1   | 1    | Fastest        |
-/// | 2     | 2    | Fast           |
-/// | 3     | 3    | Fast           |
-/// | 4     | 5    | Balanced       |
-/// | 5     | 7    | Balanced       |
-/// | 6     | 9    | Balanced       |
-/// | 7     | 12   | High           |
-/// | 8     | 15   | High           |
-/// | 9     | 19   | Maximum        |
-#[cfg(feature = "zstd")]
-const ZSTD_LEVEL_MAP: [i32; 10] = [1, 1, 2, 3, 5, 7, 9, 12, 15, 19];
-
-/// Maps zesven compression level (0-9) to Brotli quality (0-11).
-///
-/// | Input | Brotli | Characteristic |
-/// |-------|--------|----------------|
-/// | 0-6   | 0-6    | Direct mapping |
-/// | 7     | 8      | High           |
-/// | 8     | 10     | High           |
-/// | 9     | 11     | Maximum        |
-#[cfg(feature = "brotli")]
-const BROTLI_QUALITY_MAP: [u32; 10] = [0, 1, 2, 3, 4, 5, 6, 8, 10, 11];
+use crate::ArchivePath;
+use crate::format::streams::Folder;
 
 /// State of the writer.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -76,6 +65,9 @@ enum WriterState {
     Building,
     /// Archive is finished.
     Finished,
+    /// An entry failed after part of its packed data was written, so the
+    /// archive can no longer be completed.
+    Failed,
 }
 
 /// Entry data stored for header writing.
@@ -136,16 +128,30 @@ struct Bcj2FolderInfo {
     pack_sizes: [u64; 4],
 }
 
+/// Metadata for a folder copied verbatim from another archive.
+///
+/// The coder chain is written back as it was read, so the folder's packed
+/// streams decode exactly as they did in the source archive.
+#[derive(Debug, Clone)]
+struct RawFolderInfo {
+    /// Coders, bind pairs and unpack sizes of the copied folder.
+    folder: Folder,
+    /// Sizes of the folder's packed streams.
+    pack_sizes: Vec<u64>,
+}
+
 /// Stream info for pack/unpack info.
 #[derive(Debug, Default)]
 struct StreamInfo {
     /// Packed sizes for each folder. Most folders have 1, BCJ2 has 4.
-    /// For BCJ2 folders, this is empty; use bcj2_folder_info instead.
+    /// For BCJ2 and copied folders, this is empty; use bcj2_folder_info
+    /// or raw_folder_info instead.
     pack_sizes: Vec<u64>,
     /// Total unpacked size for each folder.
     unpack_sizes: Vec<u64>,
-    /// CRCs for each folder (used for non-solid).
-    crcs: Vec<u32>,
+    /// CRCs for each folder (`None` for multi-stream solid folders, whose
+    /// entries are covered by substream CRCs instead).
+    crcs: Vec<Option<u32>>,
     /// Number of unpack streams in each folder (for solid archives).
     num_unpack_streams_per_folder: Vec<u64>,
     /// Sizes of each substream within solid blocks.
@@ -159,6 +165,8 @@ struct StreamInfo {
     filter_info: Vec<Option<FilteredFolderInfo>>,
     /// Per-folder BCJ2 info (Some for BCJ2 folders, None for regular).
     bcj2_folder_info: Vec<Option<Bcj2FolderInfo>>,
+    /// Per-folder copied coder chains (Some for folders copied verbatim).
+    raw_folder_info: Vec<Option<RawFolderInfo>>,
 }
 
 /// A 7z archive writer.
@@ -174,6 +182,17 @@ pub struct Writer<W> {
     solid_buffer: Vec<SolidBufferEntry>,
     /// Current size of solid buffer (uncompressed bytes).
     solid_buffer_size: u64,
+    /// Packed data held back until the header is written (header-first mode).
+    pack_buffer: Vec<u8>,
+    /// Uncompressed bytes compressed so far, for progress reporting.
+    progress_processed: u64,
+    /// Uncompressed bytes accepted so far, for progress reporting.
+    progress_total: u64,
+    /// Recovery record computed from the data written so far.
+    recovery_record: Option<crate::recovery::RecordEncoder>,
+    /// Content hashes of the entries added so far.
+    #[cfg(feature = "merkle")]
+    content_digest: content_digest::ContentDigest,
 }
 
 #[cfg(test)]
@@ -322,6 +341,154 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "lzma")]
+    #[test]
+    fn test_small_file_threshold_groups_small_files() {
+        use crate::read::Archive;
+        use std::collections::HashMap;
+
+        let options =
+            WriteOptions::new().solid_options(SolidOptions::enabled().small_file_threshold(1024));
+        let mut writer = Writer::create(Cursor::new(Vec::new()))
+            .unwrap()
+            .options(options);
+
+        let large = vec![0x5Au8; 4096];
+        let files: [(&str, &[u8]); 5] = [
+            ("a.txt", b"tiny a"),
+            ("big1.bin", &large),
+            ("b.txt", b"tiny b"),
+            ("big2.bin", &large[..1024]),
+            ("c.txt", b"tiny c"),
+        ];
+        for (name, data) in files {
+            writer
+                .add_bytes(ArchivePath::new(name).unwrap(), data)
+                .unwrap();
+        }
+        let (_result, cursor) = writer.finish_into_inner().unwrap();
+
+        let mut archive = Archive::open(Cursor::new(cursor.into_inner())).unwrap();
+        let mut members: HashMap<usize, Vec<String>> = HashMap::new();
+        for entry in archive.entries() {
+            members
+                .entry(entry.folder_index.unwrap())
+                .or_default()
+                .push(entry.path.as_str().to_string());
+        }
+        let folder_of = |name: &str| {
+            members
+                .values()
+                .find(|names| names.iter().any(|n| n == name))
+                .unwrap()
+        };
+
+        // Files at or above the threshold are alone in their folder
+        assert_eq!(folder_of("big1.bin").len(), 1);
+        assert_eq!(folder_of("big2.bin").len(), 1);
+        // Small files share one solid folder
+        assert_eq!(folder_of("a.txt"), &["a.txt", "b.txt", "c.txt"]);
+        assert_eq!(members.len(), 3);
+
+        for (name, data) in files {
+            assert_eq!(archive.extract_to_vec(name).unwrap(), data);
+        }
+    }
+
+    #[cfg(feature = "lzma")]
+    #[test]
+    fn test_min_folders_partitions_solid_block() {
+        use crate::read::Archive;
+        use std::collections::HashSet;
+
+        let build = |files: &[(&str, &[u8])], min_folders: usize| {
+            let options = WriteOptions::new().solid().min_folders(min_folders);
+            let mut writer = Writer::create(Cursor::new(Vec::new()))
+                .unwrap()
+                .options(options);
+            for (name, data) in files {
+                writer
+                    .add_bytes(ArchivePath::new(name).unwrap(), data)
+                    .unwrap();
+            }
+            let (_result, cursor) = writer.finish_into_inner().unwrap();
+            let mut archive = Archive::open(Cursor::new(cursor.into_inner())).unwrap();
+            for (name, data) in files {
+                assert_eq!(&archive.extract_to_vec(name).unwrap(), data);
+            }
+            let folders: HashSet<usize> = archive
+                .entries()
+                .iter()
+                .filter_map(|e| e.folder_index)
+                .collect();
+            assert_eq!(folders.len(), archive.info().folder_count);
+            archive.info().folder_count
+        };
+
+        let block = vec![0x42u8; 1000];
+        let files: [(&str, &[u8]); 8] = [
+            ("a.bin", &block),
+            ("empty1.txt", b""),
+            ("b.bin", &block[..200]),
+            ("c.bin", &block[..600]),
+            ("d.bin", &block),
+            ("e.bin", &block[..50]),
+            ("f.bin", &block[..800]),
+            ("empty2.txt", b""),
+        ];
+        assert_eq!(build(&files, 1), 1);
+        assert_eq!(build(&files, 3), 3);
+        // Every folder holds at least one non-empty file
+        assert_eq!(build(&files, 10), 6);
+    }
+
+    #[cfg(feature = "lzma2")]
+    #[test]
+    fn test_solid_dictionary_reset_between_members() {
+        use crate::codec::CodecMethod;
+        use crate::read::Archive;
+
+        let files: Vec<(String, Vec<u8>)> = (0..16)
+            .map(|i| {
+                let text = format!("record {i}: the quick brown fox jumps over the lazy dog\n");
+                (format!("file{i:02}.txt"), text.repeat(20).into_bytes())
+            })
+            .collect();
+
+        let build = |reset: bool| {
+            let options = WriteOptions::new()
+                .method(CodecMethod::Lzma2)
+                .solid_options(SolidOptions::enabled().reset_dictionary_between_members(reset));
+            let mut writer = Writer::create(Cursor::new(Vec::new()))
+                .unwrap()
+                .options(options);
+            for (name, data) in &files {
+                writer
+                    .add_bytes(ArchivePath::new(name).unwrap(), data)
+                    .unwrap();
+            }
+            let (_result, cursor) = writer.finish_into_inner().unwrap();
+            cursor.into_inner()
+        };
+
+        let carried = build(false);
+        let reset = build(true);
+        assert!(
+            carried.len() < reset.len(),
+            "carried-over dictionary ({} bytes) should beat per-member reset ({} bytes)",
+            carried.len(),
+            reset.len()
+        );
+
+        for bytes in [carried, reset] {
+            let mut archive = Archive::open(Cursor::new(bytes)).unwrap();
+            assert_eq!(archive.entries().len(), files.len());
+            for (name, data) in &files {
+                assert_eq!(&archive.extract_to_vec(name).unwrap(), data);
+            }
+        }
+    }
+
     #[cfg(feature = "lzma")]
     #[test]
     fn test_no_comment() {
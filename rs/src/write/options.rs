@@ -1,11 +1,26 @@
 //! Write options and configuration for archive creation.
 
+use std::sync::{Arc, Mutex};
+
 use crate::codec::CodecMethod;
 use crate::format::streams::ResourceLimits;
+use crate::progress::ProgressReporter;
+
+use super::cdc::CdcConfig;
 
+#[cfg(feature = "lzma")]
+use crate::codec::MatchFinder;
+#[cfg(feature = "zstd")]
+use crate::codec::ZstdDictionary;
 #[cfg(feature = "aes")]
 use crate::crypto::{NoncePolicy, Password};
 
+/// Default block size for parallel LZMA2 compression (4 MiB).
+const DEFAULT_LZMA2_BLOCK_SIZE: usize = 4 * 1024 * 1024;
+
+/// Minimum block size for parallel LZMA2 compression (64 KiB).
+const MIN_LZMA2_BLOCK_SIZE: usize = 64 * 1024;
+
 /// Pre-compression filter for improving compression of specific data types.
 ///
 /// BCJ (Branch/Call/Jump) filters transform executable code addresses from
@@ -155,6 +170,18 @@ pub struct WriteOptions {
     pub level: u32,
     /// LZMA2 encoder variant (standard or fast).
     pub lzma2_variant: Lzma2Variant,
+    /// Whether LZMA2 data is compressed in parallel blocks.
+    pub parallel_lzma2: bool,
+    /// Block size in bytes for parallel LZMA2 compression.
+    pub lzma2_block_size: usize,
+    /// BZip2 block size in units of 100 KB (`None` follows the level).
+    pub bzip2_block_size: Option<u8>,
+    /// LZMA/LZMA2 match finder (`None` follows the level).
+    #[cfg(feature = "lzma")]
+    pub match_finder: Option<MatchFinder>,
+    /// Dictionary for ZSTD compression.
+    #[cfg(feature = "zstd")]
+    pub zstd_dictionary: Option<ZstdDictionary>,
     /// Pre-compression filter.
     pub filter: WriteFilter,
     /// Solid archive options.
@@ -165,6 +192,24 @@ pub struct WriteOptions {
     pub deterministic: bool,
     /// Archive comment.
     pub comment: Option<String>,
+    /// Whether to place the header before the packed data.
+    pub header_first: bool,
+    /// Whether directory entries are written before the entries they contain.
+    pub parents_first: bool,
+    /// Byte alignment for the start of each folder's packed data.
+    pub pack_alignment: Option<u32>,
+    /// Size of the recovery record in percent of the archive data.
+    pub recovery_record: Option<u8>,
+    /// Content-defined chunking of LZMA2 streams.
+    pub cdc_chunking: Option<CdcConfig>,
+    /// Sharing allowed to other processes while source files are read.
+    pub share_mode: ShareMode,
+    /// Whether to store BSD file flags of files added from disk.
+    pub preserve_bsd_flags: bool,
+    /// Whether to store the holes of sparse files added from disk.
+    pub preserve_sparse: bool,
+    /// Progress reporter for compression (optional).
+    pub progress: Option<Arc<Mutex<dyn ProgressReporter>>>,
     /// Password for encryption (requires "aes" feature).
     #[cfg(feature = "aes")]
     pub password: Option<Password>,
@@ -199,11 +244,27 @@ impl Default for WriteOptions {
             method: CodecMethod::Lzma2,
             level: 5,
             lzma2_variant: Lzma2Variant::Standard,
+            parallel_lzma2: false,
+            lzma2_block_size: DEFAULT_LZMA2_BLOCK_SIZE,
+            bzip2_block_size: None,
+            #[cfg(feature = "lzma")]
+            match_finder: None,
+            #[cfg(feature = "zstd")]
+            zstd_dictionary: None,
             filter: WriteFilter::None,
             solid: SolidOptions::default(),
             limits: ResourceLimits::default(),
             deterministic: false,
             comment: None,
+            header_first: false,
+            parents_first: false,
+            pack_alignment: None,
+            recovery_record: None,
+            cdc_chunking: None,
+            share_mode: ShareMode::default(),
+            preserve_bsd_flags: false,
+            preserve_sparse: false,
+            progress: None,
             #[cfg(feature = "aes")]
             password: None,
             #[cfg(feature = "aes")]
@@ -222,10 +283,26 @@ impl std::fmt::Debug for WriteOptions {
         s.field("method", &self.method)
             .field("level", &self.level)
             .field("lzma2_variant", &self.lzma2_variant)
+            .field("parallel_lzma2", &self.parallel_lzma2)
+            .field("lzma2_block_size", &self.lzma2_block_size)
+            .field("bzip2_block_size", &self.bzip2_block_size)
             .field("filter", &self.filter)
             .field("solid", &self.solid)
             .field("deterministic", &self.deterministic)
-            .field("comment", &self.comment);
+            .field("comment", &self.comment)
+            .field("header_first", &self.header_first)
+            .field("parents_first", &self.parents_first)
+            .field("pack_alignment", &self.pack_alignment)
+            .field("recovery_record", &self.recovery_record)
+            .field("cdc_chunking", &self.cdc_chunking)
+            .field("share_mode", &self.share_mode)
+            .field("preserve_bsd_flags", &self.preserve_bsd_flags)
+            .field("preserve_sparse", &self.preserve_sparse)
+            .field("has_progress", &self.progress.is_some());
+        #[cfg(feature = "lzma")]
+        s.field("match_finder", &self.match_finder);
+        #[cfg(feature = "zstd")]
+        s.field("zstd_dictionary", &self.zstd_dictionary);
         #[cfg(feature = "aes")]
         s.field("has_password", &self.password.is_some());
         s.finish()
@@ -342,6 +419,130 @@ impl WriteOptions {
         self.lzma2_variant(Lzma2Variant::Fast)
     }
 
+    /// Sets whether LZMA2 data is compressed in parallel blocks.
+    ///
+    /// When enabled, LZMA2 streams larger than
+    /// [`lzma2_block_size`](Self::lzma2_block_size) are split into blocks
+    /// that are compressed on multiple threads and concatenated into a single
+    /// standard LZMA2 stream, readable by any LZMA2 decoder including 7-Zip.
+    /// Each block starts with a dictionary reset, which costs a little
+    /// compression ratio. The output does not depend on the thread count.
+    ///
+    /// Disabled by default, so the same input always produces the same
+    /// archive. Without the `parallel` feature this setting has no effect.
+    pub fn parallel_lzma2(mut self, enabled: bool) -> Self {
+        self.parallel_lzma2 = enabled;
+        self
+    }
+
+    /// Sets the block size for parallel LZMA2 compression (default 4 MiB).
+    ///
+    /// Values below 64 KiB are raised to 64 KiB. Larger blocks compress
+    /// better; smaller blocks give more parallelism on small inputs.
+    pub fn lzma2_block_size(mut self, size: usize) -> Self {
+        self.lzma2_block_size = size.max(MIN_LZMA2_BLOCK_SIZE);
+        self
+    }
+
+    /// Sets the BZip2 block size in units of 100 KB (clamped to 1-9).
+    ///
+    /// Only used with [`CodecMethod::BZip2`]. By default the block size
+    /// follows the compression level. Smaller blocks reduce memory use for
+    /// compression and decompression; larger blocks usually compress better.
+    pub fn bzip2_block_size(mut self, size: u8) -> Self {
+        self.bzip2_block_size = Some(size.clamp(1, 9));
+        self
+    }
+
+    /// Sets the match finder for LZMA and LZMA2 compression.
+    ///
+    /// [`MatchFinder::HashChain4`] is faster;
+    /// [`MatchFinder::BinaryTree4`] finds longer matches and usually
+    /// compresses better. By default the encoder uses the binary tree.
+    #[cfg(feature = "lzma")]
+    pub fn match_finder(mut self, match_finder: MatchFinder) -> Self {
+        self.match_finder = Some(match_finder);
+        self
+    }
+
+    /// Sets a dictionary for ZSTD compression.
+    ///
+    /// Only used with [`CodecMethod::Zstd`]. A dictionary trained on similar
+    /// data, for example with
+    /// [`train_zstd_dictionary`](crate::codec::train_zstd_dictionary),
+    /// greatly improves the ratio of small files. The dictionary ID is
+    /// stored in the coder properties, and extraction needs the same
+    /// dictionary through
+    /// [`ExtractOptions::zstd_dictionary`](crate::read::ExtractOptions::zstd_dictionary).
+    /// Other 7z tools cannot extract such archives.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `dictionary` is too small to be a ZSTD dictionary.
+    #[cfg(feature = "zstd")]
+    pub fn zstd_dictionary(mut self, dictionary: Vec<u8>) -> crate::Result<Self> {
+        self.zstd_dictionary = Some(ZstdDictionary::from_bytes(dictionary)?);
+        Ok(self)
+    }
+
+    /// Sets content-defined chunking for LZMA2 streams (`None` disables it).
+    ///
+    /// Data is split at content-defined boundaries and each chunk is
+    /// compressed independently, so unchanged regions of an edited file
+    /// produce identical compressed bytes across archive versions. This helps
+    /// backup tools that deduplicate stored archives, at some cost in ratio.
+    /// The output is a standard LZMA2 stream; see [`CdcConfig`] for details.
+    /// Only [`CodecMethod::Lzma2`] honours this setting.
+    pub fn cdc_chunking(mut self, config: Option<CdcConfig>) -> Self {
+        self.cdc_chunking = config;
+        self
+    }
+
+    /// Sets the sharing mode used when opening source files.
+    ///
+    /// Only affects Windows, where it controls the share flags passed to
+    /// `CreateFile` by [`Writer::add_path`](super::Writer::add_path). Other
+    /// platforms do not use mandatory file locks and ignore this setting.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use zesven::write::{ShareMode, WriteOptions};
+    ///
+    /// // Fail instead of archiving files that other processes have open
+    /// let options = WriteOptions::new().share_mode(ShareMode::Exclusive);
+    /// ```
+    pub fn share_mode(mut self, mode: ShareMode) -> Self {
+        self.share_mode = mode;
+        self
+    }
+
+    /// Sets whether BSD file flags are stored for files added from disk.
+    ///
+    /// macOS and the BSDs keep flags such as `uchg` (`UF_IMMUTABLE`) and
+    /// `hidden` (`UF_HIDDEN`) outside the Unix mode. When enabled,
+    /// [`Writer::add_path`](super::Writer::add_path) stores them in a zesven
+    /// header property, and extraction with attribute preservation restores
+    /// them with `chflags`. Other readers ignore the property. Disabled by
+    /// default; has no effect on other platforms.
+    pub fn preserve_bsd_flags(mut self, enabled: bool) -> Self {
+        self.preserve_bsd_flags = enabled;
+        self
+    }
+
+    /// Sets whether the holes of sparse files added from disk are stored.
+    ///
+    /// When enabled, [`Writer::add_path`](super::Writer::add_path) finds
+    /// the unallocated ranges of each file with `SEEK_DATA`/`SEEK_HOLE` and
+    /// stores them in a zesven header property, and extraction leaves those
+    /// ranges unallocated again. Other readers ignore the property and
+    /// extract the zeros. Disabled by default; has no effect on platforms
+    /// without hole detection.
+    pub fn preserve_sparse(mut self, enabled: bool) -> Self {
+        self.preserve_sparse = enabled;
+        self
+    }
+
     /// Sets the pre-compression filter.
     ///
     /// Filters transform data before compression to improve compression ratios
@@ -438,6 +639,24 @@ impl WriteOptions {
         self
     }
 
+    /// Splits solid archives into at least `count` folders.
+    ///
+    /// Trades a little compression ratio for parallel decompression; see
+    /// [`SolidOptions::min_folders`]. Has no effect unless solid compression
+    /// is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use zesven::WriteOptions;
+    ///
+    /// let options = WriteOptions::new().solid().min_folders(4);
+    /// ```
+    pub fn min_folders(mut self, count: usize) -> Self {
+        self.solid.min_folders = Some(count);
+        self
+    }
+
     /// Enables deterministic mode for reproducible archives.
     pub fn deterministic(mut self, enabled: bool) -> Self {
         self.deterministic = enabled;
@@ -453,6 +672,108 @@ impl WriteOptions {
         self
     }
 
+    /// Places the archive header directly after the signature header.
+    ///
+    /// 7z archives normally store the header at the end of the file. With
+    /// this option the next header offset is 0 and the packed data follows
+    /// the header, so a reader fetching a prefix of the archive (e.g. over
+    /// HTTP range requests) sees the entry list without a second request
+    /// for the tail.
+    ///
+    /// # Compatibility
+    ///
+    /// The layout is valid 7z: `pack_pos` in the header points past the
+    /// header, and readers that follow the offsets (including zesven and
+    /// 7-Zip) open it normally. Tools that assume the header is the last
+    /// structure in the file (e.g. in-place appenders) may not handle it.
+    ///
+    /// Packed data is buffered in memory until [`Writer::finish`] because
+    /// the header size is only known once all entries have been added.
+    ///
+    /// [`Writer::finish`]: crate::Writer::finish
+    pub fn header_first(mut self, enabled: bool) -> Self {
+        self.header_first = enabled;
+        self
+    }
+
+    /// Writes each directory entry before any entry inside it.
+    ///
+    /// Entries are normally stored in the order they were added, so a file
+    /// may come before the entry for its parent directory. Extractors that
+    /// create directories as they encounter them then have to create the
+    /// parents implicitly. With this option, directory entries added at any
+    /// point are moved ahead of their first descendant when the archive is
+    /// finished; all other entries keep their relative order.
+    ///
+    /// Only directories that were added as entries are moved; missing
+    /// parent directories are not created.
+    pub fn parents_first(mut self, enabled: bool) -> Self {
+        self.parents_first = enabled;
+        self
+    }
+
+    /// Aligns the start of each folder's packed data to `alignment` bytes.
+    ///
+    /// Gaps before a folder are filled with zeros and described in the
+    /// header as a Copy folder with no unpack streams, so the offsets the
+    /// reader computes land exactly on the aligned positions. Offsets are
+    /// absolute within the archive file. Values of 0 and 1 disable
+    /// alignment.
+    ///
+    /// This is useful when archives are stored on block devices or
+    /// memory-mapped, where aligned packed streams can be read directly.
+    pub fn pack_alignment(mut self, alignment: u32) -> Self {
+        self.pack_alignment = (alignment > 1).then_some(alignment);
+        self
+    }
+
+    /// Returns the pack alignment in effect, treating a `pack_alignment`
+    /// field set to 0 or 1 like `None`.
+    pub(crate) fn effective_pack_alignment(&self) -> Option<u64> {
+        self.pack_alignment
+            .filter(|&alignment| alignment > 1)
+            .map(u64::from)
+    }
+
+    /// Appends a recovery record of `percent` percent of the archive size.
+    ///
+    /// The record holds Reed-Solomon parity over the archive data and is
+    /// stored after the end of the 7z structure. Readers that follow the
+    /// header offsets still extract the archive, but 7-Zip reports a warning
+    /// ("There are some data after the end of the payload data") for the
+    /// trailing bytes, and tools that treat warnings as failures may reject
+    /// the archive. [`repair_with_record`](crate::recovery::repair_with_record) uses it
+    /// to rebuild damaged regions in place.
+    ///
+    /// The data is protected in 512-byte blocks grouped by 100: each group
+    /// can lose up to `percent` blocks, so a 5% record repairs up to 2.5 KiB
+    /// of contiguous damage per 50 KiB. CRCs used to locate damage add 4
+    /// bytes per block. A value of 0 disables the record; values above 100
+    /// are treated as 100. Recovery records are not supported for
+    /// multi-volume archives.
+    pub fn recovery_record(mut self, percent: u8) -> Self {
+        self.recovery_record = (percent > 0).then(|| percent.min(100));
+        self
+    }
+
+    /// Sets a progress reporter that follows compression.
+    ///
+    /// [`ProgressReporter::on_progress`] receives the number of uncompressed
+    /// bytes compressed so far and the total size of the entries added so
+    /// far. Entries streamed with
+    /// [`Writer::add_reader`](super::Writer::add_reader) are reported after
+    /// every read; other entries are reported once compressed, and entries
+    /// of a solid block when the block is flushed. Returning `false` stops
+    /// the writer with [`Error::Cancelled`](crate::Error::Cancelled).
+    ///
+    /// The reporter is shared so its state can be inspected after writing.
+    /// Wrap it in a [`ThrottledProgress`](crate::progress::ThrottledProgress)
+    /// to limit how often it is called.
+    pub fn progress<P: ProgressReporter + 'static>(mut self, reporter: Arc<Mutex<P>>) -> Self {
+        self.progress = Some(reporter);
+        self
+    }
+
     /// Sets the password for encryption.
     #[cfg(feature = "aes")]
     pub fn password(mut self, password: impl Into<Password>) -> Self {
@@ -467,6 +788,20 @@ impl WriteOptions {
         self
     }
 
+    /// Sets the key derivation work factor: 2^`power` SHA-256 iterations.
+    ///
+    /// Values above [`MAX_NUM_CYCLES_POWER`](crate::crypto::MAX_NUM_CYCLES_POWER)
+    /// are clamped to it, since readers refuse to derive keys beyond that
+    /// limit. The default is 19, as in 7-Zip. Applies to the current
+    /// [`nonce_policy`](Self::nonce_policy), so call it after setting one.
+    #[cfg(feature = "aes")]
+    pub fn key_derivation_power(mut self, power: u8) -> Self {
+        self.nonce_policy = self
+            .nonce_policy
+            .with_num_cycles_power(power.min(crate::crypto::MAX_NUM_CYCLES_POWER));
+        self
+    }
+
     /// Enables header encryption (hides file names).
     ///
     /// When enabled, the archive header is encrypted along with the data,
@@ -537,6 +872,14 @@ pub struct SolidOptions {
     pub block_size: Option<u64>,
     /// Maximum files per solid block (None = unlimited).
     pub files_per_block: Option<usize>,
+    /// Files at or above this size get their own non-solid folder
+    /// (None = all files are grouped into solid blocks).
+    pub small_file_threshold: Option<u64>,
+    /// Whether LZMA2 resets its dictionary at each member of a solid block
+    /// (default false: the dictionary is carried over for a better ratio).
+    pub reset_dictionary_between_members: bool,
+    /// Minimum number of folders the archive is split into (None = no minimum).
+    pub min_folders: Option<usize>,
 }
 
 impl SolidOptions {
@@ -546,6 +889,9 @@ impl SolidOptions {
             enabled: false,
             block_size: None,
             files_per_block: None,
+            small_file_threshold: None,
+            reset_dictionary_between_members: false,
+            min_folders: None,
         }
     }
 
@@ -563,6 +909,9 @@ impl SolidOptions {
             enabled: true,
             block_size: Some(64 * 1024 * 1024), // 64 MB default block size
             files_per_block: None,
+            small_file_threshold: None,
+            reset_dictionary_between_members: false,
+            min_folders: None,
         }
     }
 
@@ -578,6 +927,38 @@ impl SolidOptions {
         self
     }
 
+    /// Sets the size below which files are grouped into solid blocks.
+    ///
+    /// Files of `threshold` bytes or more are stored in their own non-solid
+    /// folder, so large files can be extracted without decompressing their
+    /// neighbours and small files still share solid blocks.
+    pub fn small_file_threshold(mut self, threshold: u64) -> Self {
+        self.small_file_threshold = Some(threshold);
+        self
+    }
+
+    /// Sets whether the dictionary is reset between members of a solid block.
+    ///
+    /// Resetting makes each member compress independently of the previous
+    /// ones, at the cost of ratio for similar files. Only LZMA2 honours this
+    /// setting; other methods always compress a block as one stream.
+    pub fn reset_dictionary_between_members(mut self, reset: bool) -> Self {
+        self.reset_dictionary_between_members = reset;
+        self
+    }
+
+    /// Sets the minimum number of folders the archive is split into.
+    ///
+    /// The entries buffered when the archive is finished are partitioned
+    /// into enough solid blocks of similar size to reach `count` folders,
+    /// so the blocks can be decompressed in parallel. Each block holds at
+    /// least one non-empty file and files are never split, so fewer folders
+    /// are produced when there are fewer non-empty files.
+    pub fn min_folders(mut self, count: usize) -> Self {
+        self.min_folders = Some(count);
+        self
+    }
+
     /// Returns whether solid compression is enabled.
     pub fn is_solid(&self) -> bool {
         self.enabled
@@ -620,6 +1001,53 @@ impl Lzma2Variant {
     }
 }
 
+/// Access that other processes may keep to a source file while it is read.
+///
+/// Used by [`WriteOptions::share_mode`]. Opening a file fails with a sharing
+/// violation if another handle holds access that the mode does not allow, or
+/// if the other handle itself does not allow reading.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ShareMode {
+    /// No sharing: the file must not be open anywhere else.
+    Exclusive,
+    /// Other handles may read the file.
+    Read,
+    /// Other handles may read and write the file (e.g. active log files).
+    ReadWrite,
+    /// Other handles may read, write, rename or delete the file.
+    ///
+    /// This is the standard library's default for [`std::fs::File::open`].
+    #[default]
+    All,
+}
+
+impl ShareMode {
+    /// Returns the Windows `FILE_SHARE_*` flags for this mode.
+    pub fn flags(&self) -> u32 {
+        const FILE_SHARE_READ: u32 = 0x1;
+        const FILE_SHARE_WRITE: u32 = 0x2;
+        const FILE_SHARE_DELETE: u32 = 0x4;
+        match self {
+            Self::Exclusive => 0,
+            Self::Read => FILE_SHARE_READ,
+            Self::ReadWrite => FILE_SHARE_READ | FILE_SHARE_WRITE,
+            Self::All => FILE_SHARE_READ | FILE_SHARE_WRITE | FILE_SHARE_DELETE,
+        }
+    }
+
+    /// Opens a file for reading with this sharing mode.
+    pub(crate) fn open(&self, path: &std::path::Path) -> std::io::Result<std::fs::File> {
+        let mut options = std::fs::OpenOptions::new();
+        options.read(true);
+        #[cfg(windows)]
+        {
+            use std::os::windows::fs::OpenOptionsExt;
+            options.share_mode(self.flags());
+        }
+        options.open(path)
+    }
+}
+
 /// Metadata for an entry being written.
 #[derive(Debug, Clone, Default)]
 pub struct EntryMeta {
@@ -637,6 +1065,17 @@ pub struct EntryMeta {
     pub attributes: Option<u32>,
     /// Whether this is an anti-item (marks file for deletion in incremental backups).
     pub is_anti: bool,
+    /// Holes of a sparse file as `(offset, length)` pairs.
+    ///
+    /// Filled in by [`Writer::add_path`](super::Writer::add_path) on platforms
+    /// that support `SEEK_HOLE`/`SEEK_DATA` when
+    /// [`WriteOptions::preserve_sparse`] is enabled.
+    pub sparse_holes: Vec<(u64, u64)>,
+    /// BSD file flags (`st_flags`).
+    ///
+    /// Filled in by [`Writer::add_path`](super::Writer::add_path) on macOS
+    /// and the BSDs when [`WriteOptions::preserve_bsd_flags`] is enabled.
+    pub bsd_flags: Option<u32>,
 }
 
 impl EntryMeta {
@@ -680,6 +1119,8 @@ impl EntryMeta {
             access_time: metadata.accessed().ok().map(system_time_to_filetime),
             attributes: None, // Platform-specific
             is_anti: false,
+            sparse_holes: Vec::new(),
+            bsd_flags: None,
         }
     }
 
@@ -777,6 +1218,22 @@ pub struct WriteResult {
     pub volume_count: u32,
     /// Size of each volume in bytes.
     pub volume_sizes: Vec<u64>,
+    /// SHA-256 digest of the entry contents, independent of compression.
+    ///
+    /// Every entry added with data (files, symlinks and other streams)
+    /// contributes its archive path and the SHA-256 of its uncompressed
+    /// content. Entries are sorted by path in byte order and hashed as
+    ///
+    /// ```text
+    /// digest = SHA-256(for each entry: len(path) as u64 LE || path || SHA-256(content))
+    /// ```
+    ///
+    /// where `path` is the archive path in UTF-8 with `/` separators. The
+    /// digest does not depend on the order entries were added, the method,
+    /// level or solid settings, or encryption, so archives of the same
+    /// content compare equal.
+    #[cfg(feature = "merkle")]
+    pub content_digest: [u8; 32],
 }
 
 impl WriteResult {
@@ -809,6 +1266,18 @@ mod tests {
         assert_eq!(opts.level, 5);
         assert!(!opts.solid.is_solid());
         assert!(!opts.deterministic);
+        assert!(!opts.parallel_lzma2);
+        assert_eq!(opts.lzma2_block_size, 4 * 1024 * 1024);
+    }
+
+    #[test]
+    fn test_write_options_parallel_lzma2() {
+        let opts = WriteOptions::new()
+            .parallel_lzma2(false)
+            .lzma2_block_size(1024);
+        assert!(!opts.parallel_lzma2);
+        assert_eq!(opts.lzma2_block_size, 64 * 1024);
+        assert!(WriteOptions::new().parallel_lzma2(true).parallel_lzma2);
     }
 
     #[test]
@@ -886,11 +1355,16 @@ mod tests {
     fn test_solid_options() {
         let opts = SolidOptions::enabled()
             .block_size(1024 * 1024)
-            .files_per_block(100);
+            .files_per_block(100)
+            .small_file_threshold(4096)
+            .reset_dictionary_between_members(true);
 
         assert!(opts.is_solid());
         assert_eq!(opts.block_size, Some(1024 * 1024));
         assert_eq!(opts.files_per_block, Some(100));
+        assert_eq!(opts.small_file_threshold, Some(4096));
+        assert!(opts.reset_dictionary_between_members);
+        assert!(!SolidOptions::enabled().reset_dictionary_between_members);
     }
 
     #[test]
@@ -908,6 +1382,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(not(feature = "merkle"), allow(clippy::needless_update))]
     fn test_write_result() {
         let result = WriteResult {
             entries_written: 10,
@@ -916,6 +1391,7 @@ mod tests {
             compressed_size: 500,
             volume_count: 1,
             volume_sizes: vec![500],
+            ..Default::default()
         };
         assert!((result.compression_ratio() - 0.5).abs() < 0.001);
         assert!((result.space_savings() - 0.5).abs() < 0.001);
@@ -927,4 +1403,13 @@ mod tests {
         assert!((result.compression_ratio() - 1.0).abs() < 0.001);
         assert!((result.space_savings() - 0.0).abs() < 0.001);
     }
+
+    #[test]
+    fn test_share_mode_flags() {
+        assert_eq!(WriteOptions::default().share_mode, ShareMode::All);
+        assert_eq!(ShareMode::Exclusive.flags(), 0);
+        assert_eq!(ShareMode::Read.flags(), 0x1);
+        assert_eq!(ShareMode::ReadWrite.flags(), 0x3);
+        assert_eq!(ShareMode::All.flags(), 0x7);
+    }
 }
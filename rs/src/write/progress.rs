@@ -0,0 +1,35 @@
+//! Progress reporting during compression.
+
+use std::io::{Seek, Write};
+use std::sync::PoisonError;
+
+use crate::{Error, Result};
+
+use super::Writer;
+
+impl<W: Write + Seek> Writer<W> {
+    /// Adds `bytes` of accepted entry data to the progress total.
+    pub(crate) fn progress_add_total(&mut self, bytes: u64) {
+        self.progress_total += bytes;
+        if let Some(progress) = &self.options.progress {
+            let mut progress = progress.lock().unwrap_or_else(PoisonError::into_inner);
+            progress.on_total(self.progress_total);
+        }
+    }
+
+    /// Records `bytes` of entry data as compressed and reports progress.
+    ///
+    /// Returns [`Error::Cancelled`] if the reporter asks to stop.
+    pub(crate) fn progress_advance(&mut self, bytes: u64) -> Result<()> {
+        self.progress_processed += bytes;
+        let Some(progress) = &self.options.progress else {
+            return Ok(());
+        };
+        let mut progress = progress.lock().unwrap_or_else(PoisonError::into_inner);
+        if progress.on_progress(self.progress_processed, self.progress_total) {
+            Ok(())
+        } else {
+            Err(Error::Cancelled)
+        }
+    }
+}
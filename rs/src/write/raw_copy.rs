@@ -0,0 +1,95 @@
+//! Verbatim folder copying.
+//!
+//! This module carries folders of an existing archive over into the archive
+//! being written without decoding them: their packed streams are copied
+//! byte for byte and their coder chains are written back unchanged.
+
+use std::io::{Read, Seek, Write};
+
+use crate::format::streams::Folder;
+use crate::{ArchivePath, Error, READ_BUFFER_SIZE, Result};
+
+use super::options::EntryMeta;
+use super::{PendingEntry, RawFolderInfo, Writer};
+
+/// A folder to copy verbatim from another archive.
+#[derive(Debug, Clone)]
+pub(crate) struct RawFolder {
+    /// Coders, bind pairs and unpack sizes of the folder.
+    pub folder: Folder,
+    /// Sizes of the folder's packed streams, in order.
+    pub pack_sizes: Vec<u64>,
+    /// Size and CRC of each unpack stream in the folder, in order.
+    pub streams: Vec<(u64, u32)>,
+}
+
+impl<W: Write + Seek> Writer<W> {
+    /// Copies a folder's packed streams from `source` without decoding them.
+    ///
+    /// `source` must be positioned at the folder's first packed stream. The
+    /// entries stored in the folder must be added next, in stream order,
+    /// with [`add_copied_entry`](Self::add_copied_entry).
+    pub(crate) fn copy_raw_folder(&mut self, raw: RawFolder, source: &mut dyn Read) -> Result<()> {
+        self.ensure_accepting_entries()?;
+
+        let packed_size: u64 = raw.pack_sizes.iter().sum();
+        self.align_pack_data()?;
+
+        let mut buf = vec![0u8; READ_BUFFER_SIZE];
+        let mut remaining = packed_size;
+        while remaining > 0 {
+            let n = remaining.min(buf.len() as u64) as usize;
+            source.read_exact(&mut buf[..n]).map_err(Error::Io)?;
+            self.write_pack_data(&buf[..n])?;
+            remaining -= n as u64;
+        }
+        self.compressed_bytes += packed_size;
+
+        let sizes: Vec<u64> = raw.streams.iter().map(|&(size, _)| size).collect();
+        let crcs: Vec<u32> = raw.streams.iter().map(|&(_, crc)| crc).collect();
+
+        self.stream_info.unpack_sizes.push(sizes.iter().sum());
+        #[cfg(feature = "aes")]
+        self.stream_info.encryption_info.push(None);
+        self.stream_info.filter_info.push(None);
+        self.stream_info.bcj2_folder_info.push(None);
+        self.stream_info
+            .num_unpack_streams_per_folder
+            .push(sizes.len() as u64);
+
+        // Single-stream folders carry their entry's CRC as the folder CRC,
+        // like the folders this writer produces
+        if let [crc] = crcs.as_slice() {
+            self.stream_info.crcs.push(Some(*crc));
+        } else {
+            self.stream_info.crcs.push(raw.folder.unpack_crc);
+            self.stream_info.substream_sizes.extend_from_slice(&sizes);
+            self.stream_info.substream_crcs.extend_from_slice(&crcs);
+        }
+
+        self.stream_info.raw_folder_info.push(Some(RawFolderInfo {
+            folder: raw.folder,
+            pack_sizes: raw.pack_sizes,
+        }));
+
+        Ok(())
+    }
+
+    /// Adds an entry without data of its own.
+    ///
+    /// Used for entries whose data lives in a folder copied with
+    /// [`copy_raw_folder`](Self::copy_raw_folder), and for directories and
+    /// empty files of the source archive. `meta.size` must be the entry's
+    /// unpacked size.
+    pub(crate) fn add_copied_entry(&mut self, path: ArchivePath, meta: EntryMeta) -> Result<()> {
+        self.ensure_accepting_entries()?;
+
+        let uncompressed_size = if meta.is_directory { 0 } else { meta.size };
+        self.entries.push(PendingEntry {
+            path,
+            meta,
+            uncompressed_size,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,112 @@
+//! Writing archives to non-seekable sinks.
+//!
+//! A 7z archive starts with a signature header that records the offset, size
+//! and CRC of the header at the end of the archive, so it can only be written
+//! once everything else is known. [`Writer::create_streaming`] therefore
+//! assembles the archive in memory and copies it to the sink when finished.
+
+use std::io::{self, Cursor, Seek, SeekFrom, Write};
+
+use crate::{Error, Result};
+
+use super::Writer;
+use super::options::WriteResult;
+
+/// A sink that buffers the archive until it is complete.
+///
+/// Created by [`Writer::create_streaming`]. Writes and seeks go to an
+/// in-memory buffer; [`StreamingSink::into_inner`] copies the buffer to the
+/// wrapped writer in one sequential pass.
+///
+/// # Memory use
+///
+/// The whole archive is held in memory until it is finished: peak memory
+/// is at least the size of the compressed archive, and nothing is spilled
+/// to disk. For archives that may not fit in memory, write to a seekable
+/// output such as a temporary [`File`](std::fs::File) with
+/// [`Writer::create`] and copy that to the sink instead.
+pub struct StreamingSink<W> {
+    buffer: Cursor<Vec<u8>>,
+    inner: W,
+}
+
+impl<W: Write> StreamingSink<W> {
+    /// Writes the buffered archive to the wrapped writer and returns it.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.inner.write_all(self.buffer.get_ref())?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+impl<W> Write for StreamingSink<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<W> Seek for StreamingSink<W> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.buffer.seek(pos)
+    }
+}
+
+impl<W> std::fmt::Debug for StreamingSink<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamingSink")
+            .field("buffered_len", &self.buffer.get_ref().len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<W: Write> Writer<StreamingSink<W>> {
+    /// Creates a writer for a sink that only implements [`Write`].
+    ///
+    /// Use this for pipes, sockets and other outputs that cannot seek. The
+    /// archive is assembled in memory and written to `sink` in one pass by
+    /// [`finish`](Self::finish), and nothing reaches `sink` before then.
+    ///
+    /// # Memory use
+    ///
+    /// Memory use grows with the archive: the complete compressed archive
+    /// is buffered in memory until [`finish`](Self::finish). Only use this
+    /// when the archive is known to fit; otherwise write to a temporary
+    /// [`File`](std::fs::File) with [`Writer::create`] and copy it to the
+    /// sink. See [`StreamingSink`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the writer cannot be initialized.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use zesven::{ArchivePath, Writer};
+    ///
+    /// let stdout = std::io::stdout().lock();
+    /// let mut writer = Writer::create_streaming(stdout)?;
+    /// writer.add_bytes(ArchivePath::new("hello.txt")?, b"Hello")?;
+    /// writer.finish()?;
+    /// ```
+    pub fn create_streaming(sink: W) -> Result<Self> {
+        Self::create(StreamingSink {
+            buffer: Cursor::new(Vec::new()),
+            inner: sink,
+        })
+    }
+
+    /// Finishes the archive and writes it to the sink.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if header writing or writing to the sink fails.
+    pub fn finish(self) -> Result<WriteResult> {
+        let (result, sink) = self.finish_into_inner()?;
+        sink.into_inner().map_err(Error::Io)?;
+        Ok(result)
+    }
+}
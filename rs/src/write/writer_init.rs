@@ -3,16 +3,18 @@
 //! This module provides methods for creating writers and finishing archive writing,
 //! including signature header writing.
 
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufWriter, Seek, SeekFrom, Write};
 use std::path::Path;
 
 use crate::format::{SIGNATURE, SIGNATURE_HEADER_SIZE};
+use crate::recovery::RecordEncoder;
 use crate::volume::{MultiVolumeWriter, VolumeConfig};
 use crate::{Error, Result};
 
 use super::options::{WriteOptions, WriteResult};
-use super::{StreamInfo, Writer, WriterState};
+use super::{PendingEntry, StreamInfo, Writer, WriterState};
 
 impl Writer<BufWriter<File>> {
     /// Creates a new archive file at the given path.
@@ -91,6 +93,35 @@ impl Writer<MultiVolumeWriter> {
         Self::create(writer)
     }
 
+    /// Creates a new multi-volume archive at the given base path.
+    ///
+    /// Shorthand for [`create_multivolume`](Self::create_multivolume) with
+    /// [`VolumeConfig::new`]. Volumes are written to `path` with `.001`,
+    /// `.002`, ... appended, and each volume except the last holds exactly
+    /// `volume_size` bytes, even when a single folder spans several volumes.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Base path of the archive (e.g., `"archive.7z"`)
+    /// * `volume_size` - Maximum size of each volume in bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the first volume file cannot be created.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use zesven::{Writer, ArchivePath};
+    ///
+    /// let mut writer = Writer::create_multivolume_path("archive.7z", 50 * 1024 * 1024)?;
+    /// writer.add_bytes(ArchivePath::new("data.bin")?, &large_data)?;
+    /// writer.finish()?; // archive.7z.001, archive.7z.002, ...
+    /// ```
+    pub fn create_multivolume_path(path: impl AsRef<Path>, volume_size: u64) -> Result<Self> {
+        Self::create_multivolume(VolumeConfig::new(path, volume_size))
+    }
+
     /// Finishes writing the multi-volume archive.
     ///
     /// This finalizes all volumes and returns a WriteResult with volume information.
@@ -103,6 +134,11 @@ impl Writer<MultiVolumeWriter> {
     ///
     /// Returns an error if header writing or volume finalization fails.
     pub fn finish(self) -> Result<WriteResult> {
+        if self.options.recovery_record.is_some() {
+            return Err(Error::UnsupportedFeature {
+                feature: "recovery records in multi-volume archives",
+            });
+        }
         let (mut result, mv_writer) = self.finish_into_inner()?;
 
         // Finalize the multi-volume writer and get volume sizes
@@ -138,6 +174,12 @@ impl<W: Write + Seek> Writer<W> {
             compressed_bytes: 0,
             solid_buffer: Vec::new(),
             solid_buffer_size: 0,
+            pack_buffer: Vec::new(),
+            progress_processed: 0,
+            progress_total: 0,
+            recovery_record: None,
+            #[cfg(feature = "merkle")]
+            content_digest: Default::default(),
         })
     }
 
@@ -177,7 +219,7 @@ impl<W: Write + Seek> Writer<W> {
 
         // Flush any remaining solid buffer
         if !self.solid_buffer.is_empty() {
-            self.flush_solid_buffer()?;
+            self.flush_final_solid_buffer()?;
         }
 
         // Sort entries if deterministic mode
@@ -186,23 +228,32 @@ impl<W: Write + Seek> Writer<W> {
                 .sort_by(|a, b| a.path.as_str().cmp(b.path.as_str()));
         }
 
+        if self.options.parents_first {
+            self.order_parents_first();
+        }
+
         // Record header position
         let header_pos = self.sink.stream_position().map_err(Error::Io)?;
 
-        // Write header (optionally encrypted)
-        let header_data = self.encode_header()?;
-
-        #[cfg(feature = "aes")]
-        let header_data = if self.options.is_header_encrypted() {
-            self.encode_encrypted_header(&header_data)?
+        let header_data = if self.options.header_first {
+            self.write_header_first()?
         } else {
+            let header_data = self.encode_final_header(0)?;
+            self.write_body(&header_data)?;
             header_data
         };
-
-        self.sink.write_all(&header_data).map_err(Error::Io)?;
+        let end_pos = self.sink.stream_position().map_err(Error::Io)?;
 
         // Write signature header at start
-        self.write_signature_header(header_pos, &header_data)?;
+        let signature_header = self.write_signature_header(header_pos, &header_data)?;
+
+        if let Some(encoder) = self.recovery_record.take() {
+            let record = encoder.finish(&signature_header);
+            self.sink
+                .seek(SeekFrom::Start(end_pos))
+                .map_err(Error::Io)?;
+            self.sink.write_all(&record).map_err(Error::Io)?;
+        }
 
         self.state = WriterState::Finished;
 
@@ -221,17 +272,109 @@ impl<W: Write + Seek> Writer<W> {
             compressed_size: self.compressed_bytes,
             volume_count: 1,
             volume_sizes: vec![final_pos],
+            #[cfg(feature = "merkle")]
+            content_digest: self.content_digest.digest(),
         };
 
         Ok((result, self.sink))
     }
 
-    /// Writes the signature header at the start of the file.
+    /// Moves each directory entry ahead of the first entry inside it.
+    ///
+    /// Directory entries have no stream, so moving them does not change
+    /// which folder stream each file maps to.
+    fn order_parents_first(&mut self) {
+        let directories: HashMap<&str, usize> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.meta.is_directory)
+            .map(|(idx, entry)| (entry.path.as_str(), idx))
+            .collect();
+
+        let mut placed = vec![false; self.entries.len()];
+        let mut order = Vec::with_capacity(self.entries.len());
+        for (idx, entry) in self.entries.iter().enumerate() {
+            if placed[idx] {
+                continue;
+            }
+            // Ancestors are visited outermost first
+            let path = entry.path.as_str();
+            for (separator, _) in path.match_indices('/') {
+                if let Some(&dir_idx) = directories.get(&path[..separator]) {
+                    if !placed[dir_idx] {
+                        placed[dir_idx] = true;
+                        order.push(dir_idx);
+                    }
+                }
+            }
+            placed[idx] = true;
+            order.push(idx);
+        }
+
+        let mut entries: Vec<Option<PendingEntry>> = std::mem::take(&mut self.entries)
+            .into_iter()
+            .map(Some)
+            .collect();
+        self.entries = order
+            .into_iter()
+            .filter_map(|idx| entries[idx].take())
+            .collect();
+    }
+
+    /// Encodes the header (optionally encrypted) for the given pack position.
+    fn encode_final_header(&self, pack_pos: u64) -> Result<Vec<u8>> {
+        let header_data = self.encode_header(pack_pos)?;
+
+        #[cfg(feature = "aes")]
+        let header_data = if self.options.is_header_encrypted() {
+            self.encode_encrypted_header(&header_data)?
+        } else {
+            header_data
+        };
+
+        Ok(header_data)
+    }
+
+    /// Writes the header followed by the buffered packed data.
+    ///
+    /// The header records `pack_pos`, whose encoded size affects the header
+    /// size, so the header is re-encoded until the packed data fits after it.
+    /// Any slack between the header and the packed data is zero-filled; with
+    /// a pack alignment the packed data starts on an aligned offset.
+    fn write_header_first(&mut self) -> Result<Vec<u8>> {
+        let mut pack_pos = 0u64;
+        let header_data = loop {
+            let header_data = self.encode_final_header(pack_pos)?;
+            let header_len = header_data.len() as u64;
+            if header_len <= pack_pos {
+                break header_data;
+            }
+            pack_pos = match self.options.effective_pack_alignment() {
+                Some(alignment) => {
+                    (SIGNATURE_HEADER_SIZE + header_len).next_multiple_of(alignment)
+                        - SIGNATURE_HEADER_SIZE
+                }
+                None => header_len,
+            };
+        };
+
+        self.write_body(&header_data)?;
+        let padding = vec![0u8; (pack_pos - header_data.len() as u64) as usize];
+        self.write_body(&padding)?;
+
+        let pack_data = std::mem::take(&mut self.pack_buffer);
+        self.write_body(&pack_data)?;
+
+        Ok(header_data)
+    }
+
+    /// Writes the signature header at the start of the file and returns it.
     pub(crate) fn write_signature_header(
         &mut self,
         header_pos: u64,
         header_data: &[u8],
-    ) -> Result<()> {
+    ) -> Result<Vec<u8>> {
         // Calculate values
         let next_header_offset = header_pos - SIGNATURE_HEADER_SIZE;
         let next_header_size = header_data.len() as u64;
@@ -245,28 +388,42 @@ impl<W: Write + Seek> Writer<W> {
 
         let start_header_crc = crc32fast::hash(&start_header);
 
+        let mut signature_header = Vec::with_capacity(SIGNATURE_HEADER_SIZE as usize);
+        // Signature (6 bytes)
+        signature_header.extend_from_slice(SIGNATURE);
+        // Version (2 bytes)
+        signature_header.extend_from_slice(&[0x00, 0x04]);
+        // Start header CRC (4 bytes)
+        signature_header.extend_from_slice(&start_header_crc.to_le_bytes());
+        // Start header (20 bytes)
+        signature_header.extend_from_slice(&start_header);
+
         // Seek to start and write signature header
         self.sink.seek(SeekFrom::Start(0)).map_err(Error::Io)?;
+        self.sink.write_all(&signature_header).map_err(Error::Io)?;
 
-        // Write signature (6 bytes)
-        self.sink.write_all(SIGNATURE).map_err(Error::Io)?;
-
-        // Write version (2 bytes)
-        self.sink.write_all(&[0x00, 0x04]).map_err(Error::Io)?;
-
-        // Write start header CRC (4 bytes)
-        self.sink
-            .write_all(&start_header_crc.to_le_bytes())
-            .map_err(Error::Io)?;
-
-        // Write start header (20 bytes)
-        self.sink.write_all(&start_header).map_err(Error::Io)?;
+        Ok(signature_header)
+    }
 
-        Ok(())
+    /// Writes archive data following the signature header to the sink.
+    ///
+    /// The data is also added to the recovery record, if one is enabled.
+    pub(crate) fn write_body(&mut self, data: &[u8]) -> Result<()> {
+        if let Some(percent) = self.options.recovery_record {
+            self.recovery_record
+                .get_or_insert_with(|| RecordEncoder::new(percent))
+                .update(data);
+        }
+        self.sink.write_all(data).map_err(Error::Io)
     }
 
     /// Ensures the writer is in the AcceptingEntries state.
     pub(crate) fn ensure_accepting_entries(&self) -> Result<()> {
+        if self.state == WriterState::Failed {
+            return Err(Error::InvalidFormat(
+                "Writer failed while writing an entry; the archive is incomplete".into(),
+            ));
+        }
         if self.state != WriterState::AcceptingEntries {
             return Err(Error::InvalidFormat(
                 "Writer is not accepting entries".into(),
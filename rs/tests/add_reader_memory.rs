@@ -0,0 +1,127 @@
+//! Memory behavior of `Writer::add_reader`.
+//!
+//! This file holds a single test because it installs a counting global
+//! allocator: other tests running in the same binary would skew the peak.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use zesven::codec::CodecMethod;
+use zesven::write::EntryMeta;
+use zesven::{ArchivePath, WriteOptions, Writer};
+
+/// Allocator that tracks the current and peak number of allocated bytes.
+struct PeakAllocator {
+    current: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+unsafe impl GlobalAlloc for PeakAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            let current = self.current.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+            self.peak.fetch_max(current, Ordering::SeqCst);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        self.current.fetch_sub(layout.size(), Ordering::SeqCst);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: PeakAllocator = PeakAllocator {
+    current: AtomicUsize::new(0),
+    peak: AtomicUsize::new(0),
+};
+
+/// Reader producing `remaining` bytes of a repeating pattern.
+struct PatternReader {
+    remaining: u64,
+    position: u64,
+}
+
+impl Read for PatternReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = buf.len().min(self.remaining as usize);
+        for byte in &mut buf[..n] {
+            *byte = (self.position % 251) as u8;
+            self.position += 1;
+        }
+        self.remaining -= n as u64;
+        Ok(n)
+    }
+}
+
+/// Seekable sink that discards everything written to it.
+#[derive(Default)]
+struct DiscardSink {
+    position: u64,
+    len: u64,
+}
+
+impl Write for DiscardSink {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.position += buf.len() as u64;
+        self.len = self.len.max(self.position);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for DiscardSink {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.position = match pos {
+            SeekFrom::Start(offset) => offset,
+            SeekFrom::End(offset) => self.len.saturating_add_signed(offset),
+            SeekFrom::Current(offset) => self.position.saturating_add_signed(offset),
+        };
+        Ok(self.position)
+    }
+}
+
+#[test]
+fn test_add_reader_peak_memory_is_bounded() {
+    const SIZE: u64 = 50 * 1024 * 1024;
+    const BUDGET: usize = 1024 * 1024;
+
+    // Copy keeps the test fast in debug builds while still exercising the
+    // streaming encoder path
+    let options = WriteOptions::new().method(CodecMethod::Copy);
+    let mut writer = Writer::create(DiscardSink::default())
+        .unwrap()
+        .options(options);
+
+    let baseline = ALLOCATOR.current.load(Ordering::SeqCst);
+    ALLOCATOR.peak.store(baseline, Ordering::SeqCst);
+
+    let reader = PatternReader {
+        remaining: SIZE,
+        position: 0,
+    };
+    writer
+        .add_reader(
+            ArchivePath::new("large.bin").unwrap(),
+            reader,
+            SIZE,
+            EntryMeta::default(),
+        )
+        .unwrap();
+
+    let peak = ALLOCATOR.peak.load(Ordering::SeqCst) - baseline;
+    let (result, _) = writer.finish_into_inner().unwrap();
+    assert_eq!(result.total_size, SIZE);
+    assert!(
+        peak < BUDGET,
+        "adding a {} MiB reader peaked at {} KiB",
+        SIZE >> 20,
+        peak >> 10
+    );
+}
@@ -665,6 +665,74 @@ async fn test_async_extraction_with_progress_callback() {
     }
 }
 
+#[tokio::test]
+async fn test_extract_with_events_stream() {
+    use futures::StreamExt;
+    use zesven::ProgressEvent;
+
+    let buffer = Cursor::new(Vec::new());
+    let mut writer = AsyncWriter::create(buffer).await.unwrap();
+    let files = [
+        ("a.txt", b"first file".as_slice()),
+        ("dir/b.txt", b"second, slightly longer file".as_slice()),
+    ];
+    for (path, content) in &files {
+        writer
+            .add_bytes(ArchivePath::new(path).unwrap(), content)
+            .await
+            .unwrap();
+    }
+    let (_, cursor) = writer.finish_into_inner().await.unwrap();
+    let expected_total: u64 = files.iter().map(|(_, c)| c.len() as u64).sum();
+
+    let mut archive = AsyncArchive::open(Cursor::new(cursor.into_inner()))
+        .await
+        .unwrap();
+    let temp_dir = tempfile::tempdir().unwrap();
+    let events: Vec<_> = archive
+        .extract_with_events(temp_dir.path(), AsyncExtractOptions::new())
+        .collect()
+        .await;
+
+    let count = |f: fn(&ProgressEvent) -> bool| events.iter().filter(|e| f(e)).count();
+    assert_eq!(count(|e| matches!(e, ProgressEvent::EntryStart { .. })), 2);
+    assert_eq!(
+        count(|e| matches!(e, ProgressEvent::EntryComplete { success: true, .. })),
+        2
+    );
+    let last_progress = events
+        .iter()
+        .rev()
+        .find_map(|e| match e {
+            ProgressEvent::Progress {
+                bytes_extracted,
+                total_bytes,
+            } => Some((*bytes_extracted, *total_bytes)),
+            _ => None,
+        })
+        .expect("no progress events");
+    assert_eq!(last_progress, (expected_total, expected_total));
+
+    match events.last() {
+        Some(ProgressEvent::Done {
+            entries_extracted,
+            entries_failed,
+            bytes_extracted,
+            error,
+        }) => {
+            assert_eq!(*entries_extracted, 2);
+            assert_eq!(*entries_failed, 0);
+            assert_eq!(*bytes_extracted, expected_total);
+            assert_eq!(*error, None);
+        }
+        other => panic!("expected a final Done event, got {:?}", other),
+    }
+    assert_eq!(
+        std::fs::read(temp_dir.path().join("dir/b.txt")).unwrap(),
+        files[1].1
+    );
+}
+
 // ============================================================================
 // Password Provider Tests (requires aes feature)
 // ============================================================================
@@ -166,6 +166,30 @@ fn test_extract_to_vec() {
     assert_eq!(content, b"test content here");
 }
 
+#[test]
+fn test_extract_entry_to_writer() {
+    let large: Vec<u8> = (0..200_000u32).map(|i| (i % 253) as u8).collect();
+    let archive_bytes = create_test_archive(&[
+        ("first.txt", b"first entry"),
+        ("dir/large.bin", &large),
+        ("empty.txt", b""),
+    ]);
+
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).expect("Failed to open archive");
+    for name in ["first.txt", "dir/large.bin", "empty.txt"] {
+        let mut out = Vec::new();
+        let written = archive
+            .extract_entry_to(name, &mut out)
+            .expect("Failed to extract");
+        assert_eq!(written, out.len() as u64);
+        assert_eq!(out, archive.extract_to_vec(name).unwrap(), "{name}");
+    }
+
+    let mut out = Vec::new();
+    assert!(archive.extract_entry_to("missing.txt", &mut out).is_err());
+    assert!(out.is_empty());
+}
+
 #[test]
 fn test_extract_entry_not_found() {
     let archive_bytes = create_test_archive(&[("exists.txt", b"hello")]);
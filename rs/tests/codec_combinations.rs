@@ -5,6 +5,7 @@
 //! - Read back and verify content matches exactly
 //! - Test solid archive mode with multiple codecs
 //! - Test encryption combinations (when aes feature enabled)
+//! - Decode single folders from raw pack data
 //!
 //! Note: These tests require at least one codec feature to be enabled.
 
@@ -174,6 +175,51 @@ fn test_copy_empty_file() {
     verify_archive_contents(&archive, &entries);
 }
 
+/// File-backed Copy archives take the in-kernel `copy_file_range` path.
+#[cfg(target_os = "linux")]
+#[test]
+fn test_copy_extract_from_file() {
+    use zesven::codec::CodecMethod;
+    use zesven::read::ExtractOptions;
+
+    let text = test_data::text();
+    let binary = test_data::binary();
+    let empty = test_data::empty();
+    let entries = [
+        ("text.txt", text.as_slice()),
+        ("dir/data.bin", binary.as_slice()),
+        ("empty.txt", empty.as_slice()),
+    ];
+
+    for options in [
+        WriteOptions::new().method(CodecMethod::Copy),
+        WriteOptions::new().method(CodecMethod::Copy).solid(),
+    ] {
+        let archive =
+            create_archive_with_options(options, &entries).expect("Failed to create archive");
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let archive_path = temp_dir.path().join("stored.7z");
+        std::fs::write(&archive_path, &archive).unwrap();
+
+        let output_dir = temp_dir.path().join("out");
+        let mut archive = Archive::open_path(&archive_path).expect("Failed to open archive");
+        let result = archive
+            .extract(&output_dir, (), &ExtractOptions::default())
+            .expect("Extraction failed");
+        assert_eq!(result.entries_extracted, entries.len());
+
+        for (name, expected) in &entries {
+            let extracted = std::fs::read(output_dir.join(name)).unwrap();
+            assert_eq!(
+                extracted.as_slice(),
+                *expected,
+                "Content mismatch for {name}"
+            );
+        }
+    }
+}
+
 // =============================================================================
 // Codec Roundtrip Test Macros
 // =============================================================================
@@ -295,6 +341,79 @@ mod lzma2_roundtrip {
     );
     codec_multiple_files_test!(test_lzma2_multiple_files, CodecMethod::Lzma2);
     codec_levels_test!(test_lzma2_levels, CodecMethod::Lzma2, [1, 5, 9]);
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_lzma2_parallel_vs_serial() {
+        let mut data = Vec::new();
+        for i in 0..10_000u32 {
+            data.extend_from_slice(
+                format!("line {} value {}\n", i, i.wrapping_mul(2654435761)).as_bytes(),
+            );
+        }
+        let entries = [("big.txt", data.as_slice())];
+
+        let (serial, serial_result) = common::create_archive_with_result(
+            Some(WriteOptions::new().parallel_lzma2(false)),
+            &entries,
+        )
+        .expect("Failed to create serial archive");
+        let (parallel, parallel_result) = common::create_archive_with_result(
+            Some(
+                WriteOptions::new()
+                    .parallel_lzma2(true)
+                    .lzma2_block_size(64 * 1024),
+            ),
+            &entries,
+        )
+        .expect("Failed to create parallel archive");
+
+        assert_ne!(serial, parallel, "parallel blocks should change the stream");
+        verify_archive_contents(&serial, &entries);
+        verify_archive_contents(&parallel, &entries);
+
+        // Dictionary resets per block cost some ratio, but not much
+        assert!(parallel_result.compressed_size >= serial_result.compressed_size);
+        assert!(parallel_result.compressed_size < serial_result.compressed_size * 3 / 2);
+    }
+
+    #[test]
+    fn test_lzma2_match_finders() {
+        use zesven::codec::MatchFinder;
+
+        // Source code is a realistic corpus with matches of varied length
+        let mut data = Vec::new();
+        let src = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src/codec");
+        let mut paths: Vec<_> = std::fs::read_dir(src)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        paths.sort();
+        for path in paths {
+            data.extend(std::fs::read(path).unwrap());
+        }
+        let entries = [("corpus.txt", data.as_slice())];
+
+        let compress = |match_finder| {
+            let options = WriteOptions::new()
+                .parallel_lzma2(false)
+                .match_finder(match_finder);
+            common::create_archive_with_result(Some(options), &entries)
+                .expect("Failed to create archive")
+        };
+        let (hash_chain, hash_chain_result) = compress(MatchFinder::HashChain4);
+        let (binary_tree, binary_tree_result) = compress(MatchFinder::BinaryTree4);
+
+        assert_ne!(hash_chain, binary_tree);
+        verify_archive_contents(&hash_chain, &entries);
+        verify_archive_contents(&binary_tree, &entries);
+        assert!(
+            binary_tree_result.compressed_size <= hash_chain_result.compressed_size,
+            "binary tree {} > hash chain {}",
+            binary_tree_result.compressed_size,
+            hash_chain_result.compressed_size
+        );
+    }
 }
 
 // =============================================================================
@@ -350,6 +469,36 @@ mod deflate_roundtrip {
 
         verify_archive_contents(&archive, &entries);
     }
+
+    #[test]
+    fn test_deflate64_large_window() {
+        // A 48 KiB random chunk repeated: only Deflate64's 64 KiB window
+        // reaches back to the previous copy
+        use rand::rngs::StdRng;
+        use rand::{Rng, SeedableRng};
+        let mut rng = StdRng::seed_from_u64(0x64);
+        let mut chunk = vec![0u8; 48 * 1024];
+        rng.fill(&mut chunk[..]);
+        let data = chunk.repeat(4);
+        let entries = [("repeated.bin", data.as_slice())];
+
+        let deflate64 = create_archive_with_options(
+            WriteOptions::new().method(CodecMethod::Deflate64),
+            &entries,
+        )
+        .expect("Failed to create Deflate64 archive");
+        verify_archive_contents(&deflate64, &entries);
+
+        let deflate =
+            create_archive_with_options(WriteOptions::new().method(CodecMethod::Deflate), &entries)
+                .expect("Failed to create Deflate archive");
+        assert!(
+            deflate64.len() < deflate.len() / 2,
+            "Deflate64 ({} bytes) should beat Deflate ({} bytes)",
+            deflate64.len(),
+            deflate.len()
+        );
+    }
 }
 
 // =============================================================================
@@ -373,6 +522,39 @@ mod bzip2_roundtrip {
     codec_data_test!(test_bzip2_empty, CodecMethod::BZip2, empty, "empty.txt");
     codec_multiple_files_test!(test_bzip2_multiple_files, CodecMethod::BZip2);
     codec_levels_test!(test_bzip2_levels, CodecMethod::BZip2, [1, 5, 9]);
+
+    #[test]
+    fn test_bzip2_block_sizes() {
+        use rand::rngs::StdRng;
+        use rand::{Rng, SeedableRng};
+
+        // A random chunk repeated at a 150 KB distance: only a block size
+        // larger than the distance sees both copies in one block
+        let mut chunk = vec![0u8; 150_000];
+        StdRng::seed_from_u64(0x0B21_B10C).fill(&mut chunk[..]);
+        let data = [chunk.as_slice(), chunk.as_slice()].concat();
+        let entries = [("data.bin", data.as_slice())];
+
+        let sizes: Vec<usize> = [1u8, 9]
+            .into_iter()
+            .map(|block_size| {
+                let options = WriteOptions::new()
+                    .method(CodecMethod::BZip2)
+                    .bzip2_block_size(block_size);
+                let archive = create_archive_with_options(options, &entries)
+                    .expect("Failed to create archive");
+                verify_archive_contents(&archive, &entries);
+                archive.len()
+            })
+            .collect();
+
+        assert!(
+            sizes[1] < sizes[0],
+            "block size 9 ({} bytes) should beat block size 1 ({} bytes)",
+            sizes[1],
+            sizes[0]
+        );
+    }
 }
 
 // =============================================================================
@@ -447,6 +629,70 @@ mod zstd_roundtrip {
     codec_data_test!(test_zstd_empty, CodecMethod::Zstd, empty, "empty.txt");
     codec_multiple_files_test!(test_zstd_multiple_files, CodecMethod::Zstd);
     codec_levels_test!(test_zstd_levels, CodecMethod::Zstd, [1, 5, 9]);
+
+    #[test]
+    fn test_zstd_trained_dictionary() {
+        use zesven::codec::{ZstdDictionary, train_zstd_dictionary};
+        use zesven::read::{ExtractOptions, SelectAll, TestOptions};
+
+        let records: Vec<(String, Vec<u8>)> = (0..400)
+            .map(|i| {
+                let json = format!(
+                    r#"{{"id": {}, "type": "{}", "name": "user-{}", "email": "user{}@example.com", "active": {}, "roles": ["reader", "writer"]}}"#,
+                    i,
+                    ["user", "admin", "guest"][i % 3],
+                    i,
+                    i,
+                    i % 2 == 0
+                );
+                (format!("records/{:04}.json", i), json.into_bytes())
+            })
+            .collect();
+        let entries: Vec<(&str, &[u8])> = records
+            .iter()
+            .map(|(name, data)| (name.as_str(), data.as_slice()))
+            .collect();
+        let samples: Vec<&[u8]> = entries.iter().map(|(_, data)| *data).collect();
+        let dict_bytes = train_zstd_dictionary(&samples, 4096).unwrap();
+        let dict = ZstdDictionary::from_bytes(dict_bytes.clone()).unwrap();
+
+        let plain =
+            create_archive_with_options(WriteOptions::new().method(CodecMethod::Zstd), &entries)
+                .unwrap();
+        let with_dict = create_archive_with_options(
+            WriteOptions::new()
+                .method(CodecMethod::Zstd)
+                .zstd_dictionary(dict_bytes)
+                .unwrap(),
+            &entries,
+        )
+        .unwrap();
+        assert!(
+            with_dict.len() * 3 < plain.len() * 2,
+            "dictionary archive ({} bytes) should be noticeably smaller than {} bytes",
+            with_dict.len(),
+            plain.len()
+        );
+
+        // Without the dictionary, decoding fails with a hint
+        let mut archive = Archive::open(Cursor::new(with_dict.clone())).unwrap();
+        let err = archive.extract_to_vec(entries[0].0).unwrap_err();
+        assert!(err.to_string().contains("dictionary"), "{}", err);
+
+        // With it, every entry extracts with a matching CRC
+        let temp_dir = tempfile::tempdir().unwrap();
+        let options = ExtractOptions::new().zstd_dictionary(dict);
+        let result = archive.extract(temp_dir.path(), (), &options).unwrap();
+        assert_eq!(result.entries_failed, 0);
+        for (name, data) in &entries {
+            assert_eq!(std::fs::read(temp_dir.path().join(name)).unwrap(), *data);
+        }
+
+        // The dictionary stays installed for later reads and tests
+        assert_eq!(archive.extract_to_vec(entries[1].0).unwrap(), entries[1].1);
+        let test = archive.test(SelectAll, &TestOptions::default()).unwrap();
+        assert!(test.is_ok(), "failures: {:?}", test.failures);
+    }
 }
 
 // =============================================================================
@@ -756,6 +1002,70 @@ mod encrypted_archives {
     }
 }
 
+// =============================================================================
+// Archive Info Method Tests
+// =============================================================================
+
+#[cfg(feature = "lzma2")]
+#[test]
+fn test_archive_info_reports_methods_filters_and_encryption() {
+    use zesven::WriteFilter;
+    use zesven::codec::{CodecMethod, FilterMethod};
+
+    let data = test_data::executable_like();
+    let entries = [("app.bin", data.as_slice())];
+    let info_of = |options: WriteOptions| {
+        let archive = create_archive_with_options(options, &entries).unwrap();
+        Archive::open(Cursor::new(archive)).unwrap().info().clone()
+    };
+
+    let info = info_of(WriteOptions::new().method(CodecMethod::Lzma2));
+    assert_eq!(info.methods(), [CodecMethod::Lzma2]);
+    assert!(info.filters().is_empty());
+    assert!(!info.is_encrypted());
+
+    let info = info_of(
+        WriteOptions::new()
+            .method(CodecMethod::Lzma2)
+            .filter(WriteFilter::BcjX86),
+    );
+    assert_eq!(info.methods(), [CodecMethod::Lzma2]);
+    assert_eq!(info.filters(), [FilterMethod::BcjX86]);
+    assert!(!info.is_encrypted());
+
+    let info = info_of(
+        WriteOptions::new()
+            .method(CodecMethod::Copy)
+            .filter(WriteFilter::Delta { distance: 4 }),
+    );
+    assert_eq!(info.methods(), [CodecMethod::Copy]);
+    assert_eq!(info.filters(), [FilterMethod::Delta]);
+
+    // The writer stores the four BCJ2 streams without a codec
+    let info = info_of(
+        WriteOptions::new()
+            .method(CodecMethod::Lzma2)
+            .filter(WriteFilter::Bcj2),
+    );
+    assert!(info.methods().is_empty());
+    assert_eq!(info.filters(), [FilterMethod::Bcj2]);
+
+    // Three coders in one folder: AES, LZMA2 and BCJ
+    #[cfg(feature = "aes")]
+    {
+        let info = info_of(
+            WriteOptions::new()
+                .method(CodecMethod::Lzma2)
+                .filter(WriteFilter::BcjX86)
+                .password("secret")
+                .encrypt_data(true),
+        );
+        assert_eq!(info.methods(), [CodecMethod::Lzma2]);
+        assert_eq!(info.filters(), [FilterMethod::BcjX86]);
+        assert!(info.is_encrypted());
+    }
+}
+
 // =============================================================================
 // Archive Comments Tests
 // =============================================================================
@@ -1123,6 +1433,126 @@ fn test_deterministic_archives_identical() {
     );
 }
 
+// =============================================================================
+// Standalone Folder Decoding Tests
+// =============================================================================
+
+#[cfg(feature = "lzma2")]
+mod decode_folder {
+    use super::*;
+    use zesven::codec::decode_folder;
+    use zesven::format::parser::read_archive_header;
+    use zesven::format::streams::Folder;
+
+    /// Splits an archive's pack data into the raw packed streams of each folder.
+    fn raw_folders(archive: &[u8]) -> Vec<(Folder, Vec<Vec<u8>>)> {
+        let (_, header) = read_archive_header(&mut Cursor::new(archive), None).unwrap();
+        let pack_info = header.pack_info.as_ref().unwrap();
+        let mut offset = 32 + pack_info.pack_pos as usize;
+        let mut sizes = pack_info.pack_sizes.iter();
+        header
+            .folders()
+            .iter()
+            .map(|folder| {
+                let packed = folder
+                    .packed_streams
+                    .iter()
+                    .map(|_| {
+                        let size = *sizes.next().unwrap() as usize;
+                        offset += size;
+                        archive[offset - size..offset].to_vec()
+                    })
+                    .collect();
+                (folder.clone(), packed)
+            })
+            .collect()
+    }
+
+    fn decode(folder: &Folder, packed: &[Vec<u8>], size: u64, password: Option<&str>) -> Vec<u8> {
+        #[cfg(feature = "aes")]
+        let password = password.map(zesven::Password::new);
+        #[cfg(feature = "aes")]
+        let data = decode_folder(folder, packed, size, password.as_ref()).unwrap();
+        #[cfg(not(feature = "aes"))]
+        let data = {
+            assert!(password.is_none());
+            decode_folder(folder, packed, size).unwrap()
+        };
+        assert_eq!(
+            folder.unpack_crc.unwrap_or(crc32fast::hash(&data)),
+            crc32fast::hash(&data)
+        );
+        data
+    }
+
+    #[test]
+    fn test_decode_lzma2_folder() {
+        let text = test_data::text();
+        let archive = create_archive_with_options(WriteOptions::new(), &[("a.txt", &text)])
+            .expect("Failed to create archive");
+
+        let folders = raw_folders(&archive);
+        assert_eq!(folders.len(), 1);
+        let (folder, packed) = &folders[0];
+        let size = folder.final_unpack_size().unwrap();
+        assert_eq!(decode(folder, packed, size, None), text);
+    }
+
+    #[test]
+    fn test_decode_bcj2_folder() {
+        // x86-like code: CALL and JMP opcodes with relative targets
+        let code: Vec<u8> = (0..20_000u32)
+            .flat_map(|i| match i % 4 {
+                0 => [0xE8, (i >> 2) as u8, 0x10, 0x00, 0x00],
+                1 => [0xE9, (i >> 3) as u8, 0x20, 0x00, 0x00],
+                _ => [0x55, 0x89, 0xE5, (i % 251) as u8, 0xC3],
+            })
+            .collect();
+        let archive =
+            create_archive_with_options(WriteOptions::new().bcj2(), &[("code.bin", &code)])
+                .expect("Failed to create BCJ2 archive");
+
+        let folders = raw_folders(&archive);
+        let (folder, packed) = &folders[0];
+        assert!(folder.uses_bcj2());
+        assert_eq!(packed.len(), 4);
+        let size = folder.final_unpack_size().unwrap();
+        assert_eq!(decode(folder, packed, size, None), code);
+
+        // A single stream does not satisfy a BCJ2 folder
+        #[cfg(feature = "aes")]
+        let err = decode_folder(folder, &packed[..1], size, None).unwrap_err();
+        #[cfg(not(feature = "aes"))]
+        let err = decode_folder(folder, &packed[..1], size).unwrap_err();
+        assert!(matches!(err, zesven::Error::InvalidFormat(_)), "{err:?}");
+    }
+
+    #[cfg(feature = "aes")]
+    #[test]
+    fn test_decode_encrypted_folder() {
+        let text = test_data::text();
+        let options = WriteOptions::new()
+            .password("folder secret")
+            .encrypt_data(true);
+        let archive = create_archive_with_options(options, &[("secret.txt", &text)])
+            .expect("Failed to create encrypted archive");
+
+        let folders = raw_folders(&archive);
+        let (folder, packed) = &folders[0];
+        // The writer lists the unpack sizes of encrypted folders in reverse
+        // coder order, so `final_unpack_size` reports the AES output size;
+        // the entry size is the folder output size here
+        let size = text.len() as u64;
+        assert_eq!(decode(folder, packed, size, Some("folder secret")), text);
+
+        let err = decode_folder(folder, packed, size, None).unwrap_err();
+        assert!(
+            matches!(err, zesven::Error::PasswordRequired { .. }),
+            "{err:?}"
+        );
+    }
+}
+
 // Note: WriteOptions validation tests (level validation, level_clamped) are in
 // src/write/options.rs as unit tests, which is the appropriate layer for testing
 // API validation behavior.
@@ -264,3 +264,46 @@ pub fn verify_encrypted_archive(
         );
     }
 }
+
+/// Wraps raw packed data and an unencoded header into a complete archive.
+///
+/// Writes the 32-byte signature header (with valid start header and next
+/// header CRCs), followed by `packed` and then `header`. The header must be
+/// a plain `kHeader` structure; the next header offset is `packed.len()`.
+///
+/// This is useful for hand-crafting archives that exercise header layouts
+/// the writer never produces.
+pub fn wrap_raw_header(packed: &[u8], header: &[u8]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(32 + packed.len() + header.len());
+    data.extend_from_slice(&[0x37, 0x7A, 0xBC, 0xAF, 0x27, 0x1C, 0x00, 0x04]);
+    data.extend_from_slice(&[0u8; 4]); // start header CRC placeholder
+    data.extend_from_slice(&(packed.len() as u64).to_le_bytes());
+    data.extend_from_slice(&(header.len() as u64).to_le_bytes());
+    data.extend_from_slice(&crc32fast::hash(header).to_le_bytes());
+    let start_header_crc = crc32fast::hash(&data[12..32]);
+    data[8..12].copy_from_slice(&start_header_crc.to_le_bytes());
+    data.extend_from_slice(packed);
+    data.extend_from_slice(header);
+    data
+}
+
+/// Appends a UTF-16LE, null-terminated `kName` property for `names`.
+pub fn push_names_property(header: &mut Vec<u8>, names: &[&str]) {
+    let mut names_data = vec![0x00]; // not external
+    for name in names {
+        for unit in name.encode_utf16() {
+            names_data.extend_from_slice(&unit.to_le_bytes());
+        }
+        names_data.extend_from_slice(&[0x00, 0x00]);
+    }
+    header.push(0x11); // kName
+    push_variable_u64(header, names_data.len() as u64);
+    header.extend_from_slice(&names_data);
+}
+
+/// Appends a 7z variable-length integer to `buf`.
+pub fn push_variable_u64(buf: &mut Vec<u8>, value: u64) {
+    let mut out = Vec::new();
+    zesven::format::reader::write_variable_u64(&mut out, value).unwrap();
+    buf.extend_from_slice(&out);
+}
@@ -5,6 +5,7 @@
 //! - Renames entries within archives
 //! - Updates entry content
 //! - Adds new entries to existing archives
+//! - Appends entries without recompressing the existing folders
 //! - Returns correct error types for invalid operations
 
 #![cfg(feature = "lzma2")]
@@ -468,6 +469,81 @@ mod encrypted_editing {
     }
 }
 
+// ============================================================================
+// Append tests
+// ============================================================================
+
+/// Tests that appending copies the existing folders instead of recompressing them.
+///
+/// Appending a small file must leave the original solid block's packed form
+/// unchanged.
+#[test]
+fn test_appender_copies_existing_folders_verbatim() {
+    use zesven::write::{ArchiveAppender, EntryMeta, SolidOptions, WriteOptions, Writer};
+
+    // Compressible but not trivial data, so the solid block is non-trivial
+    let mut state = 0x2545_f491_u32;
+    let mut generate = |len: usize| -> Vec<u8> {
+        (0..len)
+            .map(|_| {
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                b"abcdefgh \n"[(state >> 28) as usize % 10]
+            })
+            .collect()
+    };
+    let large_a = generate(256 << 10);
+    let large_b = generate(256 << 10);
+
+    let dir = tempfile::tempdir().unwrap();
+    let archive_path = dir.path().join("large.7z");
+
+    let mut writer = Writer::create_path(&archive_path)
+        .unwrap()
+        .options(WriteOptions::new().solid_options(SolidOptions::enabled()));
+    writer
+        .add_directory(ArchivePath::new("data").unwrap(), EntryMeta::default())
+        .unwrap();
+    writer
+        .add_bytes(ArchivePath::new("data/a.bin").unwrap(), &large_a)
+        .unwrap();
+    writer
+        .add_bytes(ArchivePath::new("data/empty.txt").unwrap(), b"")
+        .unwrap();
+    writer
+        .add_bytes(ArchivePath::new("data/b.bin").unwrap(), &large_b)
+        .unwrap();
+    let _ = writer.finish().unwrap();
+
+    let original = Archive::open_path(&archive_path).unwrap();
+    let original_folders = original.folder_summaries();
+    drop(original);
+
+    let mut appender = ArchiveAppender::open(&archive_path).unwrap();
+    appender
+        .add_bytes(ArchivePath::new("notes.txt").unwrap(), b"appended later")
+        .unwrap();
+    let result = appender.finish().unwrap();
+
+    assert_eq!(result.entries_added, 1);
+    assert_eq!(result.total_entries, 5);
+
+    let mut archive = Archive::open_path(&archive_path).unwrap();
+    assert_eq!(archive.folder_summaries()[0], original_folders[0]);
+    assert_eq!(archive.extract_to_vec("data/a.bin").unwrap(), large_a);
+    assert_eq!(archive.extract_to_vec("data/b.bin").unwrap(), large_b);
+    assert!(archive.extract_to_vec("data/empty.txt").unwrap().is_empty());
+    assert_eq!(
+        archive.extract_to_vec("notes.txt").unwrap(),
+        b"appended later"
+    );
+    let data_dir = archive
+        .entries()
+        .iter()
+        .find(|e| e.path.as_str() == "data")
+        .unwrap();
+    assert!(data_dir.is_directory);
+}
+
 // ============================================================================
 // Writer Error Handling Tests
 // ============================================================================
@@ -115,6 +115,37 @@ fn test_delta_distance_4_roundtrip() {
     common::verify_archive_contents(&archive, &entries);
 }
 
+/// 16-bit little-endian PCM: two mixed tones at 44.1 kHz.
+fn pcm16_data(samples: usize) -> Vec<u8> {
+    (0..samples)
+        .flat_map(|i| {
+            let t = i as f64 / 44_100.0;
+            let sample = 12_000.0 * (2.0 * std::f64::consts::PI * 440.0 * t).sin()
+                + 6_000.0 * (2.0 * std::f64::consts::PI * 1_234.5 * t).sin();
+            (sample as i16).to_le_bytes()
+        })
+        .collect()
+}
+
+#[test]
+fn test_delta_shrinks_pcm16() {
+    let data = pcm16_data(200_000);
+    let entries = [("tone.pcm", data.as_slice())];
+
+    let plain = common::create_archive_with_options(WriteOptions::new(), &entries)
+        .expect("Failed to create unfiltered archive");
+    let filtered = common::create_archive_with_options(WriteOptions::new().delta(2), &entries)
+        .expect("Failed to create Delta filtered archive");
+
+    assert!(
+        filtered.len() < plain.len(),
+        "delta: {} bytes, unfiltered: {} bytes",
+        filtered.len(),
+        plain.len()
+    );
+    common::verify_archive_contents(&filtered, &entries);
+}
+
 #[test]
 fn test_filter_with_multiple_files() {
     let exe_data = x86_like_data();
@@ -268,6 +299,38 @@ mod encrypted_filter_tests {
 
         common::verify_encrypted_archive(&archive, password, &entries);
     }
+
+    #[test]
+    fn test_folder_summaries_bcj_lzma2_encrypted() {
+        use std::io::Cursor;
+        use zesven::read::Archive;
+
+        let data = super::x86_like_data();
+        let entries = [("a.exe", data.as_slice()), ("b.exe", &data[..400])];
+
+        let options = WriteOptions::new()
+            .filter(WriteFilter::BcjX86)
+            .password("summary")
+            .encrypt_data(true);
+        let archive = common::create_archive_with_options(options, &entries)
+            .expect("Failed to create encrypted + filtered archive");
+
+        // Entry contents are encrypted, but the header can be read without a password
+        let archive = Archive::open(Cursor::new(archive)).unwrap();
+        let summaries = archive.folder_summaries();
+        assert_eq!(summaries.len(), 2);
+        for (summary, (_, expected)) in summaries.iter().zip(&entries) {
+            assert_eq!(summary.methods, "BCJ (x86) → LZMA2 → AES-256");
+            assert!(summary.encrypted);
+            assert_eq!(summary.entry_count, 1);
+            assert_eq!(summary.unpacked_size, expected.len() as u64);
+            assert!(summary.packed_size > 0);
+        }
+        assert_eq!(
+            summaries.iter().map(|s| s.packed_size).sum::<u64>(),
+            archive.info().packed_size
+        );
+    }
 }
 
 #[test]
@@ -358,6 +421,32 @@ fn test_bcj2_roundtrip() {
     common::verify_archive_contents(&archive, &entries);
 }
 
+#[test]
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn test_bcj2_real_executable_roundtrip() {
+    use std::io::Cursor;
+    use zesven::format::parser::read_archive_header;
+
+    // The test binary itself is a real ELF/PE/Mach-O executable; its first
+    // megabyte holds machine code with genuine CALL and JMP instructions
+    let mut exe = std::fs::read(std::env::current_exe().unwrap()).unwrap();
+    exe.truncate(1 << 20);
+    let entries = [("test-binary", exe.as_slice())];
+
+    let archive = common::create_archive_with_options(WriteOptions::new().bcj2(), &entries)
+        .expect("Failed to create BCJ2 archive");
+
+    let (_, header) = read_archive_header(&mut Cursor::new(&archive), None).unwrap();
+    let folder = &header.folders()[0];
+    assert!(folder.uses_bcj2());
+    let pack_sizes = &header.pack_info.as_ref().unwrap().pack_sizes;
+    assert_eq!(pack_sizes.len(), 4);
+    // Main, call, jump and range streams all carry data
+    assert!(pack_sizes.iter().all(|&size| size > 0), "{pack_sizes:?}");
+
+    common::verify_archive_contents(&archive, &entries);
+}
+
 #[test]
 fn test_bcj2_empty_file() {
     let entries: [(&str, &[u8]); 1] = [("empty.bin", b"")];
@@ -422,6 +511,69 @@ fn test_bcj2_is_bcj2_method() {
     assert!(!WriteFilter::Delta { distance: 1 }.is_bcj2());
 }
 
+/// Hand-crafts a header for an encrypted BCJ2 + LZMA2 archive, laid out the
+/// way 7-Zip writes one: BCJ2 fed by LZMA2 (main), LZMA (call, jump) and the
+/// raw range stream, with AES in front of LZMA2. The writer cannot produce
+/// encrypted BCJ2 folders, and the packed data is never decoded.
+fn encrypted_bcj2_lzma2_archive() -> Vec<u8> {
+    use common::{push_names_property, wrap_raw_header};
+
+    let mut h = vec![0x01, 0x04]; // kHeader, kMainStreamsInfo
+
+    // PackInfo: four 16-byte pack streams
+    h.extend_from_slice(&[0x06, 0x00, 0x04, 0x09, 16, 16, 16, 16, 0x00]);
+
+    // UnpackInfo: one folder with five coders
+    h.extend_from_slice(&[0x07, 0x0B, 0x01, 0x00, 0x05]);
+    h.extend_from_slice(&[0x14, 0x03, 0x03, 0x01, 0x1B, 0x04, 0x01]); // BCJ2, 4 in / 1 out
+    h.extend_from_slice(&[0x21, 0x21, 0x01, 0x10]); // LZMA2
+    for _ in 0..2 {
+        h.extend_from_slice(&[0x23, 0x03, 0x01, 0x01, 0x05, 0x5D, 0x00, 0x00, 0x01, 0x00]); // LZMA
+    }
+    h.extend_from_slice(&[0x24, 0x06, 0xF1, 0x07, 0x01, 18, 0x53, 0x0F]); // AES, 2^19 cycles
+    h.extend_from_slice(&[0u8; 16]); // IV
+    h.extend_from_slice(&[0, 1, 1, 2, 2, 3, 4, 4]); // bind pairs (in, out)
+    h.extend_from_slice(&[7, 5, 6, 3]); // packed streams
+    h.extend_from_slice(&[0x0C, 64, 60, 4, 4, 16]); // unpack sizes per out stream
+    h.extend_from_slice(&[0x00, 0x00]); // end UnpackInfo, end MainStreamsInfo
+
+    // FilesInfo: a single file
+    h.extend_from_slice(&[0x05, 0x01]);
+    push_names_property(&mut h, &["app.exe"]);
+    h.extend_from_slice(&[0x00, 0x00]); // end FilesInfo, end Header
+
+    wrap_raw_header(&[0u8; 64], &h)
+}
+
+#[test]
+fn test_bcj2_required_capabilities() {
+    use std::io::Cursor;
+    use zesven::Archive;
+    use zesven::codec::{CodecMethod, FilterMethod};
+
+    let archive_bytes = encrypted_bcj2_lzma2_archive();
+    let archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+    let caps = archive.required_capabilities();
+
+    assert!(caps.has_codec(CodecMethod::Lzma2));
+    assert!(caps.has_codec(CodecMethod::Lzma));
+    assert!(caps.has_filter(FilterMethod::Bcj2));
+    assert!(caps.aes);
+    assert_eq!(caps.codecs, vec![CodecMethod::Lzma2, CodecMethod::Lzma]);
+    assert_eq!(caps.filters, vec![FilterMethod::Bcj2]);
+    assert!(caps.other_methods.is_empty());
+
+    // An unencrypted archive needs no AES
+    let archive_bytes =
+        common::create_archive_with_options(WriteOptions::new().bcj2(), &[("a.exe", b"code")])
+            .unwrap();
+    let caps = Archive::open(Cursor::new(&archive_bytes))
+        .unwrap()
+        .required_capabilities();
+    assert!(!caps.aes);
+    assert_eq!(caps.filters, vec![FilterMethod::Bcj2]);
+}
+
 #[test]
 fn test_bcj2_method_id() {
     use zesven::codec::method;
@@ -282,6 +282,52 @@ fn test_bit_flipped_version_handled() {
     );
 }
 
+/// Logger collecting warnings so tests can check what was reported.
+struct WarningCapture(std::sync::Mutex<Vec<String>>);
+
+impl log::Log for WarningCapture {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::Level::Warn
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            self.0.lock().unwrap().push(record.args().to_string());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static WARNINGS: WarningCapture = WarningCapture(std::sync::Mutex::new(Vec::new()));
+
+#[test]
+fn test_newer_minor_version_opens_with_warning() {
+    log::set_logger(&WARNINGS).unwrap();
+    log::set_max_level(log::LevelFilter::Warn);
+
+    let data = b"written by a newer 7-Zip";
+    let mut archive_bytes = build_copy_archive_with_pack_size(data, data.len() as u64);
+    // The version bytes are not covered by the start header CRC
+    archive_bytes[7] = 5;
+
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+    assert_eq!(archive.extract_to_vec("file.bin").unwrap(), data);
+    assert!(
+        WARNINGS
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|w| w.contains("format version 0.5 is newer")),
+        "no version warning was logged"
+    );
+
+    // A major version change is still rejected
+    archive_bytes[6] = 1;
+    assert!(Archive::open(Cursor::new(&archive_bytes)).is_err());
+}
+
 // =============================================================================
 // Oversized Value Tests
 // =============================================================================
@@ -431,28 +477,35 @@ fn test_symlink_extraction_forbidden_by_default() {
     let opts = ExtractOptions::default();
     let result = archive.extract(temp_dir.path(), SelectAll, &opts);
 
-    // Extract returns Ok with failures in the result
-    let extract_result = result.expect("Extract should return Ok with failures logged");
+    // Extract returns Ok with the symlinks skipped
+    let extract_result = result.expect("Extract should return Ok with symlinks skipped");
 
-    // Should have 3 failed entries (the symlinks) and 2 successful (dir + file)
+    // Should have 3 skipped entries (the symlinks) and 2 successful (dir + file)
     assert!(
-        extract_result.entries_failed >= 3,
-        "Expected at least 3 symlink failures, got {} failed. Failures: {:?}",
-        extract_result.entries_failed,
-        extract_result.failures
+        extract_result.entries_skipped >= 3,
+        "Expected at least 3 skipped symlinks, got {} skipped. Entries: {:?}",
+        extract_result.entries_skipped,
+        extract_result.entries
     );
+    assert_eq!(extract_result.entries_failed, 0);
 
-    // Verify failures mention symlink rejection
-    let has_symlink_rejection = extract_result
-        .failures
-        .iter()
-        .any(|(path, msg)| path.contains("link") && msg.to_lowercase().contains("symlink"));
-
-    assert!(
-        has_symlink_rejection,
-        "Should have at least one symlink rejection. Failures: {:?}",
-        extract_result.failures
-    );
+    // Verify skipped entries record the symlink rejection and write nothing
+    for outcome in extract_result.entries.iter().filter(|o| o.skipped) {
+        let msg = outcome.error.as_deref().unwrap_or_default();
+        assert!(
+            msg.to_lowercase().contains("symbolic link"),
+            "Skipped entry {} should record a symlink rejection, got {:?}",
+            outcome.path,
+            outcome.error
+        );
+        assert!(
+            temp_dir
+                .path()
+                .join(&outcome.path)
+                .symlink_metadata()
+                .is_err()
+        );
+    }
 }
 
 /// Test that LinkPolicy::ValidateTargets rejects symlinks with traversal targets.
@@ -954,6 +1007,35 @@ fn test_corrupted_next_header_crc_detected() {
     );
 }
 
+/// Tests that a flipped byte in the next header is reported as a CRC
+/// mismatch at the header position, rather than as a parse failure.
+#[test]
+fn test_flipped_next_header_byte_reports_crc_mismatch() {
+    let entries = [("file.txt", b"header CRC test" as &[u8])];
+    let mut data = common::create_archive(&entries).unwrap();
+
+    let next_header_offset = u64::from_le_bytes(data[12..20].try_into().unwrap());
+    let next_header_size = u64::from_le_bytes(data[20..28].try_into().unwrap());
+    let stored_crc = u32::from_le_bytes(data[28..32].try_into().unwrap());
+    let header_pos = 32 + next_header_offset;
+    let header_range = header_pos as usize..(header_pos + next_header_size) as usize;
+
+    data[header_range.start + 1] ^= 0x01;
+    let actual_crc = crc32fast::hash(&data[header_range]);
+
+    let err = expect_err(Archive::open(Cursor::new(data)));
+    match err {
+        Error::CorruptHeader { offset, reason } => {
+            assert_eq!(offset, header_pos);
+            assert_eq!(
+                reason,
+                format!("next header CRC mismatch: expected {stored_crc:#x}, got {actual_crc:#x}")
+            );
+        }
+        other => panic!("expected CorruptHeader, got {other:?}"),
+    }
+}
+
 /// Tests that valid signature with garbage header content is rejected.
 ///
 /// This verifies that having a valid 7z signature at the start doesn't cause
@@ -1124,3 +1206,524 @@ fn test_all_zeros_after_signature() {
     // Should fail because CRC would be wrong and/or parsing invalid
     assert!(result.is_err(), "All-zeros header should fail");
 }
+
+// =============================================================================
+// Folder CRC Tests
+// =============================================================================
+//
+// Some writers store a folder CRC for the concatenated output of a solid
+// block in addition to the per-substream CRCs. Both must be honored.
+
+/// Builds a Copy-method solid archive whose single folder holds `files` and
+/// carries both a folder CRC (`folder_crc`) and per-substream CRCs.
+fn build_solid_copy_archive_with_folder_crc(files: &[(&str, &[u8])], folder_crc: u32) -> Vec<u8> {
+    let substream_crcs: Vec<u32> = files.iter().map(|(_, d)| crc32fast::hash(d)).collect();
+    build_solid_copy_archive(files, folder_crc, &substream_crcs)
+}
+
+/// Builds a Copy-method solid archive with the given folder and substream CRCs.
+fn build_solid_copy_archive(
+    files: &[(&str, &[u8])],
+    folder_crc: u32,
+    substream_crcs: &[u32],
+) -> Vec<u8> {
+    use common::{push_names_property, push_variable_u64, wrap_raw_header};
+
+    let packed: Vec<u8> = files.iter().flat_map(|(_, d)| d.iter().copied()).collect();
+
+    let mut h = vec![0x01, 0x04]; // kHeader, kMainStreamsInfo
+
+    // PackInfo: pack_pos = 0, one pack stream
+    h.extend_from_slice(&[0x06, 0x00, 0x01, 0x09]);
+    push_variable_u64(&mut h, packed.len() as u64);
+    h.push(0x00);
+
+    // UnpackInfo: one folder with a single Copy coder and a folder CRC
+    h.extend_from_slice(&[0x07, 0x0B, 0x01, 0x00, 0x01, 0x01, 0x00, 0x0C]);
+    push_variable_u64(&mut h, packed.len() as u64);
+    h.extend_from_slice(&[0x0A, 0x01]);
+    h.extend_from_slice(&folder_crc.to_le_bytes());
+    h.push(0x00);
+
+    // SubStreamsInfo: all files in the folder, each with its own CRC
+    h.push(0x08);
+    h.push(0x0D);
+    push_variable_u64(&mut h, files.len() as u64);
+    h.push(0x09);
+    for (_, data) in &files[..files.len() - 1] {
+        push_variable_u64(&mut h, data.len() as u64);
+    }
+    h.extend_from_slice(&[0x0A, 0x01]);
+    for crc in substream_crcs {
+        h.extend_from_slice(&crc.to_le_bytes());
+    }
+    h.extend_from_slice(&[0x00, 0x00]); // end SubStreamsInfo, end MainStreamsInfo
+
+    // FilesInfo
+    h.push(0x05);
+    push_variable_u64(&mut h, files.len() as u64);
+    let names: Vec<&str> = files.iter().map(|(n, _)| *n).collect();
+    push_names_property(&mut h, &names);
+    h.extend_from_slice(&[0x00, 0x00]); // end FilesInfo, end Header
+
+    wrap_raw_header(&packed, &h)
+}
+
+#[test]
+fn test_solid_folder_with_folder_and_substream_crcs() {
+    use zesven::read::{SelectAll, TestOptions};
+
+    let files: [(&str, &[u8]); 3] = [
+        ("a.txt", b"first file"),
+        ("b.txt", b"second file contents"),
+        ("c.txt", b"third"),
+    ];
+    let concatenated: Vec<u8> = files.iter().flat_map(|(_, d)| d.iter().copied()).collect();
+    let archive_bytes =
+        build_solid_copy_archive_with_folder_crc(&files, crc32fast::hash(&concatenated));
+
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+    assert_eq!(archive.len(), 3);
+    for (i, (name, data)) in files.iter().enumerate() {
+        assert_eq!(archive.entries()[i].crc32, Some(crc32fast::hash(data)));
+        assert_eq!(archive.extract_to_vec(name).unwrap(), *data);
+    }
+
+    let result = archive.test(SelectAll, &TestOptions::default()).unwrap();
+    assert!(result.is_ok(), "failures: {:?}", result.failures);
+}
+
+#[test]
+fn test_solid_folder_crc_mismatch_detected() {
+    let files: [(&str, &[u8]); 2] = [("a.txt", b"alpha"), ("b.txt", b"beta")];
+    let archive_bytes = build_solid_copy_archive_with_folder_crc(&files, 0xDEADBEEF);
+
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+
+    // Substream CRCs are intact, so the first entry still extracts cleanly
+    assert_eq!(archive.extract_to_vec("a.txt").unwrap(), b"alpha");
+
+    // Decoding the last stream covers the whole folder and exposes the bad folder CRC
+    let err = expect_err(archive.extract_to_vec("b.txt"));
+    assert!(
+        matches!(
+            err,
+            Error::FolderCrcMismatch {
+                folder_index: 0,
+                expected: 0xDEADBEEF,
+                ..
+            }
+        ),
+        "unexpected error: {:?}",
+        err
+    );
+    assert!(err.is_corruption());
+}
+
+#[test]
+fn test_per_entry_crc_status() {
+    use zesven::read::{SelectAll, TestOptions};
+
+    let files: [(&str, &[u8]); 3] = [
+        ("a.txt", b"first file"),
+        ("b.txt", b"second file contents"),
+        ("c.txt", b"third"),
+    ];
+    let concatenated: Vec<u8> = files.iter().flat_map(|(_, d)| d.iter().copied()).collect();
+    let mut substream_crcs: Vec<u32> = files.iter().map(|(_, d)| crc32fast::hash(d)).collect();
+    substream_crcs[1] ^= 0xFFFF_FFFF;
+    let archive_bytes =
+        build_solid_copy_archive(&files, crc32fast::hash(&concatenated), &substream_crcs);
+
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+    let result = archive.test(SelectAll, &TestOptions::default()).unwrap();
+
+    assert_eq!(result.entries_passed, 2);
+    assert_eq!(result.entries_failed, 1);
+    assert_eq!(result.failures.len(), 1);
+    assert_eq!(result.entries.len(), files.len());
+    for (i, status) in result.entries.iter().enumerate() {
+        let (name, data) = files[i];
+        assert_eq!(status.index, i);
+        assert_eq!(status.path, name);
+        assert_eq!(status.expected_crc, Some(substream_crcs[i]));
+        assert_eq!(status.actual_crc, Some(crc32fast::hash(data)));
+        assert_eq!(status.passed, i != 1, "unexpected status for {name}");
+    }
+}
+
+// =============================================================================
+// Missing SubStreamsInfo Tests
+// =============================================================================
+//
+// SubStreamsInfo may be omitted when every folder holds exactly one stream.
+
+/// Builds a Copy archive with one folder per file and no SubStreamsInfo.
+/// Sizes and CRCs come from the folders; an empty directory entry sits
+/// between the files.
+fn build_copy_archive_without_substreams(files: &[(&str, &[u8])]) -> Vec<u8> {
+    use common::{push_names_property, push_variable_u64, wrap_raw_header};
+
+    let packed: Vec<u8> = files.iter().flat_map(|(_, d)| d.iter().copied()).collect();
+
+    let mut h = vec![0x01, 0x04]; // kHeader, kMainStreamsInfo
+
+    // PackInfo: pack_pos = 0, one pack stream per file
+    h.extend_from_slice(&[0x06, 0x00]);
+    push_variable_u64(&mut h, files.len() as u64);
+    h.push(0x09);
+    for (_, data) in files {
+        push_variable_u64(&mut h, data.len() as u64);
+    }
+    h.push(0x00);
+
+    // UnpackInfo: one single-Copy-coder folder per file, with folder CRCs
+    h.extend_from_slice(&[0x07, 0x0B]);
+    push_variable_u64(&mut h, files.len() as u64);
+    h.push(0x00);
+    for _ in files {
+        h.extend_from_slice(&[0x01, 0x01, 0x00]);
+    }
+    h.push(0x0C);
+    for (_, data) in files {
+        push_variable_u64(&mut h, data.len() as u64);
+    }
+    h.extend_from_slice(&[0x0A, 0x01]);
+    for (_, data) in files {
+        h.extend_from_slice(&crc32fast::hash(data).to_le_bytes());
+    }
+    h.extend_from_slice(&[0x00, 0x00]); // end UnpackInfo, end MainStreamsInfo
+
+    // FilesInfo: the files with a directory ("dir") after the first one
+    let mut names: Vec<&str> = files.iter().map(|(n, _)| *n).collect();
+    names.insert(1, "dir");
+    h.push(0x05);
+    push_variable_u64(&mut h, names.len() as u64);
+    h.extend_from_slice(&[0x0E, 0x01, 0x40]); // kEmptyStream: entry 1
+    push_names_property(&mut h, &names);
+    h.extend_from_slice(&[0x00, 0x00]); // end FilesInfo, end Header
+
+    wrap_raw_header(&packed, &h)
+}
+
+#[test]
+fn test_archive_without_substreams_info() {
+    use zesven::format::parser::read_archive_header;
+    use zesven::read::{SelectAll, TestOptions};
+
+    let files: [(&str, &[u8]); 3] = [
+        ("a.txt", b"first file"),
+        ("dir/b.txt", b"second file contents"),
+        ("c.txt", b"third"),
+    ];
+    let archive_bytes = build_copy_archive_without_substreams(&files);
+
+    // The parser fills in one stream per folder
+    let (_, header) = read_archive_header(&mut Cursor::new(&archive_bytes), None).unwrap();
+    let substreams = header.substreams_info.as_ref().unwrap();
+    assert_eq!(substreams.num_unpack_streams_in_folders, vec![1, 1, 1]);
+    assert_eq!(substreams.unpack_sizes, vec![10, 20, 5]);
+
+    // Files map to consecutive folders, skipping the directory
+    let locator = zesven::streaming::SolidEntryLocator::from_header(&header);
+    let folders: Vec<Option<usize>> = (0..4)
+        .map(|i| locator.get(i).map(|l| l.folder_index))
+        .collect();
+    assert_eq!(folders, vec![Some(0), None, Some(1), Some(2)]);
+
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+    assert!(!archive.info().is_solid);
+    assert!(archive.entries()[1].is_directory);
+
+    for (name, data) in files {
+        let entry = archive.entry(name).unwrap();
+        assert_eq!(entry.size, data.len() as u64);
+        assert_eq!(entry.crc32, Some(crc32fast::hash(data)));
+        assert_eq!(archive.extract_to_vec(name).unwrap(), data);
+    }
+
+    let result = archive.test(SelectAll, &TestOptions::default()).unwrap();
+    assert!(result.is_ok(), "failures: {:?}", result.failures);
+}
+
+// =============================================================================
+// Structure Validation Tests
+// =============================================================================
+
+/// Builds an archive with one file in a folder of two chained Copy coders,
+/// connected by a single bind pair `(in_index, out_index)`.
+fn build_chained_copy_archive(data: &[u8], bind_pair: (u64, u64)) -> Vec<u8> {
+    use common::{push_names_property, push_variable_u64, wrap_raw_header};
+
+    let mut h = vec![0x01, 0x04]; // kHeader, kMainStreamsInfo
+
+    // PackInfo: pack_pos = 0, one pack stream with a CRC
+    h.extend_from_slice(&[0x06, 0x00, 0x01, 0x09]);
+    push_variable_u64(&mut h, data.len() as u64);
+    h.extend_from_slice(&[0x0A, 0x01]);
+    h.extend_from_slice(&crc32fast::hash(data).to_le_bytes());
+    h.push(0x00);
+
+    // UnpackInfo: one folder, two simple Copy coders and one bind pair;
+    // the single packed stream is implied by the unbound input
+    h.extend_from_slice(&[0x07, 0x0B, 0x01, 0x00, 0x02, 0x01, 0x00, 0x01, 0x00]);
+    push_variable_u64(&mut h, bind_pair.0);
+    push_variable_u64(&mut h, bind_pair.1);
+    h.push(0x0C);
+    push_variable_u64(&mut h, data.len() as u64);
+    push_variable_u64(&mut h, data.len() as u64);
+    h.extend_from_slice(&[0x00, 0x00]); // end UnpackInfo, end MainStreamsInfo
+
+    // FilesInfo
+    h.push(0x05);
+    push_variable_u64(&mut h, 1);
+    push_names_property(&mut h, &["file.bin"]);
+    h.extend_from_slice(&[0x00, 0x00]); // end FilesInfo, end Header
+
+    wrap_raw_header(data, &h)
+}
+
+#[test]
+fn test_validate_structure_well_formed() {
+    // Coder 1 feeds coder 0, which produces the folder output
+    let archive_bytes = build_chained_copy_archive(b"chained copy payload", (0, 1));
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+
+    let report = archive.validate_structure().unwrap();
+    assert!(report.is_ok(), "issues: {:?}", report.issues);
+    assert_eq!(report.folders_checked, 1);
+    assert_eq!(report.pack_streams_checked, 1);
+    assert_eq!(report.pack_crcs_checked, 1);
+
+    #[cfg(feature = "lzma")]
+    {
+        let entries = [("a.txt", b"alpha" as &[u8]), ("b.txt", b"beta")];
+        let written = common::create_archive(&entries).unwrap();
+        let mut archive = Archive::open(Cursor::new(&written)).unwrap();
+        let report = archive.validate_structure().unwrap();
+        assert!(report.is_ok(), "issues: {:?}", report.issues);
+    }
+}
+
+#[test]
+fn test_validate_structure_reports_tampered_bind_pair() {
+    // Coder 1 is bound to its own output: the indices are in range, so the
+    // archive opens, but coder 1 is detached from the folder output
+    let archive_bytes = build_chained_copy_archive(b"chained copy payload", (1, 1));
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+
+    let report = archive.validate_structure().unwrap();
+    assert!(!report.is_ok());
+    assert_eq!(report.issues.len(), 1, "issues: {:?}", report.issues);
+    assert_eq!(report.issues[0].folder_index, Some(0));
+    assert!(
+        report.issues[0].message.contains("coder 1"),
+        "unexpected issue: {}",
+        report.issues[0].message
+    );
+}
+
+#[test]
+fn test_validate_structure_reports_pack_crc_mismatch() {
+    let mut archive_bytes = build_chained_copy_archive(b"chained copy payload", (0, 1));
+    // Flip a payload byte: the header still parses, the pack CRC no longer matches
+    archive_bytes[32] ^= 0xFF;
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+
+    let report = archive.validate_structure().unwrap();
+    assert_eq!(report.issues.len(), 1, "issues: {:?}", report.issues);
+    assert!(
+        report.issues[0]
+            .message
+            .contains("pack stream 0 CRC mismatch")
+    );
+    assert_eq!(report.issues[0].folder_index, None);
+}
+
+// =============================================================================
+// Declared Size Tests
+// =============================================================================
+
+/// Builds a Copy archive whose folder declares `declared_size` bytes while
+/// the packed stream holds `data`. The folder CRC covers `data`, so only the
+/// size is wrong.
+fn build_copy_archive_with_declared_size(data: &[u8], declared_size: u64) -> Vec<u8> {
+    use common::{push_names_property, push_variable_u64, wrap_raw_header};
+
+    let mut h = vec![0x01, 0x04]; // kHeader, kMainStreamsInfo
+
+    // PackInfo: pack_pos = 0, one pack stream
+    h.extend_from_slice(&[0x06, 0x00, 0x01, 0x09]);
+    push_variable_u64(&mut h, data.len() as u64);
+    h.push(0x00);
+
+    // UnpackInfo: one folder with a single Copy coder
+    h.extend_from_slice(&[0x07, 0x0B, 0x01, 0x00, 0x01, 0x01, 0x00]);
+    h.push(0x0C);
+    push_variable_u64(&mut h, declared_size);
+    h.extend_from_slice(&[0x0A, 0x01]);
+    h.extend_from_slice(&crc32fast::hash(data).to_le_bytes());
+    h.extend_from_slice(&[0x00, 0x00]); // end UnpackInfo, end MainStreamsInfo
+
+    // FilesInfo
+    h.push(0x05);
+    push_variable_u64(&mut h, 1);
+    push_names_property(&mut h, &["file.bin"]);
+    h.extend_from_slice(&[0x00, 0x00]); // end FilesInfo, end Header
+
+    wrap_raw_header(data, &h)
+}
+
+#[test]
+fn test_extract_detects_declared_size_mismatch() {
+    use zesven::read::ExtractOptions;
+
+    let data = b"shorter than declared";
+    let archive_bytes = build_copy_archive_with_declared_size(data, data.len() as u64 + 16);
+
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+    assert_eq!(archive.entries()[0].size, data.len() as u64 + 16);
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let result = archive
+        .extract(temp_dir.path(), (), &ExtractOptions::default())
+        .unwrap();
+    assert_eq!(result.entries_failed, 1);
+    assert!(
+        result.failures[0].1.contains("declares"),
+        "unexpected failure: {:?}",
+        result.failures
+    );
+    assert!(!temp_dir.path().join("file.bin").exists());
+
+    // With the check disabled, the CRC of the short output still matches
+    let temp_dir = tempfile::tempdir().unwrap();
+    let options = ExtractOptions::default().verify_size(false);
+    let result = archive.extract(temp_dir.path(), (), &options).unwrap();
+    assert_eq!(
+        result.entries_extracted, 1,
+        "failures: {:?}",
+        result.failures
+    );
+    assert_eq!(
+        std::fs::read(temp_dir.path().join("file.bin")).unwrap(),
+        data
+    );
+}
+
+// =============================================================================
+// Truncation Detection Tests
+// =============================================================================
+
+/// Builds a Copy archive holding `data` whose pack info declares a packed
+/// stream of `pack_size` bytes.
+fn build_copy_archive_with_pack_size(data: &[u8], pack_size: u64) -> Vec<u8> {
+    build_copy_archive_with_pack_info(data, 0, pack_size)
+}
+
+/// Builds a Copy archive holding `data` whose pack info declares a packed
+/// stream of `pack_size` bytes at `pack_pos`.
+fn build_copy_archive_with_pack_info(data: &[u8], pack_pos: u64, pack_size: u64) -> Vec<u8> {
+    use common::{push_names_property, push_variable_u64, wrap_raw_header};
+
+    let mut h = vec![0x01, 0x04]; // kHeader, kMainStreamsInfo
+
+    // PackInfo: one pack stream
+    h.push(0x06);
+    push_variable_u64(&mut h, pack_pos);
+    h.extend_from_slice(&[0x01, 0x09]);
+    push_variable_u64(&mut h, pack_size);
+    h.push(0x00);
+
+    // UnpackInfo: one folder with a single Copy coder
+    h.extend_from_slice(&[0x07, 0x0B, 0x01, 0x00, 0x01, 0x01, 0x00]);
+    h.push(0x0C);
+    push_variable_u64(&mut h, data.len() as u64);
+    h.extend_from_slice(&[0x00, 0x00]); // end UnpackInfo, end MainStreamsInfo
+
+    // FilesInfo
+    h.push(0x05);
+    push_variable_u64(&mut h, 1);
+    push_names_property(&mut h, &["file.bin"]);
+    h.extend_from_slice(&[0x00, 0x00]); // end FilesInfo, end Header
+
+    wrap_raw_header(data, &h)
+}
+
+#[test]
+fn test_open_rejects_pack_sizes_past_end() {
+    use zesven::format::streams::{LimitMode, ResourceLimits};
+
+    let data = b"packed payload";
+    let archive_bytes = build_copy_archive_with_pack_size(data, 1 << 20);
+
+    let err = expect_err(Archive::open(Cursor::new(&archive_bytes)));
+    assert!(
+        matches!(&err, Error::CorruptHeader { offset, reason }
+            if *offset == archive_bytes.len() as u64 && reason.contains("pack streams end")),
+        "unexpected error: {err:?}"
+    );
+
+    // An overflowing pack size is rejected the same way
+    let archive_bytes = build_copy_archive_with_pack_size(data, u64::MAX);
+    let err = expect_err(Archive::open(Cursor::new(&archive_bytes)));
+    assert!(matches!(err, Error::CorruptHeader { .. }), "{err:?}");
+
+    // A pack size that fits still opens
+    let archive_bytes = build_copy_archive_with_pack_size(data, data.len() as u64);
+    Archive::open(Cursor::new(&archive_bytes)).unwrap();
+
+    // With a warning only, the archive opens
+    let archive_bytes = build_copy_archive_with_pack_size(data, 1 << 20);
+    let limits = ResourceLimits::default().pack_bounds(LimitMode::Warn);
+    let archive = Archive::open_with_limits(Cursor::new(&archive_bytes), limits).unwrap();
+    assert_eq!(archive.len(), 1);
+}
+
+#[test]
+fn test_packed_folder_bytes_rejects_overflowing_offset() {
+    use zesven::format::streams::ResourceLimits;
+
+    let data = b"packed payload";
+    let archive_bytes = build_copy_archive_with_pack_info(data, u64::MAX, data.len() as u64);
+    let mut archive =
+        Archive::open_with_limits(Cursor::new(&archive_bytes), ResourceLimits::unlimited())
+            .unwrap();
+
+    let err = expect_err(archive.packed_folder_bytes(0, u64::MAX));
+    assert!(
+        matches!(&err, Error::InvalidFormat(msg) if msg.contains("overflow")),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn test_check_truncation_reports_missing_bytes() {
+    use zesven::WriteOptions;
+    use zesven::codec::CodecMethod;
+    use zesven::format::streams::{LimitMode, ResourceLimits};
+
+    let payload = vec![0xA5u8; 4096];
+    let options = WriteOptions::new()
+        .method(CodecMethod::Copy)
+        .header_first(true);
+    let archive_bytes =
+        common::create_archive_with_options(options, &[("data.bin", payload.as_slice())]).unwrap();
+
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+    assert_eq!(archive.check_truncation().unwrap(), None);
+
+    // With the header first and pack bounds only warned about, a cut in the
+    // packed data still opens
+    let cut = 1000;
+    let truncated = &archive_bytes[..archive_bytes.len() - cut];
+    let limits = ResourceLimits::default().pack_bounds(LimitMode::Warn);
+    let mut archive = Archive::open_with_limits(Cursor::new(truncated), limits).unwrap();
+    let info = archive
+        .check_truncation()
+        .unwrap()
+        .expect("truncation should be detected");
+    assert_eq!(info.missing_bytes, cut as u64);
+    assert_eq!(info.expected_size, archive_bytes.len() as u64);
+    assert_eq!(info.actual_size, truncated.len() as u64);
+}
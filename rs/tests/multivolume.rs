@@ -303,6 +303,55 @@ fn test_writer_create_multivolume() {
     assert_eq!(result.volume_count, 1);
 }
 
+/// Test: A solid folder larger than a volume is split into exactly three
+/// full-size volumes that read back through `MultiVolumeReader`
+#[test]
+fn test_writer_create_multivolume_path_three_volumes() {
+    const VOLUME_SIZE: u64 = 4000;
+
+    let dir = tempdir().unwrap();
+    let extract_dir = tempdir().unwrap();
+    let files: Vec<(String, Vec<u8>)> = (0..3u32)
+        .map(|i| {
+            let data = (0..3000u32)
+                .map(|b| (b.wrapping_mul(2_654_435_761) >> (8 + i)) as u8)
+                .collect();
+            (format!("part{i}.bin"), data)
+        })
+        .collect();
+
+    // Copy keeps the size predictable; solid puts every file in one folder
+    let options = WriteOptions::new().method(CodecMethod::Copy).solid();
+    let mut writer = Writer::create_multivolume_path(dir.path().join("split.7z"), VOLUME_SIZE)
+        .unwrap()
+        .options(options);
+    for (name, data) in &files {
+        writer
+            .add_bytes(ArchivePath::new(name).unwrap(), data)
+            .unwrap();
+    }
+    let result = writer.finish().unwrap();
+
+    assert_eq!(result.volume_count, 3);
+    assert_eq!(&result.volume_sizes[..2], &[VOLUME_SIZE, VOLUME_SIZE]);
+    for (i, &size) in result.volume_sizes.iter().enumerate() {
+        let path = dir.path().join(format!("split.7z.{:03}", i + 1));
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), size);
+    }
+    assert!(!dir.path().join("split.7z.004").exists());
+
+    let mut archive =
+        Archive::<MultiVolumeReader>::open_multivolume(dir.path().join("split.7z.001")).unwrap();
+    assert_eq!(archive.info().folder_count, 1);
+    let extract = archive
+        .extract(extract_dir.path(), (), &Default::default())
+        .unwrap();
+    assert_eq!(extract.entries_extracted, files.len());
+    for (name, data) in &files {
+        assert_eq!(&std::fs::read(extract_dir.path().join(name)).unwrap(), data);
+    }
+}
+
 /// Test: Data automatically splits across volumes
 #[test]
 fn test_writer_auto_splits_volumes() {
@@ -509,6 +558,48 @@ fn test_volume_numbering_format() {
     }
 }
 
+/// Test: Continue a volume set, appending a third volume to a two-volume stream
+#[test]
+fn test_continue_from_appends_volume() {
+    use std::io::{Cursor, Write};
+    use zesven::volume::MultiVolumeWriter;
+
+    let dir = tempdir().unwrap();
+    let data: Vec<u8> = (0..SPANNING_DATA_SIZE / 2)
+        .map(|i| (i * 31 % 256) as u8)
+        .collect();
+
+    // Build the archive in memory so it can be streamed out in two sessions
+    let mut archive_bytes = Vec::new();
+    {
+        let options = WriteOptions::new().method(CodecMethod::Copy);
+        let mut writer = Writer::create(Cursor::new(&mut archive_bytes))
+            .unwrap()
+            .options(options);
+        writer
+            .add_bytes(ArchivePath::new("data.bin").unwrap(), &data)
+            .unwrap();
+        let _ = writer.finish().unwrap();
+    }
+    let split = 2 * SMALL_VOLUME_SIZE as usize;
+    assert!(archive_bytes.len() > split && archive_bytes.len() < 3 * split / 2);
+
+    let config = VolumeConfig::new(dir.path().join("stream.7z"), SMALL_VOLUME_SIZE);
+    let mut writer = MultiVolumeWriter::create(config.clone()).unwrap();
+    writer.write_all(&archive_bytes[..split]).unwrap();
+    assert_eq!(writer.finish().unwrap().len(), 2);
+
+    let mut writer = MultiVolumeWriter::continue_from(config).unwrap();
+    writer.write_all(&archive_bytes[split..]).unwrap();
+    let sizes = writer.finish().unwrap();
+    assert_eq!(sizes.len(), 3);
+    assert!(dir.path().join("stream.7z.003").exists());
+
+    let reader = MultiVolumeReader::open(dir.path().join("stream.7z.001")).unwrap();
+    let mut archive = Archive::open(reader).unwrap();
+    assert_eq!(archive.extract_to_vec("data.bin").unwrap(), data);
+}
+
 // ============================================================================
 // Encrypted Multi-Volume Tests
 // ============================================================================
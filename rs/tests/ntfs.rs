@@ -1,8 +1,9 @@
 //! Integration tests for NTFS alternate data streams support.
 //!
 //! These tests verify the ADS path parsing, construction, and detection
-//! functionality. Windows-specific tests that interact with actual ADS
-//! are conditionally compiled.
+//! functionality, and the per-file stream limit during extraction.
+//! Windows-specific tests that interact with actual ADS are conditionally
+//! compiled.
 
 use std::path::PathBuf;
 use zesven::ntfs::{
@@ -122,6 +123,95 @@ fn test_ads_path_roundtrip() {
     }
 }
 
+/// Tests grouping of alternate stream entries with their base files.
+#[test]
+fn test_archive_groups_alt_streams() {
+    use std::io::Cursor;
+    use zesven::codec::CodecMethod;
+    use zesven::{Archive, ArchivePath, WriteOptions, Writer};
+
+    let mut archive_bytes = Vec::new();
+    {
+        let options = WriteOptions::new().method(CodecMethod::Copy);
+        let mut writer = Writer::create(Cursor::new(&mut archive_bytes))
+            .unwrap()
+            .options(options);
+        for (name, data) in [
+            ("file.txt", b"file contents".as_slice()),
+            (
+                "file.txt:Zone.Identifier",
+                b"[ZoneTransfer]\r\nZoneId=3\r\n",
+            ),
+            ("other.txt", b"other contents"),
+        ] {
+            writer
+                .add_bytes(ArchivePath::new(name).unwrap(), data)
+                .unwrap();
+        }
+        let _ = writer.finish().unwrap();
+    }
+
+    let archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+
+    let base: Vec<_> = archive
+        .base_entries()
+        .iter()
+        .map(|e| e.path.as_str())
+        .collect();
+    assert_eq!(base, ["file.txt", "other.txt"]);
+
+    let streams = archive.alt_streams_for("file.txt");
+    assert_eq!(streams.len(), 1);
+    assert_eq!(streams[0].path.as_str(), "file.txt:Zone.Identifier");
+
+    assert!(archive.alt_streams_for("other.txt").is_empty());
+    assert!(archive.alt_streams_for("file").is_empty());
+}
+
+/// Tests that extraction enforces the per-file alternate stream limit.
+#[test]
+fn test_extract_max_ads_per_file() {
+    use std::io::Cursor;
+    use zesven::codec::CodecMethod;
+    use zesven::read::ExtractOptions;
+    use zesven::{Archive, ArchivePath, Error, WriteOptions, Writer};
+
+    let mut archive_bytes = Vec::new();
+    {
+        let options = WriteOptions::new().method(CodecMethod::Copy);
+        let mut writer = Writer::create(Cursor::new(&mut archive_bytes))
+            .unwrap()
+            .options(options);
+        writer
+            .add_bytes(ArchivePath::new("file.txt").unwrap(), b"base")
+            .unwrap();
+        for i in 0..50 {
+            let name = make_ads_path("file.txt", &format!("stream{}", i));
+            writer
+                .add_bytes(ArchivePath::new(&name).unwrap(), b"ads")
+                .unwrap();
+        }
+        let _ = writer.finish().unwrap();
+    }
+
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let options = ExtractOptions::new().max_ads_per_file(10);
+    let err = archive.extract(dir.path(), (), &options).unwrap_err();
+    assert!(
+        matches!(&err, Error::ResourceLimitExceeded(msg)
+            if msg.contains("file.txt") && msg.contains("10 alternate data streams")),
+        "unexpected error: {:?}",
+        err
+    );
+    assert!(!dir.path().join("file.txt").exists());
+
+    let options = ExtractOptions::new().max_ads_per_file(50);
+    let result = archive.extract(dir.path(), (), &options).unwrap();
+    assert_eq!(result.entries_failed, 0);
+    assert_eq!(result.entries_extracted, 51);
+}
+
 /// Tests discover_alt_streams on non-Windows (should return empty).
 #[cfg(not(windows))]
 #[test]
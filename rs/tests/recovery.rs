@@ -62,6 +62,29 @@ fn test_recover_archive_with_garbage_prefix() {
     );
 }
 
+/// Tests that strict mode turns recovery warnings into errors.
+#[test]
+fn test_recover_archive_strict_fails_on_warning() {
+    let entries = [("file.txt", b"Test content" as &[u8])];
+    let archive_bytes = common::create_archive(&entries).expect("Failed to create archive");
+
+    let mut prefixed = b"garbage before the archive".to_vec();
+    prefixed.extend(&archive_bytes);
+
+    let options = RecoveryOptions::new().strict(true);
+    let err = common::expect_err(recover_archive(Cursor::new(prefixed), options));
+    assert!(
+        matches!(&err, zesven::Error::Strict(message) if message.contains("offset")),
+        "unexpected error: {:?}",
+        err
+    );
+
+    // Without warnings strict mode recovers normally
+    let options = RecoveryOptions::new().strict(true);
+    let result = recover_archive(Cursor::new(archive_bytes), options).expect("Recovery failed");
+    assert_eq!(result.status, RecoveryStatus::FullRecovery);
+}
+
 /// Tests recovery of an archive embedded in binary data (like SFX).
 #[test]
 fn test_recover_archive_embedded_in_binary() {
@@ -248,3 +271,205 @@ fn test_recovery_result_metrics() {
     assert_eq!(result.failed_count(), 0);
     assert!((result.recovery_rate() - 1.0).abs() < f64::EPSILON);
 }
+
+/// Reader over a large virtual run of zeros that sets a cancellation flag
+/// once `cancel_at` bytes have been read.
+struct CancellingZeroReader {
+    pos: u64,
+    len: u64,
+    cancel_at: u64,
+    flag: std::sync::Arc<std::sync::atomic::AtomicBool>,
+}
+
+impl std::io::Read for CancellingZeroReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = buf.len().min((self.len - self.pos) as usize);
+        buf[..n].fill(0);
+        self.pos += n as u64;
+        if self.pos >= self.cancel_at {
+            self.flag.store(true, std::sync::atomic::Ordering::Relaxed);
+        }
+        Ok(n)
+    }
+}
+
+impl std::io::Seek for CancellingZeroReader {
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        self.pos = match pos {
+            std::io::SeekFrom::Start(p) => p,
+            std::io::SeekFrom::End(d) => self.len.saturating_add_signed(d),
+            std::io::SeekFrom::Current(d) => self.pos.saturating_add_signed(d),
+        };
+        Ok(self.pos)
+    }
+}
+
+/// Tests that a cancelled scan over a large input returns promptly.
+#[test]
+fn test_signature_scan_cancellation() {
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicBool;
+    use zesven::recovery::SignatureScanner;
+
+    const LEN: u64 = 1 << 30;
+    const CANCEL_AT: u64 = 4 * 1024 * 1024;
+
+    let flag = Arc::new(AtomicBool::new(false));
+    let mut reader = CancellingZeroReader {
+        pos: 0,
+        len: LEN,
+        cancel_at: CANCEL_AT,
+        flag: flag.clone(),
+    };
+
+    let mut scanner = SignatureScanner::new(&mut reader, LEN as usize).with_cancellation(flag);
+    let offset = scanner.find_next_signature().unwrap();
+
+    assert_eq!(offset, None);
+    assert!(scanner.is_cancelled());
+    // Stops within one check interval of the flag being set, not at the limit
+    assert!(scanner.bytes_scanned() as u64 <= CANCEL_AT + 1024 * 1024);
+}
+
+/// Tests that recovery reports cancellation when the flag is set mid-scan.
+#[test]
+fn test_recover_archive_cancellation() {
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicBool;
+
+    const LEN: u64 = 1 << 30;
+
+    let flag = Arc::new(AtomicBool::new(false));
+    let reader = CancellingZeroReader {
+        pos: 0,
+        len: LEN,
+        cancel_at: 2 * 1024 * 1024,
+        flag: flag.clone(),
+    };
+
+    let options = RecoveryOptions::new()
+        .search_limit(LEN as usize)
+        .cancellation(flag);
+    let result = recover_archive(reader, options).expect("Recovery should not fail");
+
+    assert_eq!(result.status, RecoveryStatus::Cancelled);
+    assert!(result.archive.is_none());
+}
+
+/// Tests that entries are decoded when corrupt entries are skipped, and that
+/// a truncated folder fails only its own entries.
+#[test]
+fn test_recovery_decodes_entries_before_truncated_folder() {
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+    use zesven::write::{SolidOptions, WriteOptions};
+    use zesven::{ArchivePath, Writer};
+
+    let mut rng = StdRng::seed_from_u64(0x521);
+    let mut noise = vec![0u8; 4096];
+    rng.fill(&mut noise[..]);
+    let options = WriteOptions::new()
+        .header_first(true)
+        .solid_options(SolidOptions::enabled().files_per_block(2));
+    let mut writer = Writer::create(Cursor::new(Vec::new()))
+        .unwrap()
+        .options(options);
+    for (name, data) in [
+        ("first.txt", b"first entry".as_slice()),
+        ("second.txt", b"second entry".as_slice()),
+        ("third.bin", noise.as_slice()),
+    ] {
+        writer
+            .add_bytes(ArchivePath::new(name).unwrap(), data)
+            .unwrap();
+    }
+    let (_, cursor) = writer.finish_into_inner().unwrap();
+    let archive_bytes = cursor.into_inner();
+
+    // Without skipping corrupt entries only metadata is checked
+    let result =
+        recover_archive(Cursor::new(archive_bytes.clone()), RecoveryOptions::new()).unwrap();
+    assert_eq!(result.recovered_count(), 3);
+    assert!(result.recovered_entries[0].extract().is_err());
+
+    // The header comes first, so cutting the end damages only the last folder
+    let truncated = archive_bytes[..archive_bytes.len() - 1024].to_vec();
+    let options = RecoveryOptions::new().skip_corrupt_entries(true);
+    let result = recover_archive(Cursor::new(truncated), options).unwrap();
+    assert_eq!(
+        result.status,
+        RecoveryStatus::PartialRecovery,
+        "{:?}",
+        result.warnings
+    );
+    assert_eq!(result.failed_count(), 1);
+    assert_eq!(result.failed_entries[0].path.as_deref(), Some("third.bin"));
+
+    let recovered: Vec<_> = result
+        .recovered_entries
+        .iter()
+        .map(|entry| (entry.path.as_str(), entry.extract().unwrap().to_vec()))
+        .collect();
+    assert_eq!(
+        recovered,
+        [
+            ("first.txt", b"first entry".to_vec()),
+            ("second.txt", b"second entry".to_vec()),
+        ]
+    );
+    assert!(result.recovered_entries.iter().all(|entry| entry.crc_valid));
+}
+
+/// Tests that a recovery record repairs a corrupted region so that the
+/// archive extracts again.
+#[test]
+fn test_repair_with_record_restores_corrupted_archive() {
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+    use zesven::recovery::repair_with_record;
+    use zesven::write::WriteOptions;
+    use zesven::{Archive, ArchivePath, Writer};
+
+    let mut rng = StdRng::seed_from_u64(0x529);
+    let mut noise = vec![0u8; 200 * 1024];
+    rng.fill(&mut noise[..]);
+    let text = "recovery record test line\n".repeat(4000);
+
+    let options = WriteOptions::new().recovery_record(5);
+    let mut writer = Writer::create(Cursor::new(Vec::new()))
+        .unwrap()
+        .options(options);
+    writer
+        .add_bytes(ArchivePath::new("noise.bin").unwrap(), &noise)
+        .unwrap();
+    writer
+        .add_bytes(ArchivePath::new("text.txt").unwrap(), text.as_bytes())
+        .unwrap();
+    let (_, cursor) = writer.finish_into_inner().unwrap();
+    let original = cursor.into_inner();
+
+    // The record is ignored by readers
+    let mut archive = Archive::open(Cursor::new(original.clone())).unwrap();
+    assert_eq!(archive.extract_to_vec("noise.bin").unwrap(), noise);
+
+    // Corrupt 1 KiB of packed data
+    let mut damaged = original.clone();
+    for byte in &mut damaged[50_000..51_024] {
+        *byte = !*byte;
+    }
+    let mut archive = Archive::open(Cursor::new(damaged.clone())).unwrap();
+    assert!(archive.extract_to_vec("noise.bin").is_err());
+
+    let mut file = Cursor::new(damaged);
+    let repair = repair_with_record(&mut file).unwrap();
+    assert!(repair.damaged_blocks > 0);
+    assert!(repair.is_complete());
+    assert_eq!(file.get_ref(), &original);
+
+    file.set_position(0);
+    let mut archive = Archive::open(file).unwrap();
+    assert_eq!(archive.extract_to_vec("noise.bin").unwrap(), noise);
+    assert_eq!(archive.extract_to_vec("text.txt").unwrap(), text.as_bytes());
+
+    // Archives without a record are rejected
+    let plain = common::create_archive(&[("a.txt", b"a" as &[u8])]).unwrap();
+    assert!(repair_with_record(&mut Cursor::new(plain)).is_err());
+}
@@ -515,4 +515,63 @@ mod extraction_enforcement_tests {
         // To enforce extraction limits, use extract() with ExtractOptions
         // (demonstrated in test_extraction_entry_size_limit_in_options)
     }
+
+    /// Tests that per_entry_timeout fails entries that take too long to decode.
+    ///
+    /// A zero timeout expires before any decoding finishes, so every entry
+    /// with data times out while entries without data still extract.
+    /// Deadline enforcement against an actually slow decoder is covered by
+    /// the DeadlineReader unit tests in src/safety.rs.
+    #[test]
+    fn test_extraction_per_entry_timeout() {
+        use std::time::Duration;
+
+        let mut archive_bytes = Vec::new();
+        {
+            let cursor = StdCursor::new(&mut archive_bytes);
+            let mut writer = Writer::create(cursor).expect("Failed to create writer");
+            writer
+                .add_bytes(ArchivePath::new("data.bin").unwrap(), &[0x42u8; 10_000])
+                .expect("Failed to add entry");
+            writer
+                .add_bytes(ArchivePath::new("empty.txt").unwrap(), &[])
+                .expect("Failed to add entry");
+            writer
+                .add_directory(ArchivePath::new("dir").unwrap(), Default::default())
+                .expect("Failed to add directory");
+            let _ = writer.finish().expect("Failed to finish archive");
+        }
+
+        let mut archive = Archive::open(Cursor::new(&archive_bytes)).expect("Should open");
+        let temp_dir = tempfile::tempdir().unwrap();
+        let options = ExtractOptions::new().per_entry_timeout(Duration::ZERO);
+        let extract_result = archive
+            .extract(temp_dir.path(), (), &options)
+            .expect("Batch extraction should not return Err");
+
+        assert_eq!(extract_result.entries_failed, 1);
+        assert_eq!(extract_result.entries_extracted, 2);
+        assert!(
+            extract_result
+                .failures
+                .iter()
+                .any(|(path, msg)| path == "data.bin" && msg.contains("timed out")),
+            "Failure message should indicate a timeout: {:?}",
+            extract_result.failures
+        );
+        assert!(temp_dir.path().join("empty.txt").exists());
+        assert!(temp_dir.path().join("dir").is_dir());
+
+        // A generous timeout extracts everything
+        let temp_dir = tempfile::tempdir().unwrap();
+        let options = ExtractOptions::new().per_entry_timeout(Duration::from_secs(60));
+        let extract_result = archive
+            .extract(temp_dir.path(), (), &options)
+            .expect("Extraction should succeed");
+        assert_eq!(extract_result.entries_failed, 0);
+        assert_eq!(
+            std::fs::read(temp_dir.path().join("data.bin")).unwrap(),
+            vec![0x42u8; 10_000]
+        );
+    }
 }
@@ -8,7 +8,37 @@
 //! - Unicode filenames
 //! - Deep directory structures
 //! - Directory entries
+//! - Archives holding only directories
+//! - Parent directories ordered before their children
 //! - Memory destination extraction
+//! - Streaming single entries into a writer
+//! - Aligned in-memory output buffers
+//! - Per-entry extraction outcomes
+//! - Conflict callbacks for existing files
+//! - Batched writes with deferred metadata
+//! - Merkle roots over extracted entries
+//! - Content digests of written archives
+//! - Opening from a borrowed slice
+//! - Writing to a non-seekable sink
+//! - Streaming entries from readers of known size
+//! - Backslashes in entry names
+//! - Control characters in entry names
+//! - Pack data alignment
+//! - Folder (solid block) membership
+//! - Extraction time estimates
+//! - Per-codec size breakdown
+//! - Content-defined chunking
+//! - Seekable entry readers
+//! - Strict extraction
+//! - Virtual filesystem extraction
+//! - Recreating or following symbolic links
+//! - Hard-linking identical files on extraction
+//! - Entry names differing only in case
+//! - Creation time preservation
+//! - Source file share modes on Windows
+//! - Sparse files on Unix
+//! - BSD file flags
+//! - Compression progress reporting
 
 // These tests require LZMA support (default compression method for writing)
 #![cfg(feature = "lzma")]
@@ -81,6 +111,41 @@ fn test_with_directory_entry() {
     }
 }
 
+#[test]
+fn test_directories_only_archive() {
+    use zesven::read::ExtractOptions;
+    use zesven::write::EntryMeta;
+
+    let dirs = ["empty", "tree", "tree/a", "tree/a/b", "tree/c"];
+
+    let mut archive_bytes = Vec::new();
+    {
+        let mut writer = Writer::create(Cursor::new(&mut archive_bytes)).unwrap();
+        for dir in dirs {
+            writer
+                .add_directory(ArchivePath::new(dir).unwrap(), EntryMeta::directory())
+                .unwrap();
+        }
+        let result = writer.finish().unwrap();
+        assert_eq!(result.entries_written, 0);
+        assert_eq!(result.directories_written, dirs.len());
+    }
+
+    let mut archive = Archive::open(Cursor::new(archive_bytes)).unwrap();
+    assert_eq!(archive.info().folder_count, 0);
+    assert_eq!(archive.len(), dirs.len());
+    assert!(archive.entries().iter().all(|e| e.is_directory));
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let result = archive
+        .extract(temp_dir.path(), (), &ExtractOptions::default())
+        .unwrap();
+    assert_eq!(result.entries_extracted, dirs.len());
+    for dir in dirs {
+        assert!(temp_dir.path().join(dir).is_dir(), "{dir} was not created");
+    }
+}
+
 // Note: Codec-specific modules (lzma_tests, deflate_tests, bzip2_tests) were removed.
 // More thorough codec roundtrip tests exist in codec_combinations.rs which:
 // - Tests multiple data types (text, binary, random, repetitive)
@@ -127,12 +192,698 @@ fn test_memory_destination_extraction() {
     assert_eq!(files.len(), 3);
 }
 
+#[test]
+fn test_memory_destination_output_alignment() {
+    use zesven::read::{ExtractOptions, MemoryDestination};
+
+    let entries = [
+        ("a.bin", b"odd-sized payload" as &[u8]),
+        ("b.bin", &[7u8; 1000]),
+        ("empty.bin", b""),
+    ];
+    let archive_bytes = common::create_archive(&entries).expect("Failed to create test archive");
+    let mut archive = Archive::open(Cursor::new(archive_bytes)).unwrap();
+
+    let options = ExtractOptions::new().output_alignment(64);
+    let mut dest = MemoryDestination::from_options(&options);
+    let result = archive.extract_to_destination(&mut dest).unwrap();
+    assert_eq!(result.entries_failed, 0);
+    assert_eq!(dest.len(), entries.len());
+
+    for (path, expected) in &entries {
+        let buffer = &dest.aligned_files()[*path];
+        assert_eq!(
+            buffer.as_ptr() as usize % 64,
+            0,
+            "'{}' is not aligned",
+            path
+        );
+        assert_eq!(&buffer[..], *expected);
+        assert_eq!(dest.get(path), Some(*expected));
+    }
+}
+
+#[test]
+fn test_extract_reports_entry_outcomes() {
+    use zesven::read::{ExtractOptions, OverwritePolicy};
+
+    let entries = [
+        ("existing.txt", b"archived content" as &[u8]),
+        ("fresh.txt", b"new content"),
+        ("empty.txt", b""),
+    ];
+    let archive_bytes = common::create_archive(&entries).expect("Failed to create test archive");
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    std::fs::write(temp_dir.path().join("existing.txt"), b"local").unwrap();
+
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+    let options = ExtractOptions::new().overwrite(OverwritePolicy::Skip);
+    let result = archive.extract(temp_dir.path(), (), &options).unwrap();
+
+    let outcome = |path: &str| {
+        result
+            .entries
+            .iter()
+            .find(|o| o.path == path)
+            .unwrap_or_else(|| panic!("no outcome for {}", path))
+            .clone()
+    };
+
+    let existing = outcome("existing.txt");
+    assert!(existing.skipped);
+    assert_eq!(existing.bytes_written, 0);
+    assert!(!existing.crc_verified);
+    assert_eq!(existing.error, None);
+
+    let fresh = outcome("fresh.txt");
+    assert!(!fresh.skipped && !fresh.overwritten);
+    assert_eq!(fresh.bytes_written, 11);
+    assert!(fresh.crc_verified);
+
+    let empty = outcome("empty.txt");
+    assert_eq!(empty.bytes_written, 0);
+    assert!(!empty.skipped);
+
+    // Aggregate counts summarize the outcomes
+    assert_eq!(result.entries.len(), 3);
+    assert_eq!(result.entries_skipped, 1);
+    assert_eq!(result.entries_extracted, 2);
+    assert_eq!(result.bytes_extracted, 11);
+    assert_eq!(
+        std::fs::read(temp_dir.path().join("existing.txt")).unwrap(),
+        b"local"
+    );
+
+    // Overwriting replaces the existing files and says so
+    let options = ExtractOptions::new().overwrite(OverwritePolicy::Overwrite);
+    let result = archive.extract(temp_dir.path(), (), &options).unwrap();
+    assert_eq!(result.entries_extracted, 3);
+    assert!(result.entries.iter().all(|o| o.overwritten));
+    assert_eq!(
+        std::fs::read(temp_dir.path().join("existing.txt")).unwrap(),
+        b"archived content"
+    );
+}
+
+#[test]
+fn test_extract_on_conflict_callback() {
+    use std::sync::{Arc, Mutex};
+    use zesven::read::{ExtractOptions, OverwriteDecision};
+
+    let entries = [
+        ("keep.txt", b"archived keep" as &[u8]),
+        ("replace.txt", b"archived replace"),
+        ("move.txt", b"archived move"),
+        ("fresh.txt", b"archived fresh"),
+    ];
+    let archive_bytes = common::create_archive(&entries).expect("Failed to create test archive");
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    for name in ["keep.txt", "replace.txt", "move.txt"] {
+        std::fs::write(temp_dir.path().join(name), b"local").unwrap();
+    }
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&seen);
+    let options = ExtractOptions::new().on_conflict(move |entry, existing| {
+        assert!(existing.exists());
+        recorded
+            .lock()
+            .unwrap()
+            .push(entry.path.as_str().to_string());
+        match entry.path.as_str() {
+            "keep.txt" => OverwriteDecision::Skip,
+            "replace.txt" => OverwriteDecision::Overwrite,
+            _ => OverwriteDecision::Rename("renamed/move.txt".into()),
+        }
+    });
+
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+    let result = archive.extract(temp_dir.path(), (), &options).unwrap();
+    assert_eq!(result.entries_failed, 0);
+    assert_eq!(result.entries_skipped, 1);
+
+    // The callback only sees entries whose destination already exists
+    let mut seen = seen.lock().unwrap().clone();
+    seen.sort();
+    assert_eq!(seen, ["keep.txt", "move.txt", "replace.txt"]);
+
+    let read = |name: &str| std::fs::read(temp_dir.path().join(name)).unwrap();
+    assert_eq!(read("keep.txt"), b"local");
+    assert_eq!(read("replace.txt"), b"archived replace");
+    assert_eq!(read("move.txt"), b"local");
+    assert_eq!(read("renamed/move.txt"), b"archived move");
+    assert_eq!(read("fresh.txt"), b"archived fresh");
+}
+
+#[test]
+fn test_extract_on_conflict_rename_stays_in_destination() {
+    use zesven::read::{ExtractOptions, OverwriteDecision};
+
+    let archive_bytes = common::create_archive(&[("a.txt", b"archived".as_slice())]).unwrap();
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+
+    let parent = tempfile::tempdir().unwrap();
+    let dest = parent.path().join("dest");
+    std::fs::create_dir(&dest).unwrap();
+    std::fs::write(dest.join("a.txt"), b"local").unwrap();
+    let absolute = parent.path().join("absolute.txt");
+
+    for renamed in ["../escape.txt".into(), absolute.clone()] {
+        let options = ExtractOptions::new()
+            .on_conflict(move |_, _| OverwriteDecision::Rename(renamed.clone()));
+        let result = archive.extract(&dest, (), &options).unwrap();
+        assert_eq!(result.entries_failed, 1);
+        let error = result.entries[0].error.as_ref().unwrap();
+        assert!(error.contains("traversal"), "{error}");
+    }
+    assert!(!parent.path().join("escape.txt").exists());
+    assert!(!absolute.exists());
+    assert_eq!(std::fs::read(dest.join("a.txt")).unwrap(), b"local");
+}
+
+#[test]
+fn test_parents_first_orders_directories_before_children() {
+    use zesven::WriteOptions;
+    use zesven::write::EntryMeta;
+
+    let files = [
+        ("a/b/c/deep.txt", b"deep".as_slice()),
+        ("a/top.txt", b"top".as_slice()),
+        ("x/y.txt", b"y".as_slice()),
+        ("a/b/mid.txt", b"mid".as_slice()),
+        ("root.txt", b"root".as_slice()),
+    ];
+    // Directories are added last, after the files they contain
+    let directories = ["x", "a/b/c", "a", "a/b", "empty"];
+
+    for solid in [false, true] {
+        let mut options = WriteOptions::new().parents_first(true);
+        if solid {
+            options = options.solid();
+        }
+        let mut archive_bytes = Vec::new();
+        {
+            let mut writer = Writer::create(Cursor::new(&mut archive_bytes))
+                .unwrap()
+                .options(options);
+            for (name, data) in files {
+                writer
+                    .add_bytes(ArchivePath::new(name).unwrap(), data)
+                    .unwrap();
+            }
+            for dir in directories {
+                writer
+                    .add_directory(ArchivePath::new(dir).unwrap(), EntryMeta::directory())
+                    .unwrap();
+            }
+            let _ = writer.finish().unwrap();
+        }
+
+        let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+        let paths: Vec<String> = archive
+            .entries()
+            .iter()
+            .map(|e| e.path.as_str().to_string())
+            .collect();
+        assert_eq!(paths.len(), files.len() + directories.len());
+
+        for (dir_idx, dir) in paths.iter().enumerate() {
+            if !archive.entries()[dir_idx].is_directory {
+                continue;
+            }
+            let prefix = format!("{dir}/");
+            for (idx, path) in paths.iter().enumerate() {
+                if path.starts_with(&prefix) {
+                    assert!(dir_idx < idx, "{dir} comes after {path}: {paths:?}");
+                }
+            }
+        }
+
+        // Files keep their relative order and their data
+        let file_order: Vec<&str> = paths
+            .iter()
+            .map(String::as_str)
+            .filter(|p| files.iter().any(|(name, _)| name == p))
+            .collect();
+        let expected: Vec<&str> = files.iter().map(|(name, _)| *name).collect();
+        assert_eq!(file_order, expected);
+        for (name, data) in files {
+            assert_eq!(archive.extract_to_vec(name).unwrap(), data, "{name}");
+        }
+    }
+}
+
+#[test]
+fn test_extract_entry_to_solid_and_non_solid() {
+    use zesven::WriteOptions;
+
+    let large: Vec<u8> = (0..300_000u32).map(|i| (i % 241) as u8).collect();
+    let entries = [
+        ("a.txt", b"leading entry".as_slice()),
+        ("b/large.bin", large.as_slice()),
+        ("c.txt", b"entry after the large one".as_slice()),
+    ];
+
+    for solid in [false, true] {
+        let options = if solid {
+            WriteOptions::new().solid()
+        } else {
+            WriteOptions::new()
+        };
+        let archive_bytes = common::create_archive_with_options(options, &entries).unwrap();
+        let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+        assert_eq!(archive.info().is_solid, solid);
+
+        // Extract in reverse so the solid case has to skip preceding streams
+        for (name, data) in entries.iter().rev() {
+            let mut out = Vec::new();
+            let written = archive.extract_entry_to(name, &mut out).unwrap();
+            assert_eq!(written, data.len() as u64, "{name}");
+            assert_eq!(out, *data, "{name}");
+        }
+    }
+}
+
+#[test]
+fn test_extract_with_write_batching() {
+    use std::time::{Duration, UNIX_EPOCH};
+    use zesven::read::{ExtractOptions, PreserveMetadata};
+    use zesven::write::EntryMeta;
+
+    /// 2021-06-07 08:09:10 UTC as a Windows FILETIME.
+    const MTIME_SECS: u64 = 1_623_053_350;
+    let filetime = (MTIME_SECS + 11_644_473_600) * 10_000_000;
+
+    let files: Vec<(String, Vec<u8>)> = (0..200)
+        .map(|i| {
+            (
+                format!("small/{i:03}.txt"),
+                format!("file number {i}\n").repeat(i + 1).into_bytes(),
+            )
+        })
+        .collect();
+    let mut archive_bytes = Vec::new();
+    {
+        let mut writer = Writer::create(Cursor::new(&mut archive_bytes)).unwrap();
+        for (name, data) in &files {
+            let meta = EntryMeta::file(data.len() as u64).modification_time(filetime);
+            writer
+                .add_stream(
+                    ArchivePath::new(name).unwrap(),
+                    &mut Cursor::new(data),
+                    meta,
+                )
+                .unwrap();
+        }
+        let _ = writer.finish().unwrap();
+    }
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let options = ExtractOptions::new()
+        .write_batching(true)
+        .preserve_metadata(PreserveMetadata::modification_time_only());
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+    let result = archive.extract(temp_dir.path(), (), &options).unwrap();
+    assert_eq!(result.entries_failed, 0);
+    assert_eq!(result.entries_extracted, files.len());
+
+    for (name, data) in &files {
+        let path = temp_dir.path().join(name);
+        assert_eq!(&std::fs::read(&path).unwrap(), data, "{name}");
+        let modified = std::fs::metadata(&path).unwrap().modified().unwrap();
+        assert_eq!(
+            modified,
+            UNIX_EPOCH + Duration::from_secs(MTIME_SECS),
+            "{name}"
+        );
+    }
+}
+
+#[cfg(feature = "merkle")]
+#[test]
+fn test_extract_merkle_root() {
+    use zesven::read::{ExtractOptions, OverwriteDecision, OverwritePolicy};
+
+    let extract_root = |entries: &[(&str, &[u8])]| {
+        let archive_bytes = common::create_archive(entries).expect("Failed to create archive");
+        let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let options = ExtractOptions::new().compute_merkle(true);
+        let result = archive.extract(temp_dir.path(), (), &options).unwrap();
+        assert_eq!(result.entries_failed, 0);
+        result.merkle_root.expect("merkle root should be computed")
+    };
+
+    let entries: [(&str, &[u8]); 3] = [
+        ("a.txt", b"alpha"),
+        ("dir/b.txt", b"beta"),
+        ("empty.txt", b""),
+    ];
+    let root = extract_root(&entries);
+
+    // Extracting the same content again, in any entry order, gives the same root
+    assert_eq!(extract_root(&entries), root);
+    let reordered: [(&str, &[u8]); 3] = [entries[2], entries[0], entries[1]];
+    assert_eq!(extract_root(&reordered), root);
+
+    // Changing one file's content changes the root
+    let changed: [(&str, &[u8]); 3] = [entries[0], ("dir/b.txt", b"BETA"), entries[2]];
+    assert_ne!(extract_root(&changed), root);
+
+    // Skipped entries contribute no leaf
+    let archive_bytes = common::create_archive(&entries).unwrap();
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+    let temp_dir = tempfile::tempdir().unwrap();
+    std::fs::write(temp_dir.path().join("a.txt"), b"alpha").unwrap();
+    let options = ExtractOptions::new()
+        .overwrite(OverwritePolicy::Skip)
+        .compute_merkle(true);
+    let result = archive.extract(temp_dir.path(), (), &options).unwrap();
+    assert_eq!(result.entries_skipped, 1);
+    assert_ne!(result.merkle_root, Some(root));
+
+    // Without the option no root is reported
+    let temp_dir = tempfile::tempdir().unwrap();
+    let result = archive
+        .extract(temp_dir.path(), (), &ExtractOptions::default())
+        .unwrap();
+    assert_eq!(result.merkle_root, None);
+
+    // Renamed entries are hashed from the data written, under the new path,
+    // not from the file that was in the way
+    let temp_dir = tempfile::tempdir().unwrap();
+    std::fs::write(temp_dir.path().join("a.txt"), b"unrelated").unwrap();
+    let options = ExtractOptions::new()
+        .on_conflict(|_, _| OverwriteDecision::Rename("renamed.txt".into()))
+        .compute_merkle(true);
+    let result = archive.extract(temp_dir.path(), (), &options).unwrap();
+    let renamed: [(&str, &[u8]); 3] = [("renamed.txt", b"alpha"), entries[1], entries[2]];
+    assert_eq!(result.merkle_root, Some(extract_root(&renamed)));
+}
+
+#[cfg(feature = "merkle")]
+#[test]
+fn test_write_content_digest() {
+    use zesven::WriteOptions;
+
+    let entries: [(&str, &[u8]); 3] = [
+        ("a.txt", b"alpha alpha alpha"),
+        ("dir/b.txt", b"beta"),
+        ("empty.txt", b""),
+    ];
+    let digest = |options: WriteOptions, entries: &[(&str, &[u8])]| {
+        let (_, result) = common::create_archive_with_result(Some(options), entries).unwrap();
+        result.content_digest
+    };
+
+    let fast = digest(WriteOptions::new().level(1).unwrap(), &entries);
+    let best = digest(WriteOptions::new().level(9).unwrap(), &entries);
+    assert_eq!(fast, best);
+
+    // Order of addition and solid settings do not matter either
+    let reordered: [(&str, &[u8]); 3] = [entries[2], entries[0], entries[1]];
+    assert_eq!(digest(WriteOptions::new().solid(), &reordered), fast);
+
+    // Changing one file's content changes the digest
+    let changed: [(&str, &[u8]); 3] = [entries[0], ("dir/b.txt", b"BETA"), entries[2]];
+    assert_ne!(digest(WriteOptions::new(), &changed), fast);
+}
+
+#[cfg(feature = "merkle")]
+#[test]
+fn test_content_fingerprint() {
+    use zesven::WriteOptions;
+    use zesven::codec::CodecMethod;
+    use zesven::write::EntryMeta;
+
+    let entries: [(&str, &[u8]); 3] = [
+        ("a.txt", b"alpha alpha alpha"),
+        ("dir/b.txt", b"beta"),
+        ("empty.txt", b""),
+    ];
+    let fingerprint = |options: WriteOptions, mtime: u64, entries: &[(&str, &[u8])]| {
+        let mut writer = Writer::create(Cursor::new(Vec::new()))
+            .unwrap()
+            .options(options);
+        for (name, data) in entries {
+            let meta = EntryMeta::file(data.len() as u64).modification_time(mtime);
+            writer
+                .add_stream(ArchivePath::new(name).unwrap(), &mut &data[..], meta)
+                .unwrap();
+        }
+        let (_, cursor) = writer.finish_into_inner().unwrap();
+        let archive = Archive::open(Cursor::new(cursor.into_inner())).unwrap();
+        archive.content_fingerprint()
+    };
+
+    let base = fingerprint(WriteOptions::new(), 1_000, &entries);
+
+    // Method, level, solid blocks, timestamps and entry order do not matter
+    let reordered: [(&str, &[u8]); 3] = [entries[2], entries[0], entries[1]];
+    let variants = [
+        (WriteOptions::new().level(9).unwrap(), 1_000, &entries),
+        (WriteOptions::new().solid(), 2_000, &entries),
+        (
+            WriteOptions::new().method(CodecMethod::Copy),
+            3_000,
+            &reordered,
+        ),
+    ];
+    for (options, mtime, entries) in variants {
+        assert_eq!(fingerprint(options, mtime, entries), base);
+    }
+
+    // Changing one file's content or path changes the fingerprint
+    let changed: [(&str, &[u8]); 3] = [entries[0], ("dir/b.txt", b"BETA"), entries[2]];
+    assert_ne!(fingerprint(WriteOptions::new(), 1_000, &changed), base);
+    let renamed: [(&str, &[u8]); 3] = [entries[0], ("dir/c.txt", b"beta"), entries[2]];
+    assert_ne!(fingerprint(WriteOptions::new(), 1_000, &renamed), base);
+}
+
+#[test]
+fn test_open_slice_borrows_data() {
+    let entries = [
+        ("file1.txt", b"Hello, slice!" as &[u8]),
+        ("dir/file2.txt", b"Borrowed, not copied"),
+    ];
+    let archive_bytes = common::create_archive(&entries).expect("Failed to create test archive");
+
+    {
+        let mut archive = Archive::open_slice(&archive_bytes).expect("Failed to open slice");
+        assert_eq!(archive.entries().len(), 2);
+        for (path, expected) in &entries {
+            assert_eq!(archive.extract_to_vec(path).unwrap(), *expected);
+        }
+    }
+
+    // The archive only borrowed the bytes; they remain usable afterwards
+    common::verify_archive_contents(&archive_bytes, &entries);
+}
+
 /// Tests that extraction produces correct results (verifies content correctness).
 ///
 /// This test uses parallel extraction to verify content integrity after extraction.
 /// A separate single-threaded test was removed as redundant - content correctness
 /// is thread-agnostic (thread count doesn't affect data integrity). Thread-safety
 /// testing requires data race detection tools, not content verification.
+#[test]
+fn test_create_streaming_to_write_only_sink() {
+    /// A sink that only implements `Write`, like a pipe or socket.
+    struct WriteOnly(Vec<u8>);
+
+    impl std::io::Write for WriteOnly {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let entries: [(&str, &[u8]); 3] = [
+        ("a.txt", b"first file"),
+        ("dir/b.txt", b"second file contents"),
+        ("empty.txt", b""),
+    ];
+
+    let mut output = WriteOnly(Vec::new());
+    let mut writer = Writer::create_streaming(&mut output).unwrap();
+    for (name, data) in entries {
+        writer
+            .add_bytes(ArchivePath::new(name).unwrap(), data)
+            .unwrap();
+    }
+    let result = writer.finish().unwrap();
+    assert_eq!(result.entries_written, 3);
+
+    common::verify_archive_contents(&output.0, &entries);
+}
+
+#[test]
+fn test_deterministic_output_uses_serial_lzma2_by_default() {
+    use zesven::WriteOptions;
+
+    let large: Vec<u8> = (0..300_000u32)
+        .flat_map(|i| (i % 977).to_le_bytes())
+        .collect();
+    let entries: [(&str, &[u8]); 1] = [("large.bin", &large)];
+
+    // The default must not depend on the machine's CPU count
+    let options = || {
+        WriteOptions::new()
+            .deterministic(true)
+            .lzma2_block_size(64 * 1024)
+    };
+    let default = common::create_archive_with_options(options(), &entries).unwrap();
+    let serial =
+        common::create_archive_with_options(options().parallel_lzma2(false), &entries).unwrap();
+    assert_eq!(default, serial);
+    common::verify_archive_contents(&default, &entries);
+
+    #[cfg(feature = "parallel")]
+    {
+        let parallel =
+            common::create_archive_with_options(options().parallel_lzma2(true), &entries).unwrap();
+        assert_ne!(default, parallel);
+    }
+}
+
+#[test]
+fn test_add_reader_round_trip() {
+    use zesven::WriteOptions;
+    use zesven::write::{EntryMeta, WriteFilter};
+
+    let large: Vec<u8> = (0..300_000u32)
+        .flat_map(|i| (i % 977).to_le_bytes())
+        .collect();
+    let entries: [(&str, &[u8]); 3] = [
+        ("large.bin", &large),
+        ("small.txt", b"a few bytes"),
+        ("empty.txt", b""),
+    ];
+
+    // Streamed non-solid (serially and in parallel LZMA2 blocks), and the
+    // buffered fallbacks for solid and filtered
+    for options in [
+        WriteOptions::new().parallel_lzma2(false),
+        WriteOptions::new()
+            .parallel_lzma2(true)
+            .lzma2_block_size(64 * 1024),
+        WriteOptions::new().solid(),
+        WriteOptions::new().filter(WriteFilter::BcjX86),
+    ] {
+        let mut archive_bytes = Vec::new();
+        let mut writer = Writer::create(Cursor::new(&mut archive_bytes))
+            .unwrap()
+            .options(options);
+        for (name, data) in entries {
+            writer
+                .add_reader(
+                    ArchivePath::new(name).unwrap(),
+                    data,
+                    data.len() as u64,
+                    EntryMeta::default(),
+                )
+                .unwrap();
+        }
+        let result = writer.finish().unwrap();
+        assert_eq!(result.total_size, (large.len() + 11) as u64);
+        common::verify_archive_contents(&archive_bytes, &entries);
+    }
+
+    // A reader shorter than the declared size is an error
+    let mut writer = Writer::create(Cursor::new(Vec::new())).unwrap();
+    let err = writer
+        .add_reader(
+            ArchivePath::new("short.bin").unwrap(),
+            &b"short"[..],
+            100,
+            EntryMeta::default(),
+        )
+        .unwrap_err();
+    assert!(
+        matches!(&err, zesven::Error::Io(e) if e.kind() == std::io::ErrorKind::UnexpectedEof),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn test_add_reader_failure_does_not_corrupt_archive() {
+    use std::io::Read;
+    use zesven::WriteOptions;
+    use zesven::codec::CodecMethod;
+    use zesven::write::EntryMeta;
+
+    /// Yields `len` bytes, then fails.
+    struct FailingReader {
+        len: usize,
+    }
+
+    impl Read for FailingReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.len == 0 {
+                return Err(std::io::Error::other("source failed"));
+            }
+            let n = buf.len().min(self.len);
+            buf[..n].fill(0x5A);
+            self.len -= n;
+            Ok(n)
+        }
+    }
+
+    let options = WriteOptions::new().method(CodecMethod::Copy);
+    let entries: [(&str, &[u8]); 1] = [("after.txt", b"added after the failure")];
+
+    // Failing before any packed data is written leaves the writer usable
+    let mut archive_bytes = Vec::new();
+    let mut writer = Writer::create(Cursor::new(&mut archive_bytes))
+        .unwrap()
+        .options(options.clone());
+    let reader = FailingReader { len: 0 };
+    assert!(
+        writer
+            .add_reader(
+                ArchivePath::new("failed.bin").unwrap(),
+                reader,
+                100_000,
+                EntryMeta::default()
+            )
+            .is_err()
+    );
+    writer
+        .add_bytes(ArchivePath::new("after.txt").unwrap(), entries[0].1)
+        .unwrap();
+    let _ = writer.finish().unwrap();
+    common::verify_archive_contents(&archive_bytes, &entries);
+
+    // Failing after part of the packed stream reached the sink refuses
+    // further entries and finishing, instead of writing a corrupt archive
+    let mut writer = Writer::create(Cursor::new(Vec::new()))
+        .unwrap()
+        .options(options);
+    let reader = FailingReader { len: 50_000 };
+    assert!(
+        writer
+            .add_reader(
+                ArchivePath::new("failed.bin").unwrap(),
+                reader,
+                100_000,
+                EntryMeta::default()
+            )
+            .is_err()
+    );
+    assert!(
+        writer
+            .add_bytes(ArchivePath::new("after.txt").unwrap(), entries[0].1)
+            .is_err()
+    );
+    assert!(writer.finish().is_err());
+}
+
 #[test]
 fn test_parallel_extraction_produces_correct_results() {
     use zesven::read::{ExtractOptions, Threads};
@@ -181,3 +932,1413 @@ fn test_parallel_extraction_produces_correct_results() {
         );
     }
 }
+
+#[test]
+fn test_header_first_layout() {
+    use zesven::WriteOptions;
+
+    let big = b"header-first payload ".repeat(500);
+    let entries = [
+        ("a.txt", b"first entry".as_slice()),
+        ("dir/b.bin", big.as_slice()),
+        ("empty.txt", b"".as_slice()),
+    ];
+
+    for options in [
+        WriteOptions::new().header_first(true),
+        WriteOptions::new().header_first(true).solid(),
+    ] {
+        let archive_bytes = common::create_archive_with_options(options, &entries)
+            .expect("Failed to create header-first archive");
+
+        // Next header offset is 0: the header immediately follows the signature header
+        let next_header_offset = u64::from_le_bytes(archive_bytes[12..20].try_into().unwrap());
+        let next_header_size = u64::from_le_bytes(archive_bytes[20..28].try_into().unwrap());
+        assert_eq!(next_header_offset, 0);
+        assert_eq!(
+            archive_bytes[32], 0x01,
+            "plain header should follow signature"
+        );
+        assert!(32 + (next_header_size as usize) < archive_bytes.len());
+
+        common::verify_archive_contents(&archive_bytes, &entries);
+    }
+}
+
+#[test]
+fn test_estimate_extract_time_scales_with_size() {
+    use std::time::Duration;
+
+    let chunk = b"estimate me ".repeat(1000);
+    let estimates: Vec<Duration> = [1, 2, 4]
+        .into_iter()
+        .map(|copies| {
+            let data = chunk.repeat(copies);
+            let archive_bytes = common::create_archive(&[("data.bin", data.as_slice())]).unwrap();
+            let archive = Archive::open(Cursor::new(archive_bytes)).unwrap();
+            assert_eq!(archive.info().total_size, data.len() as u64);
+            archive.estimate_extract_time(1_000_000.0)
+        })
+        .collect();
+
+    assert_eq!(estimates[0], Duration::from_micros(chunk.len() as u64));
+    assert_eq!(estimates[1], estimates[0] * 2);
+    assert_eq!(estimates[2], estimates[0] * 4);
+
+    // Measured throughput of the archive itself gives a usable estimate
+    let archive_bytes = common::create_archive(&[("data.bin", chunk.as_slice())]).unwrap();
+    let mut archive = Archive::open(Cursor::new(archive_bytes)).unwrap();
+    let throughput = archive.benchmark_throughput(64 * 1024).unwrap();
+    assert!(throughput > 0.0, "throughput {}", throughput);
+    assert!(archive.estimate_extract_time(throughput) < Duration::from_secs(60));
+    assert_eq!(archive.estimate_extract_time(0.0), Duration::MAX);
+
+    // Nothing to extract takes no time
+    let archive_bytes = common::create_archive_with_result(None, &[]).unwrap().0;
+    let mut archive = Archive::open(Cursor::new(archive_bytes)).unwrap();
+    assert_eq!(archive.benchmark_throughput(1024).unwrap(), f64::INFINITY);
+    assert_eq!(archive.estimate_extract_time(1.0), Duration::ZERO);
+}
+
+#[test]
+fn test_folder_members() {
+    use zesven::WriteOptions;
+    use zesven::format::parser::read_archive_header;
+
+    let entries = [
+        ("a.txt", b"first entry".as_slice()),
+        ("b.txt", b"second entry".as_slice()),
+        ("empty.txt", b"".as_slice()),
+        ("c.txt", b"third entry".as_slice()),
+    ];
+
+    let archive_bytes =
+        common::create_archive_with_options(WriteOptions::new().solid(), &entries).unwrap();
+    let archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+    let members = archive.folder_members();
+    assert_eq!(members, [vec![0, 1, 3]]);
+
+    let (_, header) = read_archive_header(&mut Cursor::new(&archive_bytes), None).unwrap();
+    let streams_per_folder: Vec<usize> = header
+        .substreams_info
+        .unwrap()
+        .num_unpack_streams_in_folders
+        .iter()
+        .map(|&n| n as usize)
+        .collect();
+    let members_per_folder: Vec<usize> = members.iter().map(Vec::len).collect();
+    assert_eq!(members_per_folder, streams_per_folder);
+
+    let archive_bytes = common::create_archive_with_options(WriteOptions::new(), &entries).unwrap();
+    let archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+    assert_eq!(archive.folder_members(), [vec![0], vec![1], vec![3]]);
+}
+
+/// Builds an archive whose files alternate between Copy and LZMA2 folders.
+///
+/// The writer uses one method for the whole archive, so the LZMA2 folders are
+/// taken from a writer-produced archive and the header is assembled by hand.
+#[cfg(feature = "lzma2")]
+fn build_mixed_copy_lzma2_archive(copy_files: &[&[u8]], lzma2_files: &[&[u8]]) -> Vec<u8> {
+    use common::{push_names_property, push_variable_u64, wrap_raw_header};
+    use zesven::format::parser::read_archive_header;
+
+    let lzma2_entries: Vec<(String, &[u8])> = lzma2_files
+        .iter()
+        .enumerate()
+        .map(|(i, data)| (format!("{i}"), *data))
+        .collect();
+    let lzma2_entries: Vec<(&str, &[u8])> = lzma2_entries
+        .iter()
+        .map(|(name, data)| (name.as_str(), *data))
+        .collect();
+    let lzma2_archive = common::create_archive(&lzma2_entries).unwrap();
+    let (_, header) = read_archive_header(&mut Cursor::new(&lzma2_archive), None).unwrap();
+    let lzma2_pack_sizes = &header.pack_info.as_ref().unwrap().pack_sizes;
+    let lzma2_folders = header.folders();
+
+    // Folders alternate Copy, LZMA2, Copy, LZMA2, ...
+    let mut packed = Vec::new();
+    let mut pack_sizes = Vec::new();
+    let mut folders: Vec<(Vec<u8>, u64)> = Vec::new(); // (coder record, unpack size)
+    let mut lzma2_offset = 32;
+    for i in 0..copy_files.len().max(lzma2_files.len()) {
+        if let Some(data) = copy_files.get(i) {
+            packed.extend_from_slice(data);
+            pack_sizes.push(data.len() as u64);
+            folders.push((vec![0x01, 0x01, 0x00], data.len() as u64));
+        }
+        if let Some(data) = lzma2_files.get(i) {
+            let size = lzma2_pack_sizes[i] as usize;
+            packed.extend_from_slice(&lzma2_archive[lzma2_offset..lzma2_offset + size]);
+            lzma2_offset += size;
+            pack_sizes.push(size as u64);
+            let props = lzma2_folders[i].coders[0].properties.as_deref().unwrap();
+            let mut coder = vec![0x01, 0x21, 0x21, props.len() as u8];
+            coder.extend_from_slice(props);
+            folders.push((coder, data.len() as u64));
+        }
+    }
+
+    let mut h = vec![0x01, 0x04]; // kHeader, kMainStreamsInfo
+
+    // PackInfo: pack_pos = 0, one pack stream per folder
+    h.extend_from_slice(&[0x06, 0x00]);
+    push_variable_u64(&mut h, pack_sizes.len() as u64);
+    h.push(0x09);
+    for size in &pack_sizes {
+        push_variable_u64(&mut h, *size);
+    }
+    h.push(0x00);
+
+    // UnpackInfo: one single-coder folder per file
+    h.extend_from_slice(&[0x07, 0x0B]);
+    push_variable_u64(&mut h, folders.len() as u64);
+    h.push(0x00);
+    for (coder, _) in &folders {
+        h.extend_from_slice(coder);
+    }
+    h.push(0x0C);
+    for (_, size) in &folders {
+        push_variable_u64(&mut h, *size);
+    }
+    h.extend_from_slice(&[0x00, 0x00]); // end UnpackInfo, end MainStreamsInfo
+
+    let names: Vec<String> = (0..folders.len()).map(|i| format!("file{i}")).collect();
+    let names: Vec<&str> = names.iter().map(String::as_str).collect();
+    h.push(0x05);
+    push_variable_u64(&mut h, names.len() as u64);
+    push_names_property(&mut h, &names);
+    h.extend_from_slice(&[0x00, 0x00]); // end FilesInfo, end Header
+
+    wrap_raw_header(&packed, &h)
+}
+
+#[test]
+#[cfg(feature = "lzma2")]
+fn test_codec_breakdown_mixed_folders() {
+    let text = b"codec breakdown test data ".repeat(200);
+    let copy_files: [&[u8]; 2] = [b"stored as is", &[0xAB; 300]];
+    let lzma2_files: [&[u8]; 3] = [&text, &text[..1000], &text[..2600]];
+    let archive_bytes = build_mixed_copy_lzma2_archive(&copy_files, &lzma2_files);
+
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+    assert_eq!(archive.extract_to_vec("file1").unwrap(), text);
+    assert_eq!(archive.extract_to_vec("file2").unwrap(), [0xAB; 300]);
+
+    let summaries = archive.folder_summaries();
+    let breakdown = archive.codec_breakdown();
+    assert_eq!(
+        breakdown
+            .iter()
+            .map(|s| s.method.as_str())
+            .collect::<Vec<_>>(),
+        ["Copy", "LZMA2"]
+    );
+
+    for stat in &breakdown {
+        let folders: Vec<_> = summaries
+            .iter()
+            .filter(|s| s.methods == stat.method)
+            .collect();
+        assert_eq!(stat.folder_count, folders.len());
+        assert_eq!(
+            stat.packed_size,
+            folders.iter().map(|s| s.packed_size).sum::<u64>()
+        );
+        assert_eq!(
+            stat.unpacked_size,
+            folders.iter().map(|s| s.unpacked_size).sum::<u64>()
+        );
+    }
+
+    let copy = &breakdown[0];
+    assert_eq!(copy.folder_count, 2);
+    assert_eq!(copy.packed_size, 12 + 300);
+    assert_eq!(copy.unpacked_size, 12 + 300);
+    let lzma2 = &breakdown[1];
+    assert_eq!(lzma2.folder_count, 3);
+    assert_eq!(lzma2.unpacked_size, (text.len() + 1000 + 2600) as u64);
+    assert!(lzma2.packed_size < lzma2.unpacked_size);
+    assert_eq!(
+        breakdown.iter().map(|s| s.packed_size).sum::<u64>(),
+        archive.info().packed_size
+    );
+}
+
+#[test]
+#[cfg(feature = "lzma2")]
+fn test_packed_folder_bytes_mixed_folders() {
+    let text = b"packed folder bytes test data ".repeat(200);
+    let copy_files: [&[u8]; 2] = [b"stored as is", &[0xAB; 300]];
+    let lzma2_files: [&[u8]; 2] = [&text, &text[..1000]];
+    let archive_bytes = build_mixed_copy_lzma2_archive(&copy_files, &lzma2_files);
+
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+    assert_eq!(archive.folder_count(), 4);
+    assert_eq!(archive.folder_count(), archive.info().folder_count);
+
+    let summaries = archive.folder_summaries();
+    for (i, summary) in summaries.iter().enumerate() {
+        let bytes = archive.packed_folder_bytes(i, u64::MAX).unwrap();
+        assert_eq!(bytes.len() as u64, summary.packed_size, "folder {i}");
+    }
+
+    // Copy folders are stored verbatim
+    let first_copy = summaries.iter().position(|s| s.methods == "Copy").unwrap();
+    assert_eq!(
+        archive.packed_folder_bytes(first_copy, u64::MAX).unwrap(),
+        copy_files[0]
+    );
+
+    let size = summaries[0].packed_size;
+    assert!(matches!(
+        archive.packed_folder_bytes(0, size - 1),
+        Err(zesven::Error::ResourceLimitExceeded(_))
+    ));
+    assert_eq!(
+        archive.packed_folder_bytes(0, size).unwrap().len() as u64,
+        size
+    );
+    assert!(archive.packed_folder_bytes(4, u64::MAX).is_err());
+}
+
+#[test]
+fn test_pack_alignment() {
+    use zesven::WriteOptions;
+    use zesven::format::parser::read_archive_header;
+
+    const ALIGNMENT: u64 = 512;
+
+    let big = b"aligned payload ".repeat(300);
+    let entries = [
+        ("a.txt", b"first entry".as_slice()),
+        ("dir/b.bin", big.as_slice()),
+        ("empty.txt", b"".as_slice()),
+        ("c.txt", b"third entry".as_slice()),
+    ];
+
+    for options in [
+        WriteOptions::new(),
+        WriteOptions::new().header_first(true),
+        WriteOptions::new().solid().header_first(true),
+    ] {
+        let options = options.pack_alignment(ALIGNMENT as u32);
+        let archive_bytes = common::create_archive_with_options(options, &entries)
+            .expect("Failed to create aligned archive");
+
+        let (_, header) = read_archive_header(&mut Cursor::new(&archive_bytes), None).unwrap();
+        let pack_info = header.pack_info.unwrap();
+        let folders = header.unpack_info.unwrap().folders;
+        let streams = header
+            .substreams_info
+            .unwrap()
+            .num_unpack_streams_in_folders;
+
+        // Every folder holding entries starts on an aligned offset
+        let mut offset = 32 + pack_info.pack_pos;
+        let mut pack_idx = 0;
+        for (folder, &num_streams) in folders.iter().zip(&streams) {
+            if num_streams > 0 {
+                assert_eq!(offset % ALIGNMENT, 0, "folder starts at {}", offset);
+            }
+            let num_packed = folder.packed_streams.len();
+            offset += pack_info.pack_sizes[pack_idx..pack_idx + num_packed]
+                .iter()
+                .sum::<u64>();
+            pack_idx += num_packed;
+        }
+
+        common::verify_archive_contents(&archive_bytes, &entries);
+    }
+
+    // Setting the field directly to 0 or 1 disables alignment too
+    for alignment in [0, 1] {
+        for header_first in [false, true] {
+            let mut options = WriteOptions::new().header_first(header_first);
+            options.pack_alignment = Some(alignment);
+            let archive_bytes = common::create_archive_with_options(options, &entries).unwrap();
+            common::verify_archive_contents(&archive_bytes, &entries);
+        }
+    }
+}
+
+/// Splits an LZMA2 stream into segments that start with a dictionary reset.
+fn lzma2_reset_segments(stream: &[u8]) -> Vec<&[u8]> {
+    let mut segments = Vec::new();
+    let mut start = 0;
+    let mut pos = 0;
+    while pos < stream.len() && stream[pos] != 0x00 {
+        let control = stream[pos];
+        if (control == 0x01 || control >= 0xE0) && pos > start {
+            segments.push(&stream[start..pos]);
+            start = pos;
+        }
+        pos += match control {
+            0x01 | 0x02 => 3 + u16::from_be_bytes([stream[pos + 1], stream[pos + 2]]) as usize + 1,
+            0x80.. => {
+                let packed = u16::from_be_bytes([stream[pos + 3], stream[pos + 4]]) as usize + 1;
+                let props = usize::from(control >= 0xC0);
+                5 + props + packed
+            }
+            _ => panic!("invalid LZMA2 control byte {:#04x}", control),
+        };
+    }
+    segments.push(&stream[start..pos]);
+    segments
+}
+
+#[test]
+fn test_cdc_chunking_shares_chunks_across_versions() {
+    use zesven::format::parser::read_archive_header;
+    use zesven::write::{CdcConfig, WriteOptions};
+
+    const WORDS: [&str; 8] = [
+        "alpha ", "beta ", "gamma ", "delta ", "epsilon ", "zeta ", "eta ", "theta\n",
+    ];
+    let mut state: u64 = 7;
+    let mut original = Vec::new();
+    while original.len() < 256 * 1024 {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        original.extend_from_slice(WORDS[(state >> 61) as usize].as_bytes());
+    }
+    let mut edited = original.clone();
+    edited.splice(128_000..128_000, b"a small edit".iter().copied());
+
+    let options = WriteOptions::new()
+        .level_clamped(1)
+        .cdc_chunking(Some(CdcConfig::new(4 * 1024, 16 * 1024, 64 * 1024)));
+    let packed_segments = |data: &[u8]| {
+        let entries = [("large.txt", data)];
+        let archive_bytes = common::create_archive_with_options(options.clone(), &entries).unwrap();
+        common::verify_archive_contents(&archive_bytes, &entries);
+
+        let (_, header) = read_archive_header(&mut Cursor::new(&archive_bytes), None).unwrap();
+        let pack_size = header.pack_info.unwrap().pack_sizes[0] as usize;
+        let packed = archive_bytes[32..32 + pack_size].to_vec();
+        lzma2_reset_segments(&packed)
+            .into_iter()
+            .map(<[u8]>::to_vec)
+            .collect::<Vec<_>>()
+    };
+
+    let before = packed_segments(&original);
+    let after = packed_segments(&edited);
+    assert!(before.len() >= 8, "only {} chunks", before.len());
+
+    // Only the chunks around the edit differ
+    let shared = after.iter().filter(|s| before.contains(s)).count();
+    assert!(
+        shared * 10 >= after.len() * 8,
+        "{} of {} chunks shared",
+        shared,
+        after.len()
+    );
+}
+
+#[test]
+fn test_seekable_entry_reader() {
+    use std::io::{Read, Seek, SeekFrom};
+    use zesven::Error;
+    use zesven::write::{CdcConfig, WriteOptions};
+
+    let mut state: u64 = 11;
+    let mut data = Vec::new();
+    while data.len() < 256 * 1024 {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        data.extend_from_slice(format!("line {} {}\n", data.len(), state >> 48).as_bytes());
+    }
+
+    let options = WriteOptions::new()
+        .level_clamped(1)
+        .cdc_chunking(Some(CdcConfig::new(4 * 1024, 16 * 1024, 64 * 1024)));
+    let archive_bytes =
+        common::create_archive_with_options(options, &[("large.txt", data.as_slice())]).unwrap();
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+    let mut reader = archive.open_entry_reader("large.txt").unwrap();
+    assert_eq!(reader.size(), data.len() as u64);
+    assert!(reader.checkpoint_count() >= 4);
+
+    let read_at = |reader: &mut zesven::read::SeekableEntryReader, pos: SeekFrom, len| {
+        let start = reader.seek(pos).unwrap() as usize;
+        let mut buf = vec![0u8; len];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, &data[start..start + len], "read at {}", start);
+    };
+
+    // Forward, backward, relative and end-relative seeks
+    read_at(&mut reader, SeekFrom::Start(200_000), 5_000);
+    read_at(&mut reader, SeekFrom::Start(10_000), 100_000);
+    read_at(&mut reader, SeekFrom::Current(-50_000), 1_000);
+    read_at(&mut reader, SeekFrom::Current(30_000), 1_000);
+    read_at(&mut reader, SeekFrom::End(-100), 100);
+    read_at(&mut reader, SeekFrom::Start(0), 16);
+
+    // Reads stop at the end of the entry
+    reader.seek(SeekFrom::End(0)).unwrap();
+    assert_eq!(reader.read(&mut [0u8; 16]).unwrap(), 0);
+    assert!(
+        reader
+            .seek(SeekFrom::Current(-(data.len() as i64) - 1))
+            .is_err()
+    );
+
+    let mut whole = Vec::new();
+    reader.rewind().unwrap();
+    reader.read_to_end(&mut whole).unwrap();
+    assert_eq!(whole, data);
+
+    // Entries sharing a solid block are not supported
+    let archive_bytes = common::create_archive_with_options(
+        WriteOptions::new().solid(),
+        &[
+            ("a.txt", b"first".as_slice()),
+            ("b.txt", b"second".as_slice()),
+        ],
+    )
+    .unwrap();
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+    assert!(matches!(
+        archive.open_entry_reader("a.txt"),
+        Err(Error::UnsupportedFeature { .. })
+    ));
+}
+
+#[test]
+fn test_seekable_entry_reader_limits() {
+    use std::io::Read;
+    use zesven::{Error, RatioLimit, ResourceLimits};
+
+    let data = vec![b'z'; 100_000];
+    let archive_bytes = common::create_archive(&[("zeros.bin", data.as_slice())]).unwrap();
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+
+    let limits = ResourceLimits::new().max_entry_unpacked(50_000);
+    let err = common::expect_err(archive.open_entry_reader_with_limits("zeros.bin", &limits));
+    assert!(
+        matches!(&err, Error::ResourceLimitExceeded(msg) if msg.contains("size 100000")),
+        "{err:?}"
+    );
+
+    let limits = ResourceLimits::new().ratio_limit(Some(RatioLimit::new(10)));
+    let err = common::expect_err(archive.open_entry_reader_with_limits("zeros.bin", &limits));
+    assert!(matches!(err, Error::ResourceLimitExceeded(_)), "{err:?}");
+
+    let limits = ResourceLimits::new().ratio_limit(None);
+    let mut reader = archive
+        .open_entry_reader_with_limits("zeros.bin", &limits)
+        .unwrap();
+    let mut whole = Vec::new();
+    reader.read_to_end(&mut whole).unwrap();
+    assert_eq!(whole, data);
+}
+
+#[test]
+fn test_backslash_in_name_preserved() {
+    let entries = [("dir\\file.txt", b"backslash content".as_slice())];
+    let archive_bytes = common::create_archive(&entries).expect("Failed to create test archive");
+
+    let archive = Archive::open(Cursor::new(&archive_bytes)).expect("Failed to open archive");
+    assert_eq!(archive.entries().len(), 1);
+    assert_eq!(archive.entries()[0].path.as_str(), "dir\\file.txt");
+
+    common::verify_archive_contents(&archive_bytes, &entries);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_backslash_policy_on_extraction() {
+    use zesven::read::{BackslashPolicy, ExtractOptions};
+
+    let archive_bytes = common::create_archive(&[
+        ("dir\\file.txt", b"content".as_slice()),
+        ("up\\..\\..\\escape.txt", b"evil".as_slice()),
+    ])
+    .expect("Failed to create test archive");
+
+    // Default: the backslash is a literal filename character
+    let temp_dir = tempfile::tempdir().unwrap();
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+    let result = archive
+        .extract(temp_dir.path(), (), &ExtractOptions::new())
+        .unwrap();
+    assert_eq!(result.entries_extracted, 2);
+    assert!(temp_dir.path().join("dir\\file.txt").is_file());
+    assert!(!temp_dir.path().join("dir").exists());
+
+    // Separator: the backslash becomes a directory, traversal is rejected
+    let temp_dir = tempfile::tempdir().unwrap();
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+    let options = ExtractOptions::new().backslashes(BackslashPolicy::Separator);
+    let result = archive.extract(temp_dir.path(), (), &options).unwrap();
+    assert_eq!(result.entries_extracted, 1);
+    assert_eq!(result.entries_failed, 1);
+    assert_eq!(
+        std::fs::read(temp_dir.path().join("dir").join("file.txt")).unwrap(),
+        b"content"
+    );
+    assert!(
+        !temp_dir
+            .path()
+            .parent()
+            .unwrap()
+            .join("escape.txt")
+            .exists()
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_control_chars_in_entry_names() {
+    use zesven::read::{ExtractOptions, PathSafety};
+
+    let archive_bytes = common::create_archive(&[
+        ("plain.txt", b"plain".as_slice()),
+        ("evil\n\x1b[31m.txt", b"evil".as_slice()),
+    ])
+    .expect("Failed to create test archive");
+
+    // Default (strict path safety): control characters become '_'
+    let temp_dir = tempfile::tempdir().unwrap();
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+    let result = archive
+        .extract(temp_dir.path(), (), &ExtractOptions::new())
+        .unwrap();
+    assert_eq!(result.entries_extracted, 2);
+    assert_eq!(
+        std::fs::read(temp_dir.path().join("plain.txt")).unwrap(),
+        b"plain"
+    );
+    assert_eq!(
+        std::fs::read(temp_dir.path().join("evil__[31m.txt")).unwrap(),
+        b"evil"
+    );
+    assert!(!temp_dir.path().join("evil\n\x1b[31m.txt").exists());
+
+    // Strict mode: the entry is rejected
+    let temp_dir = tempfile::tempdir().unwrap();
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+    let err = archive
+        .extract(temp_dir.path(), (), &ExtractOptions::new().strict(true))
+        .unwrap_err();
+    assert!(matches!(err, zesven::Error::Strict(_)), "{err:?}");
+    assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+
+    // Disabled: names are kept verbatim
+    let temp_dir = tempfile::tempdir().unwrap();
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+    let options = ExtractOptions::new()
+        .path_safety(PathSafety::Relaxed)
+        .sanitize_control_chars(false);
+    let result = archive.extract(temp_dir.path(), (), &options).unwrap();
+    assert_eq!(result.entries_extracted, 2);
+    assert!(temp_dir.path().join("evil\n\x1b[31m.txt").is_file());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_strict_extraction_fails_on_rejected_entry() {
+    use zesven::Error;
+    use zesven::read::{BackslashPolicy, ExtractOptions};
+
+    let archive_bytes = common::create_archive(&[
+        ("ok.txt", b"content".as_slice()),
+        ("up\\..\\..\\escape.txt", b"evil".as_slice()),
+    ])
+    .expect("Failed to create test archive");
+    let options = ExtractOptions::new().backslashes(BackslashPolicy::Separator);
+
+    // Lenient: the rejected entry is reported and extraction succeeds
+    let temp_dir = tempfile::tempdir().unwrap();
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+    let result = archive.extract(temp_dir.path(), (), &options).unwrap();
+    assert_eq!(result.entries_extracted, 1);
+    assert_eq!(result.entries_failed, 1);
+
+    // Strict: the same entry aborts extraction
+    let temp_dir = tempfile::tempdir().unwrap();
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+    let err = common::expect_err(archive.extract(temp_dir.path(), (), &options.strict(true)));
+    match err {
+        Error::Strict(message) => assert!(message.contains("escape.txt"), "{}", message),
+        other => panic!("expected strict error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_strict_extraction_fails_on_skipped_existing_file() {
+    use zesven::Error;
+    use zesven::read::{ExtractOptions, OverwritePolicy};
+
+    let archive_bytes = common::create_archive(&[("existing.txt", b"archived".as_slice())])
+        .expect("Failed to create test archive");
+    let temp_dir = tempfile::tempdir().unwrap();
+    std::fs::write(temp_dir.path().join("existing.txt"), b"local").unwrap();
+    let options = ExtractOptions::new().overwrite(OverwritePolicy::Skip);
+
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+    let result = archive.extract(temp_dir.path(), (), &options).unwrap();
+    assert_eq!(result.entries_skipped, 1);
+
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+    let err = common::expect_err(archive.extract(temp_dir.path(), (), &options.strict(true)));
+    match err {
+        Error::Strict(message) => assert!(message.contains("existing.txt"), "{}", message),
+        other => panic!("expected strict error, got {:?}", other),
+    }
+    assert_eq!(
+        std::fs::read(temp_dir.path().join("existing.txt")).unwrap(),
+        b"local"
+    );
+}
+
+#[test]
+fn test_strict_extraction_enforces_warn_ratio_limit() {
+    use zesven::read::ExtractOptions;
+    use zesven::{Error, LimitMode, RatioLimit, ResourceLimits};
+
+    let data = vec![b'z'; 100_000];
+    let archive_bytes = common::create_archive(&[("zeros.bin", data.as_slice())]).unwrap();
+    let limits = ResourceLimits::new().ratio_limit(Some(RatioLimit::new(10).mode(LimitMode::Warn)));
+    let options = ExtractOptions::new().limits(limits);
+
+    // Lenient: the ratio is only logged and the entry is extracted
+    let temp_dir = tempfile::tempdir().unwrap();
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+    let result = archive.extract(temp_dir.path(), (), &options).unwrap();
+    assert_eq!(result.entries_extracted, 1);
+    assert_eq!(
+        std::fs::read(temp_dir.path().join("zeros.bin")).unwrap(),
+        data
+    );
+
+    // Strict: the same ratio aborts extraction
+    let temp_dir = tempfile::tempdir().unwrap();
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+    let err = common::expect_err(archive.extract(temp_dir.path(), (), &options.strict(true)));
+    match err {
+        Error::Strict(message) => assert!(message.contains("ratio"), "{}", message),
+        other => panic!("expected strict error, got {:?}", other),
+    }
+}
+
+/// Fixture with `symlink_test/target.txt` and relative, absolute and
+/// traversing symlinks next to it.
+const SYMLINK_FIXTURE: &str = "tests/fixtures/symlink_test.7z";
+
+#[test]
+fn test_extract_to_in_memory_vfs() {
+    use std::path::PathBuf;
+    use zesven::read::{ExtractOptions, InMemoryVfs, LinkPolicy, VfsNode};
+
+    let mut archive = Archive::open_path(SYMLINK_FIXTURE).unwrap();
+
+    // Expected tree, built from the archive's own entries
+    let mut expected = Vec::new();
+    for entry in archive.entries().to_vec() {
+        let node = if entry.is_directory {
+            VfsNode::Directory
+        } else {
+            let data = archive.extract_to_vec(entry.path.as_str()).unwrap();
+            if entry.is_symlink {
+                VfsNode::Symlink(String::from_utf8(data).unwrap())
+            } else {
+                VfsNode::File(data)
+            }
+        };
+        expected.push((PathBuf::from(entry.path.as_str()), node));
+    }
+    expected.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut vfs = InMemoryVfs::new();
+    let options = ExtractOptions::new().link_policy(LinkPolicy::Allow);
+    let result = archive.extract_to_vfs(&mut vfs, (), &options).unwrap();
+    assert_eq!(result.entries_failed, 0);
+
+    let tree: Vec<(PathBuf, VfsNode)> = vfs
+        .entries()
+        .iter()
+        .map(|(path, entry)| (path.clone(), entry.node.clone()))
+        .collect();
+    assert_eq!(tree, expected);
+    assert_eq!(
+        vfs.get("symlink_test/relative_link.txt").unwrap().node,
+        VfsNode::Symlink("target.txt".into())
+    );
+
+    // Symlinks are skipped under the default policy
+    let mut vfs = InMemoryVfs::new();
+    let result = archive
+        .extract_to_vfs(&mut vfs, (), &ExtractOptions::default())
+        .unwrap();
+    assert_eq!(result.entries_failed, 0);
+    assert_eq!(result.entries_skipped, 3);
+    assert!(vfs.get("symlink_test/relative_link.txt").is_none());
+    assert!(vfs.file("symlink_test/target.txt").is_some());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_extract_to_vfs_filesystem_destination() {
+    use zesven::read::{ExtractOptions, FilesystemDestination, LinkPolicy};
+
+    let mut archive = Archive::open_path(SYMLINK_FIXTURE).unwrap();
+    let temp_dir = tempfile::tempdir().unwrap();
+    let mut dest = FilesystemDestination::new(temp_dir.path());
+    let options = ExtractOptions::new().link_policy(LinkPolicy::ValidateTargets);
+    let result = archive.extract_to_vfs(&mut dest, (), &options).unwrap();
+
+    // The absolute and traversing links fail validation
+    assert_eq!(result.entries_failed, 2);
+    let dir = temp_dir.path().join("symlink_test");
+    assert!(dir.join("target.txt").is_file());
+    assert_eq!(
+        std::fs::read_link(dir.join("relative_link.txt")).unwrap(),
+        std::path::Path::new("target.txt")
+    );
+    assert!(!dir.join("absolute_link.txt").exists());
+}
+
+/// Builds an archive with `data/target.txt`, a chain of links to it and a
+/// link escaping the extraction directory, marked with the Unix symlink mode.
+#[cfg(unix)]
+fn create_archive_with_symlinks() -> Vec<u8> {
+    // Unix symlink mode in the high 16 bits, with the Unix extension flag
+    const SYMLINK_ATTRIBUTES: u32 = (0o120777 << 16) | 0x8000;
+    const FILE_ATTRIBUTES: u32 = 0x20;
+
+    create_archive_with_attributes(&[
+        ("data/target.txt", b"linked content", FILE_ATTRIBUTES),
+        ("data/link.txt", b"target.txt", SYMLINK_ATTRIBUTES),
+        ("links/chained.txt", b"../data/link.txt", SYMLINK_ATTRIBUTES),
+        ("links/escape.txt", b"../../outside.txt", SYMLINK_ATTRIBUTES),
+    ])
+}
+
+/// Builds an archive of `(name, data, attributes)` entries.
+///
+/// The writer does not store attributes, so the header is built by hand: one
+/// Copy folder holding every entry, and a `kWinAttributes` property with the
+/// given attributes.
+fn create_archive_with_attributes(entries: &[(&str, &[u8], u32)]) -> Vec<u8> {
+    use common::{push_names_property, push_variable_u64, wrap_raw_header};
+
+    let packed: Vec<u8> = entries
+        .iter()
+        .flat_map(|(_, d, _)| d.iter().copied())
+        .collect();
+
+    let mut h = vec![0x01, 0x04]; // kHeader, kMainStreamsInfo
+
+    // PackInfo: pack_pos = 0, one pack stream
+    h.extend_from_slice(&[0x06, 0x00, 0x01, 0x09]);
+    push_variable_u64(&mut h, packed.len() as u64);
+    h.push(0x00);
+
+    // UnpackInfo: one folder with a single Copy coder
+    h.extend_from_slice(&[0x07, 0x0B, 0x01, 0x00, 0x01, 0x01, 0x00, 0x0C]);
+    push_variable_u64(&mut h, packed.len() as u64);
+    h.push(0x00);
+
+    // SubStreamsInfo: every entry is a stream of the folder
+    h.extend_from_slice(&[0x08, 0x0D]);
+    push_variable_u64(&mut h, entries.len() as u64);
+    h.push(0x09);
+    for (_, data, _) in &entries[..entries.len() - 1] {
+        push_variable_u64(&mut h, data.len() as u64);
+    }
+    h.extend_from_slice(&[0x00, 0x00]); // end SubStreamsInfo, end MainStreamsInfo
+
+    // FilesInfo with names and attributes
+    h.push(0x05);
+    push_variable_u64(&mut h, entries.len() as u64);
+    let names: Vec<&str> = entries.iter().map(|(n, _, _)| *n).collect();
+    push_names_property(&mut h, &names);
+    h.push(0x15); // kWinAttributes
+    push_variable_u64(&mut h, 2 + 4 * entries.len() as u64);
+    h.extend_from_slice(&[0x01, 0x00]); // all defined, not external
+    for (_, _, attributes) in entries {
+        h.extend_from_slice(&attributes.to_le_bytes());
+    }
+    h.extend_from_slice(&[0x00, 0x00]); // end FilesInfo, end Header
+
+    wrap_raw_header(&packed, &h)
+}
+
+#[test]
+fn test_extract_skips_reparse_points_when_links_forbidden() {
+    use zesven::read::{ExtractOptions, LinkPolicy};
+
+    // FILE_ATTRIBUTE_REPARSE_POINT | FILE_ATTRIBUTE_ARCHIVE
+    const REPARSE_ATTRIBUTES: u32 = 0x400 | 0x20;
+
+    let archive_bytes = create_archive_with_attributes(&[
+        ("target.txt", b"linked content", 0x20),
+        ("link.txt", b"target.txt", REPARSE_ATTRIBUTES),
+    ]);
+    let mut archive = Archive::open(Cursor::new(archive_bytes)).unwrap();
+    assert!(archive.entries()[1].is_symlink);
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let options = ExtractOptions::new().link_policy(LinkPolicy::Forbid);
+    let result = archive.extract(temp_dir.path(), (), &options).unwrap();
+    assert_eq!(result.entries_extracted, 1);
+    assert_eq!(result.entries_skipped, 1);
+    assert_eq!(result.entries_failed, 0);
+
+    // Neither a link nor a file holding the target string is written
+    assert!(temp_dir.path().join("link.txt").symlink_metadata().is_err());
+    assert_eq!(
+        std::fs::read(temp_dir.path().join("target.txt")).unwrap(),
+        b"linked content"
+    );
+    let link = &result.entries[1];
+    assert!(link.skipped);
+    assert_eq!(link.bytes_written, 0);
+    assert!(
+        link.error
+            .as_ref()
+            .unwrap()
+            .contains("Symbolic link rejected")
+    );
+
+    // Strict mode turns the skip into an error, on disk and in a VFS
+    let temp_dir = tempfile::tempdir().unwrap();
+    let options = options.strict(true);
+    let err = common::expect_err(archive.extract(temp_dir.path(), (), &options));
+    assert!(
+        matches!(&err, zesven::Error::Strict(msg) if msg.contains("link.txt")),
+        "{err:?}"
+    );
+    let mut vfs = zesven::read::InMemoryVfs::new();
+    let err = common::expect_err(archive.extract_to_vfs(&mut vfs, (), &options));
+    assert!(matches!(err, zesven::Error::Strict(_)), "{err:?}");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_extract_symlinks_recreate_and_follow() {
+    use std::path::Path;
+    use zesven::ResourceLimits;
+    use zesven::read::{ExtractOptions, LinkPolicy, OverwritePolicy};
+
+    let archive_bytes = create_archive_with_symlinks();
+    let mut archive = Archive::open(Cursor::new(archive_bytes)).unwrap();
+    assert_eq!(archive.entries().iter().filter(|e| e.is_symlink).count(), 3);
+
+    // Recreate: links become symlinks, the escaping one is rejected
+    let temp_dir = tempfile::tempdir().unwrap();
+    let options = ExtractOptions::new().link_policy(LinkPolicy::ValidateTargets);
+    let result = archive.extract(temp_dir.path(), (), &options).unwrap();
+    assert_eq!(result.entries_failed, 1);
+    let link = temp_dir.path().join("data/link.txt");
+    assert!(link.symlink_metadata().unwrap().file_type().is_symlink());
+    assert_eq!(std::fs::read_link(&link).unwrap(), Path::new("target.txt"));
+    assert_eq!(std::fs::read(&link).unwrap(), b"linked content");
+    assert!(!temp_dir.path().join("links/escape.txt").exists());
+
+    // Follow: links become copies of the file they point to
+    let temp_dir = tempfile::tempdir().unwrap();
+    let options = ExtractOptions::new().link_policy(LinkPolicy::Follow);
+    let result = archive.extract(temp_dir.path(), (), &options).unwrap();
+    assert_eq!(result.entries_failed, 1);
+    for name in ["data/link.txt", "links/chained.txt"] {
+        let path = temp_dir.path().join(name);
+        assert!(path.symlink_metadata().unwrap().file_type().is_file());
+        assert_eq!(std::fs::read(&path).unwrap(), b"linked content");
+    }
+    let escape = result
+        .entries
+        .iter()
+        .find(|outcome| outcome.path == "links/escape.txt")
+        .unwrap();
+    assert!(escape.error.as_ref().unwrap().contains("escape"));
+    assert!(!temp_dir.path().join("links/escape.txt").exists());
+
+    // Follow replaces a symlink already in the way instead of writing
+    // through it
+    let temp_dir = tempfile::tempdir().unwrap();
+    let outside = tempfile::NamedTempFile::new().unwrap();
+    std::fs::write(outside.path(), b"outside").unwrap();
+    std::fs::create_dir(temp_dir.path().join("data")).unwrap();
+    let link = temp_dir.path().join("data/link.txt");
+    std::os::unix::fs::symlink(outside.path(), &link).unwrap();
+    let options = ExtractOptions::new()
+        .link_policy(LinkPolicy::Follow)
+        .overwrite(OverwritePolicy::Overwrite);
+    let result = archive.extract(temp_dir.path(), (), &options).unwrap();
+    assert_eq!(result.entries_failed, 1);
+    assert!(link.symlink_metadata().unwrap().file_type().is_file());
+    assert_eq!(std::fs::read(&link).unwrap(), b"linked content");
+    assert_eq!(std::fs::read(outside.path()).unwrap(), b"outside");
+
+    // Followed copies count against the extraction limits
+    let temp_dir = tempfile::tempdir().unwrap();
+    let options = ExtractOptions::new()
+        .link_policy(LinkPolicy::Follow)
+        .limits(ResourceLimits::new().max_total_unpacked(20));
+    let result = archive.extract(temp_dir.path(), (), &options).unwrap();
+    assert_eq!(result.entries_extracted, 1);
+    assert_eq!(result.entries_failed, 3);
+    let link = &result.entries[1];
+    assert_eq!(link.path, "data/link.txt");
+    assert!(link.error.as_ref().unwrap().contains("limit"), "{link:?}");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_extract_dedup_hard_links_identical_files() {
+    use std::os::unix::fs::MetadataExt;
+    use zesven::read::ExtractOptions;
+
+    let shared = b"the same bytes in every copy".repeat(100);
+    let archive_bytes = common::create_archive(&[
+        ("a/copy.bin", shared.as_slice()),
+        ("b/copy.bin", shared.as_slice()),
+        ("unique.bin", b"something else"),
+        ("c/copy.bin", shared.as_slice()),
+    ])
+    .unwrap();
+    let mut archive = Archive::open(Cursor::new(archive_bytes)).unwrap();
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let options = ExtractOptions::new().dedup_on_extract(true);
+    let result = archive.extract(temp_dir.path(), (), &options).unwrap();
+    assert_eq!(result.entries_failed, 0);
+
+    let inodes: Vec<u64> = ["a/copy.bin", "b/copy.bin", "c/copy.bin"]
+        .iter()
+        .map(|name| {
+            let path = temp_dir.path().join(name);
+            assert_eq!(std::fs::read(&path).unwrap(), shared);
+            std::fs::metadata(&path).unwrap().ino()
+        })
+        .collect();
+    assert_eq!(inodes[0], inodes[1]);
+    assert_eq!(inodes[0], inodes[2]);
+    let unique = std::fs::metadata(temp_dir.path().join("unique.bin")).unwrap();
+    assert_ne!(unique.ino(), inodes[0]);
+    assert_eq!(unique.nlink(), 1);
+    assert_eq!(
+        std::fs::metadata(temp_dir.path().join("a/copy.bin"))
+            .unwrap()
+            .nlink(),
+        3
+    );
+
+    // Without deduplication every file is its own copy
+    let temp_dir = tempfile::tempdir().unwrap();
+    let _ = archive
+        .extract(temp_dir.path(), (), &ExtractOptions::new())
+        .unwrap();
+    let a = std::fs::metadata(temp_dir.path().join("a/copy.bin")).unwrap();
+    let b = std::fs::metadata(temp_dir.path().join("b/copy.bin")).unwrap();
+    assert_ne!(a.ino(), b.ino());
+}
+
+#[test]
+fn test_extract_case_collision_policies() {
+    use zesven::read::{CaseCollisionPolicy, ExtractOptions};
+
+    let archive_bytes =
+        common::create_archive(&[("Foo.txt", b"upper"), ("foo.txt", b"lower")]).unwrap();
+    let mut archive = Archive::open(Cursor::new(archive_bytes)).unwrap();
+    let names_on_disk = |dir: &std::path::Path| {
+        let mut names: Vec<String> = std::fs::read_dir(dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        names
+    };
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let options = ExtractOptions::new().case_collision_policy(CaseCollisionPolicy::Error);
+    let result = archive.extract(temp_dir.path(), (), &options).unwrap();
+    assert_eq!(result.entries_failed, 1);
+    assert_eq!(result.failures[0].0, "foo.txt");
+    assert!(result.failures[0].1.contains("when case is ignored"));
+    assert_eq!(names_on_disk(temp_dir.path()), ["Foo.txt"]);
+    assert_eq!(
+        std::fs::read(temp_dir.path().join("Foo.txt")).unwrap(),
+        b"upper"
+    );
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let options = ExtractOptions::new().case_collision_policy(CaseCollisionPolicy::Rename);
+    let result = archive.extract(temp_dir.path(), (), &options).unwrap();
+    assert_eq!(result.entries_failed, 0);
+    assert_eq!(names_on_disk(temp_dir.path()), ["Foo.txt", "foo (1).txt"]);
+    assert_eq!(
+        std::fs::read(temp_dir.path().join("Foo.txt")).unwrap(),
+        b"upper"
+    );
+    assert_eq!(
+        std::fs::read(temp_dir.path().join("foo (1).txt")).unwrap(),
+        b"lower"
+    );
+
+    // The default leaves it to the filesystem; either way `foo.txt` holds the later entry
+    let temp_dir = tempfile::tempdir().unwrap();
+    let options = ExtractOptions::new()
+        .case_collision_policy(CaseCollisionPolicy::Overwrite)
+        .overwrite(zesven::read::OverwritePolicy::Overwrite);
+    let result = archive.extract(temp_dir.path(), (), &options).unwrap();
+    assert_eq!(result.entries_failed, 0);
+    assert_eq!(
+        std::fs::read(temp_dir.path().join("foo.txt")).unwrap(),
+        b"lower"
+    );
+}
+
+/// 2020-01-02 03:04:05 UTC as a Windows FILETIME.
+const KNOWN_CREATION_FILETIME: u64 = (1_577_934_245 + 11_644_473_600) * 10_000_000;
+
+/// Builds an archive with one file carrying [`KNOWN_CREATION_FILETIME`].
+fn create_archive_with_creation_time() -> Vec<u8> {
+    use zesven::write::EntryMeta;
+
+    let data = b"created long ago";
+    let mut archive_bytes = Vec::new();
+    {
+        let mut writer = Writer::create(Cursor::new(&mut archive_bytes)).unwrap();
+        let meta = EntryMeta::file(data.len() as u64).creation_time(KNOWN_CREATION_FILETIME);
+        writer
+            .add_stream(
+                ArchivePath::new("old.txt").unwrap(),
+                &mut Cursor::new(data),
+                meta,
+            )
+            .unwrap();
+        let _ = writer.finish().unwrap();
+    }
+    archive_bytes
+}
+
+#[test]
+fn test_creation_time_roundtrip() {
+    let archive_bytes = create_archive_with_creation_time();
+    let archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+    let entry = &archive.entries()[0];
+    assert_eq!(entry.creation_time, Some(KNOWN_CREATION_FILETIME));
+    assert_eq!(entry.modification_time, None);
+}
+
+#[cfg(windows)]
+#[test]
+fn test_creation_time_restored_on_extraction() {
+    use std::time::{Duration, UNIX_EPOCH};
+    use zesven::read::{ExtractOptions, PreserveMetadata};
+
+    let archive_bytes = create_archive_with_creation_time();
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+    let temp_dir = tempfile::tempdir().unwrap();
+    let options = ExtractOptions::new().preserve_metadata(PreserveMetadata::times());
+    archive.extract(temp_dir.path(), (), &options).unwrap();
+
+    let created = std::fs::metadata(temp_dir.path().join("old.txt"))
+        .unwrap()
+        .created()
+        .unwrap();
+    assert_eq!(created, UNIX_EPOCH + Duration::from_secs(1_577_934_245));
+}
+
+#[cfg(windows)]
+#[test]
+fn test_share_mode_with_file_open_for_writing() {
+    use std::io::Write;
+    use std::os::windows::fs::OpenOptionsExt;
+    use zesven::write::{ShareMode, WriteOptions};
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let log_path = temp_dir.path().join("app.log");
+    std::fs::write(&log_path, b"first line\n").unwrap();
+
+    // Another handle keeps the file open for appending, allowing shared reads
+    let mut log = std::fs::OpenOptions::new()
+        .append(true)
+        .share_mode(ShareMode::ReadWrite.flags())
+        .open(&log_path)
+        .unwrap();
+    log.write_all(b"second line\n").unwrap();
+
+    let archive_log = |mode: ShareMode| -> zesven::Result<Vec<u8>> {
+        let mut archive_bytes = Vec::new();
+        let mut writer = Writer::create(Cursor::new(&mut archive_bytes))?
+            .options(WriteOptions::new().share_mode(mode));
+        writer.add_path(&log_path, ArchivePath::new("app.log")?)?;
+        let _ = writer.finish()?;
+        Ok(archive_bytes)
+    };
+
+    let archive_bytes = archive_log(ShareMode::ReadWrite).unwrap();
+    common::verify_archive_contents(
+        &archive_bytes,
+        &[("app.log", b"first line\nsecond line\n".as_slice())],
+    );
+
+    let err = archive_log(ShareMode::Exclusive).unwrap_err();
+    assert!(
+        matches!(&err, zesven::Error::Io(e) if e.raw_os_error() == Some(32)),
+        "expected a sharing violation, got {:?}",
+        err
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_sparse_file_round_trip() {
+    use std::io::{Seek, SeekFrom, Write};
+    use std::os::unix::fs::MetadataExt;
+    use zesven::read::ExtractOptions;
+    use zesven::{ResourceLimits, WriteOptions};
+
+    const LEN: u64 = 8 * 1024 * 1024;
+    let temp_dir = tempfile::tempdir().unwrap();
+    let source_path = temp_dir.path().join("disk.img");
+    let mut source = std::fs::File::create(&source_path).unwrap();
+    source.set_len(LEN).unwrap();
+    source.write_all(b"boot sector").unwrap();
+    source.seek(SeekFrom::Start(LEN / 2)).unwrap();
+    source.write_all(b"partition table").unwrap();
+    drop(source);
+
+    let is_sparse = |path: &std::path::Path| {
+        let metadata = std::fs::metadata(path).unwrap();
+        metadata.blocks() * 512 < metadata.len() / 16
+    };
+    // Nothing to check on filesystems without holes
+    if !is_sparse(&source_path) {
+        return;
+    }
+
+    // Hole maps are only stored on request
+    let archive_with = |options: WriteOptions| {
+        let mut archive_bytes = Vec::new();
+        let mut writer = Writer::create(Cursor::new(&mut archive_bytes))
+            .unwrap()
+            .options(options);
+        writer
+            .add_path(&source_path, ArchivePath::new("disk.img").unwrap())
+            .unwrap();
+        let _ = writer.finish().unwrap();
+        archive_bytes
+    };
+    let archive = Archive::open(Cursor::new(archive_with(WriteOptions::new()))).unwrap();
+    assert!(!archive.entries()[0].is_sparse());
+
+    let archive_bytes = archive_with(WriteOptions::new().preserve_sparse(true));
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+    assert!(archive.entries()[0].is_sparse());
+
+    let dest = temp_dir.path().join("out");
+    // Zeros compress far beyond the default ratio limit
+    let options = ExtractOptions::new().limits(ResourceLimits::new().ratio_limit(None));
+    let result = archive.extract(&dest, (), &options).unwrap();
+    assert_eq!(result.entries_failed, 0);
+
+    let extracted_path = dest.join("disk.img");
+    assert!(is_sparse(&extracted_path));
+    assert_eq!(
+        std::fs::read(&extracted_path).unwrap(),
+        std::fs::read(&source_path).unwrap()
+    );
+}
+
+#[test]
+fn test_bsd_flags_header_round_trip() {
+    use zesven::write::EntryMeta;
+
+    const UF_HIDDEN: u32 = 0x8000;
+
+    let mut archive_bytes = Vec::new();
+    {
+        let mut writer = Writer::create(Cursor::new(&mut archive_bytes)).unwrap();
+        let meta = EntryMeta {
+            bsd_flags: Some(UF_HIDDEN),
+            ..EntryMeta::file(6)
+        };
+        writer
+            .add_stream(
+                ArchivePath::new("hidden.txt").unwrap(),
+                &mut Cursor::new(b"hidden"),
+                meta,
+            )
+            .unwrap();
+        writer
+            .add_bytes(ArchivePath::new("plain.txt").unwrap(), b"plain")
+            .unwrap();
+        let _ = writer.finish().unwrap();
+    }
+
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+    let flags = |archive: &Archive<_>, name: &str| {
+        archive
+            .entries()
+            .iter()
+            .find(|e| e.path.as_str() == name)
+            .unwrap()
+            .bsd_flags
+    };
+    assert_eq!(flags(&archive, "hidden.txt"), Some(UF_HIDDEN));
+    assert_eq!(flags(&archive, "plain.txt"), None);
+    assert_eq!(archive.extract_to_vec("hidden.txt").unwrap(), b"hidden");
+}
+
+#[cfg(target_os = "macos")]
+#[test]
+fn test_bsd_flags_restored_on_extraction() {
+    use std::os::macos::fs::MetadataExt;
+    use zesven::read::{ExtractOptions, PreserveMetadata};
+    use zesven::write::WriteOptions;
+
+    const UF_HIDDEN: u32 = 0x8000;
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let source_path = temp_dir.path().join("secret.txt");
+    std::fs::write(&source_path, b"not shown in Finder").unwrap();
+    let status = std::process::Command::new("chflags")
+        .arg("hidden")
+        .arg(&source_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let mut archive_bytes = Vec::new();
+    let mut writer = Writer::create(Cursor::new(&mut archive_bytes))
+        .unwrap()
+        .options(WriteOptions::new().preserve_bsd_flags(true));
+    writer
+        .add_path(&source_path, ArchivePath::new("secret.txt").unwrap())
+        .unwrap();
+    let _ = writer.finish().unwrap();
+
+    let mut archive = Archive::open(Cursor::new(&archive_bytes)).unwrap();
+    let stored = archive.entries()[0].bsd_flags.unwrap();
+    assert_ne!(stored & UF_HIDDEN, 0);
+
+    let dest = temp_dir.path().join("out");
+    let options = ExtractOptions::new().preserve_metadata(PreserveMetadata::all());
+    let result = archive.extract(&dest, (), &options).unwrap();
+    assert_eq!(result.entries_failed, 0);
+
+    let extracted = std::fs::metadata(dest.join("secret.txt")).unwrap();
+    assert_ne!(extracted.st_flags() & UF_HIDDEN, 0);
+}
+
+#[test]
+fn test_write_progress_reports_all_bytes() {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use zesven::progress::{StatisticsProgress, ThrottledProgress, progress_fn};
+    use zesven::write::{EntryMeta, WriteOptions};
+
+    let large = vec![7u8; 512 * 1024 + 17];
+    let write = |options: WriteOptions| {
+        let mut writer = Writer::create(Cursor::new(Vec::new()))
+            .unwrap()
+            .options(options);
+        writer
+            .add_bytes(ArchivePath::new("small.txt").unwrap(), b"small")
+            .unwrap();
+        writer
+            .add_reader(
+                ArchivePath::new("large.bin").unwrap(),
+                large.as_slice(),
+                large.len() as u64,
+                EntryMeta::default(),
+            )
+            .unwrap();
+        writer
+            .add_bytes(ArchivePath::new("empty.txt").unwrap(), b"")
+            .unwrap();
+        writer.finish().unwrap()
+    };
+
+    for solid in [false, true] {
+        let stats = Arc::new(Mutex::new(StatisticsProgress::new()));
+        let mut options = WriteOptions::new().progress(stats.clone());
+        if solid {
+            options = options.solid();
+        }
+        let result = write(options);
+        let stats = stats.lock().unwrap();
+        assert_eq!(
+            stats.state.total_bytes, result.total_size,
+            "solid: {}",
+            solid
+        );
+        assert_eq!(
+            stats.state.processed_bytes, result.total_size,
+            "solid: {}",
+            solid
+        );
+    }
+
+    // Throttling drops intermediate reports but keeps the final one
+    let calls = Arc::new(AtomicU64::new(0));
+    let last = Arc::new(AtomicU64::new(0));
+    let reporter = {
+        let (calls, last) = (calls.clone(), last.clone());
+        progress_fn(move |processed, _total| {
+            calls.fetch_add(1, Ordering::Relaxed);
+            last.store(processed, Ordering::Relaxed);
+            true
+        })
+    };
+    let throttled = Arc::new(Mutex::new(ThrottledProgress::new(
+        reporter,
+        Duration::from_secs(3600),
+    )));
+    let result = write(WriteOptions::new().progress(throttled));
+    assert_eq!(last.load(Ordering::Relaxed), result.total_size);
+    assert!(
+        calls.load(Ordering::Relaxed) <= 2,
+        "{} calls",
+        calls.load(Ordering::Relaxed)
+    );
+}
+
+#[test]
+fn test_entry_is_solid_member() {
+    use zesven::write::{SolidOptions, WriteOptions};
+
+    let large = vec![0x42u8; 4096];
+    let options =
+        WriteOptions::new().solid_options(SolidOptions::enabled().small_file_threshold(1024));
+    let mut writer = Writer::create(Cursor::new(Vec::new()))
+        .unwrap()
+        .options(options);
+    writer
+        .add_directory(ArchivePath::new("dir").unwrap(), Default::default())
+        .unwrap();
+    for name in ["dir/a.txt", "dir/b.txt", "dir/c.txt"] {
+        writer
+            .add_bytes(ArchivePath::new(name).unwrap(), name.as_bytes())
+            .unwrap();
+    }
+    for name in ["large1.bin", "large2.bin"] {
+        writer
+            .add_bytes(ArchivePath::new(name).unwrap(), &large)
+            .unwrap();
+    }
+    writer
+        .add_bytes(ArchivePath::new("empty.txt").unwrap(), b"")
+        .unwrap();
+    let (_, cursor) = writer.finish_into_inner().unwrap();
+
+    let archive = Archive::open(Cursor::new(cursor.into_inner())).unwrap();
+    assert_eq!(archive.info().folder_count, 3);
+    let solid_members: Vec<&str> = archive
+        .entries()
+        .iter()
+        .filter(|entry| entry.is_solid_member())
+        .map(|entry| entry.path.as_str())
+        .collect();
+    assert_eq!(solid_members, ["dir/a.txt", "dir/b.txt", "dir/c.txt"]);
+}
@@ -41,6 +41,35 @@ fn create_fake_elf_stub() -> Vec<u8> {
     stub
 }
 
+/// Tests that a signature embedded in the stub does not shadow the real archive.
+#[test]
+fn test_sfx_stub_with_fake_signature() {
+    use zesven::format::header::detect_sfx;
+    use zesven::recovery::{RecoveryOptions, recover_archive};
+
+    let entries = [("real.txt", b"Real archive content" as &[u8])];
+    let archive = common::create_archive(&entries).expect("Failed to create archive");
+
+    // The stub embeds signature and version bytes, but no valid start header
+    let mut sfx = create_fake_pe_stub();
+    sfx.extend_from_slice(&[0x37, 0x7A, 0xBC, 0xAF, 0x27, 0x1C, 0x00, 0x04]);
+    sfx.extend_from_slice(&[0x5A; 64]);
+    let real_offset = sfx.len() as u64;
+    sfx.extend_from_slice(&archive);
+
+    let detected = detect_sfx(&mut Cursor::new(&sfx))
+        .unwrap()
+        .expect("should detect SFX");
+    assert_eq!(detected.archive_offset, real_offset);
+
+    let mut opened = Archive::open(Cursor::new(sfx.clone())).expect("Failed to open SFX");
+    assert_eq!(opened.extract_to_vec("real.txt").unwrap(), entries[0].1);
+
+    let recovered = recover_archive(Cursor::new(sfx), RecoveryOptions::default()).unwrap();
+    assert_eq!(recovered.archive_offset, real_offset);
+    assert!(recovered.archive.is_some());
+}
+
 /// Tests that SfxBuilder requires a stub.
 #[test]
 fn test_sfx_builder_requires_stub() {
@@ -295,3 +324,61 @@ fn test_sfx_can_be_opened_by_archive() {
         .expect("Should extract from SFX");
     assert_eq!(extracted, content);
 }
+
+/// Tests that the streaming readers locate pack data behind an SFX stub.
+#[test]
+fn test_sfx_streaming_pack_offsets() {
+    use zesven::streaming::{
+        ParallelExtractionOptions, RandomAccessReader, StreamingArchive, StreamingConfig,
+    };
+
+    let entries = [
+        ("first.txt", b"First entry behind the stub" as &[u8]),
+        ("second.txt", b"Second entry behind the stub"),
+    ];
+    let archive = common::create_archive(&entries).expect("Failed to create archive");
+
+    let stub = SfxStub::with_format(create_fake_pe_stub(), SfxFormat::WindowsPe);
+    let mut sfx_data = Vec::new();
+    let result = SfxBuilder::new()
+        .stub(stub)
+        .validate_stub(false)
+        .build(&mut sfx_data, &archive)
+        .expect("Failed to build SFX");
+    assert!(result.stub_size > 0);
+
+    // Sequential streaming
+    let mut streaming = StreamingArchive::open(Cursor::new(sfx_data.clone()), "").unwrap();
+    let mut iter = streaming.entries().unwrap();
+    for (name, content) in entries {
+        let entry = iter.next().unwrap().unwrap();
+        assert_eq!(entry.name(), name);
+        assert_eq!(iter.extract_current_to_vec().unwrap(), content);
+    }
+
+    // Random access
+    let mut random = RandomAccessReader::new(
+        Cursor::new(sfx_data.clone()),
+        "".into(),
+        StreamingConfig::default(),
+    )
+    .unwrap();
+    for (name, content) in entries {
+        let mut extracted = Vec::new();
+        random
+            .extract_entry_by_name_to(name, &mut extracted)
+            .unwrap();
+        assert_eq!(extracted, content);
+    }
+
+    // Parallel extraction
+    let temp_dir = tempfile::tempdir().unwrap();
+    let mut streaming = StreamingArchive::open(Cursor::new(sfx_data), "").unwrap();
+    let result = streaming
+        .extract_all_parallel(temp_dir.path(), &ParallelExtractionOptions::new())
+        .unwrap();
+    assert_eq!(result.entries_failed, 0, "{:?}", result.failures);
+    for (name, content) in entries {
+        assert_eq!(std::fs::read(temp_dir.path().join(name)).unwrap(), content);
+    }
+}
@@ -204,3 +204,61 @@ fn test_stats_merge_from_multiple_archives() {
     let stats2_reads = stats2.lock().unwrap().read_count;
     assert_eq!(combined.read_count, stats1_reads + stats2_reads);
 }
+
+/// Tests that read-ahead coalesces the small reads of opening and extraction.
+#[test]
+fn test_read_ahead_reduces_read_count() {
+    use zesven::read::{BufferedSeekReader, ExtractOptions};
+
+    let files: Vec<(String, Vec<u8>)> = (0..100)
+        .map(|i| {
+            (
+                format!("dir/file{i:03}.txt"),
+                format!("content {i}").into_bytes(),
+            )
+        })
+        .collect();
+    let entries: Vec<(&str, &[u8])> = files
+        .iter()
+        .map(|(name, data)| (name.as_str(), data.as_slice()))
+        .collect();
+    let archive_bytes = common::create_archive(&entries).expect("Failed to create archive");
+
+    // Opening
+    let (reader, plain_stats) = Cursor::new(archive_bytes.clone()).with_stats_default();
+    let plain = Archive::open(reader).expect("Failed to open archive");
+    let (reader, buffered_stats) = Cursor::new(archive_bytes.clone()).with_stats_default();
+    let buffered =
+        Archive::open(BufferedSeekReader::new(reader, 64 * 1024)).expect("Failed to open archive");
+    assert_eq!(buffered.len(), plain.len());
+    let plain_reads = plain_stats.lock().unwrap().read_count;
+    let buffered_reads = buffered_stats.lock().unwrap().read_count;
+    assert!(
+        buffered_reads < plain_reads,
+        "opening took {} reads with read-ahead, {} without",
+        buffered_reads,
+        plain_reads
+    );
+
+    // Extraction of many small non-solid entries
+    let extract_reads = |options: &ExtractOptions| {
+        let (reader, stats) = Cursor::new(archive_bytes.clone()).with_stats_default();
+        let mut archive = Archive::open(reader).expect("Failed to open archive");
+        stats.lock().unwrap().clear();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let result = archive.extract(temp_dir.path(), (), options).unwrap();
+        assert_eq!(result.entries_extracted, files.len());
+        for (name, data) in &files {
+            assert_eq!(&std::fs::read(temp_dir.path().join(name)).unwrap(), data);
+        }
+        stats.lock().unwrap().read_count
+    };
+    let plain_reads = extract_reads(&ExtractOptions::default());
+    let buffered_reads = extract_reads(&ExtractOptions::new().read_ahead(64 * 1024));
+    assert!(
+        buffered_reads * 10 <= plain_reads,
+        "extraction took {} reads with read-ahead, {} without",
+        buffered_reads,
+        plain_reads
+    );
+}
@@ -3,7 +3,12 @@
 //! These tests verify that the streaming API correctly:
 //! - Reads archives entry-by-entry without full memory allocation
 //! - Produces correct output through extract_all()
+//! - Applies the backslash policy in extract_all()
 //! - Works with both solid and non-solid archives
+//! - Verifies entry CRCs as data is read
+//! - Enforces the compression ratio limit on decoded bytes
+//! - Bounds the number of files open during parallel extraction
+//! - Extracts the folders of split solid archives in parallel
 
 #![cfg(feature = "lzma2")]
 
@@ -262,6 +267,94 @@ fn test_streaming_entry_extraction() {
     }
 }
 
+#[test]
+fn test_streaming_crc_mismatch_on_final_byte() {
+    use std::io::ErrorKind;
+    use zesven::Error;
+    use zesven::codec::CodecMethod;
+
+    let content = b"streamed content whose last byte gets corrupted";
+    let mut archive_bytes = common::create_archive_with_options(
+        WriteOptions::new().method(CodecMethod::Copy),
+        &[("data.bin", content.as_slice())],
+    )
+    .unwrap();
+    let pos = archive_bytes
+        .windows(content.len())
+        .position(|w| w == content)
+        .expect("stored content not found");
+    archive_bytes[pos + content.len() - 1] ^= 0xFF;
+
+    let mut archive = StreamingArchive::open(Cursor::new(&archive_bytes), "").unwrap();
+    let mut iter = archive.entries().unwrap();
+    iter.next().unwrap().unwrap();
+
+    // Everything but the final byte reads without complaint
+    let mut buf = vec![0u8; content.len() - 1];
+    assert_eq!(iter.read_entry_data(&mut buf).unwrap(), content.len() - 1);
+    assert_eq!(buf, content[..content.len() - 1]);
+
+    let err = iter.read_entry_data(&mut buf).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    let inner = err.get_ref().and_then(|e| e.downcast_ref::<Error>());
+    assert!(
+        matches!(inner, Some(Error::CrcMismatch { entry_index: 0, entry_name: Some(name), .. }) if name == "data.bin"),
+        "unexpected error: {err:?}"
+    );
+
+    // extract_current_to surfaces the typed error directly
+    let mut archive = StreamingArchive::open(Cursor::new(&archive_bytes), "").unwrap();
+    let mut iter = archive.entries().unwrap();
+    iter.next().unwrap().unwrap();
+    let mut extracted = Vec::new();
+    let err = iter.extract_current_to(&mut extracted).unwrap_err();
+    assert!(matches!(err, Error::CrcMismatch { .. }), "{err:?}");
+
+    // Verification can be turned off
+    let config = StreamingConfig::default().verify_crc(false);
+    let mut archive =
+        StreamingArchive::open_with_config(Cursor::new(&archive_bytes), "", config).unwrap();
+    let mut iter = archive.entries().unwrap();
+    iter.next().unwrap().unwrap();
+    let mut extracted = Vec::new();
+    iter.extract_current_to(&mut extracted).unwrap();
+    assert_eq!(extracted.len(), content.len());
+}
+
+#[test]
+fn test_streaming_ratio_limit_stops_decoding() {
+    use zesven::Error;
+    use zesven::format::parser::read_archive_header;
+
+    const SIZE: usize = 4 * 1024 * 1024;
+    const MAX_RATIO: u32 = 50;
+
+    let archive_bytes = create_archive(&[("bomb.bin", &vec![0u8; SIZE])]).unwrap();
+    let (_, header) = read_archive_header(&mut Cursor::new(&archive_bytes), None).unwrap();
+    let packed_size = header.pack_info.unwrap().pack_sizes[0];
+    assert!(packed_size * u64::from(MAX_RATIO) < SIZE as u64);
+
+    let config = StreamingConfig::default().max_compression_ratio(MAX_RATIO);
+    let mut archive =
+        StreamingArchive::open_with_config(Cursor::new(&archive_bytes), "", config).unwrap();
+    let mut iter = archive.entries().unwrap();
+    iter.next().unwrap().unwrap();
+    let mut extracted = Vec::new();
+    let err = iter.extract_current_to(&mut extracted).unwrap_err();
+    assert!(matches!(err, Error::ResourceLimitExceeded(_)), "{err:?}");
+    assert!(extracted.len() as u64 <= packed_size * u64::from(MAX_RATIO));
+
+    // A ratio above the actual one lets the entry through
+    let config = StreamingConfig::default().max_compression_ratio(u32::MAX);
+    let mut archive =
+        StreamingArchive::open_with_config(Cursor::new(&archive_bytes), "", config).unwrap();
+    let mut iter = archive.entries().unwrap();
+    iter.next().unwrap().unwrap();
+    let mut extracted = Vec::new();
+    iter.extract_current_to(&mut extracted).unwrap();
+    assert_eq!(extracted.len(), SIZE);
+}
+
 // ============================================================================
 // extract_all tests
 // ============================================================================
@@ -296,6 +389,31 @@ fn test_streaming_extract_all() {
     assert_eq!(content1, b"Content 1");
 }
 
+#[cfg(unix)]
+#[test]
+fn test_streaming_extract_all_honors_backslash_policy() {
+    use zesven::read::{BackslashPolicy, ExtractOptions};
+
+    let entries = [
+        ("dir\\file.txt", b"Content" as &[u8]),
+        ("up\\..\\..\\escape.txt", b"evil"),
+    ];
+    let archive_bytes = create_archive(&entries).unwrap();
+    let options = ExtractOptions::new().backslashes(BackslashPolicy::Separator);
+
+    let mut archive = StreamingArchive::open(Cursor::new(archive_bytes), "").unwrap();
+    let temp_dir = tempfile::tempdir().unwrap();
+    let result = archive.extract_all(temp_dir.path(), &options).unwrap();
+
+    assert_eq!(result.entries_extracted, 1);
+    assert_eq!(result.entries_failed, 1);
+    assert_eq!(result.failures[0].0, "up\\..\\..\\escape.txt");
+    assert_eq!(
+        std::fs::read(temp_dir.path().join("dir/file.txt")).unwrap(),
+        b"Content"
+    );
+}
+
 // ============================================================================
 // extract_all_to_sinks tests
 // ============================================================================
@@ -595,6 +713,200 @@ fn test_solid_archive_parallel_extraction_returns_error() {
     }
 }
 
+#[test]
+fn test_parallel_extraction_max_open_files() {
+    use zesven::read::Threads;
+    use zesven::streaming::ParallelExtractionOptions;
+
+    let files: Vec<(String, Vec<u8>)> = (0..64)
+        .map(|i| {
+            (
+                format!("dir{}/file{}.txt", i % 4, i),
+                vec![i as u8; 4096 + i],
+            )
+        })
+        .collect();
+    let entries: Vec<(&str, &[u8])> = files
+        .iter()
+        .map(|(name, data)| (name.as_str(), data.as_slice()))
+        .collect();
+    let archive_bytes = create_archive(&entries).unwrap();
+
+    let mut archive = StreamingArchive::open(Cursor::new(archive_bytes), "").unwrap();
+    assert!(archive.supports_parallel_extraction());
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let options = ParallelExtractionOptions::new()
+        .threads(Threads::count_or_single(8))
+        .max_open_files(4);
+    let result = archive
+        .extract_all_parallel(temp_dir.path(), &options)
+        .unwrap();
+
+    assert!(result.is_success(), "failures: {:?}", result.failures);
+    assert_eq!(result.entries_extracted, entries.len());
+    assert!(
+        (1..=4).contains(&result.peak_open_files),
+        "peak of {} open files exceeds the cap",
+        result.peak_open_files
+    );
+    for (name, data) in &entries {
+        assert_eq!(std::fs::read(temp_dir.path().join(name)).unwrap(), *data);
+    }
+}
+
+#[test]
+fn test_parallel_extraction_min_folders() {
+    use zesven::read::Threads;
+    use zesven::streaming::ParallelExtractionOptions;
+
+    let files: Vec<(String, Vec<u8>)> = (0..16)
+        .map(|i| {
+            let data = (0..64 * 1024)
+                .map(|j: usize| (j.wrapping_mul(31) ^ (j >> 7) ^ i) as u8)
+                .collect();
+            (format!("data/file{i:02}.bin"), data)
+        })
+        .collect();
+
+    let mut archive_bytes = Vec::new();
+    {
+        let options = WriteOptions::new().solid().min_folders(4);
+        let mut writer = Writer::create(Cursor::new(&mut archive_bytes))
+            .unwrap()
+            .options(options);
+        for (name, data) in &files {
+            writer
+                .add_bytes(ArchivePath::new(name).unwrap(), data)
+                .unwrap();
+        }
+        let _ = writer.finish().unwrap();
+    }
+
+    let archive = Archive::open(Cursor::new(archive_bytes.clone())).unwrap();
+    assert!(archive.info().is_solid);
+    assert!(
+        archive.info().folder_count >= 4,
+        "expected at least 4 folders, got {}",
+        archive.info().folder_count
+    );
+
+    let mut archive = StreamingArchive::open(Cursor::new(archive_bytes), "").unwrap();
+    assert!(archive.is_solid());
+    assert!(archive.supports_parallel_extraction());
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let options = ParallelExtractionOptions::new().threads(Threads::count_or_single(4));
+    let result = archive
+        .extract_all_parallel(temp_dir.path(), &options)
+        .unwrap();
+
+    assert!(result.is_success(), "failures: {:?}", result.failures);
+    assert!(result.used_parallel);
+    assert_eq!(result.threads_used, 4);
+    assert_eq!(result.entries_extracted, files.len());
+    for (name, data) in &files {
+        assert_eq!(&std::fs::read(temp_dir.path().join(name)).unwrap(), data);
+    }
+}
+
+/// Reader that counts the seeks made through it.
+struct SeekCountingReader {
+    inner: Cursor<Vec<u8>>,
+    seeks: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+}
+
+impl std::io::Read for SeekCountingReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl std::io::Seek for SeekCountingReader {
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        self.seeks
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        self.inner.seek(pos)
+    }
+}
+
+#[test]
+fn test_streaming_entries_decode_order() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let mut archive_bytes = Vec::new();
+    {
+        let options = WriteOptions::new().solid().min_folders(3);
+        let mut writer = Writer::create(Cursor::new(&mut archive_bytes))
+            .unwrap()
+            .options(options);
+        for i in 0..6 {
+            let data = format!("content of file {i} ").repeat(2000);
+            writer
+                .add_bytes(
+                    ArchivePath::new(&format!("file{i}.txt")).unwrap(),
+                    data.as_bytes(),
+                )
+                .unwrap();
+        }
+        writer
+            .add_directory(ArchivePath::new("dir").unwrap(), Default::default())
+            .unwrap();
+        writer
+            .add_bytes(ArchivePath::new("empty.txt").unwrap(), b"")
+            .unwrap();
+        let _ = writer.finish().unwrap();
+    }
+
+    let folder_count = Archive::open(Cursor::new(archive_bytes.clone()))
+        .unwrap()
+        .info()
+        .folder_count;
+    assert!(folder_count >= 3, "got {folder_count} folders");
+
+    let seeks = Arc::new(AtomicUsize::new(0));
+    let reader = SeekCountingReader {
+        inner: Cursor::new(archive_bytes),
+        seeks: Arc::clone(&seeks),
+    };
+    let mut archive = StreamingArchive::open(reader, "").unwrap();
+
+    // Each folder is decoded once, after the entries without data
+    seeks.store(0, Ordering::Relaxed);
+    let mut names = Vec::new();
+    let mut iter = archive.entries_decode_order().unwrap();
+    while let Some(entry) = iter.next() {
+        let entry = entry.unwrap();
+        let name = entry.name().to_string();
+        let data = iter.extract_current_to_vec().unwrap();
+        if let Some(i) = name.strip_prefix("file") {
+            let i = i.trim_end_matches(".txt");
+            assert_eq!(
+                data,
+                format!("content of file {i} ").repeat(2000).as_bytes()
+            );
+        }
+        names.push(name);
+    }
+    drop(iter);
+    assert_eq!(seeks.load(Ordering::Relaxed), folder_count);
+    let expected: Vec<String> = ["dir", "empty.txt"]
+        .into_iter()
+        .map(String::from)
+        .chain((0..6).map(|i| format!("file{i}.txt")))
+        .collect();
+    assert_eq!(names, expected);
+
+    // The file list is stored in stream order, so file-list order decodes
+    // each folder once as well
+    seeks.store(0, Ordering::Relaxed);
+    for entry in archive.entries().unwrap() {
+        entry.unwrap().skip().unwrap();
+    }
+    assert_eq!(seeks.load(Ordering::Relaxed), folder_count);
+}
+
 // ============================================================================
 // Skipped entries tests
 // ============================================================================